@@ -47,7 +47,7 @@ pub use object::stage_object::StageObject;
 pub use object::{Object, ObjectPtr, TObject};
 use scope::Scope;
 use smallvec::alloc::borrow::Cow;
-pub use timer::Timers;
+pub use timer::{TimerCallback, Timers};
 pub use value::Value;
 
 macro_rules! avm_debug {
@@ -604,6 +604,7 @@ mod tests {
                 video: &mut NullVideoBackend::new(),
                 mouse_over_object: None,
                 mouse_down_object: None,
+                avm2_mouse_over_object: None,
                 mouse_position: &(Twips::ZERO, Twips::ZERO),
                 drag_object: &mut None,
                 player: None,
@@ -625,6 +626,9 @@ mod tests {
                 times_get_time_called: 0,
                 time_offset: &mut 0,
                 frame_rate: &mut frame_rate,
+                gc_total_allocated: 0,
+                gc_remembered_size: 0,
+                needs_gc: &mut false,
             };
             context.stage.replace_at_depth(&mut context, root, 0);
 
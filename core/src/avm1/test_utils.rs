@@ -60,6 +60,7 @@ where
             video: &mut NullVideoBackend::new(),
             mouse_over_object: None,
             mouse_down_object: None,
+            avm2_mouse_over_object: None,
             mouse_position: &(Twips::ZERO, Twips::ZERO),
             drag_object: &mut None,
             player: None,
@@ -82,6 +83,9 @@ where
             time_offset: &mut 0,
             audio_manager: &mut AudioManager::new(),
             frame_rate: &mut frame_rate,
+            gc_total_allocated: 0,
+            gc_remembered_size: 0,
+            needs_gc: &mut false,
         };
         context.stage.replace_at_depth(&mut context, root, 0);
 
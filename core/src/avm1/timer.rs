@@ -4,9 +4,12 @@
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
 //!
-//! TODO: Could we use this for AVM2 timers as well?
+//! AVM2's `flash.utils.setInterval` and `setTimeout` share this queue.
 
 use crate::avm1::{Activation, ActivationIdentifier, AvmString, Object, TObject, Value};
+use crate::avm2::{
+    Activation as Avm2Activation, Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
@@ -94,6 +97,13 @@ impl<'gc> Timers<'gc> {
                 TimerCallback::Method { this, method_name } => {
                     let _ = this.call_method(method_name, &params, &mut activation);
                 }
+                TimerCallback::Avm2Callback { closure, params } => {
+                    let mut avm2_activation =
+                        Avm2Activation::from_nothing(activation.context.reborrow());
+                    if let Err(e) = closure.call(None, &params, &mut avm2_activation) {
+                        log::error!("Unhandled AVM2 error in timer callback: {}", e);
+                    }
+                }
             }
 
             crate::player::Player::run_actions(&mut activation.context);
@@ -263,4 +273,9 @@ pub enum TimerCallback<'gc> {
         this: Object<'gc>,
         method_name: AvmString<'gc>,
     },
+    /// An AVM2 closure, along with the arguments it is called with.
+    Avm2Callback {
+        closure: Avm2Object<'gc>,
+        params: Vec<Avm2Value<'gc>>,
+    },
 }
@@ -1,10 +1,15 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
 use crate::avm2::globals::{SystemClasses, SystemPrototypes};
+use crate::avm2::local_connection::LocalConnections;
 use crate::avm2::method::Method;
+use crate::avm2::net_stream::NetStreams;
 use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
+use crate::avm2::socket::Sockets;
+use crate::backend::render::BitmapInfo;
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use crate::tag_utils::SwfSlice;
 use fnv::FnvHashMap;
@@ -22,15 +27,19 @@ macro_rules! avm_debug {
 }
 
 mod activation;
+mod amf;
 mod array;
 mod bytearray;
 mod class;
 mod domain;
+mod e4x;
 mod events;
 mod function;
 mod globals;
+mod local_connection;
 mod method;
 mod names;
+mod net_stream;
 mod object;
 mod property;
 mod property_map;
@@ -39,7 +48,10 @@ mod return_value;
 mod scope;
 mod script;
 mod slot;
+mod socket;
 mod string;
+#[cfg(test)]
+pub(crate) mod test_utils;
 mod traits;
 mod value;
 mod vector;
@@ -54,7 +66,10 @@ pub use crate::avm2::object::{
 };
 pub use crate::avm2::value::Value;
 
-const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
+const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
+
+/// Broadcast events that only reach display objects on the stage.
+const ON_STAGE_BROADCASTS: [&str; 1] = ["render"];
 
 /// Boxed error alias.
 ///
@@ -88,6 +103,27 @@ pub struct Avm2<'gc> {
     /// collector does not support weak references.
     broadcast_list: FnvHashMap<AvmString<'gc>, Vec<Object<'gc>>>,
 
+    /// Connection names and pending messages of `LocalConnection` objects.
+    local_connections: LocalConnections<'gc>,
+
+    /// Connections opened by `Socket` and `XMLSocket` objects.
+    sockets: Sockets<'gc>,
+
+    /// Streams played by `NetStream` objects.
+    net_streams: NetStreams<'gc>,
+
+    /// Font classes registered with `Font.registerFont`, in registration
+    /// order.
+    registered_fonts: Vec<ClassObject<'gc>>,
+
+    /// Arrays and vectors currently being joined into strings, used to stop
+    /// self-referential containers from recursing forever.
+    join_stack: Vec<Object<'gc>>,
+
+    /// Classes registered with `flash.net.registerClassAlias`, keyed by the
+    /// alias they are serialized under.
+    class_aliases: FnvHashMap<AvmString<'gc>, ClassObject<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -103,6 +139,12 @@ impl<'gc> Avm2<'gc> {
             system_prototypes: None,
             system_classes: None,
             broadcast_list: Default::default(),
+            local_connections: Default::default(),
+            sockets: Default::default(),
+            net_streams: Default::default(),
+            registered_fonts: Vec::new(),
+            join_stack: Vec::new(),
+            class_aliases: Default::default(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -173,16 +215,74 @@ impl<'gc> Avm2<'gc> {
         event: Event<'gc>,
         event_class: ClassObject<'gc>,
         target: Object<'gc>,
+    ) -> Result<bool, Error> {
+        Self::dispatch_event_with_properties(context, event, event_class, &[], target)
+    }
+
+    /// Dispatch an event on an object with a specific Event type, setting
+    /// additional public properties on the event object beforehand.
+    ///
+    /// This is used for `Event` subclasses, such as `KeyboardEvent` and
+    /// `MouseEvent`, which carry more than the base event state.
+    ///
+    /// The `bool` parameter reads true if the event was cancelled.
+    pub fn dispatch_event_with_properties(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        event_class: ClassObject<'gc>,
+        properties: &[(&'static str, Value<'gc>)],
+        target: Object<'gc>,
     ) -> Result<bool, Error> {
         use crate::avm2::events::dispatch_event;
 
         let mut activation = Activation::from_nothing(context.reborrow());
 
-        let event_object = EventObject::from_event(&mut activation, event_class, event)?;
+        let mut event_object = EventObject::from_event(&mut activation, event_class, event)?;
+
+        for (name, value) in properties {
+            event_object.set_property(
+                event_object,
+                &QName::new(Namespace::public(), *name).into(),
+                value.clone(),
+                &mut activation,
+            )?;
+        }
 
         dispatch_event(&mut activation, target, event_object)
     }
 
+    /// Deliver the messages sent by `LocalConnection` objects since the last
+    /// frame.
+    pub fn deliver_local_connection_messages(context: &mut UpdateContext<'_, 'gc, '_>) {
+        LocalConnections::deliver_messages(context)
+    }
+
+    /// Dispatch events for connections, data, and disconnections of sockets
+    /// opened by `Socket` and `XMLSocket` objects.
+    pub fn update_sockets(context: &mut UpdateContext<'_, 'gc, '_>) {
+        Sockets::update_sockets(context)
+    }
+
+    /// Advance the playback of all `NetStream` objects by one frame.
+    pub fn update_net_streams(context: &mut UpdateContext<'_, 'gc, '_>) {
+        NetStreams::update(context)
+    }
+
+    /// The decoded frame a `NetStream` is currently showing.
+    pub fn net_stream_frame(&self, net_stream: Object<'gc>) -> Option<(u32, BitmapInfo)> {
+        self.net_streams.current_frame(net_stream)
+    }
+
+    /// Play the data loaded for a `NetStream` with `NetStream.play`, or
+    /// report that it could not be loaded.
+    pub fn net_stream_loaded(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        net_stream: Object<'gc>,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), Error> {
+        NetStreams::load_complete(context, net_stream, data)
+    }
+
     /// Add an object to the broadcast list.
     ///
     /// Each broadcastable event contains it's own broadcast list. You must
@@ -222,6 +322,8 @@ impl<'gc> Avm2<'gc> {
     ///
     /// Attempts to broadcast a non-broadcast event will do nothing. To add a
     /// new broadcast type, you must add it to the `BROADCAST_WHITELIST` first.
+    /// Events in `ON_STAGE_BROADCASTS` skip display objects that are not on
+    /// the stage.
     pub fn broadcast_event(
         context: &mut UpdateContext<'_, 'gc, '_>,
         event: Event<'gc>,
@@ -235,6 +337,10 @@ impl<'gc> Avm2<'gc> {
             return Ok(());
         }
 
+        let on_stage_only = ON_STAGE_BROADCASTS
+            .iter()
+            .any(|x| AvmString::from(*x) == event_name);
+
         let el_length = context
             .avm2
             .broadcast_list
@@ -254,6 +360,15 @@ impl<'gc> Avm2<'gc> {
             if let Some(object) = object {
                 let mut activation = Activation::from_nothing(context.reborrow());
 
+                if on_stage_only
+                    && !object
+                        .as_display_object()
+                        .map(|dobj| dobj.is_on_stage(&activation.context))
+                        .unwrap_or(false)
+                {
+                    continue;
+                }
+
                 if object.is_of_type(on_type, &mut activation)? {
                     Avm2::dispatch_event(&mut activation.context, event.clone(), object)?;
                 }
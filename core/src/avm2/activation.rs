@@ -137,6 +137,13 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// and we will not allocate a class for one.
     activation_class: Option<ClassObject<'gc>>,
 
+    /// The default XML namespace, as set by the `dxns` and `dxnslate` opcodes.
+    ///
+    /// E4X lookups of unqualified names resolve in this namespace. If this is
+    /// `None`, then no default namespace has been set and such names resolve
+    /// in the empty namespace.
+    default_xml_namespace: Option<AvmString<'gc>>,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -164,6 +171,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             caller_domain: context.avm2.globals,
             subclass_object: None,
             activation_class: None,
+            default_xml_namespace: None,
             context,
         }
     }
@@ -205,6 +213,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             caller_domain: domain,
             subclass_object: None,
             activation_class: None,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -291,18 +300,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         param_config: &ParamConfig<'gc>,
         index: usize,
     ) -> Result<Value<'gc>, Error> {
-        let arg = if let Some(value) = value {
-            Cow::Borrowed(value)
-        } else if let Some(default) = &param_config.default_value {
-            Cow::Borrowed(default)
-        } else if param_config.param_type_name.is_any() {
-            return Ok(Value::Undefined);
-        } else {
-            return Err(format!(
-                "Param {} (index {}) was missing when calling {}",
-                param_config.param_name, index, method_name
-            )
-            .into());
+        let arg = match (value, &param_config.default_value) {
+            // Passing `undefined` to an optional parameter also uses the
+            // default value.
+            (Some(Value::Undefined) | None, Some(default)) => Cow::Borrowed(default),
+            (Some(value), _) => Cow::Borrowed(value),
+            (None, None) if param_config.param_type_name.is_any() => {
+                return Ok(Value::Undefined);
+            }
+            (None, None) => {
+                return Err(format!(
+                    "Param {} (index {}) was missing when calling {}",
+                    param_config.param_name, index, method_name
+                )
+                .into());
+            }
         };
 
         let type_name = param_config.param_type_name.clone();
@@ -377,10 +389,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let signature = method.signature();
         if user_arguments.len() > signature.len() && !has_rest_or_args {
             return Err(format!(
-                "Attempted to call {:?} with {} arguments (more than {} is prohibited)",
+                "ArgumentError: Error #1063: Argument count mismatch on {}. Expected {}, got {}.",
                 method.method_name(),
-                user_arguments.len(),
-                signature.len()
+                signature.len(),
+                user_arguments.len()
+            )
+            .into());
+        }
+
+        // Unchecked functions leave missing parameters undefined instead.
+        let num_required = signature
+            .iter()
+            .take_while(|param| param.default_value.is_none())
+            .count();
+        if user_arguments.len() < num_required && !method.is_unchecked() {
+            return Err(format!(
+                "ArgumentError: Error #1063: Argument count mismatch on {}. Expected {}, got {}.",
+                method.method_name(),
+                num_required,
+                user_arguments.len()
             )
             .into());
         }
@@ -426,6 +453,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             caller_domain: outer.domain(),
             subclass_object,
             activation_class,
+            default_xml_namespace: None,
             context,
         };
 
@@ -445,7 +473,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
         if has_rest_or_args {
             let args_array = if method.method().needs_arguments_object {
-                ArrayStorage::from_args(&arguments_list)
+                // `arguments` only holds what was passed, not the defaults of
+                // omitted optional parameters.
+                let num_passed = user_arguments.len().min(arguments_list.len());
+                ArrayStorage::from_args(&arguments_list[..num_passed])
             } else if method.method().needs_rest {
                 if let Some(rest_args) = arguments_list.get(signature.len()..) {
                     ArrayStorage::from_args(rest_args)
@@ -503,6 +534,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             caller_domain,
             subclass_object,
             activation_class: None,
+            default_xml_namespace: None,
             context,
         })
     }
@@ -630,6 +662,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.subclass_object
     }
 
+    /// Resolve an E4X name against the default XML namespace, if one is set.
+    ///
+    /// Runtime names such as `xml["@id"]` are interpreted first, so that
+    /// attribute names never pick up the default namespace.
+    pub fn xml_name(&self, multiname: &Multiname<'gc>) -> Multiname<'gc> {
+        let multiname = multiname.as_xml_name(self.context.gc_context);
+        match self.default_xml_namespace {
+            Some(uri) => multiname.with_default_xml_namespace(uri),
+            None => multiname,
+        }
+    }
+
     /// Retrieve a int from the current constant pool.
     fn pool_int(
         &self,
@@ -802,6 +846,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::GetLocal { index } => self.op_get_local(index),
                 Op::SetLocal { index } => self.op_set_local(index),
                 Op::Kill { index } => self.op_kill(index),
+                Op::Dxns { index } => self.op_dxns(method, index),
+                Op::DxnsLate => self.op_dxns_late(),
                 Op::Call { num_args } => self.op_call(num_args),
                 Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
                 Op::CallProperty { index, num_args } => {
@@ -824,6 +870,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::SetProperty { index } => self.op_set_property(method, index),
                 Op::InitProperty { index } => self.op_init_property(method, index),
                 Op::DeleteProperty { index } => self.op_delete_property(method, index),
+                Op::GetDescendants { index } => self.op_get_descendants(method, index),
                 Op::GetSuper { index } => self.op_get_super(method, index),
                 Op::SetSuper { index } => self.op_set_super(method, index),
                 Op::In => self.op_in(),
@@ -851,6 +898,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::NewClass { index } => self.op_new_class(method, index),
                 Op::ApplyType { num_types } => self.op_apply_type(num_types),
                 Op::NewArray { num_args } => self.op_new_array(num_args),
+                Op::CheckFilter => self.op_check_filter(),
                 Op::CoerceA => self.op_coerce_a(),
                 Op::CoerceB => self.op_coerce_b(),
                 Op::CoerceD => self.op_coerce_d(),
@@ -1104,6 +1152,28 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        self.default_xml_namespace = Some(self.pool_string(&method, index)?);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        let uri = match value {
+            Value::Object(o) if o.as_namespace().is_some() => o.as_namespace().unwrap().as_uri(),
+            value => value.coerce_to_string(self)?,
+        };
+
+        self.default_xml_namespace = Some(uri);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_kill(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
         self.set_local_register(register_index, Value::Undefined, self.context.gc_context)?;
 
@@ -1127,7 +1197,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let receiver = self.pop_receiver()?;
 
         let value = receiver.call_method(index.0, &args, self)?;
 
@@ -1144,7 +1214,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let receiver = self.pop_receiver()?;
 
         let value = receiver.call_property(&multiname, &args, self)?;
 
@@ -1161,7 +1231,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let receiver = self.pop_receiver()?;
         let function = receiver
             .get_property(receiver, &multiname, self)?
             .coerce_to_object(self)?;
@@ -1180,7 +1250,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let receiver = self.pop_receiver()?;
 
         receiver.call_property(&multiname, &args, self)?;
 
@@ -1275,7 +1345,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // rather than it's string representation.
 
             let name_value = self.context.avm2.pop();
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
             if !name_value.is_primitive() {
                 if let Some(dictionary) = object.as_dictionary_object() {
                     let value =
@@ -1286,13 +1356,31 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 }
             }
 
+            // Numeric indices into arrays and vectors skip name resolution
+            // entirely.
+            if let Some(index) = name_value.as_array_index() {
+                let value = if let Some(array) = object.as_array_storage() {
+                    array.get(index)
+                } else if let Some(vector) = object.as_vector_storage() {
+                    vector.get(index).ok()
+                } else {
+                    None
+                };
+
+                if let Some(value) = value {
+                    self.context.avm2.push(value);
+
+                    return Ok(FrameControl::Continue);
+                }
+            }
+
             (
                 Multiname::from_multiname_late(txunit, abc_multiname, name_value, self)?,
                 object,
             )
         } else {
             let multiname = self.pool_multiname(method, index)?;
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
 
             (multiname, object)
         };
@@ -1321,7 +1409,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // rather than it's string representation.
 
             let name_value = self.context.avm2.pop();
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
             if !name_value.is_primitive() {
                 if let Some(dictionary) = object.as_dictionary_object() {
                     dictionary.set_property_by_object(
@@ -1334,13 +1422,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 }
             }
 
+            if let Some(index) = name_value.as_array_index() {
+                if let Some(mut array) = object.as_array_storage_mut(self.context.gc_context) {
+                    array.set(index, value);
+
+                    return Ok(FrameControl::Continue);
+                }
+            }
+
             (
                 Multiname::from_multiname_late(txunit, abc_multiname, name_value, self)?,
                 object,
             )
         } else {
             let multiname = self.pool_multiname(method, index)?;
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
 
             (multiname, object)
         };
@@ -1357,13 +1453,28 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
-        let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut object = self.pop_receiver()?;
 
         object.init_property(object, &multiname, value, self)?;
 
         Ok(FrameControl::Continue)
     }
 
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.pop_receiver()?;
+
+        let descendants = object.get_descendants(&multiname, self)?;
+
+        self.context.avm2.push(descendants);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_delete_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1381,7 +1492,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // rather than it's string representation.
 
             let name_value = self.context.avm2.pop();
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
             if !name_value.is_primitive() {
                 if let Some(dictionary) = object.as_dictionary_object() {
                     dictionary.delete_property_by_object(
@@ -1400,7 +1511,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             )
         } else {
             let multiname = self.pool_multiname(method, index)?;
-            let object = self.context.avm2.pop().coerce_to_object(self)?;
+            let object = self.pop_receiver()?;
 
             (multiname, object)
         };
@@ -1475,6 +1586,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Pop the receiver of a property access or method call off the stack.
+    ///
+    /// Flash reports `null` and `undefined` receivers with their own error
+    /// codes, rather than the generic coercion failure.
+    fn pop_receiver(&mut self) -> Result<Object<'gc>, Error> {
+        match self.context.avm2.pop() {
+            Value::Null => Err("TypeError: Error #1009: Cannot access a property or method of a null object reference.".into()),
+            Value::Undefined => Err("TypeError: Error #1010: A term is undefined and has no properties.".into()),
+            value => value.coerce_to_object(self),
+        }
+    }
+
     fn op_push_scope(&mut self) -> Result<FrameControl<'gc>, Error> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         self.scope_stack.push(Scope::new(object));
@@ -1483,7 +1606,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_push_with(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let object = match self.context.avm2.pop() {
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #1009: Cannot access a property or method of a null object reference.".into())
+            }
+            value => value.coerce_to_object(self)?,
+        };
         self.scope_stack.push(Scope::new_with(object));
 
         Ok(FrameControl::Continue)
@@ -1554,10 +1682,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname(method, index)?;
         avm_debug!(self.context.avm2, "Resolving {:?}", multiname);
-        let found: Result<Object<'gc>, Error> = self
+        let result: Value<'gc> = self
             .find_definition(&multiname)?
-            .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
-        let result: Value<'gc> = found?.into();
+            .ok_or_else(|| undefined_variable_error(&multiname))?
+            .into();
 
         self.context.avm2.push(result);
 
@@ -1571,17 +1699,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname_static(method, index)?;
         avm_debug!(self.avm2(), "Resolving {:?}", multiname);
-        let found: Result<Value<'gc>, Error> = self
+        let found = self
             .resolve_definition(&multiname)?
-            .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
+            .ok_or_else(|| undefined_variable_error(&multiname))?;
 
-        self.context.avm2.push(found?);
+        self.context.avm2.push(found);
 
         Ok(FrameControl::Continue)
     }
 
     fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let object = self.pop_receiver()?;
         let value = object.get_slot(index)?;
 
         self.context.avm2.push(value);
@@ -1591,7 +1719,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let object = self.pop_receiver()?;
 
         object.set_slot(index, value, self.context.gc_context)?;
 
@@ -1620,13 +1748,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// The value that `new` evaluates to for a newly constructed object.
+    ///
+    /// `Number`, `int`, `uint`, `String` and `Boolean` construct a
+    /// `PrimitiveObject` to run their initializers on, but `new` on those
+    /// classes produces the primitive itself.
+    fn constructed_value(object: Object<'gc>) -> Value<'gc> {
+        let primitive = object.as_primitive().map(|primitive| *primitive);
+
+        primitive.unwrap_or_else(|| object.into())
+    }
+
     fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let ctor = self.context.avm2.pop().coerce_to_object(self)?;
 
         let object = ctor.construct(self, &args)?;
 
-        self.context.avm2.push(object);
+        self.context.avm2.push(Self::constructed_value(object));
 
         Ok(FrameControl::Continue)
     }
@@ -1639,11 +1778,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
-        let source = self.context.avm2.pop().coerce_to_object(self)?;
+        let source = self.pop_receiver()?;
 
         let object = source.construct_prop(&multiname, &args, self)?;
 
-        self.context.avm2.push(object);
+        self.context.avm2.push(Self::constructed_value(object));
 
         Ok(FrameControl::Continue)
     }
@@ -1672,10 +1811,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
         let mut object = self.context.avm2.classes().object.construct(self, &[])?;
 
+        // Pairs are popped last-first; reverse them so that properties are
+        // created (and later enumerated) in the literal's source order, and
+        // so that a repeated key keeps its last value.
+        let mut pairs = Vec::with_capacity(num_args as usize);
         for _ in 0..num_args {
             let value = self.context.avm2.pop();
             let name = self.context.avm2.pop();
 
+            pairs.push((name, value));
+        }
+
+        for (name, value) in pairs.into_iter().rev() {
             object.set_property(
                 object,
                 &QName::dynamic_name(name.coerce_to_string(self)?).into(),
@@ -1756,6 +1903,40 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `Op::CheckFilter`, which guards the target of an E4X filter
+    /// expression (`list.(predicate)`).
+    fn op_check_filter(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+
+        if let Value::Object(object) = value {
+            if object.as_xml_object().is_some() || object.as_xml_list_object().is_some() {
+                self.context.avm2.push(value);
+
+                return Ok(FrameControl::Continue);
+            }
+        }
+
+        let type_name = match value {
+            Value::Undefined => "void".to_string(),
+            Value::Null => "null".to_string(),
+            Value::Bool(_) => "Boolean".to_string(),
+            Value::Number(_) => "Number".to_string(),
+            Value::Unsigned(_) => "uint".to_string(),
+            Value::Integer(_) => "int".to_string(),
+            Value::String(_) => "String".to_string(),
+            Value::Object(object) => object
+                .instance_of_class_definition()
+                .map(|class| class.read().name().local_name().to_string())
+                .unwrap_or_else(|| "Object".to_string()),
+        };
+
+        Err(format!(
+            "TypeError: Error #1123: Filter operator not supported on type {}.",
+            type_name
+        )
+        .into())
+    }
+
     fn op_coerce_a(&mut self) -> Result<FrameControl<'gc>, Error> {
         Ok(FrameControl::Continue)
     }
@@ -2453,11 +2634,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index_register: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let mut cur_index = self.local_register(index_register)?.coerce_to_u32(self)?;
-        let mut object = Some(
-            self.local_register(object_register)?
-                .coerce_to_object(self)?,
-        );
 
+        // Enumerating `null` or `undefined` (including the `null` left behind
+        // once a prior enumeration ran off the end of the prototype chain)
+        // visits nothing.
+        let mut object = match self.local_register(object_register)? {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_object(self)?),
+        };
+
+        // Enumerate the object's own properties first, then continue through
+        // each prototype in turn, restarting the index at each step.
         while let Some(cur_object) = object {
             if let Some(index) = cur_object.get_next_enumerant(cur_index, self)? {
                 cur_index = index;
@@ -2541,9 +2728,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop();
 
-        let type_object = type_object
-            .as_class_object()
-            .ok_or_else(|| Error::from("Attempted to check against non-type"))?;
+        let type_object = type_object.as_class_object().ok_or_else(|| {
+            Error::from("TypeError: Error #1041: The right-hand side of operator must be a class.")
+        })?;
 
         let is_instance_of = value.is_of_type(self, type_object)?;
 
@@ -2557,7 +2744,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         type_name_index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
         let multiname = self.pool_multiname_static(method, type_name_index)?;
         let found: Result<Value<'gc>, Error> =
@@ -2572,10 +2759,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .coerce_to_object(self)?
             .as_class_object()
             .ok_or_else(|| {
-                Error::from("TypeError: The right-hand side of operator must be a class.")
+                Error::from(
+                    "TypeError: Error #1041: The right-hand side of operator must be a class.",
+                )
             })?;
 
-        if value.is_of_type(class, self)? {
+        if value.is_of_type(self, class)? {
             self.context.avm2.push(value);
         } else {
             self.context.avm2.push(Value::Null);
@@ -2586,13 +2775,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn op_as_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
         let class = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
         let class = class.as_class_object().ok_or_else(|| {
-            Error::from("TypeError: The right-hand side of operator must be a class.")
+            Error::from("TypeError: Error #1041: The right-hand side of operator must be a class.")
         })?;
 
-        if value.is_of_type(class, self)? {
+        if value.is_of_type(self, class)? {
             self.context.avm2.push(value);
         } else {
             self.context.avm2.push(Value::Null);
@@ -2639,7 +2828,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                             "object"
                         }
                     }
-                    Object::XmlObject(_) => {
+                    Object::XmlObject(_) | Object::XmlListObject(_) => {
                         if is_not_subclass {
                             "xml"
                         } else {
@@ -3036,3 +3225,1369 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 }
+
+/// The error thrown when a name cannot be found on the scope chain or in the
+/// domain.
+fn undefined_variable_error(name: &Multiname<'_>) -> Error {
+    format!(
+        "ReferenceError: Error #1065: Variable {} is not defined.",
+        name.local_name()
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| "*".to_string())
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::ScriptObject;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::{Activation, Error, Object, TObject, Value};
+    use swf::avm2::types::Index;
+
+    /// Call a function of an object and a key, and return what `trace` would
+    /// print for the result.
+    fn get_traced<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        function: Object<'gc>,
+        object: Object<'gc>,
+        key: Value<'gc>,
+    ) -> Result<String, Error> {
+        let value = function.call(None, &[object.into(), key], activation)?;
+
+        traced(activation, value)
+    }
+
+    #[test]
+    fn runtime_multinames() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let name = abc.runtime_name().0 as u8;
+            let attribute = abc.runtime_attribute_name().0 as u8;
+            let params = || vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function getElement(object, key) { return object[key]; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0x66, name,                     // getproperty [key]
+                0x48,                           // returnvalue
+            ];
+            abc.method("getElement", params(), &code);
+
+            // function setElement(object, key, value) { object[key] = value; }
+            let mut set_params = params();
+            set_params.push(param(Index::new(0), None));
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0xd3,                           // getlocal3
+                0x61, name,                     // setproperty [key]
+                0x47,                           // returnvoid
+            ];
+            abc.method("setElement", set_params, &code);
+
+            // function getAttribute(xml, key) { return xml.@[key]; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0x66, attribute,                // getproperty @[key]
+                0x48,                           // returnvalue
+            ];
+            abc.method("getAttribute", params(), &code);
+
+            let functions = abc.functions(activation)?;
+            let (get_element, set_element, get_attribute) =
+                (functions[0], functions[1], functions[2]);
+            let object = construct(activation, "", "Object", &[])?;
+            let args = [object.into(), "dynProp".into(), "dynamic value".into()];
+            set_element.call(None, &args, activation)?;
+            assert_eq!(
+                get_traced(activation, get_element, object, "dynProp".into())?,
+                "dynamic value"
+            );
+            let args = [object.into(), 5.into(), "five".into()];
+            set_element.call(None, &args, activation)?;
+            assert_eq!(
+                get_traced(activation, get_element, object, "5".into())?,
+                "five"
+            );
+
+            let values = ["zero".into(), "one".into(), "two".into()];
+            let array = array(activation, &values)?;
+            assert_eq!(
+                get_traced(activation, get_element, array, 1.0.into())?,
+                "one"
+            );
+            assert_eq!(
+                get_traced(activation, get_element, array, "2".into())?,
+                "two"
+            );
+            assert_eq!(
+                get_traced(activation, get_element, array, 0u32.into())?,
+                "zero"
+            );
+            assert_eq!(
+                get_traced(activation, get_element, array, 10.into())?,
+                "undefined"
+            );
+            for (key, length) in [(Value::from(-1), 3), (1.5.into(), 3), (5.into(), 6)] {
+                let args = [array.into(), key, "value".into()];
+                set_element.call(None, &args, activation)?;
+                assert_eq!(get_traced(activation, get_element, array, key)?, "value");
+                assert_eq!(get(activation, array, "length")?, length.into());
+            }
+
+            // `QName` keys carry their own namespace.
+            let holder = construct(activation, "", "Object", &[])?;
+            let custom = Namespace::Namespace("http://example.com/custom".into());
+            let prop = QName::new(custom, "prop");
+            holder.set_property_local(holder, prop, "custom".into(), activation)?;
+            set(activation, holder, "publicProp", "public")?;
+
+            let ns = construct(
+                activation,
+                "",
+                "Namespace",
+                &["http://example.com/custom".into()],
+            )?;
+            let key = construct(activation, "", "QName", &[ns.into(), "prop".into()])?;
+            assert_eq!(
+                get_traced(activation, get_element, holder, key.into())?,
+                "custom"
+            );
+            let public_key = construct(activation, "", "QName", &["".into(), "publicProp".into()])?;
+            assert_eq!(
+                get_traced(activation, get_element, holder, public_key.into())?,
+                "public"
+            );
+
+            let args = [holder.into(), key.into(), "changed again".into()];
+            set_element.call(None, &args, activation)?;
+            let value = holder.get_property_local(holder, prop, activation)?;
+            assert_eq!(value, "changed again".into());
+
+            let source = r#"<item id="7" kind="fruit"><name>apple</name></item>"#;
+            let xml = construct(activation, "", "XML", &[source.into()])?;
+            let args = [xml.into(), "kind".into()];
+            let kind = get_attribute.call(None, &args, activation)?;
+            assert_eq!(traced(activation, kind)?, "fruit");
+            let args = [xml.into(), "*".into()];
+            let attributes = get_attribute.call(None, &args, activation)?;
+            let attributes = attributes.coerce_to_object(activation)?;
+            assert_eq!(call(activation, attributes, "length", &[])?, 2.into());
+            assert_eq!(get_traced(activation, get_element, xml, "@id".into())?, "7");
+            let children = get_element.call(None, &[xml.into(), "*".into()], activation)?;
+            let children = children.coerce_to_object(activation)?;
+            assert_eq!(call(activation, children, "length", &[])?, 1.into());
+
+            Ok(())
+        })
+    }
+
+    /// The bytecode the compiler emits for `list.(predicate)`, given the
+    /// bytecode of the predicate and the index of a runtime multiname.
+    ///
+    /// The function takes the list and an empty `XMLList` to collect the
+    /// matching members into.
+    fn filter_code(predicate: &[u8], runtime_name: u8) -> Vec<u8> {
+        let len = predicate.len() as i32;
+        let jump = |offset: i32| offset.to_le_bytes()[..3].to_vec();
+
+        #[rustfmt::skip]
+        let prologue = [
+            0xd1,                           // getlocal1
+            0x78,                           // checkfilter
+            0x29,                           // pop
+            0x24, 0x00,                     // pushbyte 0
+            0xd7,                           // setlocal3
+            0x10,                           // jump condition
+        ];
+        #[rustfmt::skip]
+        let body = [
+            0x09,                           // label
+            0xd1,                           // getlocal1
+            0xd3,                           // getlocal3
+            0x23,                           // nextvalue
+            0x2a,                           // dup
+            0x63, 0x04,                     // setlocal 4
+            0x1c,                           // pushwith
+        ];
+        #[rustfmt::skip]
+        let append = [
+            0x1d,                           // popscope
+            0x12, 0x06, 0x00, 0x00,         // iffalse skip
+            0xd2,                           // getlocal2
+            0xd3,                           // getlocal3
+            0x62, 0x04,                     // getlocal 4
+            0x61, runtime_name,             // setproperty [index]
+            0x32, 0x01, 0x03,               // skip: condition: hasnext2 1, 3
+            0x11,                           // iftrue body
+        ];
+        #[rustfmt::skip]
+        let epilogue = [
+            0xd2,                           // getlocal2
+            0x48,                           // returnvalue
+        ];
+
+        let mut code = prologue.to_vec();
+        code.extend(jump(19 + len));
+        code.extend(body);
+        code.extend(predicate);
+        code.extend(append);
+        code.extend(jump(-(26 + len)));
+        code.extend(epilogue);
+        code
+    }
+
+    #[test]
+    fn xml_filters() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let index = abc.runtime_name().0 as u8;
+            let id = abc.attribute_name("id").0 as u8;
+            let two = abc.string("2").0 as u8;
+            let salary = abc.name("", "salary").0 as u8;
+            let employee = abc.name("", "employee").0 as u8;
+            let length = abc.name("", "length").0 as u8;
+            let params = || vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // list.(@id == "2")
+            #[rustfmt::skip]
+            let predicate = [
+                0x5d, id,                       // findpropstrict @id
+                0x66, id,                       // getproperty @id
+                0x2c, two,                      // pushstring "2"
+                0xab,                           // equals
+            ];
+            abc.method("byId", params(), &filter_code(&predicate, index));
+
+            // list.(salary > 1000)
+            #[rustfmt::skip]
+            let predicate = [
+                0x5d, salary,                   // findpropstrict salary
+                0x66, salary,                   // getproperty salary
+                0x25, 0xe8, 0x07,               // pushshort 1000
+                0xaf,                           // greaterthan
+            ];
+            abc.method("wellPaid", params(), &filter_code(&predicate, index));
+
+            // list.(salary > 5000)
+            #[rustfmt::skip]
+            let predicate = [
+                0x5d, salary,                   // findpropstrict salary
+                0x66, salary,                   // getproperty salary
+                0x25, 0x88, 0x27,               // pushshort 5000
+                0xaf,                           // greaterthan
+            ];
+            abc.method("overpaid", params(), &filter_code(&predicate, index));
+
+            // list.(employee.length() == 3)
+            #[rustfmt::skip]
+            let predicate = [
+                0x5d, employee,                 // findpropstrict employee
+                0x66, employee,                 // getproperty employee
+                0x46, length, 0x00,             // callproperty length 0
+                0x24, 0x03,                     // pushbyte 3
+                0xab,                           // equals
+            ];
+            abc.method("threeEmployees", params(), &filter_code(&predicate, index));
+
+            let functions = abc.functions(activation)?;
+            let (by_id, well_paid, overpaid, three_employees) =
+                (functions[0], functions[1], functions[2], functions[3]);
+
+            let source = concat!(
+                "<staff>",
+                r#"<employee id="1"><first>Ann</first><salary>900</salary></employee>"#,
+                r#"<employee id="2"><first>Bob</first><salary>1500</salary></employee>"#,
+                r#"<employee id="3"><first>Cy</first><salary>2000</salary></employee>"#,
+                "</staff>",
+            );
+            let staff = construct(activation, "", "XML", &[source.into()])?;
+            let employees = get(activation, staff, "employee")?;
+
+            let result = construct(activation, "", "XMLList", &[])?;
+            let matches = by_id.call(None, &[employees, result.into()], activation)?;
+            let matches = matches.coerce_to_object(activation)?;
+            assert_eq!(call(activation, matches, "length", &[])?, 1.into());
+            let first = get(activation, matches, "first")?;
+            assert_eq!(traced(activation, first)?, "Bob");
+
+            let result = construct(activation, "", "XMLList", &[])?;
+            let matches = well_paid.call(None, &[employees, result.into()], activation)?;
+            let matches = matches.coerce_to_object(activation)?;
+            assert_eq!(call(activation, matches, "length", &[])?, 2.into());
+            let first = get(activation, matches, "first")?.coerce_to_object(activation)?;
+            let first = call(activation, first, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, first)?,
+                "<first>Bob</first>\n<first>Cy</first>"
+            );
+
+            let result = construct(activation, "", "XMLList", &[])?;
+            let matches = overpaid.call(None, &[employees, result.into()], activation)?;
+            let matches = matches.coerce_to_object(activation)?;
+            assert_eq!(call(activation, matches, "length", &[])?, 0.into());
+
+            // A lone XML value is filtered like a list containing only itself.
+            let result = construct(activation, "", "XMLList", &[])?;
+            let args = [staff.into(), result.into()];
+            let matches = three_employees.call(None, &args, activation)?;
+            let matches = matches.coerce_to_object(activation)?;
+            assert_eq!(call(activation, matches, "length", &[])?, 1.into());
+
+            let array = array(activation, &[])?;
+            let result = construct(activation, "", "XMLList", &[])?;
+            let error = by_id
+                .call(None, &[array.into(), result.into()], activation)
+                .expect_err("arrays cannot be filtered");
+            assert_eq!(
+                error.to_string(),
+                "TypeError: Error #1123: Filter operator not supported on type Array."
+            );
+
+            Ok(())
+        })
+    }
+
+    /// The bytecode the compiler emits for
+    /// `for (var name in object) { result += name + ","; }`, where
+    /// `next` is `nextname`, or `nextvalue` for a `for each` loop.
+    fn enumeration_code(next: u8, empty: u8, comma: u8) -> Vec<u8> {
+        #[rustfmt::skip]
+        let code = vec![
+            0x2c, empty,                    // pushstring ""
+            0x63, 0x04,                     // setlocal 4
+            0x24, 0x00,                     // pushbyte 0
+            0xd7,                           // setlocal3
+            0x10, 0x0c, 0x00, 0x00,         // jump condition
+            0x09,                           // body: label
+            0x62, 0x04,                     // getlocal 4
+            0xd1,                           // getlocal1
+            0xd3,                           // getlocal3
+            next,                           // nextname or nextvalue
+            0xa0,                           // add
+            0x2c, comma,                    // pushstring ","
+            0xa0,                           // add
+            0x63, 0x04,                     // setlocal 4
+            0x32, 0x01, 0x03,               // condition: hasnext2 1, 3
+            0x11, 0xed, 0xff, 0xff,         // iftrue body
+            0x62, 0x04,                     // getlocal 4
+            0x48,                           // returnvalue
+        ];
+
+        code
+    }
+
+    #[test]
+    fn enumeration_follows_the_prototype_chain() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let empty = abc.string("").0 as u8;
+            let comma = abc.string(",").0 as u8;
+            let params = vec![param(Index::new(0), None)];
+            abc.method(
+                "names",
+                params.clone(),
+                &enumeration_code(0x1e, empty, comma),
+            );
+            abc.method("values", params, &enumeration_code(0x23, empty, comma));
+            let functions = abc.functions(activation)?;
+            let (names, values) = (functions[0], functions[1]);
+
+            let mc = activation.context.gc_context;
+            let base_proto = construct(activation, "", "Object", &[])?;
+            set(activation, base_proto, "baseProperty", "base")?;
+            let derived_proto = ScriptObject::object(mc, base_proto);
+            set(activation, derived_proto, "derivedProperty", "derived")?;
+            let instance = ScriptObject::object(mc, derived_proto);
+            set(activation, instance, "ownProperty", "own")?;
+
+            let enumerated = names.call(None, &[instance.into()], activation)?;
+            assert_eq!(
+                traced(activation, enumerated)?,
+                "ownProperty,derivedProperty,baseProperty,"
+            );
+            let enumerated = values.call(None, &[instance.into()], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "own,derived,base,");
+
+            instance.delete_property(activation, &public_name("ownProperty"))?;
+            let enumerated = names.call(None, &[instance.into()], activation)?;
+            assert_eq!(
+                traced(activation, enumerated)?,
+                "derivedProperty,baseProperty,"
+            );
+
+            let enumerated = names.call(None, &[Value::Null], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn xml_descendants() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let item = abc.name("", "item").0 as u8;
+            let section = abc.name("", "section").0 as u8;
+            let id = abc.attribute_name("id").0 as u8;
+            let any = abc.any_name().0 as u8;
+            let missing = abc.name("", "missing").0 as u8;
+            let params = || vec![param(Index::new(0), None)];
+
+            // function items(xml) { return xml..item; }
+            abc.method("items", params(), &[0xd1, 0x59, item, 0x48]);
+            // function sectionItems(xml) { return xml..section..item; }
+            abc.method(
+                "sectionItems",
+                params(),
+                &[0xd1, 0x59, section, 0x59, item, 0x48],
+            );
+            // function ids(xml) { return xml..@id; }
+            abc.method("ids", params(), &[0xd1, 0x59, id, 0x48]);
+            // function everything(xml) { return xml..*; }
+            abc.method("everything", params(), &[0xd1, 0x59, any, 0x48]);
+            // function missing(xml) { return xml..missing; }
+            abc.method("missing", params(), &[0xd1, 0x59, missing, 0x48]);
+            let functions = abc.functions(activation)?;
+
+            let source = concat!(
+                r#"<catalog><item id="1">first</item>"#,
+                r#"<section><item id="2">second</item>"#,
+                r#"<group><item id="3">third</item></group></section>"#,
+                "<note>not an item</note></catalog>",
+            );
+            let doc = construct(activation, "", "XML", &[source.into()])?;
+            let mut lists = Vec::new();
+            for function in functions {
+                let list = function.call(None, &[doc.into()], activation)?;
+                lists.push(list.coerce_to_object(activation)?);
+            }
+
+            let lengths = lists
+                .iter()
+                .map(|list| call(activation, *list, "length", &[]))
+                .collect::<Result<Vec<_>, _>>()?;
+            let expected: [Value; 5] = [3.into(), 2.into(), 3.into(), 10.into(), 0.into()];
+            assert_eq!(lengths, expected);
+
+            let items = call(activation, lists[0], "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, items)?,
+                concat!(
+                    "<item id=\"1\">first</item>\n",
+                    "<item id=\"2\">second</item>\n",
+                    "<item id=\"3\">third</item>",
+                )
+            );
+            let ids = call(activation, lists[2], "toString", &[])?;
+            assert_eq!(traced(activation, ids)?, "123");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn strict_equality_of_constructed_values() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let number = abc.name("", "Number").0 as u8;
+            let string = abc.name("", "String").0 as u8;
+            let object = abc.name("", "Object").0 as u8;
+            let params = || vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function strictEquals(a, b) { return a === b; }
+            abc.method("strictEquals", params(), &[0xd1, 0xd2, 0xac, 0x48]);
+            // function typeOf(a) { return typeof a; }
+            abc.method("typeOf", params(), &[0xd1, 0x95, 0x48]);
+            // function newNumber(a) { return new Number(a); }
+            #[rustfmt::skip]
+            let code = [
+                0x5d, number,                   // findpropstrict Number
+                0xd1,                           // getlocal1
+                0x4a, number, 0x01,             // constructprop Number 1
+                0x48,                           // returnvalue
+            ];
+            abc.method("newNumber", params(), &code);
+            // function newString(a) { return new String(a); }
+            #[rustfmt::skip]
+            let code = [
+                0x5d, string,                   // findpropstrict String
+                0xd1,                           // getlocal1
+                0x4a, string, 0x01,             // constructprop String 1
+                0x48,                           // returnvalue
+            ];
+            abc.method("newString", params(), &code);
+            // function wrap(a) { return Object(a); }
+            #[rustfmt::skip]
+            let code = [
+                0x5d, object,                   // findpropstrict Object
+                0xd1,                           // getlocal1
+                0x46, object, 0x01,             // callproperty Object 1
+                0x48,                           // returnvalue
+            ];
+            abc.method("wrap", params(), &code);
+
+            let functions = abc.functions(activation)?;
+            let (strict_equals, type_of, new_number, new_string, wrap) = (
+                functions[0],
+                functions[1],
+                functions[2],
+                functions[3],
+                functions[4],
+            );
+            assert_eq!(
+                call_with(activation, strict_equals, 5.into(), 5.0.into())?,
+                true.into()
+            );
+
+            let boxed = new_number.call(None, &[5.into()], activation)?;
+            let other_boxed = new_number.call(None, &[5.into()], activation)?;
+            assert_eq!(
+                call_with(activation, strict_equals, boxed, 5.into())?,
+                true.into()
+            );
+            assert_eq!(
+                call_with(activation, strict_equals, 5.into(), boxed)?,
+                true.into()
+            );
+            assert_eq!(type_of.call(None, &[boxed], activation)?, "number".into());
+            assert_eq!(
+                call_with(activation, strict_equals, boxed, other_boxed)?,
+                true.into()
+            );
+
+            let wrapped = wrap.call(None, &[5.into()], activation)?;
+            assert_eq!(
+                call_with(activation, strict_equals, wrapped, 5.into())?,
+                true.into()
+            );
+            assert_eq!(
+                call_with(activation, strict_equals, wrapped, "5".into())?,
+                false.into()
+            );
+
+            let string = new_string.call(None, &["a".into()], activation)?;
+            assert_eq!(
+                call_with(activation, strict_equals, string, "a".into())?,
+                true.into()
+            );
+
+            let a = construct(activation, "", "Object", &[])?;
+            let b = construct(activation, "", "Object", &[])?;
+            assert_eq!(
+                call_with(activation, strict_equals, a.into(), b.into())?,
+                false.into()
+            );
+            assert_eq!(
+                call_with(activation, strict_equals, a.into(), a.into())?,
+                true.into()
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn as_and_is_on_primitives() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let string = abc.name("", "String").0 as u8;
+            let number = abc.name("", "Number").0 as u8;
+            let params = || vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function asString(a) { return a as String; }
+            abc.method("asString", params(), &[0xd1, 0x86, string, 0x48]);
+            // function asNumber(a) { return a as Number; }
+            abc.method("asNumber", params(), &[0xd1, 0x86, number, 0x48]);
+            // function isString(a) { return a is String; }
+            abc.method("isString", params(), &[0xd1, 0xb2, string, 0x48]);
+            // function asLate(a, type) { return a as type; }
+            abc.method("asLate", params(), &[0xd1, 0xd2, 0x87, 0x48]);
+            // function isLate(a, type) { return a is type; }
+            abc.method("isLate", params(), &[0xd1, 0xd2, 0xb3, 0x48]);
+            // function toString(a) { var s:String = a; return s; }
+            abc.method("toString", params(), &[0xd1, 0x80, string, 0x48]);
+
+            let functions = abc.functions(activation)?;
+            let (as_string, as_number, is_string, as_late, is_late, to_string) = (
+                functions[0],
+                functions[1],
+                functions[2],
+                functions[3],
+                functions[4],
+                functions[5],
+            );
+
+            let some_class = define_class(activation, "SomeClass", None, &[], &[])?;
+            let other_class = define_class(activation, "OtherClass", None, &[], &[])?;
+            let string_class = activation.avm2().classes().string;
+            let instance = some_class.construct(activation, &[])?;
+
+            let result = call_with(activation, as_late, Value::Null, some_class.into())?;
+            assert_eq!(result, Value::Null);
+            assert_eq!(as_string.call(None, &[5.into()], activation)?, Value::Null);
+            assert_eq!(as_number.call(None, &[5.into()], activation)?, 5.into());
+            assert_eq!(as_string.call(None, &["x".into()], activation)?, "x".into());
+            let result = call_with(activation, as_late, instance.into(), some_class.into())?;
+            assert_eq!(result, instance.into());
+            let result = call_with(activation, as_late, instance.into(), other_class.into())?;
+            assert_eq!(result, Value::Null);
+
+            assert_eq!(
+                is_string.call(None, &["x".into()], activation)?,
+                true.into()
+            );
+            assert_eq!(is_string.call(None, &[5.into()], activation)?, false.into());
+            let result = call_with(activation, is_late, Value::Null, some_class.into())?;
+            assert_eq!(result, false.into());
+            let result = call_with(activation, is_late, instance.into(), some_class.into())?;
+            assert_eq!(result, true.into());
+            let result = call_with(activation, as_late, 5.into(), string_class.into())?;
+            assert_eq!(result, Value::Null);
+            let result = call_with(activation, is_late, "x".into(), string_class.into())?;
+            assert_eq!(result, true.into());
+
+            assert_eq!(
+                to_string.call(None, &[Value::Null], activation)?,
+                Value::Null
+            );
+            let result = to_string.call(None, &[Value::Undefined], activation)?;
+            assert_eq!(result, Value::Null);
+
+            for function in [as_late, is_late] {
+                let error = call_with(activation, function, 5.into(), 5.into())
+                    .expect_err("5 is not a class");
+                assert_eq!(
+                    error.to_string(),
+                    "TypeError: Error #1041: The right-hand side of operator must be a class."
+                );
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn object_literals_keep_source_order() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let empty = abc.string("").0 as u8;
+            let comma = abc.string(",").0 as u8;
+            let [a, b, zebra, apple, mango] =
+                ["a", "b", "zebra", "apple", "mango"].map(|name| abc.string(name).0 as u8);
+            let params = || vec![param(Index::new(0), None)];
+
+            abc.method("names", params(), &enumeration_code(0x1e, empty, comma));
+            abc.method("values", params(), &enumeration_code(0x23, empty, comma));
+            // function firstNameType(object) { return typeof <first name>; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x24, 0x01,                     // pushbyte 1
+                0x1e,                           // nextname
+                0x95,                           // typeof
+                0x48,                           // returnvalue
+            ];
+            abc.method("firstNameType", params(), &code);
+            // function numeric() { return {2: "a", 1: "b"}; }
+            #[rustfmt::skip]
+            let code = [
+                0x24, 0x02, 0x2c, a,            // 2: "a"
+                0x24, 0x01, 0x2c, b,            // 1: "b"
+                0x55, 0x02,                     // newobject 2
+                0x48,                           // returnvalue
+            ];
+            abc.method("numeric", params(), &code);
+            // function named() { return {zebra: 1, apple: 2, mango: 3}; }
+            #[rustfmt::skip]
+            let code = [
+                0x2c, zebra, 0x24, 0x01,        // zebra: 1
+                0x2c, apple, 0x24, 0x02,        // apple: 2
+                0x2c, mango, 0x24, 0x03,        // mango: 3
+                0x55, 0x03,                     // newobject 3
+                0x48,                           // returnvalue
+            ];
+            abc.method("named", params(), &code);
+            // function repeated() { return {a: 1, b: 2, a: 3}; }
+            #[rustfmt::skip]
+            let code = [
+                0x2c, a, 0x24, 0x01,            // a: 1
+                0x2c, b, 0x24, 0x02,            // b: 2
+                0x2c, a, 0x24, 0x03,            // a: 3
+                0x55, 0x03,                     // newobject 3
+                0x48,                           // returnvalue
+            ];
+            abc.method("repeated", params(), &code);
+
+            let functions = abc.functions(activation)?;
+            let (names, values, first_name_type) = (functions[0], functions[1], functions[2]);
+            let (numeric, named, repeated) = (functions[3], functions[4], functions[5]);
+
+            let object = numeric.call(None, &[], activation)?;
+            let enumerated = names.call(None, &[object], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "2,1,");
+            let enumerated = values.call(None, &[object], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "a,b,");
+            let name_type = first_name_type.call(None, &[object], activation)?;
+            assert_eq!(name_type, "string".into());
+            let object = object.coerce_to_object(activation)?;
+            assert_eq!(get(activation, object, "length")?, Value::Undefined);
+
+            let object = named.call(None, &[], activation)?;
+            let enumerated = names.call(None, &[object], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "zebra,apple,mango,");
+
+            let object = repeated
+                .call(None, &[], activation)?
+                .coerce_to_object(activation)?;
+            assert_eq!(get(activation, object, "a")?, 3.into());
+
+            let list = array(activation, &[3.into(), 1.into(), 2.into()])?;
+            let enumerated = names.call(None, &[list.into()], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "0,1,2,");
+            let enumerated = values.call(None, &[list.into()], activation)?;
+            assert_eq!(traced(activation, enumerated)?, "3,1,2,");
+
+            Ok(())
+        })
+    }
+
+    /// A top-level function declared by a script.
+    fn top_level_function<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("topLevelFunction".into())
+    }
+
+    #[test]
+    fn global_scope_resolution() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let variable = abc.name("", "topLevelVariable").0 as u8;
+            let top_level_function = abc.name("", "topLevelFunction").0 as u8;
+            let missing = abc.name("", "missing").0 as u8;
+            let math = abc.name("", "Math").0 as u8;
+            let params = || vec![param(Index::new(0), None)];
+
+            // The methods take the global object of their script.
+            let in_global_scope = |code: &[u8]| [&[0xd1u8, 0x30][..], code].concat();
+            // return findpropstrict(topLevelVariable);
+            #[rustfmt::skip]
+            let code = in_global_scope(&[
+                0x5d, variable,                 // findpropstrict topLevelVariable
+                0x48,                           // returnvalue
+            ]);
+            abc.method("findVariable", params(), &code);
+            // return findproperty(missing);
+            #[rustfmt::skip]
+            let code = in_global_scope(&[
+                0x5e, missing,                  // findproperty missing
+                0x48,                           // returnvalue
+            ]);
+            abc.method("findMissing", params(), &code);
+            // return findpropstrict(missing);
+            #[rustfmt::skip]
+            let code = in_global_scope(&[
+                0x5d, missing,                  // findpropstrict missing
+                0x48,                           // returnvalue
+            ]);
+            abc.method("findMissingStrict", params(), &code);
+            // return getglobalscope;
+            #[rustfmt::skip]
+            let code = in_global_scope(&[
+                0x64,                           // getglobalscope
+                0x48,                           // returnvalue
+            ]);
+            abc.method("globalScope", params(), &code);
+            // return topLevelFunction() + topLevelVariable;
+            #[rustfmt::skip]
+            let code = in_global_scope(&[
+                0x5d, top_level_function,       // findpropstrict topLevelFunction
+                0x46, top_level_function, 0x00, // callproperty topLevelFunction 0
+                0x5d, variable,                 // findpropstrict topLevelVariable
+                0x66, variable,                 // getproperty topLevelVariable
+                0xa0,                           // add
+                0x48,                           // returnvalue
+            ]);
+            abc.method("useTopLevel", params(), &code);
+            // return Math;
+            #[rustfmt::skip]
+            let code = [
+                0x5d, math,                     // findpropstrict Math
+                0x66, math,                     // getproperty Math
+                0x48,                           // returnvalue
+            ];
+            abc.method("getMath", params(), &code);
+
+            let functions = abc.functions(activation)?;
+            let (find_variable, find_missing, find_missing_strict) =
+                (functions[0], functions[1], functions[2]);
+            let (global_scope, use_top_level, get_math) =
+                (functions[3], functions[4], functions[5]);
+
+            let global = construct(activation, "", "Object", &[])?;
+            set(activation, global, "topLevelVariable", "topLevelVariable")?;
+            let declared = function(activation, top_level_function)?;
+            set(activation, global, "topLevelFunction", declared)?;
+            let args = [global.into()];
+
+            assert_eq!(find_variable.call(None, &args, activation)?, global.into());
+            assert_eq!(find_missing.call(None, &args, activation)?, global.into());
+            assert_eq!(global_scope.call(None, &args, activation)?, global.into());
+            let error = find_missing_strict
+                .call(None, &args, activation)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ReferenceError: Error #1065: Variable missing is not defined."
+            );
+            let result = use_top_level.call(None, &args, activation)?;
+            assert_eq!(
+                traced(activation, result)?,
+                "topLevelFunctiontopLevelVariable"
+            );
+
+            // Names missing from the scope chain are found in the domain.
+            let math_class = class(activation, "", "Math")?;
+            assert_eq!(get_math.call(None, &args, activation)?, math_class.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn with_scopes() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let [x, y, label, outside] =
+                ["x", "y", "label", "outside"].map(|name| abc.name("", name).0 as u8);
+            let space = abc.string(" ").0 as u8;
+            let params = |count| vec![param(Index::new(0), None); count];
+
+            // function sum(point) { with (point) { return x + y; } }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x1c,                           // pushwith
+                0x5d, x,                        // findpropstrict x
+                0x66, x,                        // getproperty x
+                0x5d, y,                        // findpropstrict y
+                0x66, y,                        // getproperty y
+                0xa0,                           // add
+                0x48,                           // returnvalue
+            ];
+            abc.method("sum", params(1), &code);
+            // function readOutside(global, point) { with (point) { return outside; } }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x30,                           // pushscope
+                0xd2,                           // getlocal2
+                0x1c,                           // pushwith
+                0x5d, outside,                  // findpropstrict outside
+                0x66, outside,                  // getproperty outside
+                0x48,                           // returnvalue
+            ];
+            abc.method("readOutside", params(2), &code);
+            // function writeX(point, value) { with (point) { x = value; } }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x1c,                           // pushwith
+                0x5e, x,                        // findproperty x
+                0xd2,                           // getlocal2
+                0x61, x,                        // setproperty x
+                0x47,                           // returnvoid
+            ];
+            abc.method("writeX", params(2), &code);
+            // function writeLabel(object, value) { with (object) { label = value; } }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x1c,                           // pushwith
+                0x5e, label,                    // findproperty label
+                0xd2,                           // getlocal2
+                0x61, label,                    // setproperty label
+                0x47,                           // returnvoid
+            ];
+            abc.method("writeLabel", params(2), &code);
+            // function nested(point, object) {
+            //     with (point) { with (object) { return label + " " + y; } }
+            // }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x1c,                           // pushwith
+                0xd2,                           // getlocal2
+                0x1c,                           // pushwith
+                0x5d, label,                    // findpropstrict label
+                0x66, label,                    // getproperty label
+                0x2c, space,                    // pushstring " "
+                0xa0,                           // add
+                0x5d, y,                        // findpropstrict y
+                0x66, y,                        // getproperty y
+                0xa0,                           // add
+                0x48,                           // returnvalue
+            ];
+            abc.method("nested", params(2), &code);
+
+            let functions = abc.functions(activation)?;
+            let (sum, read_outside, write_x) = (functions[0], functions[1], functions[2]);
+            let (write_label, nested) = (functions[3], functions[4]);
+
+            let point = construct(activation, "flash.geom", "Point", &[3.into(), 4.into()])?;
+            assert_eq!(sum.call(None, &[point.into()], activation)?, 7.into());
+
+            let global = construct(activation, "", "Object", &[])?;
+            set(activation, global, "outside", "outside")?;
+            let args = [global.into(), point.into()];
+            let value = read_outside.call(None, &args, activation)?;
+            assert_eq!(traced(activation, value)?, "outside");
+
+            write_x.call(None, &[point.into(), 10.into()], activation)?;
+            assert_eq!(get(activation, point, "x")?, 10.into());
+
+            let object = construct(activation, "", "Object", &[])?;
+            set(activation, object, "label", "before")?;
+            write_label.call(None, &[object.into(), "after".into()], activation)?;
+            let value = get(activation, object, "label")?;
+            assert_eq!(traced(activation, value)?, "after");
+
+            let value = nested.call(None, &[point.into(), object.into()], activation)?;
+            assert_eq!(traced(activation, value)?, "after 4");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn getlex_resolution() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let [math, constant, shadow, missing] =
+                ["Math", "TOP_LEVEL_CONST", "shadow", "missing"]
+                    .map(|name| abc.name("", name).0 as u8);
+            let params = || vec![param(Index::new(0), None); 2];
+
+            // The methods take the global object of their script and an
+            // object to push as a regular scope above it, like `this`.
+            let getlex = |name| {
+                #[rustfmt::skip]
+                let code = vec![
+                    0xd1,                       // getlocal1
+                    0x30,                       // pushscope
+                    0xd2,                       // getlocal2
+                    0x30,                       // pushscope
+                    0x60, name,                 // getlex name
+                    0x48,                       // returnvalue
+                ];
+                code
+            };
+            abc.method("getMath", params(), &getlex(math));
+            abc.method("getConst", params(), &getlex(constant));
+            abc.method("getShadow", params(), &getlex(shadow));
+            abc.method("getMissing", params(), &getlex(missing));
+
+            let functions = abc.functions(activation)?;
+            let (get_math, get_const) = (functions[0], functions[1]);
+            let (get_shadow, get_missing) = (functions[2], functions[3]);
+
+            let global = construct(activation, "", "Object", &[])?;
+            set(activation, global, "TOP_LEVEL_CONST", "top-level const")?;
+            set(activation, global, "shadow", "global shadow")?;
+            // Dynamic properties of scopes other than the global one are not
+            // found by name.
+            let holder = construct(activation, "", "Object", &[])?;
+            set(activation, holder, "shadow", "instance shadow")?;
+            let args = [global.into(), holder.into()];
+
+            let math_class = class(activation, "", "Math")?;
+            assert_eq!(get_math.call(None, &args, activation)?, math_class.into());
+            let value = get_const.call(None, &args, activation)?;
+            assert_eq!(traced(activation, value)?, "top-level const");
+            let value = get_shadow.call(None, &args, activation)?;
+            assert_eq!(traced(activation, value)?, "global shadow");
+            let error = get_missing.call(None, &args, activation).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ReferenceError: Error #1065: Variable missing is not defined."
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn default_xml_namespace() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let item = abc.name("", "item").0 as u8;
+            let params = |count| vec![param(Index::new(0), None); count];
+
+            // function items(doc) { return doc.item; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x66, item,                     // getproperty item
+                0x48,                           // returnvalue
+            ];
+            abc.method("items", params(1), &code);
+            // function namespacedItems(doc, ns) {
+            //     default xml namespace = ns;
+            //     return doc.item;
+            // }
+            #[rustfmt::skip]
+            let code = [
+                0xd2,                           // getlocal2
+                0x07,                           // dxnslate
+                0xd1,                           // getlocal1
+                0x66, item,                     // getproperty item
+                0x48,                           // returnvalue
+            ];
+            abc.method("namespacedItems", params(2), &code).needs_dxns = true;
+            // function namespacedDescendants(doc, ns) {
+            //     default xml namespace = ns;
+            //     return doc..item;
+            // }
+            #[rustfmt::skip]
+            let code = [
+                0xd2,                           // getlocal2
+                0x07,                           // dxnslate
+                0xd1,                           // getlocal1
+                0x59, item,                     // getdescendants item
+                0x48,                           // returnvalue
+            ];
+            abc.method("namespacedDescendants", params(2), &code)
+                .needs_dxns = true;
+
+            let functions = abc.functions(activation)?;
+            let (items, namespaced_items, namespaced_descendants) =
+                (functions[0], functions[1], functions[2]);
+
+            let source = r#"<catalog xmlns="http://example.com/ns"><item>namespaced</item><section><item>nested</item></section></catalog>"#;
+            let doc = construct(activation, "", "XML", &[source.into()])?;
+            let ns = construct(
+                activation,
+                "",
+                "Namespace",
+                &["http://example.com/ns".into()],
+            )?;
+
+            let list = items.call(None, &[doc.into()], activation)?;
+            let list = list.coerce_to_object(activation)?;
+            assert_eq!(call(activation, list, "length", &[])?, 0.into());
+
+            let args = [doc.into(), ns.into()];
+            let list = namespaced_items.call(None, &args, activation)?;
+            assert_eq!(traced(activation, list)?, "namespaced");
+            let list = list.coerce_to_object(activation)?;
+            assert_eq!(call(activation, list, "length", &[])?, 1.into());
+            let list = namespaced_descendants.call(None, &args, activation)?;
+            let list = list.coerce_to_object(activation)?;
+            assert_eq!(call(activation, list, "length", &[])?, 2.into());
+
+            let args = [doc.into(), "".into()];
+            let list = namespaced_items.call(None, &args, activation)?;
+            let list = list.coerce_to_object(activation)?;
+            assert_eq!(call(activation, list, "length", &[])?, 0.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn null_and_undefined_receivers_throw() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let child = abc.name("", "child").0 as u8;
+            let method = abc.name("", "method").0 as u8;
+            let params = || vec![param(Index::new(0), None)];
+
+            // Each function accesses its `receiver` parameter like its name
+            // says.
+            #[rustfmt::skip]
+            let codes: [&[u8]; 6] = [
+                // return receiver.child;
+                &[0xd1, 0x66, child, 0x48],
+                // receiver.child = 1;
+                &[0xd1, 0x24, 0x01, 0x61, child, 0x47],
+                // return delete receiver.child;
+                &[0xd1, 0x6a, child, 0x48],
+                // return receiver.method();
+                &[0xd1, 0x46, method, 0x00, 0x48],
+                // receiver.method();
+                &[0xd1, 0x4f, method, 0x00, 0x47],
+                // return receiver.<slot 1>;
+                &[0xd1, 0x6c, 0x01, 0x48],
+            ];
+            for code in codes {
+                abc.method("access", params(), code);
+            }
+
+            let holder = construct(activation, "", "Object", &[])?;
+            set(activation, holder, "child", Value::Null)?;
+            let functions = abc.functions(activation)?;
+            let child = functions[0].call(None, &[holder.into()], activation)?;
+            assert_eq!(child, Value::Null);
+            let missing = get(activation, holder, "missing")?;
+            assert_eq!(missing, Value::Undefined);
+
+            for function in functions {
+                let error = function
+                    .call(None, &[child], activation)
+                    .unwrap_err()
+                    .to_string();
+                assert_eq!(
+                    error,
+                    "TypeError: Error #1009: Cannot access a property or method of a null object reference."
+                );
+                let error = function
+                    .call(None, &[missing], activation)
+                    .unwrap_err()
+                    .to_string();
+                assert_eq!(
+                    error,
+                    "TypeError: Error #1010: A term is undefined and has no properties."
+                );
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Call a function that evaluates `key in object`.
+    fn evaluate_in<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        is_in: Object<'gc>,
+        key: Value<'gc>,
+        object: Object<'gc>,
+    ) -> Result<bool, Error> {
+        Ok(is_in
+            .call(None, &[key, object.into()], activation)?
+            .coerce_to_boolean())
+    }
+
+    #[test]
+    fn in_operator_on_arrays_and_vectors() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let params = vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function isIn(key, object) { return key in object; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0xb4,                           // in
+                0x48,                           // returnvalue
+            ];
+            abc.method("isIn", params, &code);
+            let is_in = abc.functions(activation)?[0];
+
+            let values = [1.into(), 2.into(), 3.into()];
+            let arr = array(activation, &values)?;
+            let holes = array(activation, &[])?;
+            set(activation, holes, "0", "a")?;
+            set(activation, holes, "4", "e")?;
+            set(activation, arr, "extra", "dynamic")?;
+            for (key, expected) in [
+                (1.into(), true),
+                (5.into(), false),
+                ("2".into(), true),
+                ((-1).into(), false),
+                ("extra".into(), true),
+                ("missing".into(), false),
+                ("length".into(), true),
+            ] {
+                assert_eq!(
+                    evaluate_in(activation, is_in, key, arr)?,
+                    expected,
+                    "{:?} in arr",
+                    key
+                );
+            }
+            for (key, expected) in [(0, true), (2, false), (4, true)] {
+                assert_eq!(
+                    evaluate_in(activation, is_in, key.into(), holes)?,
+                    expected,
+                    "{} in holes",
+                    key
+                );
+            }
+
+            let int = activation.avm2().classes().int;
+            let vector = activation.avm2().classes().vector;
+            let ints = vector
+                .apply(activation, &[int.into()])?
+                .construct(activation, &[3.into()])?;
+            assert!(evaluate_in(activation, is_in, 1.into(), ints)?);
+            assert!(!evaluate_in(activation, is_in, 5.into(), ints)?);
+            assert!(!evaluate_in(activation, is_in, 3.into(), ints)?);
+            call(activation, ints, "push", &[7.into()])?;
+            assert!(evaluate_in(activation, is_in, 3.into(), ints)?);
+            set(activation, ints, "length", 1)?;
+            assert!(!evaluate_in(activation, is_in, 1.into(), ints)?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn constructed_primitives_are_falsy_like_primitives() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let boolean = abc.name("", "Boolean").0 as u8;
+            let number = abc.name("", "Number").0 as u8;
+            let params = || vec![param(Index::new(0), None)];
+
+            #[rustfmt::skip]
+            let codes: [&[u8]; 4] = [
+                // return new Boolean(value);
+                &[0x5d, boolean, 0xd1, 0x4a, boolean, 0x01, 0x48],
+                // return new Number(value);
+                &[0x5d, number, 0xd1, 0x4a, number, 0x01, 0x48],
+                // if (value) { return true; } return false;
+                &[0xd1, 0x12, 0x02, 0x00, 0x00, 0x26, 0x48, 0x27, 0x48],
+                // return !value;
+                &[0xd1, 0x96, 0x48],
+            ];
+            for code in codes {
+                abc.method("test", params(), code);
+            }
+            let functions = abc.functions(activation)?;
+            let (new_boolean, new_number) = (functions[0], functions[1]);
+            let (branch, not) = (functions[2], functions[3]);
+
+            let boxed_false = new_boolean.call(None, &[false.into()], activation)?;
+            assert_eq!(boxed_false, Value::Bool(false));
+            let boxed_zero = new_number.call(None, &[0.into()], activation)?;
+            assert_eq!(boxed_zero, 0.into());
+
+            let falsy = [
+                false.into(),
+                0.into(),
+                (-0.0).into(),
+                f64::NAN.into(),
+                "".into(),
+                Value::Null,
+                Value::Undefined,
+                Value::Integer(0),
+                Value::Unsigned(0),
+                boxed_false,
+                boxed_zero,
+            ];
+            let empty_array = array(activation, &[])?;
+            let empty_object = construct(activation, "", "Object", &[])?;
+            let truthy = [
+                true.into(),
+                "0".into(),
+                "false".into(),
+                " ".into(),
+                empty_array.into(),
+                empty_object.into(),
+                (-1).into(),
+                f64::INFINITY.into(),
+                0.5.into(),
+            ];
+            let cases = falsy
+                .iter()
+                .map(|value| (*value, false))
+                .chain(truthy.iter().map(|value| (*value, true)));
+            for (value, expected) in cases {
+                assert_eq!(value.coerce_to_boolean(), expected, "Boolean({:?})", value);
+                let taken = branch.call(None, &[value], activation)?;
+                assert_eq!(taken, expected.into(), "if ({:?})", value);
+                let negated = not.call(None, &[value], activation)?;
+                assert_eq!(negated, (!expected).into(), "!{:?}", value);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn does_nothing<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn typeof_of_each_kind_of_value() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let params = || vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function typeOf(a) { return typeof a; }
+            abc.method("typeOf", params(), &[0xd1, 0x95, 0x48]);
+            // function newInstance(a, b) { return new a(b); }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0x42, 0x01,                     // construct 1
+                0x48,                           // returnvalue
+            ];
+            abc.method("newInstance", params(), &code);
+
+            let functions = abc.functions(activation)?;
+            let (type_of, new_instance) = (functions[0], functions[1]);
+
+            let doc = construct(activation, "", "XML", &["<a><b/><b/></a>".into()])?;
+            let list = get(activation, doc, "b")?;
+            let plain = function(activation, does_nothing)?;
+            let foo_class =
+                define_class(activation, "Foo", None, &[("method", does_nothing)], &[])?;
+            let foo = foo_class.construct(activation, &[])?;
+            let method = get(activation, foo, "method")?;
+            let object_class = activation.avm2().classes().object;
+
+            let classes = activation.avm2().classes();
+            let mut constructed = Vec::new();
+            for (class, arg) in [
+                (classes.number, 0.into()),
+                (classes.int, 3.into()),
+                (classes.string, "s".into()),
+                (classes.boolean, false.into()),
+            ] {
+                let instance = new_instance.call(None, &[class.into(), arg], activation)?;
+                constructed.push(instance);
+            }
+
+            let cases = [
+                (Value::Null, "object"),
+                (doc.into(), "xml"),
+                (list, "xml"),
+                (Value::Integer(5), "number"),
+                (Value::Unsigned(7), "number"),
+                (1.5.into(), "number"),
+                (plain.into(), "function"),
+                (type_of.into(), "function"),
+                (method, "function"),
+                (foo_class.into(), "object"),
+                (object_class.into(), "object"),
+                (foo.into(), "object"),
+                (constructed[0], "number"),
+                (constructed[1], "number"),
+                (constructed[2], "string"),
+                (constructed[3], "boolean"),
+            ];
+            for (value, expected) in cases {
+                let type_name = type_of.call(None, &[value], activation)?;
+                assert_eq!(type_name, expected.into(), "typeof {:?}", value);
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,531 @@
+//! AMF serialization of AVM2 values
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, ByteArrayObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+use flash_lso::types::{Element, Value as AmfValue};
+
+/// Serialize a value into its AMF representation.
+///
+/// Functions can't be serialized and return `None`, so that they are skipped
+/// when they appear as properties of an object.
+pub fn serialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Option<AmfValue>, Error> {
+    serialize_value_within(activation, value, &mut Vec::new())
+}
+
+/// Serialize a value that is nested within the objects in `parents`.
+///
+/// An object that contains itself, directly or indirectly, is cut off at the
+/// point it repeats and serialized as `undefined` there, rather than
+/// recursing forever.
+fn serialize_value_within<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+    parents: &mut Vec<Object<'gc>>,
+) -> Result<Option<AmfValue>, Error> {
+    Ok(match value {
+        Value::Undefined => Some(AmfValue::Undefined),
+        Value::Null => Some(AmfValue::Null),
+        Value::Bool(b) => Some(AmfValue::Bool(b)),
+        Value::Number(n) => Some(AmfValue::Number(n)),
+        Value::Unsigned(u) => Some(AmfValue::Number(u.into())),
+        Value::Integer(i) => Some(AmfValue::Integer(i)),
+        Value::String(s) => Some(AmfValue::String(s.to_string())),
+        Value::Object(o) => {
+            if o.as_executable().is_some() {
+                None
+            } else if o.as_display_object().is_some()
+                || parents.iter().any(|parent| Object::ptr_eq(*parent, o))
+            {
+                Some(AmfValue::Undefined)
+            } else if let Some(bytearray) = o.as_bytearray() {
+                Some(AmfValue::ByteArray(bytearray.bytes().to_vec()))
+            } else {
+                parents.push(o);
+                let serialized = serialize_object(activation, o, parents);
+                parents.pop();
+
+                Some(serialized?)
+            }
+        }
+    })
+}
+
+/// Serialize an array or plain object, whose members are nested within the
+/// objects in `parents`.
+fn serialize_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    parents: &mut Vec<Object<'gc>>,
+) -> Result<AmfValue, Error> {
+    let elements: Option<Vec<_>> = object
+        .as_array_storage()
+        .map(|array| array.iter().collect());
+
+    if let Some(elements) = elements {
+        let length = elements.len();
+        let mut values = Vec::with_capacity(length);
+        for (i, element) in elements.into_iter().enumerate() {
+            let element = element.unwrap_or(Value::Undefined);
+            if let Some(element) = serialize_value_within(activation, element, parents)? {
+                values.push(Element::new(i.to_string(), element));
+            }
+        }
+
+        Ok(AmfValue::ECMAArray(vec![], values, length as u32))
+    } else {
+        Ok(AmfValue::Object(
+            serialize_properties(activation, object, parents)?,
+            None,
+        ))
+    }
+}
+
+/// Serialize the enumerable properties of an object.
+fn serialize_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    parents: &mut Vec<Object<'gc>>,
+) -> Result<Vec<Element>, Error> {
+    let mut elements = Vec::new();
+    let mut index = 0;
+    while let Some(next_index) = object.get_next_enumerant(index, activation)? {
+        let name = object
+            .get_enumerant_name(next_index, activation)?
+            .coerce_to_string(activation)?;
+        let value = object.get_enumerant_value(next_index, activation)?;
+        if let Some(value) = serialize_value_within(activation, value, parents)? {
+            elements.push(Element::new(name.to_utf8_lossy(), value));
+        }
+        index = next_index;
+    }
+
+    Ok(elements)
+}
+
+/// Deserialize an AMF value into an AVM2 value.
+pub fn deserialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &AmfValue,
+) -> Result<Value<'gc>, Error> {
+    Ok(match value {
+        AmfValue::Undefined => Value::Undefined,
+        AmfValue::Null => Value::Null,
+        AmfValue::Bool(b) => Value::Bool(*b),
+        AmfValue::Integer(i) => Value::Integer(*i),
+        AmfValue::Number(n) => Value::Number(*n),
+        AmfValue::String(s) => Value::String(AvmString::new_utf8(activation.context.gc_context, s)),
+        AmfValue::ByteArray(bytes) => {
+            let storage = ByteArrayStorage::from_vec(bytes.clone());
+            let bytearray = ByteArrayObject::from_storage(activation, storage)?;
+            bytearray.into()
+        }
+        AmfValue::StrictArray(values) => {
+            let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
+            for value in values {
+                arr.push(Some(deserialize_value(activation, value)?));
+            }
+            let storage = ArrayStorage::from_storage(arr);
+            let array = ArrayObject::from_storage(activation, storage)?;
+            array.into()
+        }
+        AmfValue::ECMAArray(values, elements, _) => {
+            // First lets create an array out of `values` (dense portion), then we add the elements onto it.
+            let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
+            for value in values {
+                arr.push(Some(deserialize_value(activation, value)?));
+            }
+            let storage = ArrayStorage::from_storage(arr);
+            let mut array = ArrayObject::from_storage(activation, storage)?;
+            // Now lets add each element as a property
+            for element in elements {
+                array.set_property(
+                    array,
+                    &QName::new(
+                        Namespace::public(),
+                        AvmString::new_utf8(activation.context.gc_context, element.name()),
+                    )
+                    .into(),
+                    deserialize_value(activation, element.value())?,
+                    activation,
+                )?;
+            }
+            array.into()
+        }
+        AmfValue::Object(properties, _class_definition) => {
+            let obj_class = activation.avm2().classes().object;
+            let mut obj = obj_class.construct(activation, &[])?;
+            for property in properties {
+                obj.set_property(
+                    obj,
+                    &QName::new(
+                        Namespace::public(),
+                        AvmString::new_utf8(activation.context.gc_context, property.name()),
+                    )
+                    .into(),
+                    deserialize_value(activation, property.value())?,
+                    activation,
+                )?;
+            }
+            obj.into()
+            // TODO: Handle class_defintion
+        }
+        // TODO: Dictionary, Vector, XML, Date, etc...
+        _ => Value::Undefined,
+    })
+}
+
+const AMF3_UNDEFINED: u8 = 0x00;
+const AMF3_NULL: u8 = 0x01;
+const AMF3_FALSE: u8 = 0x02;
+const AMF3_TRUE: u8 = 0x03;
+const AMF3_INTEGER: u8 = 0x04;
+const AMF3_DOUBLE: u8 = 0x05;
+const AMF3_STRING: u8 = 0x06;
+const AMF3_ARRAY: u8 = 0x09;
+const AMF3_OBJECT: u8 = 0x0A;
+const AMF3_BYTEARRAY: u8 = 0x0C;
+
+/// Object traits flags marking inline, externalizable traits.
+const AMF3_TRAITS_EXTERNALIZABLE: u32 = 0x07;
+
+/// Object traits flags marking inline, dynamic traits with no sealed members.
+const AMF3_TRAITS_DYNAMIC: u32 = 0x0B;
+
+/// The range of integers that fit in an AMF3 `U29`-encoded integer.
+const AMF3_INTEGER_RANGE: std::ops::RangeInclusive<i32> = -0x1000_0000..=0x0FFF_FFFF;
+
+/// Encode a 29-bit variable length integer.
+fn encode_u29(buf: &mut Vec<u8>, value: u32) {
+    let value = value & 0x1FFF_FFFF;
+    if value < 0x80 {
+        buf.push(value as u8);
+    } else if value < 0x4000 {
+        buf.push(((value >> 7) | 0x80) as u8);
+        buf.push((value & 0x7F) as u8);
+    } else if value < 0x20_0000 {
+        buf.push(((value >> 14) | 0x80) as u8);
+        buf.push(((value >> 7) | 0x80) as u8);
+        buf.push((value & 0x7F) as u8);
+    } else {
+        buf.push(((value >> 22) | 0x80) as u8);
+        buf.push(((value >> 15) | 0x80) as u8);
+        buf.push(((value >> 8) | 0x80) as u8);
+        buf.push(value as u8);
+    }
+}
+
+/// Decode a 29-bit variable length integer, returning it along with the
+/// number of bytes it occupied.
+fn decode_u29(bytes: &[u8]) -> Option<(u32, usize)> {
+    let mut value = 0;
+    for (i, byte) in bytes.iter().copied().enumerate().take(4) {
+        if i == 3 {
+            return Some(((value << 8) | u32::from(byte), 4));
+        }
+
+        value = (value << 7) | u32::from(byte & 0x7F);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+
+    None
+}
+
+/// Encode an inline AMF3 string, without its type marker.
+fn encode_amf3_string(buf: &mut Vec<u8>, string: &str) {
+    encode_u29(buf, ((string.len() as u32) << 1) | 1);
+    buf.extend_from_slice(string.as_bytes());
+}
+
+/// Append raw bytes to a ByteArray at its current position.
+fn write_to_bytearray<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    bytes: &[u8],
+) -> Result<(), Error> {
+    target
+        .as_bytearray_mut(activation.context.gc_context)
+        .ok_or("Cannot write AMF data to a non-ByteArray")?
+        .write_bytes(bytes)
+}
+
+/// Write a value into a ByteArray in AMF3 format.
+///
+/// Instances of classes implementing `flash.utils.IExternalizable` are
+/// written with externalizable traits under their registered class alias,
+/// and have `writeExternal` called with the ByteArray to write their body.
+///
+/// String and traits reference tables are not used; every string and traits
+/// definition is written inline. Objects written more than once are written
+/// as references, which also keeps cyclic structures from recursing forever.
+pub fn write_amf3_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    value: Value<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<(), Error> {
+    let mut buf = Vec::new();
+    match value {
+        Value::Undefined => buf.push(AMF3_UNDEFINED),
+        Value::Null => buf.push(AMF3_NULL),
+        Value::Bool(false) => buf.push(AMF3_FALSE),
+        Value::Bool(true) => buf.push(AMF3_TRUE),
+        Value::Integer(i) if AMF3_INTEGER_RANGE.contains(&i) => {
+            buf.push(AMF3_INTEGER);
+            encode_u29(&mut buf, i as u32);
+        }
+        Value::Unsigned(u) if u <= *AMF3_INTEGER_RANGE.end() as u32 => {
+            buf.push(AMF3_INTEGER);
+            encode_u29(&mut buf, u);
+        }
+        Value::Integer(i) => {
+            buf.push(AMF3_DOUBLE);
+            buf.extend_from_slice(&f64::from(i).to_be_bytes());
+        }
+        Value::Unsigned(u) => {
+            buf.push(AMF3_DOUBLE);
+            buf.extend_from_slice(&f64::from(u).to_be_bytes());
+        }
+        Value::Number(n) => {
+            buf.push(AMF3_DOUBLE);
+            buf.extend_from_slice(&n.to_be_bytes());
+        }
+        Value::String(s) => {
+            buf.push(AMF3_STRING);
+            encode_amf3_string(&mut buf, &s.to_utf8_lossy());
+        }
+        Value::Object(o) => return write_amf3_object(activation, target, o, objects),
+    }
+
+    write_to_bytearray(activation, target, &buf)
+}
+
+/// Write an object into a ByteArray in AMF3 format.
+fn write_amf3_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    object: Object<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Result<(), Error> {
+    if object.as_executable().is_some() || object.as_display_object().is_some() {
+        return write_to_bytearray(activation, target, &[AMF3_UNDEFINED]);
+    }
+
+    let marker = if object.as_bytearray().is_some() {
+        AMF3_BYTEARRAY
+    } else if object.as_array_storage().is_some() {
+        AMF3_ARRAY
+    } else {
+        AMF3_OBJECT
+    };
+
+    let mut buf = vec![marker];
+    if let Some(index) = objects.iter().position(|o| Object::ptr_eq(*o, object)) {
+        encode_u29(&mut buf, (index as u32) << 1);
+        return write_to_bytearray(activation, target, &buf);
+    }
+    objects.push(object);
+
+    if let Some(bytearray) = object.as_bytearray() {
+        encode_u29(&mut buf, ((bytearray.len() as u32) << 1) | 1);
+        buf.extend_from_slice(bytearray.bytes());
+        drop(bytearray);
+
+        return write_to_bytearray(activation, target, &buf);
+    }
+
+    let elements: Option<Vec<_>> = object
+        .as_array_storage()
+        .map(|array| array.iter().collect());
+    if let Some(elements) = elements {
+        // Only the dense portion of the array is written, with an empty
+        // associative portion.
+        encode_u29(&mut buf, ((elements.len() as u32) << 1) | 1);
+        encode_amf3_string(&mut buf, "");
+        write_to_bytearray(activation, target, &buf)?;
+
+        for element in elements {
+            let element = element.unwrap_or(Value::Undefined);
+            write_amf3_value(activation, target, element, objects)?;
+        }
+
+        return Ok(());
+    }
+
+    let externalizable_class = activation
+        .avm2()
+        .global_domain()
+        .get_defined_value(
+            activation,
+            QName::new(Namespace::package("flash.utils"), "IExternalizable"),
+        )?
+        .coerce_to_object(activation)?
+        .as_class_object()
+        .ok_or("IExternalizable is not a class")?;
+
+    if object.is_of_type(externalizable_class, activation)? {
+        let alias = object.instance_of().and_then(|class| {
+            activation
+                .avm2()
+                .class_aliases
+                .iter()
+                .find(|(_, aliased)| Object::ptr_eq(**aliased, class))
+                .map(|(alias, _)| *alias)
+        });
+
+        encode_u29(&mut buf, AMF3_TRAITS_EXTERNALIZABLE);
+        encode_amf3_string(
+            &mut buf,
+            &alias
+                .map(|alias| alias.to_utf8_lossy().into_owned())
+                .unwrap_or_default(),
+        );
+        write_to_bytearray(activation, target, &buf)?;
+
+        object.call_property(
+            &QName::new(Namespace::public(), "writeExternal").into(),
+            &[target.into()],
+            activation,
+        )?;
+
+        return Ok(());
+    }
+
+    // Everything else is written as an anonymous object holding its
+    // enumerable properties.
+    encode_u29(&mut buf, AMF3_TRAITS_DYNAMIC);
+    encode_amf3_string(&mut buf, "");
+    write_to_bytearray(activation, target, &buf)?;
+
+    let mut index = 0;
+    while let Some(next_index) = object.get_next_enumerant(index, activation)? {
+        index = next_index;
+
+        let name = object
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        let value = object.get_enumerant_value(index, activation)?;
+        let is_function = matches!(value, Value::Object(o) if o.as_executable().is_some());
+        if name.is_empty() || is_function {
+            continue;
+        }
+
+        let mut name_buf = Vec::new();
+        encode_amf3_string(&mut name_buf, &name.to_utf8_lossy());
+        write_to_bytearray(activation, target, &name_buf)?;
+        write_amf3_value(activation, target, value, objects)?;
+    }
+
+    let mut end_buf = Vec::new();
+    encode_amf3_string(&mut end_buf, "");
+    write_to_bytearray(activation, target, &end_buf)
+}
+
+/// Read an externalizable object from the current position of a ByteArray
+/// in AMF3 format.
+///
+/// Returns `None` without consuming anything if the next value is not an
+/// inline object with externalizable traits. Otherwise, an instance of the
+/// class registered under the serialized alias is constructed and has
+/// `readExternal` called with the ByteArray to read its body.
+pub fn read_amf3_externalizable<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    source: Object<'gc>,
+) -> Result<Option<Value<'gc>>, Error> {
+    let header = {
+        let bytearray = source
+            .as_bytearray()
+            .ok_or("Cannot read AMF data from a non-ByteArray")?;
+        let bytes = bytearray.read_at(bytearray.bytes_available(), bytearray.position())?;
+
+        parse_externalizable_header(bytes).map(|(alias, header_len)| {
+            (
+                AvmString::new_utf8(activation.context.gc_context, alias),
+                bytearray.position() + header_len,
+            )
+        })
+    };
+    let (alias, body_position) = match header {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+
+    let class = activation
+        .avm2()
+        .class_aliases
+        .get(&alias)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "ReferenceError: Error #1014: Class {} could not be found.",
+                alias
+            )
+        })?;
+
+    let externalizable_class = activation
+        .avm2()
+        .global_domain()
+        .get_defined_value(
+            activation,
+            QName::new(Namespace::package("flash.utils"), "IExternalizable"),
+        )?
+        .coerce_to_object(activation)?
+        .as_class_object()
+        .ok_or("IExternalizable is not a class")?;
+    if !class.has_class_in_chain(externalizable_class, activation)? {
+        return Err(format!(
+            "ArgumentError: Error #2173: Unable to read object in stream. The class {} does not implement flash.utils.IExternalizable but is aliased to an externalizable class.",
+            class.inner_class_definition().read().name().to_qualified_name(activation.context.gc_context)
+        )
+        .into());
+    }
+
+    if let Some(bytearray) = source.as_bytearray() {
+        bytearray.set_position(body_position);
+    }
+
+    let object = class.construct(activation, &[])?;
+    object.call_property(
+        &QName::new(Namespace::public(), "readExternal").into(),
+        &[source.into()],
+        activation,
+    )?;
+
+    Ok(Some(object.into()))
+}
+
+/// Parse the marker, traits and class name of an inline externalizable
+/// object, returning the class name and the length of the header.
+fn parse_externalizable_header(bytes: &[u8]) -> Option<(&str, usize)> {
+    if *bytes.first()? != AMF3_OBJECT {
+        return None;
+    }
+
+    let (traits, traits_len) = decode_u29(&bytes[1..])?;
+    if traits & AMF3_TRAITS_EXTERNALIZABLE != AMF3_TRAITS_EXTERNALIZABLE {
+        return None;
+    }
+
+    let name_start = 1 + traits_len;
+    let (name_header, name_header_len) = decode_u29(&bytes[name_start..])?;
+    if name_header & 1 == 0 {
+        // String references can't be resolved without a reference table.
+        return None;
+    }
+
+    let name_start = name_start + name_header_len;
+    let name_end = name_start + (name_header >> 1) as usize;
+    let name = std::str::from_utf8(bytes.get(name_start..name_end)?).ok()?;
+
+    Some((name, name_end))
+}
@@ -77,6 +77,9 @@ impl<'gc> ArrayStorage<'gc> {
     }
 
     /// Set the length of the array.
+    ///
+    /// Growing the array fills the new space with holes; shrinking it drops
+    /// the values past the new end.
     pub fn set_length(&mut self, size: usize) {
         self.storage.resize(size, None)
     }
@@ -100,6 +103,12 @@ impl<'gc> ArrayStorage<'gc> {
         self.storage.push(Some(item))
     }
 
+    /// Reserve space for at least `additional` more values to be pushed onto
+    /// this array.
+    pub fn reserve(&mut self, additional: usize) {
+        self.storage.reserve(additional)
+    }
+
     /// Push an array hole onto the end of this array.
     pub fn push_hole(&mut self) {
         self.storage.push(None)
@@ -107,25 +116,10 @@ impl<'gc> ArrayStorage<'gc> {
 
     /// Pop a value from the back of the array.
     ///
-    /// This method preferrentially pops non-holes from the array first. If a
-    /// hole is popped, it will become `undefined`.
-    pub fn pop(&mut self) -> Value<'gc> {
-        let mut non_hole = None;
-
-        for (i, item) in self.storage.iter().enumerate().rev() {
-            if item.is_some() {
-                non_hole = Some(i);
-            }
-        }
-
-        if let Some(non_hole) = non_hole {
-            self.storage.remove(non_hole).unwrap()
-        } else {
-            self.storage
-                .pop()
-                .unwrap_or(None)
-                .unwrap_or(Value::Undefined)
-        }
+    /// The last slot is always removed, even if it is a hole. Holes and empty
+    /// arrays yield `None`; callers must resolve holes in the prototype.
+    pub fn pop(&mut self) -> Option<Value<'gc>> {
+        self.storage.pop().unwrap_or(None)
     }
 
     /// Shift a value from the front of the array.
@@ -184,3 +178,35 @@ where
         Self { storage }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pop_removes_last_slot() {
+        let mut storage = ArrayStorage::from_args(&[1.into(), 2.into()]);
+
+        assert!(matches!(storage.pop(), Some(Value::Integer(2))));
+        assert_eq!(storage.length(), 1);
+        assert!(matches!(storage.pop(), Some(Value::Integer(1))));
+        assert_eq!(storage.length(), 0);
+        assert!(storage.pop().is_none());
+        assert_eq!(storage.length(), 0);
+    }
+
+    #[test]
+    fn pop_trailing_holes() {
+        // [1, , ,]
+        let mut storage = ArrayStorage::from_args(&[1.into()]);
+        storage.push_hole();
+        storage.push_hole();
+
+        assert!(storage.pop().is_none());
+        assert_eq!(storage.length(), 2);
+        assert!(storage.pop().is_none());
+        assert_eq!(storage.length(), 1);
+        assert!(matches!(storage.pop(), Some(Value::Integer(1))));
+        assert_eq!(storage.length(), 0);
+    }
+}
@@ -50,6 +50,18 @@ impl FromWStr for CompressionAlgorithm {
     }
 }
 
+/// The error returned by reads that run past the end of a `ByteArray`.
+#[derive(Debug)]
+pub struct EofError;
+
+impl Display for EofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("EOFError: Error #2030: End of file was encountered.")
+    }
+}
+
+impl std::error::Error for EofError {}
+
 #[derive(Clone, Collect, Debug, Copy, PartialEq, Eq)]
 #[collect(no_drop)]
 pub enum ObjectEncoding {
@@ -123,7 +135,7 @@ impl ByteArrayStorage {
         self.bytes
             .get(offset..)
             .and_then(|bytes| bytes.get(..amnt))
-            .ok_or_else(|| "EOFError: Reached EOF".into())
+            .ok_or_else(|| EofError.into())
     }
 
     /// Write bytes at any offset in the ByteArray
@@ -5,7 +5,7 @@ use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::{ClassObject, Object};
 use crate::avm2::script::TranslationUnit;
-use crate::avm2::traits::{Trait, TraitKind};
+use crate::avm2::traits::{Metadata, Trait, TraitKind};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use bitflags::bitflags;
@@ -130,6 +130,13 @@ pub struct Class<'gc> {
     /// Whether or not the class initializer has already been called.
     class_initializer_called: bool,
 
+    /// The call handler for this class.
+    ///
+    /// If `None`, then calling the class as a function coerces the first
+    /// argument to the class's type. Builtin conversion functions such as
+    /// `String(x)` or `Array(1, 2, 3)` provide their own handler here.
+    call_handler: Option<Method<'gc>>,
+
     /// The class initializer for specializations of this class.
     ///
     /// Only applies for generic classes. Must be called once and only once
@@ -149,6 +156,9 @@ pub struct Class<'gc> {
     /// System defined classes are allowed to have illegal trait configurations
     /// without throwing a VerifyError.
     is_system: bool,
+
+    /// The metadata tags attached to this class's definition.
+    metadata: Vec<Metadata<'gc>>,
 }
 
 /// Find traits in a list of traits matching a slot ID.
@@ -203,6 +213,7 @@ impl<'gc> Class<'gc> {
                 instance_traits: Vec::new(),
                 class_init,
                 class_initializer_called: false,
+                call_handler: None,
                 class_traits: Vec::new(),
                 specialized_class_init: Method::from_builtin(
                     |_, _, _| Ok(Value::Undefined),
@@ -211,6 +222,7 @@ impl<'gc> Class<'gc> {
                 ),
                 traits_loaded: true,
                 is_system: true,
+                metadata: Vec::new(),
             },
         )
     }
@@ -325,6 +337,7 @@ impl<'gc> Class<'gc> {
                 instance_traits: Vec::new(),
                 class_init,
                 class_initializer_called: false,
+                call_handler: None,
                 class_traits: Vec::new(),
                 specialized_class_init: Method::from_builtin(
                     |_, _, _| Ok(Value::Undefined),
@@ -333,6 +346,7 @@ impl<'gc> Class<'gc> {
                 ),
                 traits_loaded: false,
                 is_system: false,
+                metadata: Vec::new(),
             },
         ))
     }
@@ -490,9 +504,11 @@ impl<'gc> Class<'gc> {
                     activation.context.gc_context,
                 ),
                 class_initializer_called: false,
+                call_handler: None,
                 class_traits: Vec::new(),
                 traits_loaded: true,
                 is_system: false,
+                metadata: Vec::new(),
             },
         ))
     }
@@ -505,6 +521,15 @@ impl<'gc> Class<'gc> {
         self.name = name;
     }
 
+    /// Set the metadata tags attached to this class's definition.
+    pub fn set_metadata(&mut self, metadata: Vec<Metadata<'gc>>) {
+        self.metadata = metadata;
+    }
+
+    pub fn metadata(&self) -> &[Metadata<'gc>] {
+        &self.metadata
+    }
+
     pub fn super_class_name(&self) -> &Option<Multiname<'gc>> {
         &self.super_class
     }
@@ -787,6 +812,16 @@ impl<'gc> Class<'gc> {
         self.class_init.clone()
     }
 
+    /// Get this class's call handler.
+    pub fn call_handler(&self) -> Option<Method<'gc>> {
+        self.call_handler.clone()
+    }
+
+    /// Set a call handler for this class.
+    pub fn set_call_handler(&mut self, new_call_handler: Method<'gc>) {
+        self.call_handler = Some(new_call_handler);
+    }
+
     /// Check if the class has already been initialized.
     pub fn is_class_initialized(&self) -> bool {
         self.class_initializer_called
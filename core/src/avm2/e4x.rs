@@ -0,0 +1,892 @@
+//! E4X (ECMAScript for XML) node tree
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::TObject;
+use crate::avm2::Error;
+use crate::string::{AvmString, WStr};
+use gc_arena::{Collect, GcCell, MutationContext};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::fmt;
+
+/// The URI that the `xml` prefix is always bound to.
+const XML_NAMESPACE_URI: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Characters that E4X considers to be insignificant whitespace.
+fn is_xml_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+/// Settings that control how E4X markup is parsed and serialized.
+///
+/// These correspond to the static properties of the `XML` class.
+#[derive(Clone, Copy, Debug)]
+pub struct E4XSettings {
+    pub ignore_comments: bool,
+    pub ignore_processing_instructions: bool,
+    pub ignore_whitespace: bool,
+    pub pretty_printing: bool,
+    pub pretty_indent: i32,
+}
+
+impl E4XSettings {
+    /// Read the current settings from the static properties of `XML`.
+    pub fn from_xml_class<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Self, Error> {
+        let xml_class = activation.avm2().classes().xml;
+        let mut read_setting = |name: &'static str| {
+            xml_class.get_property(
+                xml_class.into(),
+                &QName::new(Namespace::public(), name).into(),
+                activation,
+            )
+        };
+
+        let ignore_comments = read_setting("ignoreComments")?.coerce_to_boolean();
+        let ignore_processing_instructions =
+            read_setting("ignoreProcessingInstructions")?.coerce_to_boolean();
+        let ignore_whitespace = read_setting("ignoreWhitespace")?.coerce_to_boolean();
+        let pretty_printing = read_setting("prettyPrinting")?.coerce_to_boolean();
+        let pretty_indent = read_setting("prettyIndent")?;
+        let pretty_indent = pretty_indent.coerce_to_i32(activation)?;
+
+        Ok(Self {
+            ignore_comments,
+            ignore_processing_instructions,
+            ignore_whitespace,
+            pretty_printing,
+            pretty_indent,
+        })
+    }
+}
+
+/// A namespace that has been declared on an E4X element.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct E4XNamespace<'gc> {
+    /// The prefix of the namespace, or `None` for the default namespace.
+    pub prefix: Option<AvmString<'gc>>,
+
+    /// The URI of the namespace.
+    pub uri: AvmString<'gc>,
+}
+
+impl<'gc> E4XNamespace<'gc> {
+    /// Check if this namespace is bound to a given prefix.
+    fn has_prefix(&self, prefix: Option<&str>) -> bool {
+        match (&self.prefix, prefix) {
+            (None, None) => true,
+            (Some(mine), Some(theirs)) => mine.to_utf8_lossy() == theirs,
+            _ => false,
+        }
+    }
+
+    /// Check if two namespace declarations bind the same prefix to the same
+    /// URI.
+    pub fn is_same_declaration(&self, other: &Self) -> bool {
+        self.prefix == other.prefix && self.uri == other.uri
+    }
+}
+
+/// A node in an E4X tree.
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub struct E4XNode<'gc>(GcCell<'gc, E4XNodeData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct E4XNodeData<'gc> {
+    /// The element that contains this node, if any.
+    parent: Option<E4XNode<'gc>>,
+
+    /// The namespace of this node's name.
+    ///
+    /// Only elements, attributes and processing instructions have names.
+    namespace: Option<E4XNamespace<'gc>>,
+
+    /// The local name of this node.
+    local_name: Option<AvmString<'gc>>,
+
+    /// The kind-specific data of this node.
+    kind: E4XNodeKind<'gc>,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum E4XNodeKind<'gc> {
+    Text(AvmString<'gc>),
+    Comment(AvmString<'gc>),
+    ProcessingInstruction(AvmString<'gc>),
+    Attribute(AvmString<'gc>),
+    Element {
+        /// Namespaces declared on this element with `xmlns` attributes.
+        namespace_declarations: Vec<E4XNamespace<'gc>>,
+
+        /// The attributes of this element, in document order.
+        attributes: Vec<E4XNode<'gc>>,
+
+        /// The child nodes of this element, in document order.
+        children: Vec<E4XNode<'gc>>,
+    },
+}
+
+impl<'gc> fmt::Debug for E4XNode<'gc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("E4XNode")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+impl<'gc> E4XNode<'gc> {
+    fn new(
+        mc: MutationContext<'gc, '_>,
+        parent: Option<E4XNode<'gc>>,
+        namespace: Option<E4XNamespace<'gc>>,
+        local_name: Option<AvmString<'gc>>,
+        kind: E4XNodeKind<'gc>,
+    ) -> Self {
+        E4XNode(GcCell::allocate(
+            mc,
+            E4XNodeData {
+                parent,
+                namespace,
+                local_name,
+                kind,
+            },
+        ))
+    }
+
+    /// Construct a parentless text node.
+    pub fn text(mc: MutationContext<'gc, '_>, text: AvmString<'gc>) -> Self {
+        Self::new(mc, None, None, None, E4XNodeKind::Text(text))
+    }
+
+    /// Parse a string of markup into a list of top-level nodes.
+    ///
+    /// Text that is not contained within an element is returned as top-level
+    /// text nodes.
+    pub fn parse(
+        string: &WStr,
+        activation: &mut Activation<'_, 'gc, '_>,
+        settings: &E4XSettings,
+    ) -> Result<Vec<Self>, Error> {
+        let mc = activation.context.gc_context;
+        let data_utf8 = string.to_utf8_lossy();
+        let mut parser = Reader::from_str(&data_utf8);
+        let mut buf = Vec::new();
+        let mut top_level = Vec::new();
+        let mut open_tags: Vec<E4XNode<'gc>> = Vec::new();
+
+        loop {
+            buf.clear();
+
+            let event = parser.read_event(&mut buf)?;
+            let node = match event {
+                Event::Start(bs) => {
+                    let element = Self::from_start_event(mc, &bs, open_tags.last().copied())?;
+                    Self::append_to_tree(mc, element, &open_tags, &mut top_level);
+                    open_tags.push(element);
+                    continue;
+                }
+                Event::Empty(bs) => Self::from_start_event(mc, &bs, open_tags.last().copied())?,
+                Event::End(_) => {
+                    open_tags.pop();
+                    continue;
+                }
+                Event::Text(bt) => {
+                    let text = String::from_utf8(bt.unescaped()?.into_owned())?;
+                    let text = if settings.ignore_whitespace {
+                        text.trim_matches(is_xml_whitespace)
+                    } else {
+                        &text
+                    };
+
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    let text = AvmString::new_utf8(mc, text);
+                    Self::new(mc, None, None, None, E4XNodeKind::Text(text))
+                }
+                Event::CData(bt) => {
+                    let text = AvmString::new_utf8_bytes(mc, bt.escaped())?;
+                    Self::new(mc, None, None, None, E4XNodeKind::Text(text))
+                }
+                Event::Comment(bt) => {
+                    if settings.ignore_comments {
+                        continue;
+                    }
+
+                    let text = AvmString::new_utf8_bytes(mc, bt.escaped())?;
+                    Self::new(mc, None, None, None, E4XNodeKind::Comment(text))
+                }
+                Event::PI(bt) => {
+                    if settings.ignore_processing_instructions {
+                        continue;
+                    }
+
+                    let contents = std::str::from_utf8(bt.escaped())?;
+                    let (name, value) = contents
+                        .split_once(is_xml_whitespace)
+                        .unwrap_or((contents, ""));
+                    let name = AvmString::new_utf8(mc, name);
+                    let value =
+                        AvmString::new_utf8(mc, value.trim_start_matches(is_xml_whitespace));
+                    Self::new(
+                        mc,
+                        None,
+                        None,
+                        Some(name),
+                        E4XNodeKind::ProcessingInstruction(value),
+                    )
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            Self::append_to_tree(mc, node, &open_tags, &mut top_level);
+        }
+
+        if let Some(unclosed) = open_tags.last() {
+            return Err(format!(
+                "TypeError: Error #1085: The element type \"{}\" must be terminated by the matching end-tag \"</{}>\".",
+                unclosed.qualified_name(),
+                unclosed.qualified_name()
+            )
+            .into());
+        }
+
+        Ok(top_level)
+    }
+
+    /// Append a freshly-parsed node to the innermost open element, or to the
+    /// list of top-level nodes if there is none.
+    fn append_to_tree(
+        mc: MutationContext<'gc, '_>,
+        node: E4XNode<'gc>,
+        open_tags: &[E4XNode<'gc>],
+        top_level: &mut Vec<E4XNode<'gc>>,
+    ) {
+        if let Some(parent) = open_tags.last() {
+            node.0.write(mc).parent = Some(*parent);
+
+            if let E4XNodeKind::Element { children, .. } = &mut parent.0.write(mc).kind {
+                children.push(node);
+            }
+        } else {
+            top_level.push(node);
+        }
+    }
+
+    /// Construct an element from a `quick_xml` start (or empty) tag.
+    fn from_start_event(
+        mc: MutationContext<'gc, '_>,
+        bs: &BytesStart<'_>,
+        parent: Option<E4XNode<'gc>>,
+    ) -> Result<Self, Error> {
+        let mut namespace_declarations = Vec::new();
+        let mut raw_attributes = Vec::new();
+
+        for attribute in bs.attributes() {
+            let attribute = attribute?;
+            let key = std::str::from_utf8(attribute.key)?.to_string();
+            let value = AvmString::new_utf8_bytes(mc, attribute.unescaped_value()?)?;
+
+            if key == "xmlns" {
+                namespace_declarations.push(E4XNamespace {
+                    prefix: None,
+                    uri: value,
+                });
+            } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                namespace_declarations.push(E4XNamespace {
+                    prefix: Some(AvmString::new_utf8(mc, prefix)),
+                    uri: value,
+                });
+            } else {
+                raw_attributes.push((key, value));
+            }
+        }
+
+        let name = std::str::from_utf8(bs.name())?;
+        let (prefix, local_name) = match name.split_once(':') {
+            Some((prefix, local_name)) => (Some(prefix), local_name),
+            None => (None, name),
+        };
+
+        let namespace = Self::resolve_prefix(prefix, &namespace_declarations, parent);
+        if prefix.is_some() && namespace.is_none() {
+            return Err(format!(
+                "TypeError: Error #1083: The prefix \"{}\" for element \"{}\" is not bound.",
+                prefix.unwrap_or_default(),
+                local_name
+            )
+            .into());
+        }
+
+        let element = Self::new(
+            mc,
+            parent,
+            namespace,
+            Some(AvmString::new_utf8(mc, local_name)),
+            E4XNodeKind::Element {
+                namespace_declarations: namespace_declarations.clone(),
+                attributes: Vec::new(),
+                children: Vec::new(),
+            },
+        );
+
+        let mut attributes = Vec::with_capacity(raw_attributes.len());
+        for (key, value) in raw_attributes {
+            let (namespace, local_name) = match key.split_once(':') {
+                Some((prefix, local_name)) => {
+                    let namespace =
+                        Self::resolve_prefix(Some(prefix), &namespace_declarations, parent);
+                    if namespace.is_none() {
+                        return Err(format!(
+                            "TypeError: Error #1083: The prefix \"{}\" for element \"{}\" is not bound.",
+                            prefix, local_name
+                        )
+                        .into());
+                    }
+
+                    (namespace, local_name)
+                }
+                // Unprefixed attributes are never in the default namespace.
+                None => (None, key.as_str()),
+            };
+
+            attributes.push(Self::new(
+                mc,
+                Some(element),
+                namespace,
+                Some(AvmString::new_utf8(mc, local_name)),
+                E4XNodeKind::Attribute(value),
+            ));
+        }
+
+        if let E4XNodeKind::Element {
+            attributes: element_attributes,
+            ..
+        } = &mut element.0.write(mc).kind
+        {
+            *element_attributes = attributes;
+        }
+
+        Ok(element)
+    }
+
+    /// Find the namespace bound to a prefix, looking at a set of declarations
+    /// first and then at the declarations of a parent element and its
+    /// ancestors.
+    fn resolve_prefix(
+        prefix: Option<&str>,
+        declarations: &[E4XNamespace<'gc>],
+        parent: Option<E4XNode<'gc>>,
+    ) -> Option<E4XNamespace<'gc>> {
+        if let Some(namespace) = declarations.iter().find(|ns| ns.has_prefix(prefix)) {
+            return Some(namespace.clone());
+        }
+
+        if let Some(parent) = parent {
+            let parent_declarations = parent.namespace_declarations();
+            let grandparent = parent.parent();
+
+            return Self::resolve_prefix(prefix, &parent_declarations, grandparent);
+        }
+
+        if prefix == Some("xml") {
+            return Some(E4XNamespace {
+                prefix: Some("xml".into()),
+                uri: XML_NAMESPACE_URI.into(),
+            });
+        }
+
+        None
+    }
+
+    /// Make a deep copy of this node, detached from its parent.
+    pub fn deep_copy(self, mc: MutationContext<'gc, '_>) -> Self {
+        self.deep_copy_with_parent(mc, None)
+    }
+
+    fn deep_copy_with_parent(
+        self,
+        mc: MutationContext<'gc, '_>,
+        parent: Option<E4XNode<'gc>>,
+    ) -> Self {
+        let read = self.0.read();
+        let kind = match &read.kind {
+            E4XNodeKind::Element {
+                namespace_declarations,
+                ..
+            } => E4XNodeKind::Element {
+                namespace_declarations: if parent.is_none() {
+                    // Detached copies keep all namespaces that were in scope.
+                    self.in_scope_namespaces()
+                } else {
+                    namespace_declarations.clone()
+                },
+                attributes: Vec::new(),
+                children: Vec::new(),
+            },
+            kind => kind.clone(),
+        };
+        let copy = Self::new(mc, parent, read.namespace.clone(), read.local_name, kind);
+
+        if let E4XNodeKind::Element {
+            attributes,
+            children,
+            ..
+        } = &read.kind
+        {
+            let attributes: Vec<_> = attributes
+                .iter()
+                .map(|a| a.deep_copy_with_parent(mc, Some(copy)))
+                .collect();
+            let children: Vec<_> = children
+                .iter()
+                .map(|c| c.deep_copy_with_parent(mc, Some(copy)))
+                .collect();
+
+            if let E4XNodeKind::Element {
+                attributes: copy_attributes,
+                children: copy_children,
+                ..
+            } = &mut copy.0.write(mc).kind
+            {
+                *copy_attributes = attributes;
+                *copy_children = children;
+            }
+        }
+
+        copy
+    }
+
+    /// Change the namespace of this node's name.
+    pub fn set_namespace(self, mc: MutationContext<'gc, '_>, namespace: Option<E4XNamespace<'gc>>) {
+        self.0.write(mc).namespace = namespace;
+    }
+
+    /// Implements the E4X `[[AddInScopeNamespace]]` operation.
+    ///
+    /// Declaring a prefix that is already declared on this element replaces
+    /// the existing declaration.
+    pub fn add_in_scope_namespace(
+        self,
+        mc: MutationContext<'gc, '_>,
+        namespace: E4XNamespace<'gc>,
+    ) {
+        let name_uri = self.namespace().map(|ns| ns.uri).unwrap_or_default();
+        if namespace.prefix.is_none() && name_uri.is_empty() {
+            // An element in no namespace can't have a default namespace.
+            return;
+        }
+
+        if let E4XNodeKind::Element {
+            namespace_declarations,
+            ..
+        } = &mut self.0.write(mc).kind
+        {
+            namespace_declarations.retain(|ns| ns.prefix != namespace.prefix);
+            namespace_declarations.push(namespace);
+        }
+    }
+
+    /// Implements the E4X `removeNamespace` method.
+    ///
+    /// Declarations of `uri` are removed from this element and all of its
+    /// descendants, unless the name of the element or one of its attributes
+    /// still uses the namespace. If `prefix` is `None`, declarations with any
+    /// prefix are removed; otherwise only those with that prefix are, with
+    /// the empty prefix standing for the default namespace.
+    pub fn remove_namespace(
+        self,
+        mc: MutationContext<'gc, '_>,
+        prefix: Option<AvmString<'gc>>,
+        uri: AvmString<'gc>,
+    ) {
+        let is_in_use = |node: E4XNode<'gc>| node.namespace().map(|ns| ns.uri) == Some(uri);
+        if is_in_use(self) || self.attributes().into_iter().any(is_in_use) {
+            return;
+        }
+
+        if let E4XNodeKind::Element {
+            namespace_declarations,
+            ..
+        } = &mut self.0.write(mc).kind
+        {
+            namespace_declarations.retain(|ns| {
+                let prefix_matches = match prefix {
+                    Some(prefix) => ns.prefix.unwrap_or_default() == prefix,
+                    None => true,
+                };
+
+                !(prefix_matches && ns.uri == uri)
+            });
+        }
+
+        for child in self.children() {
+            if child.is_element() {
+                child.remove_namespace(mc, prefix, uri);
+            }
+        }
+    }
+
+    /// The element that contains this node, if any.
+    pub fn parent(self) -> Option<E4XNode<'gc>> {
+        self.0.read().parent
+    }
+
+    /// The local name of this node, if it has one.
+    pub fn local_name(self) -> Option<AvmString<'gc>> {
+        self.0.read().local_name
+    }
+
+    /// The namespace of this node's name, if it has one.
+    pub fn namespace(self) -> Option<E4XNamespace<'gc>> {
+        self.0.read().namespace.clone()
+    }
+
+    pub fn kind(&self) -> std::cell::Ref<E4XNodeKind<'gc>> {
+        std::cell::Ref::map(self.0.read(), |r| &r.kind)
+    }
+
+    pub fn is_element(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Element { .. })
+    }
+
+    pub fn is_text(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Text(_))
+    }
+
+    pub fn is_attribute(self) -> bool {
+        matches!(self.0.read().kind, E4XNodeKind::Attribute(_))
+    }
+
+    /// The child nodes of this element.
+    pub fn children(self) -> Vec<E4XNode<'gc>> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { children, .. } => children.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The attributes of this element.
+    pub fn attributes(self) -> Vec<E4XNode<'gc>> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element { attributes, .. } => attributes.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The child elements or attributes of this element selected by a
+    /// property access, depending on whether the multiname is an attribute.
+    pub fn properties(self, multiname: &Multiname<'gc>) -> Vec<E4XNode<'gc>> {
+        let candidates = if multiname.is_attribute() {
+            self.attributes()
+        } else {
+            self.children()
+        };
+
+        candidates
+            .into_iter()
+            .filter(|node| {
+                (node.is_element() || node.is_attribute()) && node.matches_name(multiname)
+            })
+            .collect()
+    }
+
+    /// Collect the descendants of this element selected by a multiname, in
+    /// document order, as for the E4X descendant accessor (`..`).
+    ///
+    /// A wildcard name selects descendants of every kind, while a named
+    /// access only selects elements (or attributes, for attribute names).
+    pub fn descendants(self, multiname: &Multiname<'gc>, out: &mut Vec<E4XNode<'gc>>) {
+        if multiname.is_attribute() {
+            out.extend(
+                self.attributes()
+                    .into_iter()
+                    .filter(|attribute| attribute.matches_name(multiname)),
+            );
+        }
+
+        for child in self.children() {
+            let is_selected = !multiname.is_attribute()
+                && (multiname.local_name().is_none() || child.is_element())
+                && child.matches_name(multiname);
+            if is_selected {
+                out.push(child);
+            }
+
+            child.descendants(multiname, out);
+        }
+    }
+
+    /// Determine if this node's name is matched by a multiname.
+    ///
+    /// A missing local name in the multiname matches any name. The public
+    /// namespace stands for the default namespace, which matches names in
+    /// no namespace.
+    pub fn matches_name(self, multiname: &Multiname<'gc>) -> bool {
+        let read = self.0.read();
+        let local_name_matches = match (multiname.local_name(), read.local_name) {
+            (None, _) => true,
+            (Some(wanted), Some(local_name)) => wanted == local_name,
+            (Some(_), None) => false,
+        };
+        if !local_name_matches {
+            return false;
+        }
+
+        let uri = read.namespace.as_ref().map(|ns| ns.uri).unwrap_or_default();
+
+        multiname.namespace_set().any(|ns| match ns {
+            Namespace::Any => true,
+            Namespace::Namespace(ns_uri) => *ns_uri == uri,
+            ns if ns.is_public() => uri.is_empty(),
+            _ => false,
+        })
+    }
+
+    /// The namespaces declared directly on this node.
+    pub fn namespace_declarations(self) -> Vec<E4XNamespace<'gc>> {
+        match &self.0.read().kind {
+            E4XNodeKind::Element {
+                namespace_declarations,
+                ..
+            } => namespace_declarations.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All namespaces in scope for this node, with declarations on nearer
+    /// elements shadowing those of the same prefix further up the tree.
+    pub fn in_scope_namespaces(self) -> Vec<E4XNamespace<'gc>> {
+        let mut in_scope: Vec<E4XNamespace<'gc>> = Vec::new();
+        let mut current = Some(self);
+
+        while let Some(node) = current {
+            for declaration in node.namespace_declarations() {
+                if !in_scope.iter().any(|ns| ns.prefix == declaration.prefix) {
+                    in_scope.push(declaration);
+                }
+            }
+
+            current = node.parent();
+        }
+
+        in_scope
+    }
+
+    /// The name of this node as it would appear in markup, including any
+    /// namespace prefix.
+    pub fn qualified_name(self) -> String {
+        let read = self.0.read();
+        let local_name = read
+            .local_name
+            .map(|n| n.to_utf8_lossy().into_owned())
+            .unwrap_or_default();
+
+        match read.namespace.as_ref().and_then(|ns| ns.prefix) {
+            Some(prefix) if !prefix.is_empty() => {
+                format!("{}:{}", prefix.to_utf8_lossy(), local_name)
+            }
+            _ => local_name,
+        }
+    }
+
+    /// Determine if this node has simple content.
+    ///
+    /// Text and attribute nodes always have simple content, as do elements
+    /// that contain no child elements. Comments and processing instructions
+    /// never do.
+    pub fn has_simple_content(self) -> bool {
+        match &self.0.read().kind {
+            E4XNodeKind::Comment(_) | E4XNodeKind::ProcessingInstruction(_) => false,
+            E4XNodeKind::Element { children, .. } => !children.iter().any(|c| c.is_element()),
+            _ => true,
+        }
+    }
+
+    /// Implements the E4X `ToString` operation.
+    ///
+    /// Nodes with simple content are converted to their text content;
+    /// everything else is serialized as markup.
+    pub fn xml_to_string(self, settings: &E4XSettings) -> String {
+        if !self.has_simple_content() {
+            return self.xml_to_xml_string(settings);
+        }
+
+        match &self.0.read().kind {
+            E4XNodeKind::Text(text) | E4XNodeKind::Attribute(text) => {
+                text.to_utf8_lossy().into_owned()
+            }
+            E4XNodeKind::Element { children, .. } => {
+                let mut out = String::new();
+
+                for child in children {
+                    if let E4XNodeKind::Text(text) = &child.0.read().kind {
+                        out.push_str(&text.to_utf8_lossy());
+                    }
+                }
+
+                out
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Implements the E4X `ToXMLString` operation.
+    pub fn xml_to_xml_string(self, settings: &E4XSettings) -> String {
+        let mut out = String::new();
+
+        self.write_xml_string(&mut out, settings, None, 0);
+
+        out
+    }
+
+    /// Serialize this node as markup.
+    ///
+    /// `ancestor_namespaces` holds the namespaces already declared by the
+    /// elements this one is being written inside of. It is `None` for the
+    /// node that serialization started from, which must declare everything
+    /// in scope for it.
+    fn write_xml_string(
+        self,
+        out: &mut String,
+        settings: &E4XSettings,
+        ancestor_namespaces: Option<&[E4XNamespace<'gc>]>,
+        indent_level: usize,
+    ) {
+        if settings.pretty_printing {
+            out.extend(std::iter::repeat(' ').take(indent_level));
+        }
+
+        let read = self.0.read();
+        match &read.kind {
+            E4XNodeKind::Text(text) => {
+                let text = text.to_utf8_lossy();
+                if settings.pretty_printing {
+                    escape_element_value(out, text.trim_matches(is_xml_whitespace));
+                } else {
+                    escape_element_value(out, &text);
+                }
+            }
+            E4XNodeKind::Attribute(value) => escape_attribute_value(out, &value.to_utf8_lossy()),
+            E4XNodeKind::Comment(text) => {
+                out.push_str("<!--");
+                out.push_str(&text.to_utf8_lossy());
+                out.push_str("-->");
+            }
+            E4XNodeKind::ProcessingInstruction(value) => {
+                out.push_str("<?");
+                out.push_str(&self.qualified_name());
+                if !value.is_empty() {
+                    out.push(' ');
+                    out.push_str(&value.to_utf8_lossy());
+                }
+                out.push_str("?>");
+            }
+            E4XNodeKind::Element {
+                namespace_declarations,
+                attributes,
+                children,
+            } => {
+                let name = self.qualified_name();
+                let (candidates, mut declared) = match ancestor_namespaces {
+                    None => (self.in_scope_namespaces(), Vec::new()),
+                    Some(ancestors) => (namespace_declarations.clone(), ancestors.to_vec()),
+                };
+
+                out.push('<');
+                out.push_str(&name);
+
+                for namespace in candidates {
+                    if declared.iter().any(|ns| ns.is_same_declaration(&namespace)) {
+                        continue;
+                    }
+
+                    out.push_str(" xmlns");
+                    if let Some(prefix) = namespace.prefix {
+                        out.push(':');
+                        out.push_str(&prefix.to_utf8_lossy());
+                    }
+                    out.push_str("=\"");
+                    escape_attribute_value(out, &namespace.uri.to_utf8_lossy());
+                    out.push('"');
+
+                    declared.push(namespace);
+                }
+
+                for attribute in attributes {
+                    if let E4XNodeKind::Attribute(value) = &attribute.0.read().kind {
+                        out.push(' ');
+                        out.push_str(&attribute.qualified_name());
+                        out.push_str("=\"");
+                        escape_attribute_value(out, &value.to_utf8_lossy());
+                        out.push('"');
+                    }
+                }
+
+                if children.is_empty() {
+                    out.push_str("/>");
+                    return;
+                }
+
+                out.push('>');
+
+                let indent_children = children.len() > 1 || children.iter().any(|c| !c.is_text());
+                let child_indent_level = if settings.pretty_printing && indent_children {
+                    indent_level + settings.pretty_indent.max(0) as usize
+                } else {
+                    0
+                };
+
+                for child in children {
+                    if settings.pretty_printing && indent_children {
+                        out.push('\n');
+                    }
+
+                    child.write_xml_string(out, settings, Some(&declared), child_indent_level);
+                }
+
+                if settings.pretty_printing && indent_children {
+                    out.push('\n');
+                    out.extend(std::iter::repeat(' ').take(indent_level));
+                }
+
+                out.push_str("</");
+                out.push_str(&name);
+                out.push('>');
+            }
+        }
+    }
+}
+
+/// Implements the E4X `EscapeElementValue` operation.
+fn escape_element_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Implements the E4X `EscapeAttributeValue` operation.
+fn escape_attribute_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '&' => out.push_str("&amp;"),
+            '\n' => out.push_str("&#xA;"),
+            '\r' => out.push_str("&#xD;"),
+            '\t' => out.push_str("&#x9;"),
+            c => out.push(c),
+        }
+    }
+}
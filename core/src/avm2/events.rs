@@ -363,12 +363,15 @@ pub fn parent_of(target: Object<'_>) -> Option<Object<'_>> {
 
 /// Call all of the event handlers on a given target.
 ///
-/// The `target` is the current target of the `event`. `event` must be a valid
-/// `EventObject`, or this function will panic. You must have already set the
-/// event's phase to match what targets you are dispatching to, or you will
-/// call the wrong handlers.
+/// The `target` is the current target of the `event`, and `dispatcher` is the
+/// object whose handlers are called. These differ only when an
+/// `EventDispatcher` was constructed to dispatch on behalf of another object.
+/// `event` must be a valid `EventObject`, or this function will panic. You
+/// must have already set the event's phase to match what targets you are
+/// dispatching to, or you will call the wrong handlers.
 pub fn dispatch_event_to_target<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
+    dispatcher: Object<'gc>,
     target: Object<'gc>,
     event: Object<'gc>,
 ) -> Result<(), Error> {
@@ -378,9 +381,9 @@ pub fn dispatch_event_to_target<'gc>(
         event.as_event().unwrap().event_type(),
         target
     );
-    let dispatch_list = target
+    let dispatch_list = dispatcher
         .get_property(
-            target,
+            dispatcher,
             &QName::new(Namespace::private(NS_EVENT_DISPATCHER), "dispatch_list").into(),
             activation,
         )?
@@ -458,7 +461,7 @@ pub fn dispatch_event<'gc>(
             break;
         }
 
-        dispatch_event_to_target(activation, *ancestor, event)?;
+        dispatch_event_to_target(activation, *ancestor, *ancestor, event)?;
     }
 
     event
@@ -467,7 +470,7 @@ pub fn dispatch_event<'gc>(
         .set_phase(EventPhase::AtTarget);
 
     if !event.as_event().unwrap().is_propagation_stopped() {
-        dispatch_event_to_target(activation, target, event)?;
+        dispatch_event_to_target(activation, this, target, event)?;
     }
 
     event
@@ -481,7 +484,7 @@ pub fn dispatch_event<'gc>(
                 break;
             }
 
-            dispatch_event_to_target(activation, *ancestor, event)?;
+            dispatch_event_to_target(activation, *ancestor, *ancestor, event)?;
         }
     }
 
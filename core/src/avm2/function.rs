@@ -89,6 +89,15 @@ impl<'gc> Executable<'gc> {
         }
     }
 
+    /// The number of parameters this executable declares, not counting a
+    /// rest parameter.
+    pub fn num_parameters(&self) -> usize {
+        match self {
+            Executable::Native(bm) => bm.method.signature.len(),
+            Executable::Action(bm) => bm.method.signature().len(),
+        }
+    }
+
     /// Execute a method.
     ///
     /// The function will either be called directly if it is a Rust builtin, or
@@ -183,3 +192,315 @@ impl<'gc> fmt::Debug for Executable<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+    use crate::avm2::{Activation, Error, TObject, Value};
+    use swf::avm2::types::{DefaultValue, Index};
+
+    /// Read an element of an array, like `array[index]`.
+    fn element<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        array: Value<'gc>,
+        index: usize,
+    ) -> Result<Value<'gc>, Error> {
+        let array = array.coerce_to_object(activation)?;
+        let value = array
+            .as_array_storage()
+            .and_then(|storage| storage.get(index));
+
+        Ok(value.unwrap_or(Value::Undefined))
+    }
+
+    #[test]
+    fn arguments_object() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let index = abc.runtime_name();
+            let length = abc.name("", "length");
+            let callee = abc.name("", "callee");
+            let (index, length, callee) = (index.0 as u8, length.0 as u8, callee.0 as u8);
+
+            // function sum() {
+            //     var total = 0;
+            //     for (var i = 0; i < arguments.length; i += 1) {
+            //         total += arguments[i];
+            //     }
+            //     return total;
+            // }
+            #[rustfmt::skip]
+            let code = [
+                0x24, 0x00,                     // pushbyte 0
+                0xd6,                           // setlocal2
+                0x24, 0x00,                     // pushbyte 0
+                0xd7,                           // setlocal3
+                0x10, 0x0a, 0x00, 0x00,         // jump +10
+                0x09,                           // label
+                0xd2,                           // getlocal2
+                0xd1,                           // getlocal1
+                0xd3,                           // getlocal3
+                0x66, index,                    // getproperty [index]
+                0xa0,                           // add
+                0xd6,                           // setlocal2
+                0xc2, 0x03,                     // inclocal_i 3
+                0xd3,                           // getlocal3
+                0xd1,                           // getlocal1
+                0x66, length,                   // getproperty length
+                0x15, 0xee, 0xff, 0xff,         // iflt -18
+                0xd2,                           // getlocal2
+                0x48,                           // returnvalue
+            ];
+            abc.method("sum", vec![], &code).needs_arguments_object = true;
+
+            // function callee() { return arguments.callee; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x66, callee,                   // getproperty callee
+                0x48,                           // returnvalue
+            ];
+            abc.method("callee", vec![], &code).needs_arguments_object = true;
+
+            // function withDefaults(a, b = 5) { return [arguments, b]; }
+            let params = vec![
+                param(Index::new(0), None),
+                param(Index::new(0), Some(DefaultValue::Int(abc.int(5)))),
+            ];
+            #[rustfmt::skip]
+            let code = [
+                0xd3,                           // getlocal3
+                0xd2,                           // getlocal2
+                0x56, 0x02,                     // newarray 2
+                0x48,                           // returnvalue
+            ];
+            abc.method("withDefaults", params, &code)
+                .needs_arguments_object = true;
+
+            let functions = abc.functions(activation)?;
+            let (sum, callee, with_defaults) = (functions[0], functions[1], functions[2]);
+
+            assert_eq!(sum.call(None, &[], activation)?, 0.into());
+            let args = [1.into(), 2.into(), 3.into(), 4.into()];
+            assert_eq!(sum.call(None, &args, activation)?, 10.into());
+
+            let result = callee.call(None, &[], activation)?;
+            assert_eq!(result, callee.into());
+
+            // `arguments` only holds the arguments that were passed.
+            let result = with_defaults.call(None, &[1.into()], activation)?;
+            let arguments = element(activation, result, 0)?;
+            let arguments_object = arguments.coerce_to_object(activation)?;
+            let num_arguments = get(activation, arguments_object, "length")?;
+            assert_eq!(num_arguments, 1.into());
+            assert_eq!(element(activation, arguments, 0)?, 1.into());
+            assert_eq!(element(activation, arguments, 1)?, Value::Undefined);
+            assert_eq!(element(activation, result, 1)?, 5.into());
+
+            let result = with_defaults.call(None, &[1.into(), 2.into()], activation)?;
+            let arguments = element(activation, result, 0)?;
+            let arguments_object = arguments.coerce_to_object(activation)?;
+            let num_arguments = get(activation, arguments_object, "length")?;
+            assert_eq!(num_arguments, 2.into());
+            assert_eq!(element(activation, result, 1)?, 2.into());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn rest_parameter() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+
+            // function f(a, ...rest) { return rest; }
+            let params = vec![param(Index::new(0), None)];
+            #[rustfmt::skip]
+            let code = [
+                0xd2,                           // getlocal2
+                0x48,                           // returnvalue
+            ];
+            abc.method("f", params, &code).needs_rest = true;
+
+            // function restOnly(...rest) { return rest; }
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x48,                           // returnvalue
+            ];
+            abc.method("restOnly", vec![], &code).needs_rest = true;
+
+            // function twoParams(a, b) {}
+            let params = vec![param(Index::new(0), None), param(Index::new(0), None)];
+            abc.method("twoParams", params, &[0x47]);
+
+            let functions = abc.functions(activation)?;
+            let (f, rest_only, two_params) = (functions[0], functions[1], functions[2]);
+
+            let args = [1.into(), 2.into(), 3.into()];
+            let rest = f.call(None, &args, activation)?;
+            assert_eq!(traced(activation, rest)?, "2,3");
+            let rest = f
+                .call(None, &[1.into()], activation)?
+                .coerce_to_object(activation)?;
+            assert_eq!(get(activation, rest, "length")?, 0.into());
+
+            let rest = rest_only
+                .call(None, &[], activation)?
+                .coerce_to_object(activation)?;
+            let array_class = activation.avm2().classes().array;
+            assert!(rest.is_of_type(array_class, activation)?);
+
+            let args = array(activation, &[1.into(), 2.into(), 3.into(), 4.into()])?;
+            let rest = call(activation, f, "apply", &[Value::Null, args.into()])?;
+            assert_eq!(traced(activation, rest)?, "2,3,4");
+            let rest = call(activation, f, "call", &[Value::Null, 1.into(), 2.into()])?;
+            assert_eq!(traced(activation, rest)?, "2");
+
+            // The rest parameter isn't counted by `length`.
+            assert_eq!(get(activation, f, "length")?, 1.into());
+            assert_eq!(get(activation, rest_only, "length")?, 0.into());
+            assert_eq!(get(activation, two_params, "length")?, 2.into());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn default_parameters() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+
+            // function f(a, b = 5) { return b; }
+            let params = vec![
+                param(Index::new(0), None),
+                param(Index::new(0), Some(DefaultValue::Int(abc.int(5)))),
+            ];
+            #[rustfmt::skip]
+            let code = [
+                0xd2,                           // getlocal2
+                0x48,                           // returnvalue
+            ];
+            abc.method("f", params, &code);
+
+            // function typed(a:int, b:Number = 2.5, c:String = null, d:uint = 7) {
+            //     return [a, b, c, d];
+            // }
+            let params = vec![
+                param(abc.name("", "int"), None),
+                param(
+                    abc.name("", "Number"),
+                    Some(DefaultValue::Double(abc.double(2.5))),
+                ),
+                param(abc.name("", "String"), Some(DefaultValue::Null)),
+                param(abc.name("", "uint"), Some(DefaultValue::Uint(abc.uint(7)))),
+            ];
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0xd2,                           // getlocal2
+                0xd3,                           // getlocal3
+                0x62, 0x04,                     // getlocal 4
+                0x56, 0x04,                     // newarray 4
+                0x48,                           // returnvalue
+            ];
+            abc.method("typed", params, &code);
+
+            // function needsTwo(a:int, b:int) {}
+            let params = vec![
+                param(abc.name("", "int"), None),
+                param(abc.name("", "int"), None),
+            ];
+            abc.method("needsTwo", params, &[0x47]);
+
+            // function untyped(a, b) { return b; }
+            let params = vec![param(Index::new(0), None), param(Index::new(0), None)];
+            #[rustfmt::skip]
+            let code = [
+                0xd2,                           // getlocal2
+                0x48,                           // returnvalue
+            ];
+            abc.method("untyped", params, &code);
+
+            let functions = abc.functions(activation)?;
+            let (f, typed, needs_two, untyped) =
+                (functions[0], functions[1], functions[2], functions[3]);
+
+            assert_eq!(f.call(None, &[1.into()], activation)?, 5.into());
+            let args = [1.into(), Value::Undefined];
+            assert_eq!(f.call(None, &args, activation)?, 5.into());
+            assert_eq!(f.call(None, &[1.into(), 2.into()], activation)?, 2.into());
+
+            let result = typed.call(None, &[1.into()], activation)?;
+            assert_eq!(element(activation, result, 0)?, 1.into());
+            assert_eq!(element(activation, result, 1)?, 2.5.into());
+            assert_eq!(element(activation, result, 2)?, Value::Null);
+            assert_eq!(element(activation, result, 3)?, 7.into());
+            let result = typed.call(None, &[1.into(), 3.into()], activation)?;
+            assert_eq!(element(activation, result, 1)?, 3.into());
+            assert_eq!(element(activation, result, 3)?, 7.into());
+
+            // Unchecked functions leave missing parameters undefined.
+            assert_eq!(
+                untyped.call(None, &[1.into()], activation)?,
+                Value::Undefined
+            );
+
+            let error = needs_two
+                .call(None, &[1.into()], activation)
+                .expect_err("a required parameter is missing");
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #1063: Argument count mismatch on needsTwo. Expected 2, got 1."
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn parameter_coercion() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+
+            #[rustfmt::skip]
+            let code = [
+                0xd1,                           // getlocal1
+                0x48,                           // returnvalue
+            ];
+
+            // function takesInt(i:int) { return i; }
+            let params = vec![param(abc.name("", "int"), None)];
+            abc.method("takesInt", params, &code);
+
+            // function takesMovieClip(clip:MovieClip) { return clip; }
+            let params = vec![param(abc.name("flash.display", "MovieClip"), None)];
+            abc.method("takesMovieClip", params, &code);
+
+            let functions = abc.functions(activation)?;
+            let (takes_int, takes_movie_clip) = (functions[0], functions[1]);
+
+            // Strings are coerced to numbers rather than rejected.
+            let args = ["123".into()];
+            assert_eq!(takes_int.call(None, &args, activation)?, 123.into());
+            assert_eq!(takes_int.call(None, &[Value::Null], activation)?, 0.into());
+
+            let args = [Value::Null];
+            assert_eq!(takes_movie_clip.call(None, &args, activation)?, Value::Null);
+            let clip = construct(activation, "flash.display", "MovieClip", &[])?;
+            let args = [clip.into()];
+            assert_eq!(takes_movie_clip.call(None, &args, activation)?, clip.into());
+
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            let error = takes_movie_clip
+                .call(None, &[sprite.into()], activation)
+                .expect_err("a Sprite is not a MovieClip");
+            assert_eq!(
+                error.to_string(),
+                "TypeError: Error #1034: Type Coercion failed: cannot convert flash.display::Sprite to flash.display.MovieClip."
+            );
+
+            Ok(())
+        });
+    }
+}
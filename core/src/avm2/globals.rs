@@ -16,6 +16,7 @@ use crate::string::{AvmString, WStr};
 use gc_arena::{Collect, GcCell, MutationContext};
 
 mod array;
+mod avmplus;
 mod boolean;
 mod class;
 mod date;
@@ -109,13 +110,29 @@ pub struct SystemPrototypes<'gc> {
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
     pub fullscreenevent: Object<'gc>,
+    pub keyboardevent: Object<'gc>,
+    pub mouseevent: Object<'gc>,
+    pub progressevent: Object<'gc>,
+    pub textevent: Object<'gc>,
+    pub dataevent: Object<'gc>,
+    pub ioerrorevent: Object<'gc>,
+    pub securityerrorevent: Object<'gc>,
+    pub statusevent: Object<'gc>,
+    pub netstatusevent: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
     pub display_object: Object<'gc>,
     pub shape: Object<'gc>,
+    pub blurfilter: Object<'gc>,
+    pub colormatrixfilter: Object<'gc>,
+    pub dropshadowfilter: Object<'gc>,
+    pub glowfilter: Object<'gc>,
+    pub colortransform: Object<'gc>,
+    pub matrix: Object<'gc>,
     pub point: Object<'gc>,
     pub rectangle: Object<'gc>,
+    pub transform: Object<'gc>,
     pub textfield: Object<'gc>,
     pub textformat: Object<'gc>,
     pub graphics: Object<'gc>,
@@ -167,13 +184,29 @@ impl<'gc> SystemPrototypes<'gc> {
             application_domain: empty,
             event: empty,
             fullscreenevent: empty,
+            keyboardevent: empty,
+            mouseevent: empty,
+            progressevent: empty,
+            textevent: empty,
+            dataevent: empty,
+            ioerrorevent: empty,
+            securityerrorevent: empty,
+            statusevent: empty,
+            netstatusevent: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
             display_object: empty,
             shape: empty,
+            blurfilter: empty,
+            colormatrixfilter: empty,
+            dropshadowfilter: empty,
+            glowfilter: empty,
+            colortransform: empty,
+            matrix: empty,
             point: empty,
             rectangle: empty,
+            transform: empty,
             textfield: empty,
             textformat: empty,
             graphics: empty,
@@ -216,13 +249,29 @@ pub struct SystemClasses<'gc> {
     pub application_domain: ClassObject<'gc>,
     pub event: ClassObject<'gc>,
     pub fullscreenevent: ClassObject<'gc>,
+    pub keyboardevent: ClassObject<'gc>,
+    pub mouseevent: ClassObject<'gc>,
+    pub progressevent: ClassObject<'gc>,
+    pub textevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
+    pub ioerrorevent: ClassObject<'gc>,
+    pub securityerrorevent: ClassObject<'gc>,
+    pub statusevent: ClassObject<'gc>,
+    pub netstatusevent: ClassObject<'gc>,
     pub video: ClassObject<'gc>,
     pub xml: ClassObject<'gc>,
     pub xml_list: ClassObject<'gc>,
     pub display_object: ClassObject<'gc>,
     pub shape: ClassObject<'gc>,
+    pub blurfilter: ClassObject<'gc>,
+    pub colormatrixfilter: ClassObject<'gc>,
+    pub dropshadowfilter: ClassObject<'gc>,
+    pub glowfilter: ClassObject<'gc>,
+    pub colortransform: ClassObject<'gc>,
+    pub matrix: ClassObject<'gc>,
     pub point: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
+    pub transform: ClassObject<'gc>,
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
@@ -270,13 +319,29 @@ impl<'gc> SystemClasses<'gc> {
             application_domain: object,
             event: object,
             fullscreenevent: object,
+            keyboardevent: object,
+            mouseevent: object,
+            progressevent: object,
+            textevent: object,
+            dataevent: object,
+            ioerrorevent: object,
+            securityerrorevent: object,
+            statusevent: object,
+            netstatusevent: object,
             video: object,
             xml: object,
             xml_list: object,
             display_object: object,
             shape: object,
+            blurfilter: object,
+            colormatrixfilter: object,
+            dropshadowfilter: object,
+            glowfilter: object,
+            colortransform: object,
+            matrix: object,
             point: object,
             rectangle: object,
+            transform: object,
             textfield: object,
             textformat: object,
             graphics: object,
@@ -547,6 +612,13 @@ pub fn load_player_globals<'gc>(
     constant(mc, "", "null", Value::Null, script)?;
     constant(mc, "", "NaN", f64::NAN.into(), script)?;
     constant(mc, "", "Infinity", f64::INFINITY.into(), script)?;
+    namespace(
+        activation,
+        "",
+        "AS3",
+        "http://adobe.com/AS3/2006/builtin",
+        script,
+    )?;
 
     class(activation, math::create_class(mc), script)?;
     avm2_system_class!(regexp, activation, regexp::create_class(mc), script);
@@ -556,6 +628,18 @@ pub fn load_player_globals<'gc>(
 
     avm2_system_class!(date, activation, date::create_class(mc), script);
 
+    // package `avmplus`
+    function(
+        activation,
+        "avmplus",
+        "describeTypeJSON",
+        avmplus::describe_type_json,
+        script,
+    )?;
+    for (name, value) in avmplus::FLAGS {
+        constant(mc, "avmplus", *name, (*value).into(), script)?;
+    }
+
     // package `flash.system`
     avm2_system_class!(
         application_domain,
@@ -592,21 +676,36 @@ pub fn load_player_globals<'gc>(
         flash::events::eventdispatcher::create_class(mc),
         script,
     )?;
-    class(
+    avm2_system_class!(
+        mouseevent,
         activation,
         flash::events::mouseevent::create_class(mc),
-        script,
-    )?;
-    class(
+        script
+    );
+    avm2_system_class!(
+        keyboardevent,
         activation,
         flash::events::keyboardevent::create_class(mc),
-        script,
-    )?;
-    class(
+        script
+    );
+    avm2_system_class!(
+        progressevent,
         activation,
         flash::events::progressevent::create_class(mc),
-        script,
-    )?;
+        script
+    );
+    avm2_system_class!(
+        statusevent,
+        activation,
+        flash::events::statusevent::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        netstatusevent,
+        activation,
+        flash::events::netstatusevent::create_class(mc),
+        script
+    );
     class(
         activation,
         flash::events::activityevent::create_class(mc),
@@ -618,7 +717,51 @@ pub fn load_player_globals<'gc>(
         flash::events::fullscreenevent::create_class(mc),
         script
     );
+    avm2_system_class!(
+        textevent,
+        activation,
+        flash::events::textevent::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        dataevent,
+        activation,
+        flash::events::dataevent::create_class(mc),
+        script
+    );
+    class(
+        activation,
+        flash::events::errorevent::create_class(mc),
+        script,
+    )?;
+    avm2_system_class!(
+        ioerrorevent,
+        activation,
+        flash::events::ioerrorevent::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        securityerrorevent,
+        activation,
+        flash::events::securityerrorevent::create_class(mc),
+        script
+    );
     // package `flash.utils`
+    class(
+        activation,
+        flash::utils::idatainput::create_interface(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::idataoutput::create_interface(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::utils::iexternalizable::create_interface(mc),
+        script,
+    )?;
     avm2_system_class!(
         bytearray,
         activation,
@@ -659,6 +802,38 @@ pub fn load_player_globals<'gc>(
         script,
     )?;
 
+    function(
+        activation,
+        "flash.utils",
+        "setInterval",
+        flash::utils::set_interval,
+        script,
+    )?;
+
+    function(
+        activation,
+        "flash.utils",
+        "setTimeout",
+        flash::utils::set_timeout,
+        script,
+    )?;
+
+    function(
+        activation,
+        "flash.utils",
+        "clearInterval",
+        flash::utils::clear_interval,
+        script,
+    )?;
+
+    function(
+        activation,
+        "flash.utils",
+        "clearTimeout",
+        flash::utils::clear_interval,
+        script,
+    )?;
+
     function(
         activation,
         "flash.utils",
@@ -747,6 +922,61 @@ pub fn load_player_globals<'gc>(
         flash::display::graphics::create_class(mc),
         script
     );
+    class(
+        activation,
+        flash::display::igraphicsdata::create_interface(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicsbitmapfill::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicsendfill::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicsgradientfill::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicspath::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicspathcommand::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicspathwinding::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicssolidfill::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicsstroke::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicstrianglepath::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::triangleculling::create_class(mc),
+        script,
+    )?;
     class(
         activation,
         flash::display::jointstyle::create_class(mc),
@@ -822,7 +1052,50 @@ pub fn load_player_globals<'gc>(
         script,
     )?;
 
+    // package `flash.filters`
+    class(
+        activation,
+        flash::filters::bitmapfilter::create_class(mc),
+        script,
+    )?;
+    avm2_system_class!(
+        blurfilter,
+        activation,
+        flash::filters::blurfilter::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        colormatrixfilter,
+        activation,
+        flash::filters::colormatrixfilter::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        dropshadowfilter,
+        activation,
+        flash::filters::dropshadowfilter::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        glowfilter,
+        activation,
+        flash::filters::glowfilter::create_class(mc),
+        script
+    );
+
     // package `flash.geom`
+    avm2_system_class!(
+        colortransform,
+        activation,
+        flash::geom::colortransform::create_class(mc),
+        script
+    );
+    avm2_system_class!(
+        matrix,
+        activation,
+        flash::geom::matrix::create_class(mc),
+        script
+    );
     avm2_system_class!(
         point,
         activation,
@@ -835,6 +1108,12 @@ pub fn load_player_globals<'gc>(
         flash::geom::rectangle::create_class(mc),
         script
     );
+    avm2_system_class!(
+        transform,
+        activation,
+        flash::geom::transform::create_class(mc),
+        script
+    );
 
     // package `flash.media`
     avm2_system_class!(
@@ -861,6 +1140,12 @@ pub fn load_player_globals<'gc>(
         flash::media::soundchannel::create_class(mc),
         script
     );
+    class(activation, flash::media::camera::create_class(mc), script)?;
+    class(
+        activation,
+        flash::media::microphone::create_class(mc),
+        script,
+    )?;
 
     // package `flash.net`
     avm2_system_class!(
@@ -875,6 +1160,34 @@ pub fn load_player_globals<'gc>(
         flash::net::object_encoding::create_class(mc),
         script,
     )?;
+    class(
+        activation,
+        flash::net::localconnection::create_class(mc),
+        script,
+    )?;
+    class(activation, flash::net::socket::create_class(mc), script)?;
+    class(activation, flash::net::xmlsocket::create_class(mc), script)?;
+    class(
+        activation,
+        flash::net::netconnection::create_class(mc),
+        script,
+    )?;
+    class(activation, flash::net::netstream::create_class(mc), script)?;
+
+    function(
+        activation,
+        "flash.net",
+        "registerClassAlias",
+        flash::net::register_class_alias,
+        script,
+    )?;
+    function(
+        activation,
+        "flash.net",
+        "getClassByAlias",
+        flash::net::get_class_by_alias,
+        script,
+    )?;
 
     // package `flash.text`
     avm2_system_class!(
@@ -915,5 +1228,36 @@ pub fn load_player_globals<'gc>(
         script,
     )?;
 
+    // package `flash.sampler`
+    const SAMPLER_FUNCTIONS: &[(&str, NativeMethodImpl)] = &[
+        ("clearSamples", flash::sampler::no_op),
+        ("getGetterInvocationCount", flash::sampler::zero),
+        ("getInvocationCount", flash::sampler::zero),
+        ("getLexicalScopes", flash::sampler::null),
+        ("getMasterString", flash::sampler::null),
+        ("getMemberNames", flash::sampler::empty_iterable),
+        ("getSampleCount", flash::sampler::zero),
+        ("getSamples", flash::sampler::empty_iterable),
+        ("getSavedThis", flash::sampler::null),
+        ("getSetterInvocationCount", flash::sampler::zero),
+        ("getSize", flash::sampler::get_size),
+        ("isGetterSetter", flash::sampler::is_getter_setter),
+        ("pauseSampling", flash::sampler::no_op),
+        ("sampleInternalAllocs", flash::sampler::no_op),
+        ("setSamplerCallback", flash::sampler::no_op),
+        ("startSampling", flash::sampler::no_op),
+        ("stopSampling", flash::sampler::no_op),
+    ];
+    for &(name, nf) in SAMPLER_FUNCTIONS {
+        function(activation, "flash.sampler", name, nf, script)?;
+    }
+
+    // package `flash.external`
+    class(
+        activation,
+        flash::external::externalinterface::create_class(mc),
+        script,
+    )?;
+
     Ok(())
 }
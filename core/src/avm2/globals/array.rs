@@ -5,7 +5,7 @@ use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{array_allocator, ArrayObject, Object, TObject};
+use crate::avm2::object::{array_allocator, ArrayObject, FunctionObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::string::AvmString;
@@ -49,14 +49,55 @@ pub fn instance_init<'gc>(
 }
 
 /// Implements `Array`'s class initializer.
+///
+/// The `AS3` namespaced methods are also installed as non-enumerable
+/// prototype methods, which is how code compiled without the `AS3` namespace
+/// open (such as with `-es`) reaches them.
 pub fn class_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut array_proto = this
+            .get_property(this, &QName::dynamic_name("prototype").into(), activation)?
+            .coerce_to_object(activation)?;
+        let scope = activation.create_scopechain();
+        let gc_context = activation.context.gc_context;
+        let this_class = this.as_class_object().unwrap();
+
+        for (name, method) in AS3_INSTANCE_METHODS {
+            array_proto.install_dynamic_property(
+                gc_context,
+                QName::new(Namespace::public(), *name),
+                FunctionObject::from_method(
+                    activation,
+                    Method::from_builtin(*method, name, gc_context),
+                    scope,
+                    None,
+                    Some(this_class),
+                )
+                .into(),
+            )?;
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `Array`'s call handler.
+///
+/// Calling `Array` as a function behaves identically to constructing it.
+pub fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let array_class = activation.avm2().classes().array;
+
+    Ok(array_class.construct(activation, args)?.into())
+}
+
 /// Implements `Array.length`'s getter
 pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -79,11 +120,19 @@ pub fn set_length<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
+        let size = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        if size < 0.0 || size.fract() != 0.0 || size > u32::MAX as f64 || size.is_nan() {
+            return Err(format!(
+                "RangeError: Error #1005: Array index is not a positive integer ({}).",
+                size
+            )
+            .into());
+        }
+
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
-            let size = args
-                .get(0)
-                .unwrap_or(&Value::Undefined)
-                .coerce_to_u32(activation)?;
             array.set_length(size as usize);
         }
     }
@@ -111,10 +160,11 @@ pub fn concat<'gc>(
         .unwrap_or_else(|| ArrayStorage::new(0));
 
     for arg in args {
-        if let Some(other_array) = arg.coerce_to_object(activation)?.as_array_storage() {
-            base_array.append(&other_array);
-        } else {
-            base_array.push(*arg);
+        match arg {
+            Value::Object(o) if o.as_array_storage().is_some() => {
+                base_array.append(&o.as_array_storage().unwrap());
+            }
+            arg => base_array.push(*arg),
         }
     }
 
@@ -161,18 +211,39 @@ where
 
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
+            // An array that contains itself joins as an empty string the
+            // second time around.
+            if activation
+                .avm2()
+                .join_stack
+                .iter()
+                .any(|joining| Object::ptr_eq(*joining, this))
+            {
+                return Ok("".into());
+            }
+
             let string_separator = separator.coerce_to_string(activation)?;
             let mut accum = Vec::with_capacity(array.length());
 
+            activation.avm2().join_stack.push(this);
             for (i, item) in array.iter().enumerate() {
-                let item = resolve_array_hole(activation, this, i, item)?;
+                let item = resolve_array_hole(activation, this, i, item).and_then(|item| {
+                    if matches!(item, Value::Undefined) || matches!(item, Value::Null) {
+                        Ok("".into())
+                    } else {
+                        conv(item, activation)?.coerce_to_string(activation)
+                    }
+                });
 
-                if matches!(item, Value::Undefined) || matches!(item, Value::Null) {
-                    accum.push("".into());
-                } else {
-                    accum.push(conv(item, activation)?.coerce_to_string(activation)?);
+                match item {
+                    Ok(item) => accum.push(item),
+                    Err(e) => {
+                        activation.avm2().join_stack.pop();
+                        return Err(e);
+                    }
                 }
             }
+            activation.avm2().join_stack.pop();
 
             return Ok(AvmString::new(
                 activation.context.gc_context,
@@ -347,6 +418,35 @@ impl<'gc> ArrayIter<'gc> {
     }
 }
 
+/// Determine if an index of an array is a hole.
+///
+/// Holes are indices that have no value in the array's storage, and that are
+/// not filled in by the array's prototype chain either. Objects without array
+/// storage never have holes.
+fn is_array_hole<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    i: u32,
+) -> Result<bool, Error> {
+    let is_storage_hole = this
+        .as_array_storage()
+        .map(|a| a.get(i as usize).is_none())
+        .unwrap_or(false);
+    if !is_storage_hole {
+        return Ok(false);
+    }
+
+    let name = QName::new(
+        Namespace::public(),
+        AvmString::new_utf8(activation.context.gc_context, i.to_string()),
+    );
+
+    match this.proto() {
+        Some(proto) => Ok(!proto.has_property(name)?),
+        None => Ok(true),
+    }
+}
+
 /// Implements `Array.forEach`
 pub fn for_each<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -369,6 +469,9 @@ pub fn for_each<'gc>(
 
         while let Some(r) = iter.next(activation) {
             let (i, item) = r?;
+            if is_array_hole(activation, this, i)? {
+                continue;
+            }
 
             callback.call(receiver, &[item, i.into(), this.into()], activation)?;
         }
@@ -400,6 +503,12 @@ pub fn map<'gc>(
 
         while let Some(r) = iter.next(activation) {
             let (i, item) = r?;
+            if is_array_hole(activation, this, i)? {
+                // Holes are kept so that the new array has the same length.
+                new_array.push_hole();
+                continue;
+            }
+
             let new_item = callback.call(receiver, &[item, i.into(), this.into()], activation)?;
 
             new_array.push(new_item);
@@ -434,6 +543,10 @@ pub fn filter<'gc>(
 
         while let Some(r) = iter.next(activation) {
             let (i, item) = r?;
+            if is_array_hole(activation, this, i)? {
+                continue;
+            }
+
             let is_allowed = callback
                 .call(receiver, &[item, i.into(), this.into()], activation)?
                 .coerce_to_boolean();
@@ -534,15 +647,15 @@ pub fn index_of<'gc>(
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
-            let from = args
-                .get(1)
-                .cloned()
-                .unwrap_or_else(|| 0.into())
-                .coerce_to_u32(activation)?;
+            let from = resolve_index(
+                activation,
+                args.get(1).cloned().unwrap_or_else(|| 0.into()),
+                array.length(),
+            )?;
 
             for (i, val) in array.iter().enumerate() {
                 let val = resolve_array_hole(activation, this, i, val)?;
-                if i >= from as usize && val == search_val {
+                if i >= from && val == search_val {
                     return Ok(i.into());
                 }
             }
@@ -563,15 +676,15 @@ pub fn last_index_of<'gc>(
     if let Some(this) = this {
         if let Some(array) = this.as_array_storage() {
             let search_val = args.get(0).cloned().unwrap_or(Value::Undefined);
-            let from = args
-                .get(1)
-                .cloned()
-                .unwrap_or_else(|| i32::MAX.into())
-                .coerce_to_u32(activation)?;
+            let from = resolve_index(
+                activation,
+                args.get(1).cloned().unwrap_or_else(|| i32::MAX.into()),
+                array.length(),
+            )?;
 
             for (i, val) in array.iter().enumerate().rev() {
                 let val = resolve_array_hole(activation, this, i, val)?;
-                if i <= from as usize && val == search_val {
+                if i <= from && val == search_val {
                     return Ok(i.into());
                 }
             }
@@ -590,8 +703,12 @@ pub fn pop<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
-        if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
-            return Ok(array.pop());
+        let popped = this
+            .as_array_storage_mut(activation.context.gc_context)
+            .map(|mut array| (array.length().checked_sub(1), array.pop()));
+
+        if let Some((Some(index), item)) = popped {
+            return resolve_array_hole(activation, this, index, item);
         }
     }
 
@@ -606,9 +723,12 @@ pub fn push<'gc>(
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+            array.reserve(args.len());
             for arg in args {
                 array.push(*arg)
             }
+
+            return Ok(array.length().into());
         }
     }
 
@@ -675,9 +795,10 @@ pub fn unshift<'gc>(
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
-            for arg in args.iter().rev() {
-                array.unshift(*arg)
-            }
+            // Insert everything at once so the existing contents only move once.
+            array.splice(0..0, args.iter().cloned()).for_each(drop);
+
+            return Ok(array.length().into());
         }
     }
 
@@ -757,34 +878,32 @@ pub fn splice<'gc>(
                     .get(1)
                     .cloned()
                     .unwrap_or_else(|| array_length.into())
-                    .coerce_to_i32(activation)?;
+                    .coerce_to_i32(activation)?
+                    .max(0);
 
                 let actual_end = min(array_length, actual_start + delete_count as usize);
                 let args_slice = if args.len() > 2 {
-                    args[2..].iter().cloned()
+                    args[2..].iter().cloned().map(Some)
                 } else {
-                    [].iter().cloned()
+                    [].iter().cloned().map(Some)
                 };
 
-                let contents = this
+                // Holes are kept as-is, both in this array and in the array
+                // of removed elements.
+                let mut contents = this
                     .as_array_storage()
                     .map(|a| a.iter().collect::<Vec<Option<Value<'gc>>>>())
                     .unwrap();
 
-                let mut resolved = Vec::with_capacity(contents.len());
-                for (i, v) in contents.iter().enumerate() {
-                    resolved.push(resolve_array_hole(activation, this, i, *v)?);
-                }
-
-                let removed = resolved
+                let removed = contents
                     .splice(actual_start..actual_end, args_slice)
-                    .collect::<Vec<Value<'gc>>>();
-                let removed_array = ArrayStorage::from_args(&removed[..]);
+                    .collect::<Vec<Option<Value<'gc>>>>();
+                let removed_array = ArrayStorage::from_storage(removed);
 
-                let mut resolved_array = ArrayStorage::from_args(&resolved[..]);
+                let mut new_array = ArrayStorage::from_storage(contents);
 
                 if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
-                    swap(&mut *array, &mut resolved_array)
+                    swap(&mut *array, &mut new_array)
                 }
 
                 return build_array(activation, removed_array);
@@ -1215,6 +1334,28 @@ pub fn sort_on<'gc>(
     Ok(0.into())
 }
 
+/// Methods defined in the `AS3` namespace on every `Array`.
+const AS3_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+    ("concat", concat),
+    ("join", join),
+    ("forEach", for_each),
+    ("map", map),
+    ("filter", filter),
+    ("every", every),
+    ("some", some),
+    ("indexOf", index_of),
+    ("lastIndexOf", last_index_of),
+    ("pop", pop),
+    ("push", push),
+    ("reverse", reverse),
+    ("shift", shift),
+    ("unshift", unshift),
+    ("slice", slice),
+    ("splice", splice),
+    ("sort", sort),
+    ("sortOn", sort_on),
+];
+
 /// Construct `Array`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -1228,6 +1369,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     let mut write = class.write(mc);
 
     write.set_instance_allocator(array_allocator);
+    write.set_call_handler(Method::from_builtin(
+        call_handler,
+        "<Array call handler>",
+        mc,
+    ));
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("toString", to_string),
@@ -1243,26 +1389,6 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     )] = &[("length", Some(length), Some(set_length))];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
-    const AS3_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
-        ("concat", concat),
-        ("join", join),
-        ("forEach", for_each),
-        ("map", map),
-        ("filter", filter),
-        ("every", every),
-        ("some", some),
-        ("indexOf", index_of),
-        ("lastIndexOf", last_index_of),
-        ("pop", pop),
-        ("push", push),
-        ("reverse", reverse),
-        ("shift", shift),
-        ("unshift", unshift),
-        ("slice", slice),
-        ("splice", splice),
-        ("sort", sort),
-        ("sortOn", sort_on),
-    ];
     write.define_as3_builtin_instance_methods(mc, AS3_INSTANCE_METHODS);
 
     const CONSTANTS: &[(&str, u32)] = &[
@@ -1282,3 +1408,424 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Push the index and value of an element onto the receiver.
+    fn record<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let this = this.ok_or("Callback called without a receiver")?;
+
+        call(activation, this, "push", &[args[1], args[0]])
+    }
+
+    fn double<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok((args[0].coerce_to_number(activation)? * 2.0).into())
+    }
+
+    fn keep<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(true.into())
+    }
+
+    fn record_and_throw<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        record(activation, this, args)?;
+
+        Err("Error: Stop iterating".into())
+    }
+
+    /// Construct `[1, , 3, ,]`.
+    fn holey_array<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+        let array = construct(activation, "", "Array", &[4.into()])?;
+        set(activation, array, "0", 1)?;
+        set(activation, array, "2", 3)?;
+
+        Ok(array)
+    }
+
+    #[test]
+    fn iteration_skips_holes() {
+        with_avm2(|activation| {
+            let a = holey_array(activation)?;
+
+            let visited = construct(activation, "", "Array", &[])?;
+            let callback = function(activation, record)?;
+            call(activation, a, "forEach", &[callback.into(), visited.into()])?;
+            assert_eq!(traced(activation, visited)?, "0,1,2,3");
+
+            let callback = function(activation, double)?;
+            let b = call(activation, a, "map", &[callback.into()])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, b, "length")?, 4.into());
+            assert_eq!(traced(activation, b)?, "2,,6,");
+            assert_eq!(get(activation, b, "1")?, Value::Undefined);
+
+            let callback = function(activation, keep)?;
+            let c =
+                call(activation, a, "filter", &[callback.into()])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, c, "length")?, 2.into());
+            assert_eq!(traced(activation, c)?, "1,3");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn errors_stop_iteration() {
+        with_avm2(|activation| {
+            let a = holey_array(activation)?;
+
+            let visited = construct(activation, "", "Array", &[])?;
+            let callback = function(activation, record_and_throw)?;
+            let error =
+                call(activation, a, "forEach", &[callback.into(), visited.into()]).unwrap_err();
+            assert_eq!(error.to_string(), "Error: Stop iterating");
+            assert_eq!(traced(activation, visited)?, "0,1");
+
+            Ok(())
+        })
+    }
+
+    /// Call a method and return what `trace` would print for the result.
+    fn traced_call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<String, Error> {
+        let value = call(activation, object, name, args)?;
+
+        traced(activation, value)
+    }
+
+    #[test]
+    fn join_prints_null_and_undefined_as_empty() {
+        with_avm2(|activation| {
+            let values = [1.into(), Value::Null, Value::Undefined, 2.into()];
+            let a = array(activation, &values)?;
+
+            assert_eq!(traced_call(activation, a, "join", &[])?, "1,,,2");
+            assert_eq!(traced_call(activation, a, "join", &["-".into()])?, "1---2");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn splice_and_slice_count_negative_indices_from_the_end() {
+        with_avm2(|activation| {
+            let values = [1.into(), 2.into(), 3.into(), 4.into(), 5.into()];
+            let a = array(activation, &values)?;
+
+            let args = [(-2).into(), 1.into(), "x".into(), "y".into()];
+            let removed = call(activation, a, "splice", &args)?.coerce_to_object(activation)?;
+            assert_eq!(traced(activation, removed)?, "4");
+            assert_eq!(get(activation, removed, "length")?, 1.into());
+            assert_eq!(traced(activation, a)?, "1,2,3,x,y,5");
+            assert_eq!(get(activation, a, "length")?, 6.into());
+
+            assert_eq!(
+                traced_call(activation, a, "splice", &[1.into(), (-3).into()])?,
+                ""
+            );
+            assert_eq!(traced(activation, a)?, "1,2,3,x,y,5");
+
+            let args = [(-3).into(), (-1).into()];
+            assert_eq!(traced_call(activation, a, "slice", &args)?, "x,y");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn concat_keeps_holes_and_flattens_one_level() {
+        with_avm2(|activation| {
+            let sparse = construct(activation, "", "Array", &[3.into()])?;
+            set(activation, sparse, "1", "b")?;
+            let d = array(activation, &["d".into()])?;
+            let c = array(activation, &["c".into(), d.into()])?;
+            let a = array(activation, &["a".into()])?;
+
+            let args = [sparse.into(), Value::Null, c.into()];
+            let joined = call(activation, a, "concat", &args)?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, joined, "length")?, 7.into());
+            assert_eq!(traced(activation, joined)?, "a,,b,,,c,d");
+
+            let name = |name: &'static str| QName::new(Namespace::public(), name);
+            assert!(!joined.has_property_via_in(activation, name("1"))?);
+            assert!(joined.has_property_via_in(activation, name("2"))?);
+
+            let array_class = activation.avm2().classes().array;
+            let last = get(activation, joined, "6")?.coerce_to_object(activation)?;
+            assert!(last.is_of_type(array_class, activation)?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn index_of_counts_negative_indices_from_the_end() {
+        with_avm2(|activation| {
+            let b = array(activation, &[1.into(), 2.into(), 1.into(), 2.into()])?;
+
+            assert_eq!(
+                call(activation, b, "indexOf", &[2.into(), 2.into()])?,
+                3.into()
+            );
+            assert_eq!(
+                call(activation, b, "indexOf", &[1.into(), (-2).into()])?,
+                2.into()
+            );
+            let args = [2.into(), (-2).into()];
+            assert_eq!(call(activation, b, "lastIndexOf", &args)?, 1.into());
+            assert_eq!(call(activation, b, "lastIndexOf", &[1.into()])?, 2.into());
+
+            assert_eq!(call(activation, b, "reverse", &[])?, b.into());
+            assert_eq!(traced(activation, b)?, "2,1,2,1");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn push_and_unshift_return_the_new_length() {
+        with_avm2(|activation| {
+            let a = array(activation, &[])?;
+            assert_eq!(call(activation, a, "pop", &[])?, Value::Undefined);
+            assert_eq!(get(activation, a, "length")?, 0.into());
+            let args = [1.into(), 2.into(), 3.into()];
+            assert_eq!(call(activation, a, "push", &args)?, 3.into());
+            assert_eq!(call(activation, a, "push", &[])?, 3.into());
+            assert_eq!(call(activation, a, "pop", &[])?, 3.into());
+            assert_eq!(get(activation, a, "length")?, 2.into());
+            assert_eq!(call(activation, a, "shift", &[])?, 1.into());
+            assert_eq!(traced(activation, a)?, "2");
+
+            let b = construct(activation, "", "Array", &[3.into()])?;
+            set(activation, b, "1", "x")?;
+            let args = ["first".into(), "second".into()];
+            assert_eq!(call(activation, b, "unshift", &args)?, 5.into());
+            assert_eq!(traced(activation, b)?, "first,second,,x,");
+            assert_eq!(get(activation, b, "length")?, 5.into());
+            let name = |name: &'static str| QName::new(Namespace::public(), name);
+            assert!(!b.has_property_via_in(activation, name("2"))?);
+            assert!(b.has_property_via_in(activation, name("3"))?);
+            assert_eq!(call(activation, b, "unshift", &[])?, 5.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn pop_removes_trailing_holes() {
+        with_avm2(|activation| {
+            let holes = array(activation, &[1.into()])?;
+            set(activation, holes, "length", 3)?;
+
+            assert_eq!(call(activation, holes, "pop", &[])?, Value::Undefined);
+            assert_eq!(get(activation, holes, "length")?, 2.into());
+            assert_eq!(call(activation, holes, "pop", &[])?, Value::Undefined);
+            assert_eq!(get(activation, holes, "length")?, 1.into());
+            assert_eq!(call(activation, holes, "pop", &[])?, 1.into());
+            assert_eq!(get(activation, holes, "length")?, 0.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn setting_length_truncates_or_adds_holes() {
+        with_avm2(|activation| {
+            let values = [1.into(), 2.into(), 3.into(), 4.into(), 5.into()];
+            let a = array(activation, &values)?;
+            let name = |name: &'static str| QName::new(Namespace::public(), name);
+
+            set(activation, a, "length", 2)?;
+            assert_eq!(traced(activation, a)?, "1,2");
+            assert_eq!(get(activation, a, "3")?, Value::Undefined);
+            assert!(!a.has_property_via_in(activation, name("3"))?);
+
+            set(activation, a, "length", 4)?;
+            assert_eq!(get(activation, a, "length")?, 4.into());
+            assert_eq!(get(activation, a, "2")?, Value::Undefined);
+            assert!(!a.has_property_via_in(activation, name("2"))?);
+            assert_eq!(traced(activation, a)?, "1,2,,");
+
+            let b = array(activation, &[])?;
+            set(activation, b, "100", 1)?;
+            assert_eq!(get(activation, b, "length")?, 101.into());
+            set(activation, b, "length", "3")?;
+            assert_eq!(get(activation, b, "length")?, 3.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn setting_length_to_a_fraction_throws() {
+        with_avm2(|activation| {
+            let a = array(activation, &[1.into(), 2.into(), 3.into()])?;
+
+            let error = set(activation, a, "length", 1.5).unwrap_err();
+            assert!(error.to_string().starts_with("RangeError: Error #1005"));
+            assert_eq!(get(activation, a, "length")?, 3.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn cycles_print_as_empty() {
+        with_avm2(|activation| {
+            let inner = array(activation, &[3.into(), 4.into()])?;
+            let middle = array(activation, &[2.into(), inner.into()])?;
+            let nested = array(activation, &[1.into(), middle.into(), 5.into()])?;
+            assert_eq!(traced(activation, nested)?, "1,2,3,4,5");
+
+            let self_array = array(activation, &[1.into(), 2.into()])?;
+            call(activation, self_array, "push", &[self_array.into()])?;
+            call(activation, self_array, "push", &[3.into()])?;
+            assert_eq!(
+                traced_call(activation, self_array, "toString", &[])?,
+                "1,2,,3"
+            );
+            let value = traced_call(activation, self_array, "toLocaleString", &[])?;
+            assert_eq!(value, "1,2,,3");
+            let value = traced_call(activation, self_array, "join", &["-".into()])?;
+            assert_eq!(value, "1-2--3");
+
+            // Each array stops at itself, wherever printing started.
+            let first = array(activation, &["first".into()])?;
+            let second = array(activation, &["second".into(), first.into()])?;
+            call(activation, first, "push", &[second.into()])?;
+            assert_eq!(traced(activation, first)?, "first,second,");
+            assert_eq!(traced(activation, second)?, "second,first,");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn prototype_methods_match_the_as3_methods() {
+        with_avm2(|activation| {
+            let as3_push = QName::new(Namespace::as3_namespace(), "push").into();
+            let public_push = QName::new(Namespace::public(), "push").into();
+            let a = array(activation, &[])?;
+            let b = array(activation, &[])?;
+            assert_eq!(
+                a.call_property(&as3_push, &[1.into()], activation)?,
+                1.into()
+            );
+            assert_eq!(
+                b.call_property(&public_push, &[1.into()], activation)?,
+                1.into()
+            );
+            let args = [2.into(), 3.into()];
+            assert_eq!(a.call_property(&as3_push, &args, activation)?, 3.into());
+            assert_eq!(b.call_property(&public_push, &args, activation)?, 3.into());
+            assert_eq!(traced(activation, a)?, "1,2,3");
+            assert_eq!(traced(activation, b)?, "1,2,3");
+
+            // The prototype methods can be called on any array.
+            let prototype = activation.avm2().prototypes().array;
+            let push = get(activation, prototype, "push")?.coerce_to_object(activation)?;
+            assert_eq!(push.call(Some(a), &[4.into()], activation)?, 4.into());
+            let join = get(activation, prototype, "join")?.coerce_to_object(activation)?;
+            let joined = join.call(Some(a), &["-".into()], activation)?;
+            assert_eq!(traced(activation, joined)?, "1-2-3-4");
+            let pop = get(activation, prototype, "pop")?.coerce_to_object(activation)?;
+            assert_eq!(pop.call(Some(a), &[], activation)?, 4.into());
+            let as3_pop = QName::new(Namespace::as3_namespace(), "pop").into();
+            assert_eq!(a.call_property(&as3_pop, &[], activation)?, 3.into());
+            assert_eq!(traced(activation, a)?, "1,2");
+
+            // They are hidden from enumeration, and not own properties of
+            // arrays.
+            let args = ["push".into()];
+            let own = call(activation, prototype, "hasOwnProperty", &args)?;
+            assert_eq!(own, true.into());
+            let enumerable = call(activation, prototype, "propertyIsEnumerable", &args)?;
+            assert_eq!(enumerable, false.into());
+            assert_eq!(call(activation, a, "hasOwnProperty", &args)?, false.into());
+
+            Ok(())
+        })
+    }
+
+    /// The names and values an object's own properties are enumerated with,
+    /// as `for..in` and `for each` see them.
+    fn enumerate<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<Vec<(Value<'gc>, Value<'gc>)>, Error> {
+        let mut enumerants = Vec::new();
+        let mut index = 0;
+        while let Some(next) = object.get_next_enumerant(index, activation)? {
+            let name = object.get_enumerant_name(next, activation)?;
+            let value = object.get_enumerant_value(next, activation)?;
+            enumerants.push((name, value));
+            index = next;
+        }
+
+        Ok(enumerants)
+    }
+
+    #[test]
+    fn holes_are_not_enumerated() {
+        with_avm2(|activation| {
+            let a = array(activation, &[])?;
+            set(activation, a, "0", 1)?;
+            set(activation, a, "5", 2)?;
+            assert_eq!(get(activation, a, "length")?, 6.into());
+
+            let enumerants = enumerate(activation, a)?;
+            assert_eq!(enumerants, [(0.into(), 1.into()), (5.into(), 2.into())]);
+            // Indices are enumerated as numbers, not strings.
+            for (name, _) in enumerants {
+                assert!(matches!(
+                    name,
+                    Value::Integer(_) | Value::Unsigned(_) | Value::Number(_)
+                ));
+            }
+
+            let mut enumerable = Vec::new();
+            for index in [0, 3, 5, 6] {
+                let is_enumerable = call(activation, a, "propertyIsEnumerable", &[index.into()])?;
+                enumerable.push(is_enumerable.coerce_to_boolean());
+            }
+            assert_eq!(enumerable, [true, false, true, false]);
+
+            let b = construct(activation, "", "Array", &[3.into()])?;
+            assert!(enumerate(activation, b)?.is_empty());
+
+            // Dynamic properties follow the elements.
+            let c = array(activation, &[])?;
+            set(activation, c, "2", "two")?;
+            set(activation, c, "extra", "dynamic")?;
+            let mut traced_enumerants = Vec::new();
+            for (name, value) in enumerate(activation, c)? {
+                let name = traced(activation, name)?;
+                traced_enumerants.push(format!("{} {}", name, traced(activation, value)?));
+            }
+            assert_eq!(traced_enumerants, ["2 two", "extra dynamic"]);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,818 @@
+//! `avmplus` namespace
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{ArrayObject, ClassObject, TObject};
+use crate::avm2::traits::{Metadata, Trait, TraitKind};
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Omit methods declared in a namespace other than the public one.
+pub const HIDE_NSURI_METHODS: u32 = 0x0001;
+
+/// Include the `bases` list of superclass names.
+pub const INCLUDE_BASES: u32 = 0x0002;
+
+/// Include the `interfaces` list of implemented interface names.
+pub const INCLUDE_INTERFACES: u32 = 0x0004;
+
+/// Include the `variables` list of slots and constants.
+pub const INCLUDE_VARIABLES: u32 = 0x0008;
+
+/// Include the `accessors` list of getters and setters.
+pub const INCLUDE_ACCESSORS: u32 = 0x0010;
+
+/// Include the `methods` list.
+pub const INCLUDE_METHODS: u32 = 0x0020;
+
+/// Include the `metadata` of the type and of each listed trait.
+pub const INCLUDE_METADATA: u32 = 0x0040;
+
+/// Include the `constructor` parameter list.
+pub const INCLUDE_CONSTRUCTOR: u32 = 0x0080;
+
+/// Include the `traits` object at all. Without this flag, `traits` is null.
+pub const INCLUDE_TRAITS: u32 = 0x0100;
+
+/// Describe the instances of a class rather than the class object itself.
+pub const USE_ITRAITS: u32 = 0x0200;
+
+/// Omit `Object` from `bases`, and omit everything declared by `Object`.
+pub const HIDE_OBJECT: u32 = 0x0400;
+
+/// The flag set used by Flash Player 10's `flash.utils.describeType`.
+pub const FLASH10_FLAGS: u32 = INCLUDE_BASES
+    | INCLUDE_INTERFACES
+    | INCLUDE_VARIABLES
+    | INCLUDE_ACCESSORS
+    | INCLUDE_METHODS
+    | INCLUDE_METADATA
+    | INCLUDE_CONSTRUCTOR
+    | INCLUDE_TRAITS
+    | HIDE_NSURI_METHODS
+    | HIDE_OBJECT;
+
+/// All public flag constants of the `avmplus` package.
+pub const FLAGS: &[(&str, u32)] = &[
+    ("HIDE_NSURI_METHODS", HIDE_NSURI_METHODS),
+    ("INCLUDE_BASES", INCLUDE_BASES),
+    ("INCLUDE_INTERFACES", INCLUDE_INTERFACES),
+    ("INCLUDE_VARIABLES", INCLUDE_VARIABLES),
+    ("INCLUDE_ACCESSORS", INCLUDE_ACCESSORS),
+    ("INCLUDE_METHODS", INCLUDE_METHODS),
+    ("INCLUDE_METADATA", INCLUDE_METADATA),
+    ("INCLUDE_CONSTRUCTOR", INCLUDE_CONSTRUCTOR),
+    ("INCLUDE_TRAITS", INCLUDE_TRAITS),
+    ("USE_ITRAITS", USE_ITRAITS),
+    ("HIDE_OBJECT", HIDE_OBJECT),
+    ("FLASH10_FLAGS", FLASH10_FLAGS),
+];
+
+/// An accessor pair collected while walking the traits of a type.
+struct Accessor<'gc> {
+    name: QName<'gc>,
+    getter: Option<Trait<'gc>>,
+    setter: Option<Trait<'gc>>,
+    declared_by: ClassObject<'gc>,
+}
+
+/// Create an empty `Object` to hold part of a type description.
+fn new_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+    activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])
+}
+
+/// Set a public property on a type description object.
+fn set<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error> {
+    object.set_property(
+        object,
+        &QName::new(Namespace::public(), name).into(),
+        value.into(),
+        activation,
+    )
+}
+
+/// Build an `Array` out of a list of values.
+fn array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: Vec<Value<'gc>>,
+) -> Result<Value<'gc>, Error> {
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into())
+}
+
+/// The qualified name of a class, as reported by `getQualifiedClassName`.
+fn class_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: ClassObject<'gc>,
+) -> Value<'gc> {
+    class
+        .inner_class_definition()
+        .read()
+        .name()
+        .to_qualified_name(activation.context.gc_context)
+        .into()
+}
+
+/// The qualified name of a type annotation.
+///
+/// Untyped values are reported as `*`.
+fn qualified_type_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &Multiname<'gc>,
+) -> Value<'gc> {
+    if name.is_any() {
+        return "*".into();
+    }
+
+    let local_name = name.local_name().unwrap_or_else(|| "*".into());
+    let ns = name
+        .namespace_set()
+        .next()
+        .copied()
+        .unwrap_or_else(Namespace::public);
+
+    QName::new(ns, local_name)
+        .to_qualified_name(activation.context.gc_context)
+        .into()
+}
+
+/// Describe a list of metadata tags, or yield null if metadata was not
+/// requested.
+fn metadata<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    metadata: &[Metadata<'gc>],
+    flags: u32,
+) -> Result<Value<'gc>, Error> {
+    if flags & INCLUDE_METADATA == 0 {
+        return Ok(Value::Null);
+    }
+
+    let mut tags = Vec::with_capacity(metadata.len());
+    for tag in metadata {
+        let mut items = Vec::with_capacity(tag.items.len());
+        for item in &tag.items {
+            let item_object = new_object(activation)?;
+            set(activation, item_object, "key", item.key)?;
+            set(activation, item_object, "value", item.value)?;
+            items.push(item_object.into());
+        }
+
+        let tag_object = new_object(activation)?;
+        set(activation, tag_object, "name", tag.name)?;
+        let items = array(activation, items)?;
+        set(activation, tag_object, "value", items)?;
+        tags.push(tag_object.into());
+    }
+
+    array(activation, tags)
+}
+
+/// Describe the parameters of a method.
+fn parameters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    method: &Method<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let mut params = Vec::with_capacity(method.signature().len());
+    for param in method.signature() {
+        let param_object = new_object(activation)?;
+        let param_type = qualified_type_name(activation, &param.param_type_name);
+        set(activation, param_object, "type", param_type)?;
+        set(
+            activation,
+            param_object,
+            "optional",
+            param.default_value.is_some(),
+        )?;
+        params.push(param_object.into());
+    }
+
+    array(activation, params)
+}
+
+/// Set the `name`, and for traits in a user-defined namespace the `uri`, of a
+/// described trait.
+fn set_trait_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: QName<'gc>,
+) -> Result<(), Error> {
+    set(activation, object, "name", name.local_name())?;
+
+    if let Namespace::Namespace(uri) = name.namespace() {
+        set(activation, object, "uri", uri)?;
+    }
+
+    Ok(())
+}
+
+/// Determine if a trait's name is visible to reflection.
+///
+/// Only public traits, and traits in user-defined namespaces, are described.
+fn is_visible<'gc>(name: QName<'gc>) -> bool {
+    let ns = name.namespace();
+
+    ns.is_public() || ns.is_namespace()
+}
+
+/// Collect every trait of a described type, paired with the class that
+/// declared it. Traits of subclasses come first.
+fn collect_traits<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: ClassObject<'gc>,
+    is_static: bool,
+    flags: u32,
+) -> Vec<(ClassObject<'gc>, Trait<'gc>)> {
+    let mut traits = Vec::new();
+
+    let mut next_class = if is_static {
+        let class_def = class.inner_class_definition();
+        for class_trait in class_def.read().class_traits() {
+            traits.push((class, class_trait.clone()));
+        }
+
+        class.instance_of()
+    } else {
+        Some(class)
+    };
+
+    let object_class = activation.avm2().classes().object;
+    while let Some(class) = next_class {
+        if flags & HIDE_OBJECT != 0 && class == object_class {
+            break;
+        }
+
+        let class_def = class.inner_class_definition();
+        for instance_trait in class_def.read().instance_traits() {
+            traits.push((class, instance_trait.clone()));
+        }
+
+        next_class = class.superclass_object();
+    }
+
+    traits
+}
+
+/// Describe the traits of a type.
+fn describe_traits<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: ClassObject<'gc>,
+    is_static: bool,
+    flags: u32,
+) -> Result<Object<'gc>, Error> {
+    let object_class = activation.avm2().classes().object;
+    let traits_object = new_object(activation)?;
+
+    let bases = if flags & INCLUDE_BASES != 0 {
+        let mut bases = Vec::new();
+        let mut next_class = if is_static {
+            class.instance_of()
+        } else {
+            class.superclass_object()
+        };
+
+        while let Some(class) = next_class {
+            if flags & HIDE_OBJECT != 0 && class == object_class {
+                break;
+            }
+
+            bases.push(class_name(activation, class));
+            next_class = class.superclass_object();
+        }
+
+        array(activation, bases)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "bases", bases)?;
+
+    let interfaces = if flags & INCLUDE_INTERFACES != 0 {
+        let mut interfaces = Vec::new();
+        if !is_static {
+            for interface in class.interfaces() {
+                interfaces.push(class_name(activation, interface));
+            }
+        }
+
+        array(activation, interfaces)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "interfaces", interfaces)?;
+
+    let constructor = if flags & INCLUDE_CONSTRUCTOR != 0 && !is_static {
+        let instance_init = class.inner_class_definition().read().instance_init();
+        parameters(activation, &instance_init)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "constructor", constructor)?;
+
+    let mut variables = Vec::new();
+    let mut accessors: Vec<Accessor<'gc>> = Vec::new();
+    let mut methods = Vec::new();
+    let mut seen_names = Vec::new();
+
+    for (declared_by, described_trait) in collect_traits(activation, class, is_static, flags) {
+        let name = described_trait.name();
+        if !is_visible(name) {
+            continue;
+        }
+
+        let is_getter = matches!(described_trait.kind(), TraitKind::Getter { .. });
+        let is_setter = matches!(described_trait.kind(), TraitKind::Setter { .. });
+        let is_variable = matches!(
+            described_trait.kind(),
+            TraitKind::Slot { .. } | TraitKind::Const { .. }
+        );
+        let is_method = matches!(described_trait.kind(), TraitKind::Method { .. });
+
+        if is_getter || is_setter {
+            if let Some(accessor) = accessors.iter_mut().find(|a| a.name == name) {
+                if is_getter && accessor.getter.is_none() {
+                    accessor.getter = Some(described_trait);
+                } else if is_setter && accessor.setter.is_none() {
+                    accessor.setter = Some(described_trait);
+                }
+            } else if !seen_names.contains(&name) {
+                seen_names.push(name);
+                let (getter, setter) = if is_getter {
+                    (Some(described_trait), None)
+                } else {
+                    (None, Some(described_trait))
+                };
+
+                accessors.push(Accessor {
+                    name,
+                    getter,
+                    setter,
+                    declared_by,
+                });
+            }
+        } else if is_variable {
+            if !seen_names.contains(&name) {
+                seen_names.push(name);
+                variables.push(described_trait);
+            }
+        } else if is_method {
+            if flags & HIDE_NSURI_METHODS != 0 && name.namespace().is_namespace() {
+                continue;
+            }
+
+            if !seen_names.contains(&name) {
+                seen_names.push(name);
+                methods.push((declared_by, described_trait));
+            }
+        }
+    }
+
+    let variables = if flags & INCLUDE_VARIABLES != 0 {
+        let mut described = Vec::with_capacity(variables.len());
+        for variable in variables {
+            let variable_object = new_object(activation)?;
+            set_trait_name(activation, variable_object, variable.name())?;
+
+            let (access, variable_type) = match variable.kind() {
+                TraitKind::Const { type_name, .. } => ("readonly", type_name.clone()),
+                TraitKind::Slot { type_name, .. } => ("readwrite", type_name.clone()),
+                _ => unreachable!(),
+            };
+            let variable_type = qualified_type_name(activation, &variable_type);
+            set(activation, variable_object, "type", variable_type)?;
+            set(activation, variable_object, "access", access)?;
+
+            let variable_metadata = metadata(activation, variable.metadata(), flags)?;
+            set(activation, variable_object, "metadata", variable_metadata)?;
+            described.push(variable_object.into());
+        }
+
+        array(activation, described)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "variables", variables)?;
+
+    let accessors = if flags & INCLUDE_ACCESSORS != 0 {
+        let mut described = Vec::with_capacity(accessors.len());
+        for accessor in accessors {
+            let accessor_object = new_object(activation)?;
+            set_trait_name(activation, accessor_object, accessor.name)?;
+
+            let access = match (&accessor.getter, &accessor.setter) {
+                (Some(_), Some(_)) => "readwrite",
+                (Some(_), None) => "readonly",
+                _ => "writeonly",
+            };
+            set(activation, accessor_object, "access", access)?;
+
+            let accessor_type = if let Some(getter) = &accessor.getter {
+                getter.as_method().unwrap().return_type()
+            } else {
+                accessor
+                    .setter
+                    .as_ref()
+                    .and_then(|s| s.as_method())
+                    .and_then(|m| m.signature().get(0).map(|p| p.param_type_name.clone()))
+                    .unwrap_or_else(Multiname::any)
+            };
+            let accessor_type = qualified_type_name(activation, &accessor_type);
+            set(activation, accessor_object, "type", accessor_type)?;
+
+            let declared_by = class_name(activation, accessor.declared_by);
+            set(activation, accessor_object, "declaredBy", declared_by)?;
+
+            let mut accessor_metadata = Vec::new();
+            for accessor_trait in accessor.getter.iter().chain(accessor.setter.iter()) {
+                accessor_metadata.extend_from_slice(accessor_trait.metadata());
+            }
+            let accessor_metadata = metadata(activation, &accessor_metadata, flags)?;
+            set(activation, accessor_object, "metadata", accessor_metadata)?;
+            described.push(accessor_object.into());
+        }
+
+        array(activation, described)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "accessors", accessors)?;
+
+    let methods = if flags & INCLUDE_METHODS != 0 {
+        let mut described = Vec::with_capacity(methods.len());
+        for (declared_by, method_trait) in methods {
+            let method_object = new_object(activation)?;
+            set_trait_name(activation, method_object, method_trait.name())?;
+
+            let method = method_trait.as_method().unwrap();
+            let return_type = qualified_type_name(activation, &method.return_type());
+            set(activation, method_object, "returnType", return_type)?;
+
+            let declared_by = class_name(activation, declared_by);
+            set(activation, method_object, "declaredBy", declared_by)?;
+
+            let params = parameters(activation, &method)?;
+            set(activation, method_object, "parameters", params)?;
+
+            let method_metadata = metadata(activation, method_trait.metadata(), flags)?;
+            set(activation, method_object, "metadata", method_metadata)?;
+            described.push(method_object.into());
+        }
+
+        array(activation, described)?
+    } else {
+        Value::Null
+    };
+    set(activation, traits_object, "methods", methods)?;
+
+    let type_metadata = if is_static {
+        metadata(activation, &[], flags)?
+    } else {
+        let class_def = class.inner_class_definition();
+        let class_metadata = class_def.read().metadata().to_vec();
+        metadata(activation, &class_metadata, flags)?
+    };
+    set(activation, traits_object, "metadata", type_metadata)?;
+
+    Ok(traits_object)
+}
+
+/// Implements `avmplus.describeTypeJSON`
+///
+/// The `flags` argument selects which parts of the description are built;
+/// parts that were not requested are null.
+pub fn describe_type_json<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    if matches!(value, Value::Undefined | Value::Null) {
+        return Ok(Value::Null);
+    }
+
+    let flags = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    let object = value.coerce_to_object(activation)?;
+    let (class, is_static) = match object.as_class_object() {
+        Some(class) => (class, flags & USE_ITRAITS == 0),
+        None => match object.instance_of() {
+            Some(class) => (class, false),
+            None => return Ok(Value::Null),
+        },
+    };
+
+    let description = new_object(activation)?;
+
+    let name = class_name(activation, class);
+    set(activation, description, "name", name)?;
+
+    let (is_dynamic, is_final) = if is_static {
+        (true, true)
+    } else {
+        let class_def = class.inner_class_definition();
+        let class_def = class_def.read();
+        (!class_def.is_sealed(), class_def.is_final())
+    };
+    set(activation, description, "isDynamic", is_dynamic)?;
+    set(activation, description, "isFinal", is_final)?;
+    set(activation, description, "isStatic", is_static)?;
+
+    let traits = if flags & INCLUDE_TRAITS != 0 {
+        describe_traits(activation, class, is_static, flags)?.into()
+    } else {
+        Value::Null
+    };
+    set(activation, description, "traits", traits)?;
+
+    Ok(description.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        describe_type_json, HIDE_OBJECT, INCLUDE_ACCESSORS, INCLUDE_BASES, INCLUDE_CONSTRUCTOR,
+        INCLUDE_INTERFACES, INCLUDE_METHODS, INCLUDE_TRAITS, INCLUDE_VARIABLES, USE_ITRAITS,
+    };
+    use crate::avm2::class::{Class, ClassAttributes};
+    use crate::avm2::method::{Method, ParamConfig};
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{ClassObject, TObject};
+    use crate::avm2::test_utils::*;
+    use crate::avm2::traits::Trait;
+    use crate::avm2::{Activation, Error, Object, Value};
+
+    fn noop<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    fn super_init<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        activation.super_init(this.unwrap(), &[])
+    }
+
+    fn public<'gc>(name: &'static str) -> QName<'gc> {
+        QName::new(Namespace::public(), name)
+    }
+
+    /// Define the classes
+    ///
+    /// ```as3
+    /// class Base {
+    ///     public var baseVar:int;
+    ///     public function baseMethod() {}
+    /// }
+    ///
+    /// final class Child extends Base implements IExternalizable {
+    ///     public static const VERSION:String = "1";
+    ///     public var count:Number;
+    ///     private var hidden:Boolean;
+    ///     public function Child(count:Number, label:String = "none") {}
+    ///     public function get label() {}
+    ///     public function set label(value) {}
+    ///     public function get readOnly() {}
+    ///     public function greet() {}
+    /// }
+    /// ```
+    fn define_child<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<ClassObject<'gc>, Error> {
+        let mc = activation.context.gc_context;
+        let object = activation.avm2().classes().object;
+        let base = Class::new(
+            public("Base"),
+            Some(public("Object").into()),
+            Method::from_builtin(super_init, "Base", mc),
+            Method::from_builtin(noop, "Base", mc),
+            mc,
+        );
+        let mut write = base.write(mc);
+        write.define_instance_trait(Trait::from_slot(
+            public("baseVar"),
+            public("int").into(),
+            None,
+        ));
+        write.define_public_builtin_instance_methods(mc, &[("baseMethod", noop)]);
+        drop(write);
+        let base = ClassObject::from_class(activation, base, Some(object))?;
+
+        let constructor = Method::from_builtin_and_params(
+            super_init,
+            "Child",
+            vec![
+                ParamConfig::of_type("count", public("Number").into()),
+                ParamConfig::optional("label", public("String").into(), "none"),
+            ],
+            false,
+            mc,
+        );
+        let child = Class::new(
+            public("Child"),
+            Some(public("Base").into()),
+            constructor,
+            Method::from_builtin(noop, "Child", mc),
+            mc,
+        );
+        let mut write = child.write(mc);
+        write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+        write.implements(QName::new(Namespace::package("flash.utils"), "IExternalizable").into());
+        write.define_public_constant_string_class_traits(&[("VERSION", "1")]);
+        write.define_public_slot_number_instance_traits(&[("count", None)]);
+        write.define_private_slot_instance_traits(&[("Child", "hidden", "", "Boolean")]);
+        write.define_public_builtin_instance_properties(
+            mc,
+            &[
+                ("label", Some(noop), Some(noop)),
+                ("readOnly", Some(noop), None),
+            ],
+        );
+        write.define_public_builtin_instance_methods(mc, &[("greet", noop)]);
+        drop(write);
+
+        ClassObject::from_class(activation, child, Some(base))
+    }
+
+    fn describe<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: impl Into<Value<'gc>>,
+        flags: u32,
+    ) -> Result<Object<'gc>, Error> {
+        describe_type_json(activation, None, &[value.into(), flags.into()])?
+            .coerce_to_object(activation)
+    }
+
+    /// The entries of a description list, like `desc.traits.variables`.
+    fn entries<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        description: Object<'gc>,
+        list: &'static str,
+    ) -> Result<Vec<Object<'gc>>, Error> {
+        let traits = get(activation, description, "traits")?.coerce_to_object(activation)?;
+        let list = get(activation, traits, list)?.coerce_to_object(activation)?;
+        let storage = list.as_array_storage().unwrap();
+        let entries = storage.iter().flatten().collect::<Vec<_>>();
+        drop(storage);
+
+        entries
+            .into_iter()
+            .map(|entry| entry.coerce_to_object(activation))
+            .collect()
+    }
+
+    /// The sorted names of a description list.
+    fn names<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        description: Object<'gc>,
+        list: &'static str,
+    ) -> Result<String, Error> {
+        let mut names = Vec::new();
+        for entry in entries(activation, description, list)? {
+            let name = get(activation, entry, "name")?;
+            names.push(traced(activation, name)?);
+        }
+        names.sort();
+
+        Ok(names.join(","))
+    }
+
+    /// A property of the entry with the given name in a description list.
+    fn entry_property<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        description: Object<'gc>,
+        list: &'static str,
+        name: &'static str,
+        property: &'static str,
+    ) -> Result<String, Error> {
+        for entry in entries(activation, description, list)? {
+            if get(activation, entry, "name")? == name.into() {
+                let value = get(activation, entry, property)?;
+                return traced(activation, value);
+            }
+        }
+
+        Err(format!("{} has no entry named {}", list, name).into())
+    }
+
+    /// A property of the `traits` object, as `trace` would print it.
+    fn traits_property<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        description: Object<'gc>,
+        name: &'static str,
+    ) -> Result<String, Error> {
+        let traits = get(activation, description, "traits")?.coerce_to_object(activation)?;
+        let value = get(activation, traits, name)?;
+
+        traced(activation, value)
+    }
+
+    #[test]
+    fn describe_type_json_flags() {
+        with_avm2(|activation| {
+            let child_class = define_child(activation)?;
+            let child = child_class.construct(activation, &[5.into()])?;
+
+            let desc = describe(activation, child, 0)?;
+            assert_eq!(get(activation, desc, "name")?, "Child".into());
+            assert_eq!(get(activation, desc, "isDynamic")?, false.into());
+            assert_eq!(get(activation, desc, "isFinal")?, true.into());
+            assert_eq!(get(activation, desc, "isStatic")?, false.into());
+            assert_eq!(get(activation, desc, "traits")?, Value::Null);
+
+            let desc = describe(activation, child, INCLUDE_TRAITS)?;
+            for list in [
+                "bases",
+                "interfaces",
+                "variables",
+                "accessors",
+                "methods",
+                "constructor",
+                "metadata",
+            ] {
+                assert_eq!(traits_property(activation, desc, list)?, "null");
+            }
+
+            let desc = describe(activation, child, INCLUDE_TRAITS | INCLUDE_BASES)?;
+            assert_eq!(traits_property(activation, desc, "bases")?, "Base,Object");
+            let flags = INCLUDE_TRAITS | INCLUDE_BASES | HIDE_OBJECT;
+            let desc = describe(activation, child, flags)?;
+            assert_eq!(traits_property(activation, desc, "bases")?, "Base");
+
+            let desc = describe(activation, child, INCLUDE_TRAITS | INCLUDE_INTERFACES)?;
+            assert_eq!(
+                traits_property(activation, desc, "interfaces")?,
+                "flash.utils::IExternalizable"
+            );
+
+            let desc = describe(activation, child, INCLUDE_TRAITS | INCLUDE_VARIABLES)?;
+            assert_eq!(names(activation, desc, "variables")?, "baseVar,count");
+            let variable_type = entry_property(activation, desc, "variables", "count", "type")?;
+            assert_eq!(variable_type, "Number");
+            let access = entry_property(activation, desc, "variables", "count", "access")?;
+            assert_eq!(access, "readwrite");
+            let metadata = entry_property(activation, desc, "variables", "count", "metadata")?;
+            assert_eq!(metadata, "null");
+
+            let flags = INCLUDE_TRAITS | INCLUDE_ACCESSORS | HIDE_OBJECT;
+            let desc = describe(activation, child, flags)?;
+            assert_eq!(names(activation, desc, "accessors")?, "label,readOnly");
+            let access = entry_property(activation, desc, "accessors", "label", "access")?;
+            assert_eq!(access, "readwrite");
+            let declared_by = entry_property(activation, desc, "accessors", "label", "declaredBy")?;
+            assert_eq!(declared_by, "Child");
+            let access = entry_property(activation, desc, "accessors", "readOnly", "access")?;
+            assert_eq!(access, "readonly");
+
+            let flags = INCLUDE_TRAITS | INCLUDE_METHODS | HIDE_OBJECT;
+            let desc = describe(activation, child, flags)?;
+            assert_eq!(names(activation, desc, "methods")?, "baseMethod,greet");
+            let declared_by =
+                entry_property(activation, desc, "methods", "baseMethod", "declaredBy")?;
+            assert_eq!(declared_by, "Base");
+
+            let desc = describe(activation, child, INCLUDE_TRAITS | INCLUDE_CONSTRUCTOR)?;
+            let mut params = Vec::new();
+            for param in entries(activation, desc, "constructor")? {
+                let param_type = get(activation, param, "type")?;
+                let param_type = traced(activation, param_type)?;
+                let optional = get(activation, param, "optional")?.coerce_to_boolean();
+                params.push(format!("{}{}", param_type, if optional { "?" } else { "" }));
+            }
+            assert_eq!(params.join(","), "Number,String?");
+
+            let flags = INCLUDE_TRAITS | INCLUDE_VARIABLES | INCLUDE_BASES;
+            let desc = describe(activation, child_class, flags)?;
+            assert_eq!(get(activation, desc, "name")?, "Child".into());
+            assert_eq!(get(activation, desc, "isStatic")?, true.into());
+            assert_eq!(get(activation, desc, "isDynamic")?, true.into());
+            assert_eq!(get(activation, desc, "isFinal")?, true.into());
+            assert_eq!(traits_property(activation, desc, "bases")?, "Class,Object");
+            assert_eq!(names(activation, desc, "variables")?, "VERSION");
+
+            let flags = INCLUDE_TRAITS | INCLUDE_VARIABLES | USE_ITRAITS;
+            let desc = describe(activation, child_class, flags)?;
+            assert_eq!(get(activation, desc, "isStatic")?, false.into());
+            assert_eq!(names(activation, desc, "variables")?, "baseVar,count");
+
+            let args = [Value::Null, INCLUDE_TRAITS.into()];
+            assert_eq!(describe_type_json(activation, None, &args)?, Value::Null);
+
+            Ok(())
+        })
+    }
+}
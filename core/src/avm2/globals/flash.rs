@@ -3,9 +3,12 @@
 pub mod crypto;
 pub mod display;
 pub mod events;
+pub mod external;
+pub mod filters;
 pub mod geom;
 pub mod media;
 pub mod net;
+pub mod sampler;
 pub mod system;
 pub mod text;
 pub mod utils;
@@ -8,7 +8,17 @@ pub mod displayobject;
 pub mod displayobjectcontainer;
 pub mod framelabel;
 pub mod graphics;
+pub mod graphicsbitmapfill;
+pub mod graphicsendfill;
+pub mod graphicsgradientfill;
+pub mod graphicspath;
+pub mod graphicspathcommand;
+pub mod graphicspathwinding;
+pub mod graphicssolidfill;
+pub mod graphicsstroke;
+pub mod graphicstrianglepath;
 pub mod ibitmapdrawable;
+pub mod igraphicsdata;
 pub mod interactiveobject;
 pub mod jointstyle;
 pub mod linescalemode;
@@ -25,3 +35,4 @@ pub mod stagedisplaystate;
 pub mod stagequality;
 pub mod stagescalemode;
 pub mod swfversion;
+pub mod triangleculling;
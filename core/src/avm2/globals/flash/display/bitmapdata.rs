@@ -2,12 +2,13 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::colortransform::object_to_color_transform_params;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{bitmapdata_allocator, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::bitmap::bitmap_data::BitmapData;
+use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color, ThresholdOperation};
 use crate::bitmap::is_size_valid;
 use crate::character::Character;
 use gc_arena::{GcCell, MutationContext};
@@ -180,6 +181,461 @@ pub fn get_pixel<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.colorTransform`.
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let rectangle = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let color_transform = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let mut bounds = [0; 4];
+        for (bound, name) in bounds.iter_mut().zip(["x", "y", "width", "height"]) {
+            *bound = rectangle
+                .get_property(
+                    rectangle,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_number(activation)? as i32;
+        }
+        let [x, y, width, height] = bounds;
+
+        let min_x = x.max(0) as u32;
+        let end_x = x.saturating_add(width).max(0) as u32;
+        let min_y = y.max(0) as u32;
+        let end_y = y.saturating_add(height).max(0) as u32;
+
+        let params = object_to_color_transform_params(color_transform, activation)?;
+        bitmap_data
+            .write(activation.context.gc_context)
+            .color_transform(min_x, min_y, end_x, end_y, &params);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.noise`.
+pub fn noise<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let random_seed = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let low = args.get(1).unwrap_or(&0.into()).coerce_to_u32(activation)? as u8;
+        let high = args
+            .get(2)
+            .unwrap_or(&0xFF.into())
+            .coerce_to_u32(activation)? as u8;
+        let channel_options = ChannelOptions::from_bits_truncate(
+            args.get(3)
+                .unwrap_or(&ChannelOptions::RGB.bits().into())
+                .coerce_to_u32(activation)? as u8,
+        );
+        let gray_scale = args
+            .get(4)
+            .unwrap_or(&Value::Bool(false))
+            .coerce_to_boolean();
+
+        bitmap_data.write(activation.context.gc_context).noise(
+            random_seed,
+            low,
+            high.max(low),
+            channel_options,
+            gray_scale,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The most octaves `perlinNoise` computes.
+///
+/// Each octave contributes half as much as the one before it, so octaves past
+/// this many cannot change an 8-bit channel.
+const MAX_PERLIN_OCTAVES: i32 = 32;
+
+/// Implements `BitmapData.perlinNoise`.
+pub fn perlin_noise<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let base_x = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        let base_y = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        let num_octaves = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?
+            .clamp(0, MAX_PERLIN_OCTAVES) as usize;
+        let random_seed = args
+            .get(3)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)? as i64;
+        let stitch = args.get(4).unwrap_or(&Value::Undefined).coerce_to_boolean();
+        let fractal_noise = args.get(5).unwrap_or(&Value::Undefined).coerce_to_boolean();
+        let channel_options = ChannelOptions::from_bits_truncate(
+            args.get(6)
+                .unwrap_or(&ChannelOptions::RGB.bits().into())
+                .coerce_to_u32(activation)? as u8,
+        );
+        let grayscale = args
+            .get(7)
+            .unwrap_or(&Value::Bool(false))
+            .coerce_to_boolean();
+
+        // Octaves without a matching `Point` in `offsets` are not offset.
+        let offsets: Vec<Value<'gc>> = match args.get(8) {
+            Some(Value::Object(offsets)) => match offsets.as_array_storage() {
+                Some(offsets) => offsets
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        let mut octave_offsets = Vec::with_capacity(num_octaves);
+        for i in 0..num_octaves {
+            octave_offsets.push(match offsets.get(i) {
+                Some(Value::Object(point)) => {
+                    let mut coords = [0.0; 2];
+                    for (coord, name) in coords.iter_mut().zip(["x", "y"]) {
+                        *coord = point
+                            .get_property(
+                                *point,
+                                &QName::new(Namespace::public(), name).into(),
+                                activation,
+                            )?
+                            .coerce_to_number(activation)?;
+                    }
+                    (coords[0], coords[1])
+                }
+                _ => (0.0, 0.0),
+            });
+        }
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .perlin_noise(
+                (base_x, base_y),
+                num_octaves,
+                random_seed,
+                stitch,
+                fractal_noise,
+                channel_options,
+                grayscale,
+                octave_offsets,
+            );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.threshold`.
+pub fn threshold<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let operation = args
+            .get(3)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+        let threshold = args
+            .get(4)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let color = args.get(5).unwrap_or(&0.into()).coerce_to_u32(activation)?;
+        let mask = args
+            .get(6)
+            .unwrap_or(&0xFFFFFFFFu32.into())
+            .coerce_to_u32(activation)?;
+        let copy_source = args
+            .get(7)
+            .unwrap_or(&Value::Bool(false))
+            .coerce_to_boolean();
+
+        let mut bounds = [0; 4];
+        for (bound, name) in bounds.iter_mut().zip(["x", "y", "width", "height"]) {
+            *bound = source_rect
+                .get_property(
+                    source_rect,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_number(activation)? as i32;
+        }
+
+        let mut point = [0; 2];
+        for (coord, name) in point.iter_mut().zip(["x", "y"]) {
+            *coord = dest_point
+                .get_property(
+                    dest_point,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_number(activation)? as i32;
+        }
+
+        let operation = match ThresholdOperation::from_wstr(&operation) {
+            Some(operation) => operation,
+            None => return Ok(0.into()),
+        };
+
+        if let Some(source_bitmap) = source_bitmap.as_bitmap_data() {
+            // The source may be this same bitmap, which can't be read while
+            // it is being written to, so work from a copy of it instead.
+            let source_copy;
+            let source_ref;
+            let source: &BitmapData = if GcCell::ptr_eq(source_bitmap, bitmap_data) {
+                source_copy = source_bitmap.read().clone();
+                &source_copy
+            } else {
+                source_ref = source_bitmap.read();
+                &source_ref
+            };
+
+            let [x, y, width, height] = bounds;
+            let [dest_x, dest_y] = point;
+            let matched = bitmap_data.write(activation.context.gc_context).threshold(
+                source,
+                (x, y, width, height),
+                (dest_x, dest_y),
+                operation,
+                threshold,
+                Color::from(color as i32),
+                mask,
+                copy_source,
+            );
+
+            return Ok(matched.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `BitmapData.scroll`.
+pub fn scroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let x = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let y = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .scroll(x, y);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let (width, height, transparency, pixels) = {
+            let read = bitmap_data.read();
+            (
+                read.width(),
+                read.height(),
+                read.transparency(),
+                read.pixels().to_vec(),
+            )
+        };
+
+        let bitmap_data_class = activation.context.avm2.classes().bitmapdata;
+        let new_bitmap = bitmap_data_class.construct(
+            activation,
+            &[width.into(), height.into(), transparency.into()],
+        )?;
+
+        if let Some(new_bitmap_data) = new_bitmap.as_bitmap_data() {
+            new_bitmap_data
+                .write(activation.context.gc_context)
+                .set_pixels(width, height, transparency, pixels);
+        }
+
+        return Ok(new_bitmap.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.applyFilter`.
+pub fn apply_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|t| t.as_bitmap_data()) {
+        let source_bitmap = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let source_rect = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let dest_point = args
+            .get(2)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let filter = args
+            .get(3)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        let mut bounds = [0; 4];
+        for (bound, name) in bounds.iter_mut().zip(["x", "y", "width", "height"]) {
+            *bound = source_rect
+                .get_property(
+                    source_rect,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_number(activation)? as i32;
+        }
+        let [x, y, width, height] = bounds;
+
+        let mut point = [0; 2];
+        for (coord, name) in point.iter_mut().zip(["x", "y"]) {
+            *coord = dest_point
+                .get_property(
+                    dest_point,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_number(activation)? as i32;
+        }
+        let [dest_x, dest_y] = point;
+
+        let source_bitmap = match source_bitmap.as_bitmap_data() {
+            Some(source_bitmap) => source_bitmap,
+            None => return Ok(Value::Undefined),
+        };
+
+        // The source may be this same bitmap, which can't be read while
+        // it is being written to, so work from a copy of it instead.
+        let source_copy;
+        let source_ref;
+        let source: &BitmapData = if GcCell::ptr_eq(source_bitmap, bitmap_data) {
+            source_copy = source_bitmap.read().clone();
+            &source_copy
+        } else {
+            source_ref = source_bitmap.read();
+            &source_ref
+        };
+
+        let filter_name = filter
+            .instance_of_class_definition()
+            .map(|class| class.read().name().local_name());
+
+        match filter_name {
+            Some(name) if &name == b"BlurFilter" => {
+                let mut blur = [0.0; 3];
+                for (value, name) in blur.iter_mut().zip(["blurX", "blurY", "quality"]) {
+                    *value = filter
+                        .get_property(
+                            filter,
+                            &QName::new(Namespace::public(), name).into(),
+                            activation,
+                        )?
+                        .coerce_to_number(activation)?;
+                }
+                let [blur_x, blur_y, quality] = blur;
+
+                bitmap_data.write(activation.context.gc_context).blur(
+                    source,
+                    (x, y, width, height),
+                    (dest_x, dest_y),
+                    blur_x,
+                    blur_y,
+                    quality.clamp(0.0, 15.0) as u32,
+                );
+            }
+            Some(name) if &name == b"ColorMatrixFilter" => {
+                let matrix = filter.get_property(
+                    filter,
+                    &QName::new(Namespace::public(), "matrix").into(),
+                    activation,
+                )?;
+                let matrix: Vec<Value<'gc>> = match matrix {
+                    Value::Object(matrix) => match matrix.as_array_storage() {
+                        Some(matrix) => matrix
+                            .iter()
+                            .map(|v| v.unwrap_or(Value::Undefined))
+                            .collect(),
+                        None => Vec::new(),
+                    },
+                    _ => Vec::new(),
+                };
+
+                // Missing entries of a short matrix are zero.
+                let mut entries = [0.0; 20];
+                for (entry, value) in entries.iter_mut().zip(matrix) {
+                    *entry = value.coerce_to_number(activation)?;
+                }
+
+                bitmap_data
+                    .write(activation.context.gc_context)
+                    .color_matrix(source, (x, y, width, height), (dest_x, dest_y), &entries);
+            }
+            _ => log::warn!(
+                "BitmapData.applyFilter: unsupported filter {:?}",
+                filter_name
+            ),
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `BitmapData`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -208,8 +664,419 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
-    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("getPixel", get_pixel)];
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("colorTransform", color_transform),
+        ("getPixel", get_pixel),
+        ("noise", noise),
+        ("perlinNoise", perlin_noise),
+        ("threshold", threshold),
+        ("scroll", scroll),
+        ("clone", clone),
+        ("applyFilter", apply_filter),
+    ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Construct an opaque black bitmap.
+    fn bitmap<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+        let args = [16.into(), 16.into(), false.into(), 0.into()];
+
+        construct(activation, "flash.display", "BitmapData", &args)
+    }
+
+    fn same_pixels(a: Object<'_>, b: Object<'_>) -> bool {
+        let (a, b) = (a.as_bitmap_data().unwrap(), b.as_bitmap_data().unwrap());
+        let (a, b) = (a.read(), b.read());
+
+        a.pixels() == b.pixels()
+    }
+
+    /// Construct an opaque bitmap of the given size and color.
+    fn filled<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        width: i32,
+        height: i32,
+        color: u32,
+    ) -> Result<Object<'gc>, Error> {
+        let args = [width.into(), height.into(), false.into(), color.into()];
+
+        construct(activation, "flash.display", "BitmapData", &args)
+    }
+
+    /// The rows of a bitmap's `getPixel` values, in hex.
+    fn rows<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        bitmap: Object<'gc>,
+    ) -> Result<Vec<String>, Error> {
+        let width = get(activation, bitmap, "width")?.coerce_to_i32(activation)?;
+        let height = get(activation, bitmap, "height")?.coerce_to_i32(activation)?;
+        let mut rows = Vec::new();
+        for y in 0..height {
+            let mut row = Vec::new();
+            for x in 0..width {
+                let pixel = call(activation, bitmap, "getPixel", &[x.into(), y.into()])?;
+                row.push(format!("{:x}", pixel.coerce_to_u32(activation)?));
+            }
+            rows.push(row.join(","));
+        }
+
+        Ok(rows)
+    }
+
+    /// Call `threshold` on a bitmap.
+    #[allow(clippy::too_many_arguments)]
+    fn threshold<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        bitmap: Object<'gc>,
+        source: Object<'gc>,
+        rect: (i32, i32, i32, i32),
+        point: (i32, i32),
+        operation: &'static str,
+        threshold: u32,
+        color: u32,
+        mask: u32,
+        copy_source: bool,
+    ) -> Result<Value<'gc>, Error> {
+        let (x, y, width, height) = rect;
+        let args = [x.into(), y.into(), width.into(), height.into()];
+        let rect = construct(activation, "flash.geom", "Rectangle", &args)?;
+        let args = [point.0.into(), point.1.into()];
+        let point = construct(activation, "flash.geom", "Point", &args)?;
+        let args = [
+            source.into(),
+            rect.into(),
+            point.into(),
+            operation.into(),
+            threshold.into(),
+            color.into(),
+            mask.into(),
+            copy_source.into(),
+        ];
+
+        call(activation, bitmap, "threshold", &args)
+    }
+
+    /// Apply a filter from one bitmap to another, over the whole source.
+    fn apply_filter<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        bitmap: Object<'gc>,
+        source: Object<'gc>,
+        filter: Object<'gc>,
+    ) -> Result<(), Error> {
+        let width = get(activation, source, "width")?;
+        let height = get(activation, source, "height")?;
+        let args = [0.into(), 0.into(), width, height];
+        let rect = construct(activation, "flash.geom", "Rectangle", &args)?;
+        let point = construct(activation, "flash.geom", "Point", &[])?;
+        let args = [source.into(), rect.into(), point.into(), filter.into()];
+        call(activation, bitmap, "applyFilter", &args)?;
+
+        Ok(())
+    }
+
+    /// Construct a `ColorMatrixFilter` with the given matrix.
+    fn color_matrix<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        matrix: &[i32; 20],
+    ) -> Result<Object<'gc>, Error> {
+        let matrix = matrix.iter().map(|v| (*v).into()).collect::<Vec<_>>();
+        let matrix = array(activation, &matrix)?;
+
+        construct(
+            activation,
+            "flash.filters",
+            "ColorMatrixFilter",
+            &[matrix.into()],
+        )
+    }
+
+    /// Set one pixel of a bitmap to an opaque color.
+    fn mark<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        bitmap: Object<'gc>,
+        point: (i32, i32),
+        color: u32,
+    ) -> Result<(), Error> {
+        let pixel = filled(activation, 1, 1, color)?;
+        let rect = (0, 0, 1, 1);
+        threshold(
+            activation, bitmap, pixel, rect, point, "<", 0, 0, 0xFFFFFFFF, true,
+        )?;
+
+        Ok(())
+    }
+
+    /// The arguments of a `perlinNoise` call with the given octaves and seed.
+    fn perlin_args<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        num_octaves: Value<'gc>,
+        seed: i32,
+    ) -> Result<Vec<Value<'gc>>, Error> {
+        let first = construct(activation, "flash.geom", "Point", &[1.into(), 2.into()])?;
+        let second = construct(activation, "flash.geom", "Point", &[3.into(), 4.into()])?;
+        let offsets = array(activation, &[first.into(), second.into()])?;
+
+        Ok(vec![
+            8.into(),
+            8.into(),
+            num_octaves,
+            seed.into(),
+            false.into(),
+            true.into(),
+            7.into(),
+            false.into(),
+            offsets.into(),
+        ])
+    }
+
+    #[test]
+    fn noise_is_seeded() {
+        with_avm2(|activation| {
+            let (first, second, other) = (
+                bitmap(activation)?,
+                bitmap(activation)?,
+                bitmap(activation)?,
+            );
+
+            call(activation, first, "noise", &[12345.into()])?;
+            call(activation, second, "noise", &[12345.into()])?;
+            call(activation, other, "noise", &[54321.into()])?;
+            assert!(same_pixels(first, second));
+            assert!(!same_pixels(first, other));
+
+            let args = [42.into(), 100.into(), 100.into(), 7.into(), true.into()];
+            call(activation, first, "noise", &args)?;
+            let args = [3.into(), 5.into()];
+            assert_eq!(call(activation, first, "getPixel", &args)?, 0x646464.into());
+
+            let args = perlin_args(activation, 2.into(), 777)?;
+            call(activation, first, "perlinNoise", &args)?;
+            call(activation, second, "perlinNoise", &args)?;
+            let args = perlin_args(activation, 2.into(), 778)?;
+            call(activation, other, "perlinNoise", &args)?;
+            assert!(same_pixels(first, second));
+            assert!(!same_pixels(first, other));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn perlin_noise_octaves() {
+        with_avm2(|activation| {
+            // Without octaves, fractal noise is a flat middle gray.
+            let flat = bitmap(activation)?;
+            for num_octaves in [0.into(), (-1).into(), 4294967295u32.into()] {
+                let args = perlin_args(activation, num_octaves, 777)?;
+                call(activation, flat, "perlinNoise", &args)?;
+                let args = [3.into(), 5.into()];
+                assert_eq!(call(activation, flat, "getPixel", &args)?, 0x7F7F7F.into());
+            }
+
+            // Huge octave counts are capped instead of running forever.
+            let (capped, many) = (bitmap(activation)?, bitmap(activation)?);
+            let args = perlin_args(activation, i32::MAX.into(), 777)?;
+            call(activation, capped, "perlinNoise", &args)?;
+            let args = perlin_args(activation, MAX_PERLIN_OCTAVES.into(), 777)?;
+            call(activation, many, "perlinNoise", &args)?;
+            assert!(same_pixels(capped, many));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn threshold_tests_and_copies_pixels() {
+        with_avm2(|activation| {
+            let gradient = filled(activation, 4, 1, 0)?;
+            // Copy single pixels into place through a threshold that never matches.
+            for i in 0..4 {
+                let step = filled(activation, 1, 1, i as u32 * 0x40)?;
+                let matched = threshold(
+                    activation,
+                    gradient,
+                    step,
+                    (0, 0, 1, 1),
+                    (i, 0),
+                    "<",
+                    0,
+                    0,
+                    0xFFFFFFFF,
+                    true,
+                )?;
+                assert_eq!(matched, 0.into());
+            }
+            assert_eq!(rows(activation, gradient)?, ["0,40,80,c0"]);
+
+            let target = filled(activation, 4, 1, 0xFFFFFF)?;
+            let matched = threshold(
+                activation,
+                target,
+                gradient,
+                (0, 0, 4, 1),
+                (0, 0),
+                "<",
+                0x40,
+                0x00FF00,
+                0xFF,
+                true,
+            )?;
+            assert_eq!(matched, 1.into());
+            assert_eq!(rows(activation, target)?, ["ff00,40,80,c0"]);
+
+            let kept = filled(activation, 4, 1, 0xFFFFFF)?;
+            let matched = threshold(
+                activation,
+                kept,
+                gradient,
+                (0, 0, 4, 1),
+                (0, 0),
+                "==",
+                0x80,
+                0x00FF00,
+                0xFF,
+                false,
+            )?;
+            assert_eq!(matched, 1.into());
+            assert_eq!(rows(activation, kept)?, ["ffffff,ffffff,ff00,ffffff"]);
+
+            let matched = threshold(
+                activation,
+                gradient,
+                gradient,
+                (0, 0, 4, 1),
+                (0, 0),
+                ">=",
+                0x80,
+                0xFF0000,
+                0xFF,
+                false,
+            )?;
+            assert_eq!(matched, 2.into());
+            assert_eq!(rows(activation, gradient)?, ["0,40,ff0000,ff0000"]);
+
+            let offset = filled(activation, 4, 1, 0)?;
+            let matched = threshold(
+                activation,
+                offset,
+                target,
+                (1, 0, 2, 1),
+                (2, 0),
+                "!=",
+                0,
+                0x123456,
+                0xFF,
+                true,
+            )?;
+            assert_eq!(matched, 2.into());
+            assert_eq!(rows(activation, offset)?, ["0,0,123456,123456"]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn scroll_and_clone() {
+        with_avm2(|activation| {
+            let bitmap = filled(activation, 4, 4, 0)?;
+            mark(activation, bitmap, (0, 0), 0xFF0000)?;
+            mark(activation, bitmap, (1, 0), 0x00FF00)?;
+
+            call(activation, bitmap, "scroll", &[2.into(), 1.into()])?;
+            let scrolled = ["ff0000,ff00,0,0", "0,0,ff0000,ff00", "0,0,0,0", "0,0,0,0"];
+            assert_eq!(rows(activation, bitmap)?, scrolled);
+
+            // Pixels scrolled in from outside keep their old values.
+            call(activation, bitmap, "scroll", &[(-3).into(), (-1).into()])?;
+            let scrolled = ["ff00,ff00,0,0", "0,0,ff0000,ff00", "0,0,0,0", "0,0,0,0"];
+            assert_eq!(rows(activation, bitmap)?, scrolled);
+            call(activation, bitmap, "scroll", &[4.into(), 0.into()])?;
+            assert_eq!(rows(activation, bitmap)?, scrolled);
+
+            let copy = call(activation, bitmap, "clone", &[])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, copy, "width")?, 4.into());
+            assert_eq!(get(activation, copy, "height")?, 4.into());
+            assert_eq!(get(activation, copy, "transparent")?, false.into());
+            assert_eq!(rows(activation, copy)?, scrolled);
+
+            mark(activation, copy, (3, 3), 0x0000FF)?;
+            assert_eq!(rows(activation, bitmap)?, scrolled);
+            let marked = ["ff00,ff00,0,0", "0,0,ff0000,ff00", "0,0,0,0", "0,0,0,ff"];
+            assert_eq!(rows(activation, copy)?, marked);
+
+            let args = [2.into(), 1.into(), true.into(), 0x80FF0000u32.into()];
+            let translucent = construct(activation, "flash.display", "BitmapData", &args)?;
+            let copy = call(activation, translucent, "clone", &[])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, copy, "transparent")?, true.into());
+            assert!(same_pixels(translucent, copy));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn apply_filter_blurs_and_transforms_colors() {
+        with_avm2(|activation| {
+            let image = filled(activation, 3, 2, 0x336699)?;
+            mark(activation, image, (0, 0), 0xFF0000)?;
+            mark(activation, image, (2, 1), 0x00FF00)?;
+            let original = ["ff0000,336699,336699", "336699,336699,ff00"];
+            assert_eq!(rows(activation, image)?, original);
+
+            let blur = construct(activation, "flash.filters", "BlurFilter", &[])?;
+            assert_eq!(get(activation, blur, "blurX")?, 4.into());
+            assert_eq!(get(activation, blur, "blurY")?, 4.into());
+            assert_eq!(get(activation, blur, "quality")?, 1.into());
+
+            let args = [0.into(), 0.into(), 1.into()];
+            let blur = construct(activation, "flash.filters", "BlurFilter", &args)?;
+            apply_filter(activation, image, image, blur)?;
+            assert_eq!(rows(activation, image)?, original);
+
+            let identity = construct(activation, "flash.filters", "ColorMatrixFilter", &[])?;
+            let matrix = get(activation, identity, "matrix")?;
+            assert_eq!(
+                traced(activation, matrix)?,
+                "1,0,0,0,0,0,1,0,0,0,0,0,1,0,0,0,0,0,1,0"
+            );
+            apply_filter(activation, image, image, identity)?;
+            assert_eq!(rows(activation, image)?, original);
+
+            let swapped = filled(activation, 3, 2, 0)?;
+            let swap = color_matrix(
+                activation,
+                &[0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0],
+            )?;
+            apply_filter(activation, swapped, image, swap)?;
+            let expected = ["ff,996633,996633", "996633,996633,ff00"];
+            assert_eq!(rows(activation, swapped)?, expected);
+
+            let clamped = filled(activation, 3, 2, 0)?;
+            let clamp = color_matrix(
+                activation,
+                &[
+                    2, 0, 0, 0, 100, 0, 0, 0, 0, -10, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0,
+                ],
+            )?;
+            apply_filter(activation, clamped, image, clamp)?;
+            let expected = ["ff0000,ca0099,ca0099", "ca0099,ca0099,640000"];
+            assert_eq!(rows(activation, clamped)?, expected);
+
+            let line = filled(activation, 5, 1, 0)?;
+            mark(activation, line, (2, 0), 0xFFFFFF)?;
+            let args = [3.into(), 0.into(), 1.into()];
+            let blur = construct(activation, "flash.filters", "BlurFilter", &args)?;
+            apply_filter(activation, line, line, blur)?;
+            assert_eq!(rows(activation, line)?, ["0,555555,555555,555555,0"]);
+
+            Ok(())
+        })
+    }
+}
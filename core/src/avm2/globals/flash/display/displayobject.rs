@@ -1,13 +1,17 @@
 //! `flash.display.DisplayObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::filters::bitmapfilter;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{stage_allocator, LoaderInfoObject, Object, TObject};
+use crate::avm2::object::{stage_allocator, ArrayObject, LoaderInfoObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::bounding_box::BoundingBox;
 use crate::display_object::{DisplayObject, HitTestOptions, TDisplayObject};
+use crate::matrix::Matrix;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
 use gc_arena::{GcCell, MutationContext};
@@ -316,10 +320,13 @@ pub fn rotation<'gc>(
         let rot: f64 = dobj.rotation(activation.context.gc_context).into();
         let rem = rot % 360.0;
 
-        if rem <= 180.0 {
-            return Ok(rem.into());
-        } else {
+        // Flash reports rotations in the range -180..=180.
+        if rem > 180.0 {
             return Ok((rem - 360.0).into());
+        } else if rem < -180.0 {
+            return Ok((rem + 360.0).into());
+        } else {
+            return Ok(rem.into());
         }
     }
 
@@ -495,6 +502,121 @@ pub fn mouse_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `transform`'s getter.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let transform_class = activation.context.avm2.classes().transform;
+
+        return Ok(transform_class
+            .construct(activation, &[this.into()])?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s setter.
+///
+/// The matrix and color transform of the given `Transform` are copied onto
+/// this display object.
+pub fn set_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let transform = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #2007: Parameter transform must be non-null.".into())
+            }
+            value => value.coerce_to_object(activation)?,
+        };
+        let transform_class = activation.context.avm2.classes().transform;
+        let mut our_transform = transform_class.construct(activation, &[this.into()])?;
+
+        for name in ["matrix", "colorTransform"] {
+            let value = transform.get_property(
+                transform,
+                &QName::new(Namespace::public(), name).into(),
+                activation,
+            )?;
+
+            our_transform.set_property(
+                our_transform,
+                &QName::new(Namespace::public(), name).into(),
+                value,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `filters`'s getter.
+pub fn filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        // Flash hands out fresh filter objects on every read.
+        let mut filters = Vec::new();
+        for filter in dobj.filters() {
+            if let Some(filter) = bitmapfilter::filter_to_object(activation, &filter)? {
+                filters.push(filter.into());
+            }
+        }
+
+        return Ok(
+            ArrayObject::from_storage(activation, ArrayStorage::from_args(&filters))?.into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `filters`'s setter.
+pub fn set_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut filters = Vec::new();
+
+        if let Value::Object(list) = args.get(0).cloned().unwrap_or(Value::Undefined) {
+            let list: Vec<Value<'gc>> = match list.as_array_storage() {
+                Some(storage) => storage
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            for filter in list {
+                let filter = match filter {
+                    Value::Object(filter) => bitmapfilter::object_to_filter(activation, filter)?,
+                    _ => None,
+                };
+
+                match filter {
+                    Some(filter) => filters.push(filter),
+                    None => return Err("ArgumentError: Error #2005: Parameter 0 is of the incorrect type. Should be type Filter.".into()),
+                }
+            }
+        }
+
+        dobj.set_filters(activation.context.gc_context, filters);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `hitTestPoint`.
 pub fn hit_test_point<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -557,6 +679,151 @@ pub fn hit_test_object<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Express the untransformed bounds of a display object in the coordinate
+/// space of another, as a new `Rectangle`.
+fn bounds_in_space<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    dobj: DisplayObject<'gc>,
+    bounds: BoundingBox,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let target = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => dobj,
+        target => {
+            let display_object_class = activation.avm2().classes().display_object;
+            target
+                .coerce_to_type(activation, display_object_class)?
+                .coerce_to_object(activation)?
+                .as_display_object()
+                .unwrap_or(dobj)
+        }
+    };
+
+    let out_bounds = if DisplayObject::ptr_eq(dobj, target) {
+        // Getting the object's bounds in its own coordinate space; no AABB transform needed.
+        bounds
+    } else {
+        // Like AVM1, this transforms the AABB rather than the shape itself,
+        // which matches Flash's looser bounds.
+        let bounds_transform = target.global_to_local_matrix() * dobj.local_to_global_matrix();
+        bounds.transform(&bounds_transform)
+    };
+
+    let x = out_bounds.x_min.to_pixels();
+    let y = out_bounds.y_min.to_pixels();
+    let width = (out_bounds.x_max - out_bounds.x_min).to_pixels();
+    let height = (out_bounds.y_max - out_bounds.y_min).to_pixels();
+    let rectangle_class = activation.context.avm2.classes().rectangle;
+
+    Ok(rectangle_class
+        .construct(
+            activation,
+            &[x.into(), y.into(), width.into(), height.into()],
+        )?
+        .into())
+}
+
+/// Implements `getBounds`.
+pub fn get_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return bounds_in_space(activation, dobj, dobj.bounds(), args);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `getRect`.
+///
+/// Unlike `getBounds`, this leaves out the width of strokes.
+pub fn get_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let bounds = dobj.rect_bounds_with_transform(&Matrix::default());
+        return bounds_in_space(activation, dobj, bounds, args);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read the coordinates of a `Point` argument in twips.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error> {
+    let point = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => {
+            return Err("TypeError: Error #2007: Parameter point must be non-null.".into())
+        }
+        point => point.coerce_to_object(activation)?,
+    };
+
+    let x = point
+        .get_property(
+            point,
+            &QName::new(Namespace::public(), "x").into(),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let y = point
+        .get_property(
+            point,
+            &QName::new(Namespace::public(), "y").into(),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+/// Construct a new `Point` from coordinates in twips.
+fn new_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    (x, y): (Twips, Twips),
+) -> Result<Value<'gc>, Error> {
+    let point_class = activation.context.avm2.classes().point;
+
+    Ok(point_class
+        .construct(activation, &[x.to_pixels().into(), y.to_pixels().into()])?
+        .into())
+}
+
+/// Implements `localToGlobal`.
+pub fn local_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = point_arg(activation, args)?;
+
+        return new_point(activation, dobj.local_to_global(point));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `globalToLocal`.
+pub fn global_to_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let point = point_arg(activation, args)?;
+
+        return new_point(activation, dobj.global_to_local(point));
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `loaderInfo` getter
 pub fn loader_info<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -626,14 +893,312 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("mouseX", Some(mouse_x), None),
         ("mouseY", Some(mouse_y), None),
         ("loaderInfo", Some(loader_info), None),
+        ("transform", Some(transform), Some(set_transform)),
+        ("filters", Some(filters), Some(set_filters)),
     ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("hitTestPoint", hit_test_point),
         ("hitTestObject", hit_test_object),
+        ("getBounds", get_bounds),
+        ("getRect", get_rect),
+        ("localToGlobal", local_to_global),
+        ("globalToLocal", global_to_local),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// The filter at an index of a display object's `filters`.
+    fn filter_at<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        sprite: Object<'gc>,
+        index: &'static str,
+    ) -> Result<Object<'gc>, Error> {
+        let filters = get(activation, sprite, "filters")?.coerce_to_object(activation)?;
+
+        get(activation, filters, index)?.coerce_to_object(activation)
+    }
+
+    /// The values of some properties of an object, joined like `trace` does.
+    fn properties<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        names: &[&'static str],
+    ) -> Result<String, Error> {
+        let mut values = Vec::new();
+        for name in names {
+            let value = get(activation, object, name)?;
+            values.push(traced(activation, value)?);
+        }
+
+        Ok(values.join(" "))
+    }
+
+    /// Construct a sprite with a 20x20 square drawn in it.
+    fn square<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        x: i32,
+        y: i32,
+    ) -> Result<Object<'gc>, Error> {
+        let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+        let graphics = get(activation, sprite, "graphics")?.coerce_to_object(activation)?;
+        call(activation, graphics, "beginFill", &[0x0000FF.into()])?;
+        let args = [0.into(), 0.into(), 20.into(), 20.into()];
+        call(activation, graphics, "drawRect", &args)?;
+        call(activation, graphics, "endFill", &[])?;
+        set(activation, sprite, "x", x)?;
+        set(activation, sprite, "y", y)?;
+
+        Ok(sprite)
+    }
+
+    #[test]
+    fn filters_are_copied() {
+        with_avm2(|activation| {
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            let filters = get(activation, sprite, "filters")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, filters, "length")?, 0.into());
+
+            let args = [
+                0x00FF00.into(),
+                0.5.into(),
+                8.into(),
+                10.into(),
+                3.into(),
+                2.into(),
+                true.into(),
+                false.into(),
+            ];
+            let glow = construct(activation, "flash.filters", "GlowFilter", &args)?;
+            let args = [6.into(), 90.into()];
+            let shadow = construct(activation, "flash.filters", "DropShadowFilter", &args)?;
+            let args = [2.into(), 3.into()];
+            let blur = construct(activation, "flash.filters", "BlurFilter", &args)?;
+            let list = array(activation, &[glow.into(), shadow.into(), blur.into()])?;
+            set(activation, sprite, "filters", list)?;
+
+            let filters = get(activation, sprite, "filters")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, filters, "length")?, 3.into());
+            let read_glow = get(activation, filters, "0")?.coerce_to_object(activation)?;
+            assert!(!Object::ptr_eq(read_glow, glow));
+            let filters_again = get(activation, sprite, "filters")?;
+            assert_ne!(Value::from(filters), filters_again);
+            let glow_class = class(activation, "flash.filters", "GlowFilter")?;
+            assert!(read_glow.is_of_type(glow_class, activation)?);
+
+            let names = [
+                "color", "alpha", "blurX", "blurY", "strength", "quality", "inner", "knockout",
+            ];
+            let glow_properties = properties(activation, read_glow, &names)?;
+            assert_eq!(glow_properties, "65280 0.5 8 10 3 2 true false");
+            let read_shadow = get(activation, filters, "1")?.coerce_to_object(activation)?;
+            let names = [
+                "distance",
+                "angle",
+                "color",
+                "alpha",
+                "blurX",
+                "blurY",
+                "hideObject",
+            ];
+            let shadow_properties = properties(activation, read_shadow, &names)?;
+            assert_eq!(shadow_properties, "6 90 0 1 4 4 false");
+            let read_blur = get(activation, filters, "2")?.coerce_to_object(activation)?;
+            let names = ["blurX", "blurY", "quality"];
+            assert_eq!(properties(activation, read_blur, &names)?, "2 3 1");
+
+            // Neither the assigned nor the read back filters are live.
+            set(activation, glow, "blurX", 20)?;
+            let read_glow = filter_at(activation, sprite, "0")?;
+            assert_eq!(get(activation, read_glow, "blurX")?, 8.into());
+            set(activation, read_glow, "blurX", 30)?;
+            let read_glow = filter_at(activation, sprite, "0")?;
+            assert_eq!(get(activation, read_glow, "blurX")?, 8.into());
+
+            let list = array(activation, &[])?;
+            set(activation, sprite, "filters", list)?;
+            let filters = get(activation, sprite, "filters")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, filters, "length")?, 0.into());
+
+            let list = array(activation, &[glow.into()])?;
+            set(activation, sprite, "filters", list)?;
+            set(activation, sprite, "filters", Value::Null)?;
+            let filters = get(activation, sprite, "filters")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, filters, "length")?, 0.into());
+
+            let list = array(activation, &[sprite.into()])?;
+            let error = set(activation, sprite, "filters", list).unwrap_err();
+            assert!(error.to_string().starts_with("ArgumentError: Error #2005"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn rotation_is_normalized() {
+        with_avm2(|activation| {
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            for (angle, normalized) in [
+                (90, "90"),
+                (181, "-179"),
+                (270, "-90"),
+                (359, "-1"),
+                (540, "180"),
+                (720, "0"),
+                (-90, "-90"),
+                (-181, "179"),
+                (-270, "90"),
+                (-359, "1"),
+                (-540, "-180"),
+            ] {
+                set(activation, sprite, "rotation", angle)?;
+                let rotation = get(activation, sprite, "rotation")?;
+                assert_eq!(traced(activation, rotation)?, normalized, "{}", angle);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn size_and_scale_are_linked() {
+        with_avm2(|activation| {
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            let graphics = get(activation, sprite, "graphics")?.coerce_to_object(activation)?;
+            call(activation, graphics, "beginFill", &[0xFF0000.into()])?;
+            let args = [0.into(), 0.into(), 100.into(), 50.into()];
+            call(activation, graphics, "drawRect", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+
+            let names = ["width", "height", "scaleX", "scaleY"];
+            assert_eq!(properties(activation, sprite, &names)?, "100 50 1 1");
+            set(activation, sprite, "width", 200)?;
+            assert_eq!(properties(activation, sprite, &names)?, "200 50 2 1");
+            set(activation, sprite, "height", 25)?;
+            assert_eq!(properties(activation, sprite, &names)?, "200 25 2 0.5");
+            set(activation, sprite, "scaleX", 0.5)?;
+            assert_eq!(properties(activation, sprite, &names)?, "50 25 0.5 0.5");
+            set(activation, sprite, "scaleY", 3)?;
+            assert_eq!(properties(activation, sprite, &names)?, "50 150 0.5 3");
+            set(activation, sprite, "width", 0)?;
+            assert_eq!(properties(activation, sprite, &["width", "scaleX"])?, "0 0");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn coordinate_spaces() {
+        with_avm2(|activation| {
+            let container = construct(activation, "flash.display", "Sprite", &[])?;
+            set(activation, container, "x", 10)?;
+            set(activation, container, "scaleX", 2)?;
+            let (a, b, c) = (
+                square(activation, 5, 7)?,
+                square(activation, 15, 10)?,
+                square(activation, 50, 0)?,
+            );
+            for child in [a, b, c] {
+                call(activation, container, "addChild", &[child.into()])?;
+            }
+
+            let local = construct(activation, "flash.geom", "Point", &[3.into(), 4.into()])?;
+            let global = call(activation, a, "localToGlobal", &[local.into()])?;
+            assert_eq!(traced(activation, global)?, "(x=26, y=11)");
+            let back = call(activation, a, "globalToLocal", &[global])?;
+            assert_eq!(traced(activation, back)?, "(x=3, y=4)");
+            assert_eq!(traced(activation, local)?, "(x=3, y=4)");
+
+            let bounds = call(activation, a, "getBounds", &[a.into()])?;
+            assert_eq!(traced(activation, bounds)?, "(x=0, y=0, w=20, h=20)");
+            let bounds = call(activation, a, "getBounds", &[container.into()])?;
+            assert_eq!(traced(activation, bounds)?, "(x=5, y=7, w=20, h=20)");
+            let rect = call(activation, a, "getRect", &[container.into()])?;
+            assert_eq!(traced(activation, rect)?, "(x=5, y=7, w=20, h=20)");
+
+            assert_eq!(
+                call(activation, a, "hitTestObject", &[b.into()])?,
+                true.into()
+            );
+            assert_eq!(
+                call(activation, b, "hitTestObject", &[a.into()])?,
+                true.into()
+            );
+            assert_eq!(
+                call(activation, a, "hitTestObject", &[c.into()])?,
+                false.into()
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_rect_leaves_out_strokes() {
+        with_avm2(|activation| {
+            let stroked = square(activation, 0, 0)?;
+            let graphics = get(activation, stroked, "graphics")?.coerce_to_object(activation)?;
+            call(activation, graphics, "lineStyle", &[10.into()])?;
+            let args = [0.into(), 0.into(), 20.into(), 20.into()];
+            call(activation, graphics, "drawRect", &args)?;
+
+            let bounds = call(activation, stroked, "getBounds", &[stroked.into()])?;
+            assert_eq!(traced(activation, bounds)?, "(x=-5, y=-5, w=30, h=30)");
+            let rect = call(activation, stroked, "getRect", &[stroked.into()])?;
+            assert_eq!(traced(activation, rect)?, "(x=0, y=0, w=20, h=20)");
+
+            // Children are measured without their strokes too.
+            let parent = construct(activation, "flash.display", "Sprite", &[])?;
+            call(activation, parent, "addChild", &[stroked.into()])?;
+            let rect = call(activation, parent, "getRect", &[parent.into()])?;
+            assert_eq!(traced(activation, rect)?, "(x=0, y=0, w=20, h=20)");
+
+            let point = construct(activation, "flash.geom", "Point", &[])?;
+            let error = call(activation, stroked, "getRect", &[point.into()]).unwrap_err();
+            assert!(error.to_string().starts_with("TypeError: Error #1034"));
+            let error = call(activation, stroked, "getBounds", &[point.into()]).unwrap_err();
+            assert!(error.to_string().starts_with("TypeError: Error #1034"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn stage_root_and_parent_follow_attachment() {
+        with_avm2(|activation| {
+            let main = timeline(activation, 1)?;
+            let container = construct(activation, "flash.display", "Sprite", &[])?;
+            let child = construct(activation, "flash.display", "Sprite", &[])?;
+            call(activation, container, "addChild", &[child.into()])?;
+
+            assert_eq!(get(activation, child, "stage")?, Value::Null);
+            assert_eq!(get(activation, child, "root")?, Value::Null);
+            assert_eq!(get(activation, child, "parent")?, container.into());
+            assert_eq!(get(activation, container, "parent")?, Value::Null);
+
+            call(activation, main, "addChild", &[container.into()])?;
+            let stage = get(activation, main, "stage")?;
+            assert_ne!(stage, Value::Null);
+            assert_eq!(get(activation, child, "stage")?, stage);
+            assert_eq!(get(activation, child, "root")?, main.into());
+            assert_eq!(get(activation, child, "parent")?, container.into());
+            assert_eq!(get(activation, container, "parent")?, main.into());
+            assert_eq!(get(activation, main, "parent")?, stage);
+
+            call(activation, main, "removeChild", &[container.into()])?;
+            assert_eq!(get(activation, child, "stage")?, Value::Null);
+            assert_eq!(get(activation, child, "root")?, Value::Null);
+            assert_eq!(get(activation, child, "parent")?, container.into());
+
+            Ok(())
+        })
+    }
+}
@@ -8,7 +8,9 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
-use crate::display_object::{DisplayObject, Lists, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{
+    DisplayObject, Lists, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
+};
 use gc_arena::{GcCell, MutationContext};
 use std::cmp::min;
 
@@ -140,12 +142,9 @@ pub fn get_child_at<'gc>(
             .cloned()
             .unwrap_or(Value::Undefined)
             .coerce_to_i32(activation)?;
-        let child = dobj.child_by_index(index as usize).ok_or_else(|| {
-            format!(
-                "RangeError: Display object container has no child with id {}",
-                index
-            )
-        })?;
+        let child = dobj
+            .child_by_index(index as usize)
+            .ok_or("RangeError: Error #2006: The supplied index is out of bounds.")?;
 
         return Ok(child.object2());
     }
@@ -168,14 +167,11 @@ pub fn get_child_by_name<'gc>(
             .cloned()
             .unwrap_or(Value::Undefined)
             .coerce_to_string(activation)?;
-        let child = dobj.child_by_name(&name, false).ok_or_else(|| {
-            format!(
-                "RangeError: Display object container has no child with name {}",
-                name
-            )
-        })?;
-
-        return Ok(child.object2());
+        // Unlike `getChildAt`, a missing child is not an error.
+        return Ok(dobj
+            .child_by_name(&name, false)
+            .map(|child| child.object2())
+            .unwrap_or(Value::Null));
     }
 
     Ok(Value::Undefined)
@@ -537,6 +533,43 @@ pub fn swap_children<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObjectContainer.mouseChildren`'s getter.
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        return Ok(int.mouse_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s setter.
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(int) = this
+        .and_then(|t| t.as_display_object())
+        .and_then(|dobj| dobj.as_interactive())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        int.set_mouse_children(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `DisplayObjectContainer.stopAllMovieClips`
 pub fn stop_all_movie_clips<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -608,7 +641,14 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         &str,
         Option<NativeMethodImpl>,
         Option<NativeMethodImpl>,
-    )] = &[("numChildren", Some(num_children), None)];
+    )] = &[
+        ("numChildren", Some(num_children), None),
+        (
+            "mouseChildren",
+            Some(mouse_children),
+            Some(set_mouse_children),
+        ),
+    ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
@@ -635,3 +675,294 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use swf::Twips;
+
+    /// The names of an event's target and of the target's parent.
+    fn target_names<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        event: Value<'gc>,
+    ) -> Result<(String, String), Error> {
+        let event = event.coerce_to_object(activation)?;
+        let target = get(activation, event, "target")?.coerce_to_object(activation)?;
+        let name = get(activation, target, "name")?;
+        let parent = get(activation, target, "parent")?.coerce_to_object(activation)?;
+        let parent_name = get(activation, parent, "name")?;
+
+        Ok((traced(activation, name)?, traced(activation, parent_name)?))
+    }
+
+    fn on_added<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let (name, parent_name) = target_names(activation, args[0])?;
+        log(format!("added: {} to {}", name, parent_name));
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_removed<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let (name, parent_name) = target_names(activation, args[0])?;
+        log(format!("removed: {} from {}", name, parent_name));
+
+        Ok(Value::Undefined)
+    }
+
+    fn sprite<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+    ) -> Result<Object<'gc>, Error> {
+        let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+        set(activation, sprite, "name", name)?;
+
+        Ok(sprite)
+    }
+
+    /// The names of a container's children in render order, and the index
+    /// `getChildIndex` reports for each of `children`.
+    fn child_order<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        container: Object<'gc>,
+        children: &[Object<'gc>],
+    ) -> Result<(String, Vec<String>), Error> {
+        let display_container = container
+            .as_display_object()
+            .and_then(|display_object| display_object.as_container())
+            .ok_or("container is not a DisplayObjectContainer")?;
+        let names: Vec<_> = display_container
+            .iter_render_list()
+            .map(|child| child.name().to_string())
+            .collect();
+
+        let mut indices = Vec::new();
+        for child in children {
+            if get(activation, *child, "parent")? == container.into() {
+                let index = call(activation, container, "getChildIndex", &[(*child).into()])?;
+                indices.push(traced(activation, index)?);
+            } else {
+                indices.push("removed".to_string());
+            }
+        }
+
+        Ok((names.join(","), indices))
+    }
+
+    /// Construct a named sprite at `x`, with a 100x100 square drawn in it if
+    /// it's `filled`.
+    fn placed_sprite<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        x: i32,
+        filled: bool,
+    ) -> Result<Object<'gc>, Error> {
+        let placed = sprite(activation, name)?;
+        set(activation, placed, "x", x)?;
+        if filled {
+            let graphics = get(activation, placed, "graphics")?.coerce_to_object(activation)?;
+            call(activation, graphics, "beginFill", &[0xFF0000.into()])?;
+            let args = [0.into(), 0.into(), 100.into(), 100.into()];
+            call(activation, graphics, "drawRect", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+        }
+
+        Ok(placed)
+    }
+
+    /// The name of the object that would be the target of a mouse event at
+    /// the given point.
+    fn mouse_target<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        root: Object<'gc>,
+        x: i32,
+        y: i32,
+    ) -> Option<String> {
+        let point = (Twips::from_pixels(x.into()), Twips::from_pixels(y.into()));
+        root.as_display_object()?
+            .as_interactive()?
+            .mouse_pick_avm2(&mut activation.context, point)
+            .map(|target| target.as_displayobject().name().to_string())
+    }
+
+    #[test]
+    fn reparenting_moves_the_child() {
+        with_avm2(|activation| {
+            let first = sprite(activation, "first")?;
+            let second = sprite(activation, "second")?;
+            let child = sprite(activation, "child")?;
+
+            let listener = function(activation, on_added)?;
+            call(
+                activation,
+                child,
+                "addEventListener",
+                &["added".into(), listener.into()],
+            )?;
+            let listener = function(activation, on_removed)?;
+            let args = ["removed".into(), listener.into()];
+            call(activation, child, "addEventListener", &args)?;
+
+            call(activation, first, "addChild", &[child.into()])?;
+            assert_eq!(take_log(), ["added: child to first"]);
+            let parent = get(activation, child, "parent")?;
+            assert_eq!(parent, first.into());
+            assert_eq!(get(activation, first, "numChildren")?, 1.into());
+
+            call(activation, second, "addChild", &[child.into()])?;
+            assert_eq!(
+                take_log(),
+                ["removed: child from first", "added: child to second"]
+            );
+            let parent = get(activation, child, "parent")?;
+            assert_eq!(parent, second.into());
+            assert_eq!(get(activation, first, "numChildren")?, 0.into());
+            assert_eq!(get(activation, second, "numChildren")?, 1.into());
+            assert_eq!(
+                call(activation, first, "contains", &[child.into()])?,
+                false.into()
+            );
+            assert_eq!(
+                call(activation, second, "contains", &[child.into()])?,
+                true.into()
+            );
+
+            let found = call(activation, second, "getChildByName", &["child".into()])?;
+            assert_eq!(found, child.into());
+            let found = call(activation, second, "getChildByName", &["missing".into()])?;
+            assert_eq!(found, Value::Null);
+            let found = call(activation, first, "getChildByName", &["child".into()])?;
+            assert_eq!(found, Value::Null);
+
+            let sibling = sprite(activation, "sibling")?;
+            call(
+                activation,
+                second,
+                "addChildAt",
+                &[sibling.into(), 0.into()],
+            )?;
+            call(activation, first, "addChildAt", &[child.into(), 0.into()])?;
+            assert_eq!(
+                take_log(),
+                ["removed: child from second", "added: child to first"]
+            );
+            let first_child = call(activation, second, "getChildAt", &[0.into()])?;
+            assert_eq!(first_child, sibling.into());
+            assert_eq!(get(activation, second, "numChildren")?, 1.into());
+            let index = call(activation, first, "getChildIndex", &[child.into()])?;
+            assert_eq!(index, 0.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn reordering_children() {
+        with_avm2(|activation| {
+            let container = sprite(activation, "container")?;
+            let a = sprite(activation, "a")?;
+            let b = sprite(activation, "b")?;
+            let c = sprite(activation, "c")?;
+            let children = [a, b, c];
+
+            let steps: [(&'static str, &[Value<'_>], &str, [&str; 3]); 9] = [
+                ("addChild", &[a.into()], "a", ["0", "removed", "removed"]),
+                ("addChild", &[b.into()], "a,b", ["0", "1", "removed"]),
+                ("addChild", &[c.into()], "a,b,c", ["0", "1", "2"]),
+                (
+                    "swapChildren",
+                    &[a.into(), c.into()],
+                    "c,b,a",
+                    ["2", "1", "0"],
+                ),
+                (
+                    "swapChildrenAt",
+                    &[0.into(), 1.into()],
+                    "b,c,a",
+                    ["2", "0", "1"],
+                ),
+                (
+                    "setChildIndex",
+                    &[a.into(), 0.into()],
+                    "a,b,c",
+                    ["0", "1", "2"],
+                ),
+                (
+                    "setChildIndex",
+                    &[a.into(), 2.into()],
+                    "b,c,a",
+                    ["2", "0", "1"],
+                ),
+                ("removeChild", &[b.into()], "c,a", ["1", "removed", "0"]),
+                (
+                    "addChildAt",
+                    &[b.into(), 1.into()],
+                    "c,b,a",
+                    ["2", "1", "0"],
+                ),
+            ];
+            for (method, args, names, indices) in steps {
+                call(activation, container, method, args)?;
+                let (render_order, child_indices) = child_order(activation, container, &children)?;
+                assert_eq!(render_order, names, "after {}", method);
+                assert_eq!(child_indices, indices, "after {}", method);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn mouse_targets_respect_mouse_enabled_and_mouse_children() {
+        with_avm2(|activation| {
+            let main = timeline(activation, 1)?;
+
+            let blocked = placed_sprite(activation, "blocked", 0, false)?;
+            let blocked_child = placed_sprite(activation, "blocked_child", 0, true)?;
+            call(activation, blocked, "addChild", &[blocked_child.into()])?;
+            set(activation, blocked, "mouseChildren", false)?;
+
+            let open = placed_sprite(activation, "open", 100, false)?;
+            let open_child = placed_sprite(activation, "open_child", 0, true)?;
+            call(activation, open, "addChild", &[open_child.into()])?;
+
+            let under = placed_sprite(activation, "under", 200, true)?;
+            let overlay = placed_sprite(activation, "overlay", 200, true)?;
+            set(activation, overlay, "mouseEnabled", false)?;
+
+            let disabled = placed_sprite(activation, "disabled", 300, false)?;
+            let disabled_child = placed_sprite(activation, "disabled_child", 0, true)?;
+            call(activation, disabled, "addChild", &[disabled_child.into()])?;
+            set(activation, disabled, "mouseEnabled", false)?;
+
+            for child in [blocked, open, under, overlay, disabled] {
+                call(activation, main, "addChild", &[child.into()])?;
+            }
+
+            assert_eq!(get(activation, blocked, "mouseChildren")?, false.into());
+            assert_eq!(get(activation, open, "mouseChildren")?, true.into());
+            assert_eq!(get(activation, overlay, "mouseEnabled")?, false.into());
+
+            for (x, expected) in [
+                (50, "blocked"),
+                (150, "open_child"),
+                (250, "under"),
+                (350, "disabled_child"),
+            ] {
+                let target = mouse_target(activation, main, x, 50);
+                assert_eq!(target.as_deref(), Some(expected), "target at x = {}", x);
+            }
+            assert_eq!(mouse_target(activation, main, 450, 50), None);
+
+            Ok(())
+        })
+    }
+}
@@ -2,18 +2,25 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::matrix::object_to_matrix;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{stage_allocator, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
+use crate::backend::render::{BitmapInfo, BitmapSource};
+use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::drawing::Drawing;
-use crate::shape_utils::DrawCommand;
+use crate::ecma_conversions::f64_to_wrapping_u32;
+use crate::matrix::Matrix;
+use crate::shape_utils::{DrawCommand, FillRule};
 use crate::string::WStr;
 use gc_arena::{GcCell, MutationContext};
 use std::f64::consts::FRAC_1_SQRT_2;
-use swf::{Color, FillStyle, Fixed8, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+use swf::{
+    Color, FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
+};
 
 /// Implements `flash.display.Graphics`'s instance constructor.
 fn instance_init<'gc>(
@@ -78,6 +85,206 @@ fn begin_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Collect the elements of an `Array` or `Vector` argument.
+///
+/// Arguments that are neither are treated as empty.
+fn list_arg<'gc>(value: Option<&Value<'gc>>) -> Vec<Value<'gc>> {
+    match value {
+        Some(Value::Object(object)) => {
+            if let Some(array) = object.as_array_storage() {
+                array
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            } else if let Some(vector) = object.as_vector_storage() {
+                vector.iter().collect()
+            } else {
+                Vec::new()
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Read every element of an `Array` or `Vector` argument as a number.
+fn number_list_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Option<&Value<'gc>>,
+) -> Result<Vec<f64>, Error> {
+    list_arg(value)
+        .into_iter()
+        .map(|v| v.coerce_to_number(activation))
+        .collect()
+}
+
+/// Read the arguments of `beginGradientFill` as a fill style.
+fn gradient_fill_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<FillStyle, Error> {
+    let gradient_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = number_list_arg(activation, args.get(1))?;
+    let alphas = number_list_arg(activation, args.get(2))?;
+    let ratios = number_list_arg(activation, args.get(3))?;
+    let matrix = match args.get(4) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    let spread = args
+        .get(5)
+        .cloned()
+        .unwrap_or_else(|| "pad".into())
+        .coerce_to_string(activation)?;
+    let interpolation = args
+        .get(6)
+        .cloned()
+        .unwrap_or_else(|| "rgb".into())
+        .coerce_to_string(activation)?;
+    let focal_point = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 0.0.into())
+        .coerce_to_number(activation)?;
+
+    let records = colors
+        .iter()
+        .zip(alphas.iter())
+        .zip(ratios.iter())
+        .map(|((color, alpha), ratio)| GradientRecord {
+            ratio: ratio.clamp(0.0, 255.0) as u8,
+            color: color_from_args(f64_to_wrapping_u32(*color), alpha.clamp(0.0, 1.0)),
+        })
+        .collect();
+
+    let gradient = Gradient {
+        matrix: matrix.into(),
+        spread: if &spread == b"reflect" {
+            GradientSpread::Reflect
+        } else if &spread == b"repeat" {
+            GradientSpread::Repeat
+        } else {
+            GradientSpread::Pad
+        },
+        interpolation: if &interpolation == b"linearRGB" {
+            GradientInterpolation::LinearRgb
+        } else {
+            GradientInterpolation::Rgb
+        },
+        records,
+    };
+
+    let fill_style = if &gradient_type == b"linear" {
+        FillStyle::LinearGradient(gradient)
+    } else if &gradient_type == b"radial" {
+        if focal_point == 0.0 {
+            FillStyle::RadialGradient(gradient)
+        } else {
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point: Fixed8::from_f64(focal_point.clamp(-1.0, 1.0)),
+            }
+        }
+    } else {
+        return Err(
+            "ArgumentError: Error #2008: Parameter type must be one of the accepted values.".into(),
+        );
+    };
+
+    Ok(fill_style)
+}
+
+/// Implements `Graphics.beginGradientFill`.
+fn begin_gradient_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = gradient_fill_style(activation, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(Some(fill_style));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read the arguments of `beginBitmapFill` as a fill style, registering the
+/// bitmap with a display object's drawing.
+///
+/// This yields `None` if the bitmap cannot be drawn.
+fn bitmap_fill_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<FillStyle>, Error> {
+    let bitmap_data = match args.get(0) {
+        Some(Value::Object(bitmap_data)) => bitmap_data.as_bitmap_data(),
+        _ => None,
+    }
+    .ok_or("TypeError: Error #2007: Parameter bitmap must be non-null.")?;
+    let matrix = match args.get(1) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::IDENTITY,
+    };
+    let is_repeating = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| true.into())
+        .coerce_to_boolean();
+    let is_smoothed = args
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| false.into())
+        .coerce_to_boolean();
+
+    let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
+    let handle = match bitmap_data.bitmap_handle(activation.context.renderer) {
+        Some(handle) => handle,
+        None => return Ok(None),
+    };
+    let bitmap = BitmapInfo {
+        handle,
+        width: bitmap_data.width() as u16,
+        height: bitmap_data.height() as u16,
+    };
+
+    let id = match this.as_drawing(activation.context.gc_context) {
+        Some(mut draw) => draw.add_bitmap(bitmap),
+        None => return Ok(None),
+    };
+
+    // Bitmap fill matrices map pixels of the bitmap to twips.
+    Ok(Some(FillStyle::Bitmap {
+        id,
+        matrix: (matrix * Matrix::scale(20.0, 20.0)).into(),
+        is_smoothed,
+        is_repeating,
+    }))
+}
+
+/// Implements `Graphics.beginBitmapFill`.
+fn begin_bitmap_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = bitmap_fill_style(activation, this, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(fill_style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.clear`
 fn clear<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -197,6 +404,70 @@ fn scale_mode_to_allow_scale_bits(scale_mode: &WStr) -> Result<(bool, bool), Err
     }
 }
 
+/// Read the arguments of `lineStyle` as a line style.
+///
+/// A thickness of `NaN` yields `None`, which stops drawing lines.
+fn line_style_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<LineStyle>, Error> {
+    let thickness = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| f64::NAN.into())
+        .coerce_to_number(activation)?;
+
+    if thickness.is_nan() {
+        return Ok(None);
+    }
+
+    let color = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_u32(activation)?;
+    let alpha = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| 1.0.into())
+        .coerce_to_number(activation)?;
+    let is_pixel_hinted = args
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| false.into())
+        .coerce_to_boolean();
+    let scale_mode = args
+        .get(4)
+        .cloned()
+        .unwrap_or_else(|| "normal".into())
+        .coerce_to_string(activation)?;
+    let caps = caps_to_cap_style(activation, args.get(5).cloned().unwrap_or(Value::Null))?;
+    let joints = args.get(6).cloned().unwrap_or(Value::Null);
+    let miter_limit = args
+        .get(7)
+        .cloned()
+        .unwrap_or_else(|| 3.0.into())
+        .coerce_to_number(activation)?;
+
+    let width = Twips::from_pixels(thickness.clamp(0.0, 255.0));
+    let color = color_from_args(color, alpha);
+    let join_style = joints_to_join_style(activation, joints, miter_limit)?;
+    let (allow_scale_x, allow_scale_y) = scale_mode_to_allow_scale_bits(&scale_mode)?;
+
+    Ok(Some(LineStyle {
+        width,
+        color,
+        start_cap: caps,
+        end_cap: caps,
+        join_style,
+        fill_style: None,
+        allow_scale_x,
+        allow_scale_y,
+        is_pixel_hinted,
+        allow_close: true,
+    }))
+}
+
 /// Implements `Graphics.lineStyle`.
 fn line_style<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -204,66 +475,10 @@ fn line_style<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this.and_then(|t| t.as_display_object()) {
-        let thickness = args
-            .get(0)
-            .cloned()
-            .unwrap_or_else(|| f64::NAN.into())
-            .coerce_to_number(activation)?;
-
-        if thickness.is_nan() {
-            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-                draw.set_line_style(None);
-            }
-        } else {
-            let color = args
-                .get(1)
-                .cloned()
-                .unwrap_or_else(|| 0.into())
-                .coerce_to_u32(activation)?;
-            let alpha = args
-                .get(2)
-                .cloned()
-                .unwrap_or_else(|| 1.0.into())
-                .coerce_to_number(activation)?;
-            let is_pixel_hinted = args
-                .get(3)
-                .cloned()
-                .unwrap_or_else(|| false.into())
-                .coerce_to_boolean();
-            let scale_mode = args
-                .get(4)
-                .cloned()
-                .unwrap_or_else(|| "normal".into())
-                .coerce_to_string(activation)?;
-            let caps = caps_to_cap_style(activation, args.get(5).cloned().unwrap_or(Value::Null))?;
-            let joints = args.get(6).cloned().unwrap_or(Value::Null);
-            let miter_limit = args
-                .get(7)
-                .cloned()
-                .unwrap_or_else(|| 3.0.into())
-                .coerce_to_number(activation)?;
+        let line_style = line_style_from_args(activation, args)?;
 
-            let width = Twips::from_pixels(thickness.clamp(0.0, 255.0));
-            let color = color_from_args(color, alpha);
-            let join_style = joints_to_join_style(activation, joints, miter_limit)?;
-            let (allow_scale_x, allow_scale_y) = scale_mode_to_allow_scale_bits(&scale_mode)?;
-
-            let line_style = LineStyle {
-                width,
-                color,
-                start_cap: caps,
-                end_cap: caps,
-                join_style,
-                fill_style: None,
-                allow_scale_x,
-                allow_scale_y,
-                is_pixel_hinted,
-                allow_close: true,
-            };
-
-            if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-                draw.set_line_style(Some(line_style));
-            }
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_line_style(line_style);
         }
     }
 
@@ -766,6 +981,453 @@ fn draw_ellipse<'gc>(
     Ok(Value::Undefined)
 }
 
+/// The largest distance, in pixels, between a cubic curve and the quadratic
+/// curves approximating it.
+const CUBIC_TOLERANCE: f64 = 0.1;
+
+/// Approximate a cubic curve with quadratic curves, as shapes only support
+/// quadratic curves.
+///
+/// The cubic curve is split into pieces that each stay close enough to the
+/// quadratic curve best matching them. This yields the control and anchor
+/// points of each quadratic curve.
+fn cubic_to_quadratics(
+    start: (f64, f64),
+    control1: (f64, f64),
+    control2: (f64, f64),
+    anchor: (f64, f64),
+) -> Vec<((f64, f64), (f64, f64))> {
+    let point = |t: f64| {
+        let s = 1.0 - t;
+        let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+        (
+            a * start.0 + b * control1.0 + c * control2.0 + d * anchor.0,
+            a * start.1 + b * control1.1 + c * control2.1 + d * anchor.1,
+        )
+    };
+    let tangent = |t: f64| {
+        let s = 1.0 - t;
+        let (a, b, c) = (3.0 * s * s, 6.0 * s * t, 3.0 * t * t);
+        (
+            a * (control1.0 - start.0)
+                + b * (control2.0 - control1.0)
+                + c * (anchor.0 - control2.0),
+            a * (control1.1 - start.1)
+                + b * (control2.1 - control1.1)
+                + c * (anchor.1 - control2.1),
+        )
+    };
+
+    // The error of the quadratic approximation is proportional to the
+    // cubic's third difference, which shrinks with the cube of the number of
+    // pieces.
+    let third_difference = (
+        anchor.0 - 3.0 * control2.0 + 3.0 * control1.0 - start.0,
+        anchor.1 - 3.0 * control2.1 + 3.0 * control1.1 - start.1,
+    );
+    let error = 3f64.sqrt() / 36.0 * third_difference.0.hypot(third_difference.1);
+    let pieces = (error / CUBIC_TOLERANCE).cbrt().ceil().clamp(1.0, 64.0) as usize;
+
+    (0..pieces)
+        .map(|piece| {
+            let (t0, t1) = (
+                piece as f64 / pieces as f64,
+                (piece + 1) as f64 / pieces as f64,
+            );
+            let third = (t1 - t0) / 3.0;
+            let (p0, p3) = (point(t0), point(t1));
+            let (d0, d3) = (tangent(t0), tangent(t1));
+            let p1 = (p0.0 + third * d0.0, p0.1 + third * d0.1);
+            let p2 = (p3.0 - third * d3.0, p3.1 - third * d3.1);
+            let control = (
+                (3.0 * (p1.0 + p2.0) - p0.0 - p3.0) / 4.0,
+                (3.0 * (p1.1 + p2.1) - p0.1 - p3.1) / 4.0,
+            );
+
+            (control, p3)
+        })
+        .collect()
+}
+
+/// Append the segments described by `drawPath`-style command and coordinate
+/// lists to a drawing.
+///
+/// Commands are the values of `GraphicsPathCommand`. Drawing stops early if
+/// the coordinates run out.
+fn draw_path_commands(draw: &mut Drawing, commands: &[f64], data: &[f64]) {
+    let mut points = data.chunks_exact(2).map(|point| (point[0], point[1]));
+    let twips = |(x, y): (f64, f64)| (Twips::from_pixels(x), Twips::from_pixels(y));
+
+    for command in commands {
+        let command = match *command as i32 {
+            1 => points
+                .next()
+                .map(twips)
+                .map(|(x, y)| DrawCommand::MoveTo { x, y }),
+            2 => points
+                .next()
+                .map(twips)
+                .map(|(x, y)| DrawCommand::LineTo { x, y }),
+            3 => match (points.next(), points.next()) {
+                (Some(control), Some(anchor)) => {
+                    let ((x1, y1), (x2, y2)) = (twips(control), twips(anchor));
+                    Some(DrawCommand::CurveTo { x1, y1, x2, y2 })
+                }
+                _ => None,
+            },
+            4 => points
+                .nth(1)
+                .map(twips)
+                .map(|(x, y)| DrawCommand::MoveTo { x, y }),
+            5 => points
+                .nth(1)
+                .map(twips)
+                .map(|(x, y)| DrawCommand::LineTo { x, y }),
+            6 => match (points.next(), points.next(), points.next()) {
+                (Some(control1), Some(control2), Some(anchor)) => {
+                    let (start_x, start_y) = draw.cursor();
+                    let start = (start_x.to_pixels(), start_y.to_pixels());
+                    for (control, anchor) in cubic_to_quadratics(start, control1, control2, anchor)
+                    {
+                        let ((x1, y1), (x2, y2)) = (twips(control), twips(anchor));
+                        draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+                    }
+                    continue;
+                }
+                _ => None,
+            },
+            _ => continue,
+        };
+
+        match command {
+            Some(command) => draw.draw_command(command),
+            None => break,
+        }
+    }
+}
+
+/// Implements `Graphics.drawPath`.
+fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let commands = number_list_arg(activation, args.get(0))?;
+        let data = number_list_arg(activation, args.get(1))?;
+        let winding = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| "evenOdd".into())
+            .coerce_to_string(activation)?;
+
+        let winding_rule = if &winding == b"nonZero" {
+            FillRule::NonZero
+        } else {
+            FillRule::EvenOdd
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_winding_rule(winding_rule);
+            draw_path_commands(&mut draw, &commands, &data);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The matrix of a bitmap fill that maps three points of the bitmap onto the
+/// corners of a triangle.
+///
+/// The points of the bitmap are given as fractions of its size, like the `u`
+/// and `v` values of `uvtData`. This yields `None` if those points are on one
+/// line.
+fn triangle_bitmap_matrix(
+    corners: [(f64, f64); 3],
+    uvs: [(f64, f64); 3],
+    (width, height): (f64, f64),
+) -> Option<Matrix> {
+    let [(x0, y0), (x1, y1), (x2, y2)] = corners;
+    let [(u0, v0), (u1, v1), (u2, v2)] = uvs.map(|(u, v)| (u * width, v * height));
+    let (du1, dv1, du2, dv2) = (u1 - u0, v1 - v0, u2 - u0, v2 - v0);
+    let (dx1, dy1, dx2, dy2) = (x1 - x0, y1 - y0, x2 - x0, y2 - y0);
+    let determinant = du1 * dv2 - du2 * dv1;
+    if determinant == 0.0 {
+        return None;
+    }
+
+    let a = (dx1 * dv2 - dx2 * dv1) / determinant;
+    let b = (dy1 * dv2 - dy2 * dv1) / determinant;
+    let c = (dx2 * du1 - dx1 * du2) / determinant;
+    let d = (dy2 * du1 - dy1 * du2) / determinant;
+
+    // Bitmap fill matrices map pixels of the bitmap to twips.
+    Some(Matrix {
+        a: (a * 20.0) as f32,
+        b: (b * 20.0) as f32,
+        c: (c * 20.0) as f32,
+        d: (d * 20.0) as f32,
+        tx: Twips::from_pixels(x0 - a * u0 - c * v0),
+        ty: Twips::from_pixels(y0 - b * u0 - d * v0),
+    })
+}
+
+/// Implements `Graphics.drawTriangles`.
+///
+/// With `uvtData` and a bitmap fill, the bitmap is mapped onto each triangle.
+/// The `t` values of `uvtData` are ignored, so the mapping is affine rather
+/// than perspective-correct.
+fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let vertices = number_list_arg(activation, args.get(0))?;
+        let mut indices = number_list_arg(activation, args.get(1))?
+            .into_iter()
+            .map(|index| index as usize)
+            .collect::<Vec<_>>();
+        let uvt_data = number_list_arg(activation, args.get(2))?;
+        let culling = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| "none".into())
+            .coerce_to_string(activation)?;
+
+        // Without indices, every three vertices form a triangle.
+        let num_vertices = vertices.len() / 2;
+        if indices.is_empty() {
+            indices = (0..num_vertices).collect();
+        }
+
+        // `uvtData` holds either `u, v` or `u, v, t` for every vertex.
+        let uv_stride = if uvt_data.len() >= num_vertices * 3 {
+            3
+        } else {
+            2
+        };
+        let vertex = |index: usize| {
+            vertices
+                .get(index * 2..index * 2 + 2)
+                .map(|vertex| (vertex[0], vertex[1]))
+        };
+        let uv = |index: usize| {
+            uvt_data
+                .get(index * uv_stride..index * uv_stride + 2)
+                .map(|uv| (uv[0], uv[1]))
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let fill_style = draw.fill_style().cloned();
+            let bitmap_fill = match &fill_style {
+                Some(FillStyle::Bitmap {
+                    id,
+                    is_smoothed,
+                    is_repeating,
+                    ..
+                }) if !uvt_data.is_empty() => draw.bitmap(*id).map(|bitmap| {
+                    let size = (f64::from(bitmap.width), f64::from(bitmap.height));
+                    (*id, size, *is_smoothed, *is_repeating)
+                }),
+                _ => None,
+            };
+
+            for triangle in indices.chunks_exact(3) {
+                let (a, b, c) = match (
+                    vertex(triangle[0]),
+                    vertex(triangle[1]),
+                    vertex(triangle[2]),
+                ) {
+                    (Some(a), Some(b), Some(c)) => (a, b, c),
+                    _ => continue,
+                };
+
+                let winding = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+                if (&culling == b"positive" && winding > 0.0)
+                    || (&culling == b"negative" && winding < 0.0)
+                {
+                    continue;
+                }
+
+                if let Some((id, size, is_smoothed, is_repeating)) = bitmap_fill {
+                    let uvs = match (uv(triangle[0]), uv(triangle[1]), uv(triangle[2])) {
+                        (Some(uv_a), Some(uv_b), Some(uv_c)) => [uv_a, uv_b, uv_c],
+                        _ => continue,
+                    };
+                    let matrix = match triangle_bitmap_matrix([a, b, c], uvs, size) {
+                        Some(matrix) => matrix,
+                        None => continue,
+                    };
+                    draw.set_fill_style(Some(FillStyle::Bitmap {
+                        id,
+                        matrix: matrix.into(),
+                        is_smoothed,
+                        is_repeating,
+                    }));
+                }
+
+                let data = [a.0, a.1, b.0, b.1, c.0, c.1, a.0, a.1];
+                draw_path_commands(&mut draw, &[1.0, 2.0, 2.0, 2.0], &data);
+            }
+
+            // Continue the bitmap fill as it was before the triangles.
+            if bitmap_fill.is_some() {
+                draw.set_fill_style(fill_style);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The properties of `GraphicsGradientFill`, in the order `beginGradientFill`
+/// takes them.
+const GRADIENT_FILL_PROPERTIES: &[&str] = &[
+    "type",
+    "colors",
+    "alphas",
+    "ratios",
+    "matrix",
+    "spreadMethod",
+    "interpolationMethod",
+    "focalPointRatio",
+];
+
+/// The properties of `GraphicsBitmapFill`, in the order `beginBitmapFill`
+/// takes them.
+const BITMAP_FILL_PROPERTIES: &[&str] = &["bitmapData", "matrix", "repeat", "smooth"];
+
+/// Read public properties of an `IGraphicsData` object, to pass them on as
+/// the arguments of a drawing call.
+fn public_properties<'gc>(
+    object: Object<'gc>,
+    names: &[&'static str],
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Vec<Value<'gc>>, Error> {
+    let mut values = Vec::with_capacity(names.len());
+    for name in names {
+        values.push(object.get_property(
+            object,
+            &QName::new(Namespace::public(), *name).into(),
+            activation,
+        )?);
+    }
+
+    Ok(values)
+}
+
+/// Determine if an object is an instance of an `IGraphicsData` class.
+fn is_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    class_name: &'static str,
+) -> Result<bool, Error> {
+    let class = activation
+        .avm2()
+        .global_domain()
+        .get_defined_value(
+            activation,
+            QName::new(Namespace::package("flash.display"), class_name),
+        )?
+        .coerce_to_object(activation)?
+        .as_class_object()
+        .ok_or_else(|| format!("{} is not a class", class_name))?;
+
+    object.is_of_type(class, activation)
+}
+
+/// Read a `GraphicsStroke` object as a line style.
+fn stroke_line_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
+    stroke: Object<'gc>,
+) -> Result<Option<LineStyle>, Error> {
+    let fill = public_properties(stroke, &["fill"], activation)?[0];
+    let fill = match fill {
+        Value::Object(fill) => Some(fill),
+        _ => None,
+    };
+
+    let (color, alpha) = match fill {
+        Some(fill) if is_graphics_data(activation, fill, "GraphicsSolidFill")? => {
+            let values = public_properties(fill, &["color", "alpha"], activation)?;
+            (values[0], values[1])
+        }
+        _ => (0.into(), 1.0.into()),
+    };
+    let thickness = public_properties(stroke, &["thickness"], activation)?[0];
+    let mut args = vec![thickness, color, alpha];
+    let names = ["pixelHinting", "scaleMode", "caps", "joints", "miterLimit"];
+    args.extend(public_properties(stroke, &names, activation)?);
+
+    let mut line_style = match line_style_from_args(activation, &args)? {
+        Some(line_style) => line_style,
+        None => return Ok(None),
+    };
+    if let Some(fill) = fill {
+        if is_graphics_data(activation, fill, "GraphicsGradientFill")? {
+            let args = public_properties(fill, GRADIENT_FILL_PROPERTIES, activation)?;
+            line_style.fill_style = Some(gradient_fill_style(activation, &args)?);
+        } else if is_graphics_data(activation, fill, "GraphicsBitmapFill")? {
+            let args = public_properties(fill, BITMAP_FILL_PROPERTIES, activation)?;
+            line_style.fill_style = bitmap_fill_style(activation, this, &args)?;
+        }
+    }
+
+    Ok(Some(line_style))
+}
+
+/// Implements `Graphics.drawGraphicsData`.
+///
+/// Each `IGraphicsData` object is replayed as the equivalent drawing call.
+fn draw_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let display_object = match this.and_then(|t| t.as_display_object()) {
+        Some(display_object) => display_object,
+        None => return Ok(Value::Undefined),
+    };
+
+    for data in list_arg(args.get(0)) {
+        let data = match data {
+            Value::Object(data) => data,
+            _ => continue,
+        };
+
+        if is_graphics_data(activation, data, "GraphicsSolidFill")? {
+            let args = public_properties(data, &["color", "alpha"], activation)?;
+            begin_fill(activation, this, &args)?;
+        } else if is_graphics_data(activation, data, "GraphicsGradientFill")? {
+            let args = public_properties(data, GRADIENT_FILL_PROPERTIES, activation)?;
+            begin_gradient_fill(activation, this, &args)?;
+        } else if is_graphics_data(activation, data, "GraphicsBitmapFill")? {
+            let args = public_properties(data, BITMAP_FILL_PROPERTIES, activation)?;
+            begin_bitmap_fill(activation, this, &args)?;
+        } else if is_graphics_data(activation, data, "GraphicsEndFill")? {
+            end_fill(activation, this, &[])?;
+        } else if is_graphics_data(activation, data, "GraphicsStroke")? {
+            let line_style = stroke_line_style(activation, display_object, data)?;
+            if let Some(mut draw) = display_object.as_drawing(activation.context.gc_context) {
+                draw.set_line_style(line_style);
+            }
+        } else if is_graphics_data(activation, data, "GraphicsPath")? {
+            let args = public_properties(data, &["commands", "data", "winding"], activation)?;
+            draw_path(activation, this, &args)?;
+        } else if is_graphics_data(activation, data, "GraphicsTrianglePath")? {
+            let names = ["vertices", "indices", "uvtData", "culling"];
+            let args = public_properties(data, &names, activation)?;
+            draw_triangles(activation, this, &args)?;
+        } else {
+            return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Graphics`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -788,6 +1450,8 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("beginFill", begin_fill),
+        ("beginBitmapFill", begin_bitmap_fill),
+        ("beginGradientFill", begin_gradient_fill),
         ("clear", clear),
         ("curveTo", curve_to),
         ("endFill", end_fill),
@@ -798,8 +1462,315 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("drawRoundRect", draw_round_rect),
         ("drawCircle", draw_circle),
         ("drawEllipse", draw_ellipse),
+        ("drawPath", draw_path),
+        ("drawTriangles", draw_triangles),
+        ("drawGraphicsData", draw_graphics_data),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Construct a sprite and return it along with its `graphics`.
+    fn sprite<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(Object<'gc>, Object<'gc>), Error> {
+        let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+        let graphics = get(activation, sprite, "graphics")?.coerce_to_object(activation)?;
+
+        Ok((sprite, graphics))
+    }
+
+    /// The width and height of a display object.
+    fn size<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<(Value<'gc>, Value<'gc>), Error> {
+        Ok((
+            get(activation, object, "width")?,
+            get(activation, object, "height")?,
+        ))
+    }
+
+    /// Construct a `Vector.<Number>` holding the given numbers.
+    fn numbers<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        numbers: &[f64],
+    ) -> Result<Object<'gc>, Error> {
+        let number = activation.avm2().classes().number;
+        let vector = activation.avm2().classes().vector;
+        let numbers_class = vector.apply(activation, &[number.into()])?;
+        let vector = numbers_class.construct(activation, &[])?;
+        let values = numbers.iter().map(|n| (*n).into()).collect::<Vec<_>>();
+        call(activation, vector, "push", &values)?;
+
+        Ok(vector)
+    }
+
+    /// Draw a filled path on a new sprite.
+    fn filled_path<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        commands: &[f64],
+        data: &[f64],
+        winding: &'static str,
+    ) -> Result<Object<'gc>, Error> {
+        let (sprite, graphics) = sprite(activation)?;
+        let commands = numbers(activation, commands)?;
+        let data = numbers(activation, data)?;
+        call(activation, graphics, "beginFill", &[0x0000FF.into()])?;
+        let args = [commands.into(), data.into(), winding.into()];
+        call(activation, graphics, "drawPath", &args)?;
+        call(activation, graphics, "endFill", &[])?;
+
+        Ok(sprite)
+    }
+
+    #[test]
+    fn fills_set_the_bounds() {
+        with_avm2(|activation| {
+            let (solid, graphics) = sprite(activation)?;
+            call(activation, graphics, "beginFill", &[0xFF0000.into()])?;
+            let args = [10.into(), 20.into(), 100.into(), 50.into()];
+            call(activation, graphics, "drawRect", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+            assert_eq!(size(activation, solid)?, (100.into(), 50.into()));
+
+            let matrix = construct(activation, "flash.geom", "Matrix", &[])?;
+            let args = [200.into(), 40.into(), 0.into(), 0.into(), 0.into()];
+            call(activation, matrix, "createGradientBox", &args)?;
+            let colors = array(activation, &[0xFF0000.into(), 0x0000FF.into()])?;
+            let alphas = array(activation, &[1.into(), 0.5.into()])?;
+            let ratios = array(activation, &[0.into(), 255.into()])?;
+            let (linear, graphics) = sprite(activation)?;
+            let args = [
+                "linear".into(),
+                colors.into(),
+                alphas.into(),
+                ratios.into(),
+                matrix.into(),
+            ];
+            call(activation, graphics, "beginGradientFill", &args)?;
+            let args = [0.into(), 0.into(), 200.into(), 40.into()];
+            call(activation, graphics, "drawRect", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+            assert_eq!(size(activation, linear)?, (200.into(), 40.into()));
+
+            let (radial, radial_graphics) = sprite(activation)?;
+            let args = [
+                "radial".into(),
+                colors.into(),
+                alphas.into(),
+                ratios.into(),
+                Value::Null,
+                "reflect".into(),
+                "linearRGB".into(),
+                0.5.into(),
+            ];
+            call(activation, radial_graphics, "beginGradientFill", &args)?;
+            let args = [0.into(), 0.into(), 25.into()];
+            call(activation, radial_graphics, "drawCircle", &args)?;
+            call(activation, radial_graphics, "endFill", &[])?;
+            assert_eq!(size(activation, radial)?, (50.into(), 50.into()));
+
+            call(activation, graphics, "clear", &[])?;
+            assert_eq!(size(activation, linear)?, (0.into(), 0.into()));
+
+            let error = call(activation, graphics, "beginGradientFill", &["conic".into()]);
+            assert!(error
+                .unwrap_err()
+                .to_string()
+                .starts_with("ArgumentError: Error #2008"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn triangles_and_paths_set_the_bounds() {
+        with_avm2(|activation| {
+            let (triangles, graphics) = sprite(activation)?;
+            let vertices = numbers(
+                activation,
+                &[10.0, 10.0, 110.0, 10.0, 110.0, 60.0, 10.0, 60.0],
+            )?;
+            let indices = numbers(activation, &[0.0, 1.0, 2.0, 0.0, 2.0, 3.0])?;
+            call(activation, graphics, "beginFill", &[0x00FF00.into()])?;
+            let args = [vertices.into(), indices.into()];
+            call(activation, graphics, "drawTriangles", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+            assert_eq!(size(activation, triangles)?, (100.into(), 50.into()));
+
+            let (unindexed, graphics) = sprite(activation)?;
+            let vertices = [
+                0.0, 0.0, 30.0, 0.0, 0.0, 20.0, 30.0, 0.0, 30.0, 20.0, 0.0, 20.0,
+            ];
+            let vertices = numbers(activation, &vertices)?;
+            call(activation, graphics, "beginFill", &[0x00FF00.into()])?;
+            let args = [vertices.into(), Value::Null, Value::Null, "none".into()];
+            call(activation, graphics, "drawTriangles", &args)?;
+            call(activation, graphics, "endFill", &[])?;
+            assert_eq!(size(activation, unindexed)?, (30.into(), 20.into()));
+
+            let data = [0.0, 0.0, 80.0, 0.0, 80.0, 40.0, 0.0, 40.0];
+            let path = filled_path(activation, &[1.0, 2.0, 2.0, 2.0], &data, "evenOdd")?;
+            assert_eq!(size(activation, path)?, (80.into(), 40.into()));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn paths_honor_the_winding_rule() {
+        with_avm2(|activation| {
+            // Two overlapping squares, drawn in the same direction.
+            let commands = [1.0, 2.0, 2.0, 2.0, 1.0, 2.0, 2.0, 2.0];
+            let data = [
+                0.0, 0.0, 20.0, 0.0, 20.0, 20.0, 0.0, 20.0, 10.0, 10.0, 30.0, 10.0, 30.0, 30.0,
+                10.0, 30.0,
+            ];
+
+            let even_odd = filled_path(activation, &commands, &data, "evenOdd")?;
+            let args = [5.into(), 5.into(), true.into()];
+            assert_eq!(
+                call(activation, even_odd, "hitTestPoint", &args)?,
+                true.into()
+            );
+            let args = [15.into(), 15.into(), true.into()];
+            assert_eq!(
+                call(activation, even_odd, "hitTestPoint", &args)?,
+                false.into()
+            );
+
+            let non_zero = filled_path(activation, &commands, &data, "nonZero")?;
+            let args = [5.into(), 5.into(), true.into()];
+            assert_eq!(
+                call(activation, non_zero, "hitTestPoint", &args)?,
+                true.into()
+            );
+            let args = [15.into(), 15.into(), true.into()];
+            assert_eq!(
+                call(activation, non_zero, "hitTestPoint", &args)?,
+                true.into()
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn graphics_data_is_replayed() {
+        with_avm2(|activation| {
+            let fill = construct(
+                activation,
+                "flash.display",
+                "GraphicsSolidFill",
+                &[0xFF0000.into(), 0.5.into()],
+            )?;
+            let commands = numbers(activation, &[1.0, 2.0, 2.0, 2.0])?;
+            let data = numbers(activation, &[0.0, 0.0, 25.0, 0.0, 25.0, 75.0, 0.0, 75.0])?;
+            let args = [commands.into(), data.into()];
+            let path = construct(activation, "flash.display", "GraphicsPath", &args)?;
+            let end_fill = construct(activation, "flash.display", "GraphicsEndFill", &[])?;
+            let data = array(activation, &[fill.into(), path.into(), end_fill.into()])?;
+
+            let (replayed, graphics) = sprite(activation)?;
+            call(activation, graphics, "drawGraphicsData", &[data.into()])?;
+            assert_eq!(size(activation, replayed)?, (25.into(), 75.into()));
+
+            let args = [
+                10.into(),
+                false.into(),
+                "normal".into(),
+                "round".into(),
+                "round".into(),
+                3.into(),
+                fill.into(),
+            ];
+            let stroke = construct(activation, "flash.display", "GraphicsStroke", &args)?;
+            let commands = numbers(activation, &[1.0, 2.0])?;
+            let data = numbers(activation, &[0.0, 0.0, 100.0, 0.0])?;
+            let args = [commands.into(), data.into()];
+            let line = construct(activation, "flash.display", "GraphicsPath", &args)?;
+            let data = array(activation, &[stroke.into(), line.into()])?;
+
+            let (stroked, graphics) = sprite(activation)?;
+            call(activation, graphics, "drawGraphicsData", &[data.into()])?;
+            assert_eq!(size(activation, stroked)?, (110.into(), 10.into()));
+
+            let bitmap_data = construct(
+                activation,
+                "flash.display",
+                "BitmapData",
+                &[10.into(), 10.into()],
+            )?;
+            let bitmap_fill = construct(
+                activation,
+                "flash.display",
+                "GraphicsBitmapFill",
+                &[bitmap_data.into()],
+            )?;
+            let vertices = numbers(activation, &[0.0, 0.0, 40.0, 0.0, 0.0, 30.0])?;
+            let uvt_data = numbers(activation, &[0.0, 0.0, 1.0, 0.0, 0.0, 1.0])?;
+            let args = [vertices.into(), Value::Null, uvt_data.into()];
+            let triangles = construct(activation, "flash.display", "GraphicsTrianglePath", &args)?;
+            let data = array(activation, &[bitmap_fill.into(), triangles.into()])?;
+
+            let (textured, graphics) = sprite(activation)?;
+            call(activation, graphics, "drawGraphicsData", &[data.into()])?;
+            assert_eq!(size(activation, textured)?, (40.into(), 30.into()));
+
+            let display_object = textured.as_display_object().unwrap();
+            let draw = display_object
+                .as_drawing(activation.context.gc_context)
+                .unwrap();
+            match draw.fill_style() {
+                Some(FillStyle::Bitmap { id: 0, matrix, .. }) => {
+                    assert_eq!(Matrix::from(*matrix), Matrix::scale(20.0, 20.0));
+                }
+                fill_style => panic!("unexpected fill style {:?}", fill_style),
+            }
+            drop(draw);
+
+            let object = construct(activation, "", "Object", &[])?;
+            let data = array(activation, &[object.into()])?;
+            let error = call(activation, graphics, "drawGraphicsData", &[data.into()]);
+            assert_eq!(
+                error.unwrap_err().to_string(),
+                "ArgumentError: Error #2004: One of the parameters is invalid."
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn cubics_become_quadratics() {
+        let curve = cubic_to_quadratics((0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0));
+        assert!(curve.len() > 1);
+        assert_eq!(curve.last().unwrap().1, (100.0, 0.0));
+
+        let line = cubic_to_quadratics((0.0, 0.0), (10.0, 0.0), (20.0, 0.0), (30.0, 0.0));
+        assert_eq!(line, vec![((15.0, 0.0), (30.0, 0.0))]);
+    }
+
+    #[test]
+    fn triangle_bitmap_matrices_map_uvs_to_corners() {
+        let corners = [(10.0, 10.0), (110.0, 10.0), (10.0, 60.0)];
+        let uvs = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)];
+        let matrix = triangle_bitmap_matrix(corners, uvs, (10.0, 10.0)).unwrap();
+        for ((x, y), (u, v)) in corners.iter().zip(uvs.iter()) {
+            // The matrix takes bitmap pixels, not twips.
+            let point = (Twips::new((u * 10.0) as i32), Twips::new((v * 10.0) as i32));
+            let (mapped_x, mapped_y) = matrix * point;
+            assert_eq!((mapped_x.to_pixels(), mapped_y.to_pixels()), (*x, *y));
+        }
+
+        let uvs = [(0.0, 0.0), (0.5, 0.5), (1.0, 1.0)];
+        assert_eq!(triangle_bitmap_matrix(corners, uvs, (10.0, 10.0)), None);
+    }
+}
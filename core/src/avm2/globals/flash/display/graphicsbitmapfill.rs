@@ -0,0 +1,91 @@
+//! `flash.display.GraphicsBitmapFill` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.GraphicsBitmapFill`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (name, value) in ["bitmapData", "matrix", "repeat", "smooth"]
+            .iter()
+            .zip(args.iter())
+        {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name).into(),
+                *value,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsBitmapFill`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GraphicsBitmapFill`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsBitmapFill"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(
+            instance_init,
+            "<GraphicsBitmapFill instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<GraphicsBitmapFill class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsData").into());
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "bitmapData"),
+        QName::new(Namespace::package("flash.display"), "BitmapData").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "matrix"),
+        QName::new(Namespace::package("flash.geom"), "Matrix").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "repeat"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(true.into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "smooth"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+
+    class
+}
@@ -0,0 +1,124 @@
+//! `flash.display.GraphicsGradientFill` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.GraphicsGradientFill`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (name, value) in [
+            "type",
+            "colors",
+            "alphas",
+            "ratios",
+            "matrix",
+            "spreadMethod",
+            "interpolationMethod",
+            "focalPointRatio",
+        ]
+        .iter()
+        .zip(args.iter())
+        {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name).into(),
+                *value,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsGradientFill`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GraphicsGradientFill`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsGradientFill"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(
+            instance_init,
+            "<GraphicsGradientFill instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<GraphicsGradientFill class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsData").into());
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "type"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("linear".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "colors"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "alphas"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "ratios"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "matrix"),
+        QName::new(Namespace::package("flash.geom"), "Matrix").into(),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "spreadMethod"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("pad".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "interpolationMethod"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("rgb".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "focalPointRatio"),
+        QName::new(Namespace::public(), "Number").into(),
+        Some(0.0.into()),
+    ));
+
+    class
+}
@@ -0,0 +1,113 @@
+//! `flash.display.GraphicsStroke` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.GraphicsStroke`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (name, value) in [
+            "thickness",
+            "pixelHinting",
+            "scaleMode",
+            "caps",
+            "joints",
+            "miterLimit",
+            "fill",
+        ]
+        .iter()
+        .zip(args.iter())
+        {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name).into(),
+                *value,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsStroke`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GraphicsStroke`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsStroke"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<GraphicsStroke instance initializer>", mc),
+        Method::from_builtin(class_init, "<GraphicsStroke class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsData").into());
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "thickness"),
+        QName::new(Namespace::public(), "Number").into(),
+        Some(f64::NAN.into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "pixelHinting"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "scaleMode"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("normal".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "caps"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("none".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "joints"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("round".into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "miterLimit"),
+        QName::new(Namespace::public(), "Number").into(),
+        Some(3.0.into()),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "fill"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+
+    class
+}
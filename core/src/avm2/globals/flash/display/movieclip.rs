@@ -59,7 +59,10 @@ pub fn add_frame_script<'gc>(
     {
         for (frame_id, callable) in args.chunks_exact(2).map(|s| (s[0], s[1])) {
             let frame_id = frame_id.coerce_to_u32(activation)? as u16 + 1;
-            let callable = callable.coerce_to_object(activation)?;
+            let callable = match callable {
+                Value::Undefined | Value::Null => None,
+                callable => Some(callable.coerce_to_object(activation)?),
+            };
 
             mc.register_frame_script(frame_id, callable, &mut activation.context);
         }
@@ -375,31 +378,34 @@ pub fn goto_frame<'gc>(
             let frame_or_label = frame_or_label.coerce_to_string(activation)?;
             if let Ok(frame) = frame_or_label.parse::<u32>() {
                 frame + scene
-            } else {
-                if let Some(scene) = args.get(1).cloned() {
-                    //If the user specified a scene, we need to validate that
-                    //the requested frame exists within that scene.
-                    let scene = scene.coerce_to_string(activation)?;
-                    if !mc.frame_exists_within_scene(&frame_or_label, &scene) {
-                        return Err(format!(
-                            "ArgumentError: Frame label {} not found in scene {}",
+            } else if let Some(scene) = args.get(1).filter(|v| !matches!(v, Value::Null)) {
+                //If the user specified a scene, the label has to be resolved
+                //within that scene, as other scenes may reuse the same name.
+                let scene = scene.coerce_to_string(activation)?;
+                mc.frame_label_to_number_in_scene(&frame_or_label, &scene)
+                    .ok_or_else(|| {
+                        format!(
+                            "ArgumentError: Error #2109: Frame label {} not found in scene {}.",
                             frame_or_label, scene
                         )
-                        .into());
-                    }
-                }
-
+                    })? as u32
+            } else {
                 mc.frame_label_to_number(&frame_or_label).ok_or_else(|| {
+                    let scene = mc.current_scene().unwrap_or_default();
+
                     format!(
-                        "ArgumentError: {} is not a valid frame label.",
-                        frame_or_label
+                        "ArgumentError: Error #2109: Frame label {} not found in scene {}.",
+                        frame_or_label, scene.name
                     )
                 })? as u32
             }
         }
     };
 
-    mc.goto_frame(&mut activation.context, frame as u16, stop);
+    // Frame numbers past the end of the timeline are clamped by the seek, so
+    // don't let them wrap around here.
+    let frame = u16::try_from(frame).unwrap_or(u16::MAX);
+    mc.goto_frame(&mut activation.context, frame, stop);
 
     Ok(())
 }
@@ -559,3 +565,158 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    #[test]
+    fn goto_and_stop_by_number() {
+        with_avm2(|activation| {
+            let clip = timeline(activation, 5)?;
+            call(activation, clip, "stop", &[])?;
+
+            assert_eq!(get(activation, clip, "totalFrames")?, 5.into());
+            assert_eq!(get(activation, clip, "framesLoaded")?, 5.into());
+            assert_eq!(get(activation, clip, "currentFrame")?, 1.into());
+
+            call(activation, clip, "gotoAndStop", &[3.into()])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 3.into());
+            call(activation, clip, "nextFrame", &[])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 4.into());
+            call(activation, clip, "gotoAndStop", &["5".into()])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 5.into());
+            call(activation, clip, "prevFrame", &[])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 4.into());
+            call(activation, clip, "gotoAndStop", &[1.into()])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 1.into());
+            call(activation, clip, "gotoAndStop", &[100.into()])?;
+            assert_eq!(get(activation, clip, "currentFrame")?, 5.into());
+            assert_eq!(get(activation, clip, "isPlaying")?, false.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn goto_and_stop_with_missing_label_throws() {
+        with_avm2(|activation| {
+            let clip = timeline(activation, 5)?;
+
+            let error = call(activation, clip, "gotoAndStop", &["missing".into()]).unwrap_err();
+            assert!(error
+                .to_string()
+                .starts_with("ArgumentError: Error #2109: Frame label missing not found"));
+
+            Ok(())
+        })
+    }
+
+    /// Log which frame script ran, and the frame the clip is on.
+    fn log_frame_script<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        script: &str,
+    ) -> Result<Object<'gc>, Error> {
+        let this = this.ok_or("Frame script called without a receiver")?;
+        let current_frame = get(activation, this, "currentFrame")?;
+        log(format!(
+            "{} script, currentFrame = {}",
+            script,
+            traced(activation, current_frame)?
+        ));
+
+        Ok(this)
+    }
+
+    fn frame1<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log_frame_script(activation, this, "frame 1")?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn frame2<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log_frame_script(activation, this, "frame 2")?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn frame3<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log_frame_script(activation, this, "frame 3")?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn frame4<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let this = log_frame_script(activation, this, "frame 4")?;
+        call(activation, this, "stop", &[])?;
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn add_frame_script_replaces_and_clears() {
+        with_avm2(|activation| {
+            let clip = timeline(activation, 5)?;
+            let frame1 = function(activation, frame1)?;
+            let frame2 = function(activation, frame2)?;
+            let frame3 = function(activation, frame3)?;
+            let frame4 = function(activation, frame4)?;
+
+            let args = [1.into(), frame2.into(), 2.into(), frame3.into()];
+            call(activation, clip, "addFrameScript", &args)?;
+            call(
+                activation,
+                clip,
+                "addFrameScript",
+                &[0.into(), frame1.into()],
+            )?;
+            call(activation, clip, "addFrameScript", &[0.into(), Value::Null])?;
+            call(
+                activation,
+                clip,
+                "addFrameScript",
+                &[3.into(), frame3.into()],
+            )?;
+            call(
+                activation,
+                clip,
+                "addFrameScript",
+                &[3.into(), frame4.into()],
+            )?;
+
+            for _ in 0..5 {
+                run_frame(activation);
+            }
+
+            assert_eq!(
+                take_log(),
+                [
+                    "frame 2 script, currentFrame = 2",
+                    "frame 3 script, currentFrame = 3",
+                    "frame 4 script, currentFrame = 4",
+                ]
+            );
+            assert_eq!(get(activation, clip, "currentFrame")?, 4.into());
+
+            Ok(())
+        })
+    }
+}
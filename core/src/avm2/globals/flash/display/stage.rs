@@ -619,6 +619,18 @@ pub fn allows_full_screen_interactive<'gc>(
     Ok(false.into())
 }
 
+/// Implement `invalidate`
+pub fn invalidate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let stage = activation.context.stage;
+    stage.set_invalidated(activation.context.gc_context, true);
+
+    Ok(Value::Undefined)
+}
+
 /// Implement `quality`'s getter
 pub fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -759,5 +771,256 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     ];
     write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
 
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("invalidate", invalidate)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    fn stage_size<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        stage: Object<'gc>,
+    ) -> Result<String, Error> {
+        let width = get(activation, stage, "stageWidth")?;
+        let width = traced(activation, width)?;
+        let height = get(activation, stage, "stageHeight")?;
+        let height = traced(activation, height)?;
+
+        Ok(format!("{}x{}", width, height))
+    }
+
+    fn log_frame_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        label: &str,
+        event: Value<'gc>,
+    ) -> Result<(), Error> {
+        let event = event.coerce_to_object(activation)?;
+        let event_type = get(activation, event, "type")?;
+        log(format!("{} {}", label, traced(activation, event_type)?));
+
+        Ok(())
+    }
+
+    fn on_root_frame_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log_frame_event(activation, "root", args[0])?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_child_frame_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let target = get(activation, event, "currentTarget")?.coerce_to_object(activation)?;
+        let name = get(activation, target, "name")?;
+        let name = traced(activation, name)?;
+        log_frame_event(activation, &name, args[0])?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_unexpected_render<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log("unexpected render");
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_render_add_child<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log("render");
+
+        let event = args[0].coerce_to_object(activation)?;
+        let target = get(activation, event, "currentTarget")?.coerce_to_object(activation)?;
+        let late = construct(activation, "flash.display", "Sprite", &[])?;
+        let listener = function(activation, on_unexpected_render)?;
+        call(
+            activation,
+            late,
+            "addEventListener",
+            &["render".into(), listener.into()],
+        )?;
+        call(activation, target, "addChild", &[late.into()])?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_resize<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let stage = activation.context.stage.object2();
+        let stage = stage.coerce_to_object(activation)?;
+        let event = args[0].coerce_to_object(activation)?;
+        let target = get(activation, event, "target")?;
+        let size = stage_size(activation, stage)?;
+        log(format!("resize: {} {}", target == Value::from(stage), size));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn no_scale_tracks_the_viewport() {
+        with_avm2(|activation| {
+            let display_object = activation.context.stage;
+            display_object.set_viewport_size(&mut activation.context, 900, 900, 1.0);
+            let stage = display_object.object2().coerce_to_object(activation)?;
+
+            let scale_mode = get(activation, stage, "scaleMode")?;
+            assert_eq!(traced(activation, scale_mode)?, "showAll");
+            assert_eq!(stage_size(activation, stage)?, "550x400");
+
+            let listener = function(activation, on_resize)?;
+            call(
+                activation,
+                stage,
+                "addEventListener",
+                &["resize".into(), listener.into()],
+            )?;
+
+            set(activation, stage, "align", "TL")?;
+            let align = get(activation, stage, "align")?;
+            assert_eq!(traced(activation, align)?, "TL");
+            assert!(take_log().is_empty());
+
+            set(activation, stage, "scaleMode", "noScale")?;
+            assert_eq!(take_log(), ["resize: true 900x900"]);
+            let scale_mode = get(activation, stage, "scaleMode")?;
+            assert_eq!(traced(activation, scale_mode)?, "noScale");
+            assert_eq!(stage_size(activation, stage)?, "900x900");
+
+            display_object.set_viewport_size(&mut activation.context, 640, 480, 1.0);
+            assert_eq!(take_log(), ["resize: true 640x480"]);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn render_follows_invalidate_in_frame_order() {
+        with_avm2(|activation| {
+            let main = timeline(activation, 1)?;
+            let on_root = function(activation, on_root_frame_event)?;
+            let on_child = function(activation, on_child_frame_event)?;
+            let mut watched = vec![(main, on_root)];
+            for name in ["a", "b"] {
+                let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+                set(activation, sprite, "name", name)?;
+                call(activation, main, "addChild", &[sprite.into()])?;
+                watched.push((sprite, on_child));
+            }
+
+            for (target, listener) in watched {
+                for event_type in ["enterFrame", "frameConstructed", "exitFrame", "render"] {
+                    call(
+                        activation,
+                        target,
+                        "addEventListener",
+                        &[event_type.into(), listener.into()],
+                    )?;
+                }
+            }
+
+            let stage = activation
+                .context
+                .stage
+                .object2()
+                .coerce_to_object(activation)?;
+            call(activation, stage, "invalidate", &[])?;
+            assert!(take_log().is_empty());
+
+            run_frame(activation);
+            assert_eq!(
+                take_log(),
+                [
+                    "root exitFrame",
+                    "a exitFrame",
+                    "b exitFrame",
+                    "root enterFrame",
+                    "a enterFrame",
+                    "b enterFrame",
+                    "root frameConstructed",
+                    "a frameConstructed",
+                    "b frameConstructed",
+                    "root render",
+                    "a render",
+                    "b render",
+                ]
+            );
+
+            run_frame(activation);
+            assert_eq!(
+                take_log(),
+                [
+                    "root exitFrame",
+                    "a exitFrame",
+                    "b exitFrame",
+                    "root enterFrame",
+                    "a enterFrame",
+                    "b enterFrame",
+                    "root frameConstructed",
+                    "a frameConstructed",
+                    "b frameConstructed",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn invalidating_twice_renders_once() {
+        with_avm2(|activation| {
+            let main = timeline(activation, 1)?;
+            let offstage = construct(activation, "flash.display", "Sprite", &[])?;
+            let unexpected = function(activation, on_unexpected_render)?;
+            call(
+                activation,
+                offstage,
+                "addEventListener",
+                &["render".into(), unexpected.into()],
+            )?;
+            let listener = function(activation, on_render_add_child)?;
+            call(
+                activation,
+                main,
+                "addEventListener",
+                &["render".into(), listener.into()],
+            )?;
+
+            let stage = activation
+                .context
+                .stage
+                .object2()
+                .coerce_to_object(activation)?;
+            call(activation, stage, "invalidate", &[])?;
+            call(activation, stage, "invalidate", &[])?;
+
+            run_frame(activation);
+            assert_eq!(take_log(), ["render"]);
+
+            run_frame(activation);
+            assert!(take_log().is_empty());
+
+            Ok(())
+        });
+    }
+}
@@ -1,10 +1,17 @@
 //! `flash.events` namespace
 
 pub mod activityevent;
+pub mod dataevent;
+pub mod errorevent;
 pub mod event;
 pub mod eventdispatcher;
 pub mod fullscreenevent;
 pub mod ieventdispatcher;
+pub mod ioerrorevent;
 pub mod keyboardevent;
 pub mod mouseevent;
+pub mod netstatusevent;
 pub mod progressevent;
+pub mod securityerrorevent;
+pub mod statusevent;
+pub mod textevent;
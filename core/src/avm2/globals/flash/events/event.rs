@@ -150,6 +150,53 @@ pub fn clone<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Clone an event as a new instance of one of the built-in event classes.
+///
+/// The clone is constructed with the original's `type`, `bubbles`, and
+/// `cancelable`, followed by the current values of `properties`. Subclasses
+/// list those properties in the order their constructor accepts them.
+pub fn clone_as<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    class_name: &'static str,
+    properties: &[&'static str],
+) -> Result<Value<'gc>, Error> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut args = match this.as_event() {
+        Some(evt) => vec![
+            evt.event_type().into(),
+            evt.is_bubbling().into(),
+            evt.is_cancelable().into(),
+        ],
+        None => return Ok(Value::Undefined),
+    };
+
+    for name in properties {
+        args.push(this.get_property(
+            this,
+            &QName::new(Namespace::public(), *name).into(),
+            activation,
+        )?);
+    }
+
+    let class = activation
+        .avm2()
+        .global_domain()
+        .get_defined_value(
+            activation,
+            QName::new(Namespace::package("flash.events"), class_name),
+        )?
+        .coerce_to_object(activation)?
+        .as_class_object()
+        .ok_or("Event clone target is not a class")?;
+
+    Ok(class.construct(activation, &args)?.into())
+}
+
 /// Implements `formatToString`
 pub fn format_to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -365,3 +412,105 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Construct an event, clone it, and return the fields of the clone as
+    /// `trace` would print them.
+    fn cloned_fields<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &'static str,
+        args: &[Value<'gc>],
+        names: &[&'static str],
+    ) -> Result<Vec<String>, Error> {
+        let event = construct(activation, "flash.events", class_name, args)?;
+        let cloned = call(activation, event, "clone", &[])?;
+        assert_ne!(cloned, event.into());
+        let event_class = class(activation, "flash.events", class_name)?;
+        assert!(cloned.is_of_type(activation, event_class)?);
+
+        let cloned = cloned.coerce_to_object(activation)?;
+        let mut fields = Vec::new();
+        for name in names {
+            let value = get(activation, cloned, name)?;
+            fields.push(traced(activation, value)?);
+        }
+
+        Ok(fields)
+    }
+
+    #[test]
+    fn builtin_subclasses_clone_as_themselves() {
+        with_avm2(|activation| {
+            let args = [
+                "progress".into(),
+                true.into(),
+                false.into(),
+                25.into(),
+                100.into(),
+            ];
+            let names = ["type", "bubbles", "cancelable", "bytesLoaded", "bytesTotal"];
+            assert_eq!(
+                cloned_fields(activation, "ProgressEvent", &args, &names)?,
+                ["progress", "true", "false", "25", "100"]
+            );
+
+            let args = [
+                "keyDown".into(),
+                true.into(),
+                true.into(),
+                65.into(),
+                66.into(),
+                0.into(),
+                true.into(),
+                false.into(),
+                true.into(),
+            ];
+            let names = [
+                "type",
+                "bubbles",
+                "cancelable",
+                "charCode",
+                "keyCode",
+                "keyLocation",
+                "ctrlKey",
+                "altKey",
+                "shiftKey",
+            ];
+            assert_eq!(
+                cloned_fields(activation, "KeyboardEvent", &args, &names)?,
+                ["keyDown", "true", "true", "65", "66", "0", "true", "false", "true"]
+            );
+
+            let args = [
+                "textInput".into(),
+                false.into(),
+                true.into(),
+                "hello".into(),
+            ];
+            let names = ["type", "bubbles", "cancelable", "text"];
+            assert_eq!(
+                cloned_fields(activation, "TextEvent", &args, &names)?,
+                ["textInput", "false", "true", "hello"]
+            );
+
+            let args = [
+                "ioError".into(),
+                false.into(),
+                false.into(),
+                "Error #2031".into(),
+                2031.into(),
+            ];
+            let names = ["type", "text", "errorID"];
+            assert_eq!(
+                cloned_fields(activation, "IOErrorEvent", &args, &names)?,
+                ["ioError", "Error #2031", "2031"]
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -296,3 +296,83 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use crate::string::AvmString;
+
+    /// `toString` of an object that owns an `EventDispatcher`, which prints
+    /// `"[Owner " + this.name + "]"`.
+    fn owner_to_string<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = get(activation, this.unwrap(), "name")?;
+        let owner = format!("[Owner {}]", traced(activation, name)?);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, owner).into())
+    }
+
+    fn on_ping<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let target = get(activation, event, "target")?;
+        let current_target = get(activation, event, "currentTarget")?;
+        log(format!(
+            "{} {}",
+            traced(activation, target)?,
+            traced(activation, current_target)?
+        ));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn redirected_dispatchers_call_their_own_listeners() {
+        with_avm2(|activation| {
+            let owner = construct(activation, "", "Object", &[])?;
+            set(activation, owner, "name", "owner")?;
+            let to_string_function = function(activation, owner_to_string)?;
+            set(activation, owner, "toString", to_string_function)?;
+            let dispatcher = construct(
+                activation,
+                "flash.events",
+                "EventDispatcher",
+                &[owner.into()],
+            )?;
+            let standalone = construct(activation, "flash.events", "EventDispatcher", &[])?;
+
+            let listener = function(activation, on_ping)?;
+            for target in [dispatcher, standalone] {
+                let args = ["ping".into(), listener.into()];
+                call(activation, target, "addEventListener", &args)?;
+                let args = ["ping".into()];
+                assert_eq!(
+                    call(activation, target, "hasEventListener", &args)?,
+                    true.into()
+                );
+
+                let event = construct(activation, "flash.events", "Event", &["ping".into()])?;
+                let dispatched = call(activation, target, "dispatchEvent", &[event.into()])?;
+                assert_eq!(dispatched, true.into());
+            }
+
+            // The owner is both the target and the current target.
+            assert_eq!(
+                take_log(),
+                [
+                    "[Owner owner] [Owner owner]",
+                    "[object EventDispatcher] [object EventDispatcher]",
+                ]
+            );
+
+            Ok(())
+        })
+    }
+}
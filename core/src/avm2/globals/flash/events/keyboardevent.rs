@@ -1,20 +1,62 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::globals::flash::events::event::clone_as;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
 
+/// The `uint` properties set by the constructor, in parameter order.
+const UINT_PROPERTIES: [&str; 3] = ["charCode", "keyCode", "keyLocation"];
+
+/// The modifier key properties set by the constructor, in parameter order.
+const BOOLEAN_PROPERTIES: [&str; 3] = ["ctrlKey", "altKey", "shiftKey"];
+
 /// Implements `flash.events.KeyboardEvent`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    if let Some(this) = this {
-        activation.super_init(this, args)?; // Event uses the first three parameters
+    if let Some(mut this) = this {
+        // Unlike `Event`, keyboard events bubble by default.
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        for (i, name) in UINT_PROPERTIES.into_iter().enumerate() {
+            let value = args
+                .get(i + 3)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_u32(activation)?;
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), name).into(),
+                value.into(),
+                activation,
+            )?;
+        }
+
+        for (i, name) in BOOLEAN_PROPERTIES.into_iter().enumerate() {
+            let value = args
+                .get(i + 6)
+                .cloned()
+                .unwrap_or(Value::Bool(false))
+                .coerce_to_boolean();
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), name).into(),
+                value.into(),
+                activation,
+            )?;
+        }
     }
     Ok(Value::Undefined)
 }
@@ -28,6 +70,27 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `KeyboardEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    clone_as(
+        activation,
+        this,
+        "KeyboardEvent",
+        &[
+            "charCode",
+            "keyCode",
+            "keyLocation",
+            "ctrlKey",
+            "altKey",
+            "shiftKey",
+        ],
+    )
+}
+
 /// Construct `KeyboardEvent`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -46,5 +109,113 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.define_public_constant_string_class_traits(CONSTANTS);
 
+    for name in UINT_PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "uint").into(),
+            None,
+        ));
+    }
+
+    for name in BOOLEAN_PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "Boolean").into(),
+            None,
+        ));
+    }
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("clone", clone)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    const FIELDS: [&str; 9] = [
+        "type",
+        "bubbles",
+        "cancelable",
+        "charCode",
+        "keyCode",
+        "keyLocation",
+        "ctrlKey",
+        "altKey",
+        "shiftKey",
+    ];
+
+    fn fields<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        event: Object<'gc>,
+        names: &[&'static str],
+    ) -> Result<Vec<String>, Error> {
+        let mut fields = Vec::new();
+        for name in names {
+            let value = get(activation, event, name)?;
+            fields.push(traced(activation, value)?);
+        }
+
+        Ok(fields)
+    }
+
+    fn on_key_up<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let names = ["keyCode", "ctrlKey", "altKey", "shiftKey"];
+        log(fields(activation, event, &names)?.join(" "));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn constructor_defaults() {
+        with_avm2(|activation| {
+            let args = ["keyDown".into()];
+            let event = construct(activation, "flash.events", "KeyboardEvent", &args)?;
+            assert_eq!(
+                fields(activation, event, &FIELDS)?,
+                ["keyDown", "true", "false", "0", "0", "0", "false", "false", "false"]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn constructor_arguments_reach_listeners() {
+        with_avm2(|activation| {
+            let args = [
+                "keyUp".into(),
+                false.into(),
+                true.into(),
+                97.into(),
+                65.into(),
+                1.into(),
+                true.into(),
+                false.into(),
+                true.into(),
+            ];
+            let event = construct(activation, "flash.events", "KeyboardEvent", &args)?;
+            assert_eq!(
+                fields(activation, event, &FIELDS)?,
+                ["keyUp", "false", "true", "97", "65", "1", "true", "false", "true"]
+            );
+
+            let dispatcher = construct(activation, "flash.events", "EventDispatcher", &[])?;
+            let listener = function(activation, on_key_up)?;
+            let args = ["keyUp".into(), listener.into()];
+            call(activation, dispatcher, "addEventListener", &args)?;
+            call(activation, dispatcher, "dispatchEvent", &[event.into()])?;
+            assert_eq!(take_log(), ["65 true false true"]);
+
+            Ok(())
+        });
+    }
+}
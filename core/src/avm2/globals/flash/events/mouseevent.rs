@@ -1,11 +1,46 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::globals::flash::events::event::clone_as;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// The modifier and button properties set by the constructor, in parameter
+/// order.
+const BOOLEAN_PROPERTIES: [&str; 4] = ["ctrlKey", "altKey", "shiftKey", "buttonDown"];
+
+fn set_public_property<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        value,
+        activation,
+    )
+}
+
+fn get_number<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        activation,
+    )?
+    .coerce_to_number(activation)
+}
 
 /// Implements `flash.events.MouseEvent`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -14,7 +49,44 @@ pub fn instance_init<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
-        activation.super_init(this, args)?; // Event uses the first three parameters
+        // Unlike `Event`, mouse events bubble by default.
+        let event_args = [
+            args.get(0).cloned().unwrap_or(Value::Undefined),
+            args.get(1).cloned().unwrap_or(Value::Bool(true)),
+            args.get(2).cloned().unwrap_or(Value::Bool(false)),
+        ];
+        activation.super_init(this, &event_args)?;
+
+        for (i, name) in ["localX", "localY"].into_iter().enumerate() {
+            let value = args
+                .get(i + 3)
+                .cloned()
+                .unwrap_or_else(|| f64::NAN.into())
+                .coerce_to_number(activation)?;
+            set_public_property(this, activation, name, value.into())?;
+        }
+
+        let related_object = match args.get(5) {
+            Some(Value::Object(o)) => Value::Object(*o),
+            _ => Value::Null,
+        };
+        set_public_property(this, activation, "relatedObject", related_object)?;
+
+        for (i, name) in BOOLEAN_PROPERTIES.into_iter().enumerate() {
+            let value = args
+                .get(i + 6)
+                .cloned()
+                .unwrap_or(Value::Bool(false))
+                .coerce_to_boolean();
+            set_public_property(this, activation, name, value.into())?;
+        }
+
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        set_public_property(this, activation, "delta", delta.into())?;
     }
     Ok(Value::Undefined)
 }
@@ -28,6 +100,81 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Convert this event's local coordinates into stage coordinates.
+///
+/// Events without a display object target report their local coordinates
+/// unchanged.
+fn stage_position<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<(f64, f64), Error> {
+    let local_x = get_number(this, activation, "localX")?;
+    let local_y = get_number(this, activation, "localY")?;
+    let target = this
+        .as_event()
+        .and_then(|evt| evt.target())
+        .and_then(|target| target.as_display_object());
+
+    if let Some(target) = target {
+        if local_x.is_finite() && local_y.is_finite() {
+            let (stage_x, stage_y) =
+                target.local_to_global((Twips::from_pixels(local_x), Twips::from_pixels(local_y)));
+            return Ok((stage_x.to_pixels(), stage_y.to_pixels()));
+        }
+    }
+
+    Ok((local_x, local_y))
+}
+
+/// Implements `stageX`'s getter
+pub fn stage_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(stage_position(this, activation)?.0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageY`'s getter
+pub fn stage_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(stage_position(this, activation)?.1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MouseEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    clone_as(
+        activation,
+        this,
+        "MouseEvent",
+        &[
+            "localX",
+            "localY",
+            "relatedObject",
+            "ctrlKey",
+            "altKey",
+            "shiftKey",
+            "buttonDown",
+            "delta",
+        ],
+    )
+}
+
 /// Construct `MouseEvent`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -65,5 +212,42 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.define_public_constant_string_class_traits(CONSTANTS);
 
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("stageX", Some(stage_x), None),
+        ("stageY", Some(stage_y), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_NUMBER_SLOTS: &[(&str, Option<f64>)] =
+        &[("localX", None), ("localY", None)];
+    write.define_public_slot_number_instance_traits(PUBLIC_INSTANCE_NUMBER_SLOTS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "relatedObject"),
+        QName::new(Namespace::package("flash.display"), "InteractiveObject").into(),
+        None,
+    ));
+
+    for name in BOOLEAN_PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "Boolean").into(),
+            None,
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "delta"),
+        QName::new(Namespace::public(), "int").into(),
+        None,
+    ));
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("clone", clone)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
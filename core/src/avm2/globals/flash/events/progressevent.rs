@@ -1,8 +1,9 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::globals::flash::events::event::clone_as;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
@@ -13,8 +14,22 @@ pub fn instance_init<'gc>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    if let Some(this) = this {
+    if let Some(mut this) = this {
         activation.super_init(this, args)?; // Event uses the first three parameters
+
+        for (i, name) in ["bytesLoaded", "bytesTotal"].into_iter().enumerate() {
+            let value = args
+                .get(i + 3)
+                .cloned()
+                .unwrap_or(Value::Number(0.0))
+                .coerce_to_number(activation)?;
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), name).into(),
+                value.into(),
+                activation,
+            )?;
+        }
     }
     Ok(Value::Undefined)
 }
@@ -28,6 +43,20 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `ProgressEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    clone_as(
+        activation,
+        this,
+        "ProgressEvent",
+        &["bytesLoaded", "bytesTotal"],
+    )
+}
+
 /// Construct `ProgressEvent`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -52,5 +81,39 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.define_public_constant_string_class_traits(CONSTANTS);
 
+    const PUBLIC_INSTANCE_NUMBER_SLOTS: &[(&str, Option<f64>)] =
+        &[("bytesLoaded", Some(0.0)), ("bytesTotal", Some(0.0))];
+    write.define_public_slot_number_instance_traits(PUBLIC_INSTANCE_NUMBER_SLOTS);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("clone", clone)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+
+    #[test]
+    fn constructor_arguments() {
+        with_avm2(|activation| {
+            let args = [
+                "socketData".into(),
+                false.into(),
+                false.into(),
+                12.into(),
+                34.into(),
+            ];
+            let progress = construct(activation, "flash.events", "ProgressEvent", &args)?;
+            let event_type = get(activation, progress, "type")?;
+            assert_eq!(traced(activation, event_type)?, "socketData");
+            let bytes_loaded = get(activation, progress, "bytesLoaded")?;
+            assert_eq!(traced(activation, bytes_loaded)?, "12");
+            let bytes_total = get(activation, progress, "bytesTotal")?;
+            assert_eq!(traced(activation, bytes_total)?, "34");
+
+            Ok(())
+        });
+    }
+}
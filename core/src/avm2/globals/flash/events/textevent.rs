@@ -0,0 +1,166 @@
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::event::clone_as;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.TextEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args)?; // Event uses the first three parameters
+
+        let text = match args.get(3) {
+            Some(Value::Undefined) | Some(Value::Null) => Value::Null,
+            Some(value) => value.coerce_to_string(activation)?.into(),
+            None => "".into(),
+        };
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "text").into(),
+            text,
+            activation,
+        )?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.TextEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextEvent.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    clone_as(activation, this, "TextEvent", &["text"])
+}
+
+/// Construct `TextEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "TextEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init, "<TextEvent instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextEvent class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[("LINK", "link"), ("TEXT_INPUT", "textInput")];
+
+    write.define_public_constant_string_class_traits(CONSTANTS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "text"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("clone", clone)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    fn fields<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        event: Object<'gc>,
+        names: &[&'static str],
+    ) -> Result<Vec<String>, Error> {
+        let mut fields = Vec::new();
+        for name in names {
+            let value = get(activation, event, name)?;
+            fields.push(traced(activation, value)?);
+        }
+
+        Ok(fields)
+    }
+
+    #[test]
+    fn text_event_subclasses() {
+        with_avm2(|activation| {
+            let args = [
+                "link".into(),
+                true.into(),
+                false.into(),
+                "event:link".into(),
+            ];
+            let text = construct(activation, "flash.events", "TextEvent", &args)?;
+            let names = ["type", "bubbles", "text"];
+            assert_eq!(
+                fields(activation, text, &names)?,
+                ["link", "true", "event:link"]
+            );
+
+            let args = [
+                "data".into(),
+                false.into(),
+                false.into(),
+                "<message/>".into(),
+            ];
+            let data = construct(activation, "flash.events", "DataEvent", &args)?;
+            let names = ["type", "data", "text"];
+            assert_eq!(
+                fields(activation, data, &names)?,
+                ["data", "<message/>", ""]
+            );
+
+            let args = ["error".into()];
+            let error = construct(activation, "flash.events", "ErrorEvent", &args)?;
+            let names = ["type", "text", "errorID"];
+            assert_eq!(fields(activation, error, &names)?, ["error", "", "0"]);
+
+            let args = [
+                "ioError".into(),
+                false.into(),
+                false.into(),
+                "Error #2031".into(),
+                2031.into(),
+            ];
+            let io_error = construct(activation, "flash.events", "IOErrorEvent", &args)?;
+            assert_eq!(
+                fields(activation, io_error, &names)?,
+                ["ioError", "Error #2031", "2031"]
+            );
+            let error_event = class(activation, "flash.events", "ErrorEvent")?;
+            assert!(io_error.is_of_type(error_event, activation)?);
+            let text_event = class(activation, "flash.events", "TextEvent")?;
+            assert!(io_error.is_of_type(text_event, activation)?);
+
+            let args = ["securityError".into()];
+            let security_error =
+                construct(activation, "flash.events", "SecurityErrorEvent", &args)?;
+            let names = ["type", "errorID"];
+            assert_eq!(
+                fields(activation, security_error, &names)?,
+                ["securityError", "0"]
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,320 @@
+//! `flash.external.ExternalInterface` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::external::{Callback, Value as ExternalValue};
+use gc_arena::{GcCell, MutationContext};
+
+fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ExternalInterface cannot be constructed.".into())
+}
+
+fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Throw if there is no host container to communicate with.
+fn check_available<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error> {
+    if activation.context.external_interface.available() {
+        Ok(())
+    } else {
+        Err("Error: Error #2067: The ExternalInterface is not available in this container. ExternalInterface requires Internet Explorer ActiveX, Firefox, Mozilla 1.7.5 and greater, or other browsers that support NPRuntime.".into())
+    }
+}
+
+/// Implements `ExternalInterface.available`
+fn available<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.external_interface.available().into())
+}
+
+/// Implements `ExternalInterface.objectID`
+///
+/// Ruffle does not expose the embedding element's ID, so this is always null.
+fn object_id<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `ExternalInterface.addCallback`
+fn add_callback<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_available(activation)?;
+
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(Value::Object(method)) = args.get(1) {
+        activation
+            .context
+            .external_interface
+            .add_callback(name.to_string(), Callback::Avm2 { method: *method });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ExternalInterface.call`
+fn call<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_available(activation)?;
+
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(method) = activation
+        .context
+        .external_interface
+        .get_method_for(&name.to_utf8_lossy())
+    {
+        let mut external_args = Vec::with_capacity(args.len().saturating_sub(1));
+        for arg in args.iter().skip(1) {
+            external_args.push(ExternalValue::from_avm2(activation, arg.clone())?);
+        }
+
+        method
+            .call(&mut activation.context, &external_args)
+            .into_avm2(activation)
+    } else {
+        Ok(Value::Null)
+    }
+}
+
+/// Construct `ExternalInterface`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.external"), "ExternalInterface"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(
+            instance_init,
+            "<ExternalInterface instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<ExternalInterface class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_CLASS_TRAITS: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] = &[
+        ("available", Some(available), None),
+        ("objectID", Some(object_id), None),
+    ];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_TRAITS);
+
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("addCallback", add_callback), ("call", call)];
+    write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Activation, Error, ExternalValue, Object, Value};
+    use crate::avm2::test_utils::*;
+    use crate::context::UpdateContext;
+    use crate::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
+    use std::collections::BTreeMap;
+
+    /// A host container that answers calls like the page script of a test
+    /// harness would.
+    struct TestProvider;
+
+    fn ping(_context: &mut UpdateContext<'_, '_, '_>, _args: &[ExternalValue]) -> ExternalValue {
+        log("[ExternalInterface] ping");
+        "Pong!".into()
+    }
+
+    fn trace(_context: &mut UpdateContext<'_, '_, '_>, args: &[ExternalValue]) -> ExternalValue {
+        log(format!("[ExternalInterface] trace: {:?}", args));
+        "Traced!".into()
+    }
+
+    fn reentry(context: &mut UpdateContext<'_, '_, '_>, _args: &[ExternalValue]) -> ExternalValue {
+        log("[ExternalInterface] starting reentry");
+        if let Some(callback) = context.external_interface.get_callback("callWith") {
+            callback.call(
+                context,
+                "callWith",
+                vec!["trace".into(), "successful reentry!".into()],
+            )
+        } else {
+            ExternalValue::Null
+        }
+    }
+
+    impl ExternalInterfaceProvider for TestProvider {
+        fn get_method(&self, name: &str) -> Option<Box<dyn ExternalInterfaceMethod>> {
+            match name {
+                "ping" => Some(Box::new(ping)),
+                "trace" => Some(Box::new(trace)),
+                "reentry" => Some(Box::new(reentry)),
+                _ => None,
+            }
+        }
+
+        fn on_callback_available(&self, _name: &str) {}
+
+        fn on_fs_command(&self, _command: &str, _args: &str) -> bool {
+            false
+        }
+    }
+
+    fn parrot<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log(format!("parrot: {}", traced(activation, args[0].clone())?));
+
+        Ok(args[0].clone())
+    }
+
+    fn call_with<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let external_interface = class(activation, "flash.external", "ExternalInterface")?;
+        let result = super::call(activation, Some(external_interface.into()), args)?;
+        log(format!("callWith: {}", traced(activation, result.clone())?));
+
+        Ok(result)
+    }
+
+    #[test]
+    fn calls_round_trip_through_the_container() {
+        with_avm2(|activation| {
+            let provider = Box::new(TestProvider);
+            activation.context.external_interface.add_provider(provider);
+            let external_interface = class(activation, "flash.external", "ExternalInterface")?;
+
+            let available = get(activation, external_interface, "available")?;
+            assert_eq!(available, true.into());
+            let object_id = get(activation, external_interface, "objectID")?;
+            assert_eq!(object_id, Value::Null);
+
+            let parrot = function(activation, parrot)?;
+            let args = ["parrot".into(), parrot.into()];
+            call(activation, external_interface, "addCallback", &args)?;
+            let call_with = function(activation, call_with)?;
+            let args = ["callWith".into(), call_with.into()];
+            call(activation, external_interface, "addCallback", &args)?;
+
+            let result = call(activation, external_interface, "call", &["ping".into()])?;
+            assert_eq!(result, "Pong!".into());
+            assert_eq!(take_log(), ["[ExternalInterface] ping"]);
+
+            let args = ["non_existent".into()];
+            let result = call(activation, external_interface, "call", &args)?;
+            assert_eq!(result, Value::Null);
+
+            let inner = array(activation, &[2.into()])?;
+            let list = array(activation, &[1.into(), inner.into()])?;
+            let object = construct(activation, "", "Object", &[])?;
+            set(activation, object, "a", 1)?;
+            let args = [
+                "trace".into(),
+                1.into(),
+                true.into(),
+                "string".into(),
+                Value::Null,
+                list.into(),
+                object.into(),
+            ];
+            let result = call(activation, external_interface, "call", &args)?;
+            assert_eq!(result, "Traced!".into());
+            assert_eq!(
+                take_log(),
+                [
+                    r#"[ExternalInterface] trace: [Number(1.0), Bool(true), String("string"), Null, List([Number(1.0), List([Number(2.0)])]), Object({"a": Number(1.0)})]"#
+                ]
+            );
+
+            let result = call(activation, external_interface, "call", &["reentry".into()])?;
+            assert_eq!(result, "Traced!".into());
+            assert_eq!(
+                take_log(),
+                [
+                    "[ExternalInterface] starting reentry",
+                    r#"[ExternalInterface] trace: [String("successful reentry!")]"#,
+                    "callWith: Traced!",
+                ]
+            );
+
+            // The host calls back into the registered closures.
+            let context = &mut activation.context;
+            let parrot = context.external_interface.get_callback("parrot").unwrap();
+            let result = parrot.call(context, "parrot", vec!["Hello World!".into()]);
+            assert_eq!(result, "Hello World!".into());
+            assert_eq!(take_log(), ["parrot: Hello World!"]);
+
+            let mut payload = BTreeMap::new();
+            let list = vec!["string".into(), 100.into(), false.into()];
+            payload.insert("list".to_string(), ExternalValue::List(list));
+            payload.insert("number".to_string(), (-500.1).into());
+            let call_with = context.external_interface.get_callback("callWith").unwrap();
+            let args = vec!["trace".into(), payload.into()];
+            let result = call_with.call(context, "callWith", args);
+            assert_eq!(result, "Traced!".into());
+            assert_eq!(
+                take_log(),
+                [
+                    r#"[ExternalInterface] trace: [Object({"list": List([String("string"), Number(100.0), Bool(false)]), "number": Number(-500.1)})]"#,
+                    "callWith: Traced!",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn without_a_container_calls_throw() {
+        with_avm2(|activation| {
+            let external_interface = class(activation, "flash.external", "ExternalInterface")?;
+            let available = get(activation, external_interface, "available")?;
+            assert_eq!(available, false.into());
+
+            let error = call(activation, external_interface, "call", &["ping".into()])
+                .expect_err("call should throw without a container");
+            assert!(error.to_string().starts_with("Error: Error #2067"));
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,7 @@
+//! `flash.filters` namespace
+
+pub mod bitmapfilter;
+pub mod blurfilter;
+pub mod colormatrixfilter;
+pub mod dropshadowfilter;
+pub mod glowfilter;
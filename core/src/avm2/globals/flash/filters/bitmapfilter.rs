@@ -0,0 +1,236 @@
+//! `flash.filters.BitmapFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::filters::{
+    blurfilter, colormatrixfilter, dropshadowfilter, glowfilter,
+};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::filters::{BlurFilter, ColorMatrixFilter, DropShadowFilter, Filter, GlowFilter};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.filters.BitmapFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.filters.BitmapFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapFilter.clone`.
+///
+/// The copy is made by calling the filter's own constructor with its current
+/// property values, so that any arrays it holds are copied as well.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = this
+            .instance_of_class_definition()
+            .map(|class| class.read().name().local_name());
+        let properties = match name {
+            Some(name) if &name == b"BlurFilter" => blurfilter::CONSTRUCTOR_PROPERTIES,
+            Some(name) if &name == b"ColorMatrixFilter" => {
+                colormatrixfilter::CONSTRUCTOR_PROPERTIES
+            }
+            Some(name) if &name == b"DropShadowFilter" => dropshadowfilter::CONSTRUCTOR_PROPERTIES,
+            Some(name) if &name == b"GlowFilter" => glowfilter::CONSTRUCTOR_PROPERTIES,
+            _ => &[],
+        };
+
+        let mut args = Vec::with_capacity(properties.len());
+        for name in properties {
+            args.push(this.get_property(
+                this,
+                &QName::new(Namespace::public(), *name).into(),
+                activation,
+            )?);
+        }
+
+        if let Some(class) = this.instance_of() {
+            return Ok(class.construct(activation, &args)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Read a public property of a filter object.
+fn property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        activation,
+    )
+}
+
+/// Read the filter described by a filter object.
+///
+/// Returns `None` if the object is not an instance of one of the filter
+/// classes.
+pub fn object_to_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<Filter>, Error> {
+    let classes = activation.context.avm2.classes();
+    let (blur, color_matrix, drop_shadow, glow) = (
+        classes.blurfilter,
+        classes.colormatrixfilter,
+        classes.dropshadowfilter,
+        classes.glowfilter,
+    );
+
+    let filter = if this.is_of_type(blur, activation)? {
+        Filter::Blur(BlurFilter {
+            blur_x: property(activation, this, "blurX")?.coerce_to_number(activation)?,
+            blur_y: property(activation, this, "blurY")?.coerce_to_number(activation)?,
+            quality: property(activation, this, "quality")?.coerce_to_i32(activation)?,
+        })
+    } else if this.is_of_type(color_matrix, activation)? {
+        let values: Vec<Value<'gc>> = match property(activation, this, "matrix")? {
+            Value::Object(matrix) => match matrix.as_array_storage() {
+                Some(storage) => storage
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        let mut matrix = [0.0; 20];
+        for (entry, value) in matrix.iter_mut().zip(values) {
+            *entry = value.coerce_to_number(activation)?;
+        }
+
+        Filter::ColorMatrix(ColorMatrixFilter { matrix })
+    } else if this.is_of_type(drop_shadow, activation)? {
+        Filter::DropShadow(DropShadowFilter {
+            distance: property(activation, this, "distance")?.coerce_to_number(activation)?,
+            angle: property(activation, this, "angle")?.coerce_to_number(activation)?,
+            color: property(activation, this, "color")?.coerce_to_u32(activation)?,
+            alpha: property(activation, this, "alpha")?.coerce_to_number(activation)?,
+            blur_x: property(activation, this, "blurX")?.coerce_to_number(activation)?,
+            blur_y: property(activation, this, "blurY")?.coerce_to_number(activation)?,
+            strength: property(activation, this, "strength")?.coerce_to_number(activation)?,
+            quality: property(activation, this, "quality")?.coerce_to_i32(activation)?,
+            inner: property(activation, this, "inner")?.coerce_to_boolean(),
+            knockout: property(activation, this, "knockout")?.coerce_to_boolean(),
+            hide_object: property(activation, this, "hideObject")?.coerce_to_boolean(),
+        })
+    } else if this.is_of_type(glow, activation)? {
+        Filter::Glow(GlowFilter {
+            color: property(activation, this, "color")?.coerce_to_u32(activation)?,
+            alpha: property(activation, this, "alpha")?.coerce_to_number(activation)?,
+            blur_x: property(activation, this, "blurX")?.coerce_to_number(activation)?,
+            blur_y: property(activation, this, "blurY")?.coerce_to_number(activation)?,
+            strength: property(activation, this, "strength")?.coerce_to_number(activation)?,
+            quality: property(activation, this, "quality")?.coerce_to_i32(activation)?,
+            inner: property(activation, this, "inner")?.coerce_to_boolean(),
+            knockout: property(activation, this, "knockout")?.coerce_to_boolean(),
+        })
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(filter))
+}
+
+/// Construct a new filter object describing a filter.
+///
+/// Returns `None` for filters that have no class yet.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Result<Option<Object<'gc>>, Error> {
+    let classes = activation.context.avm2.classes();
+    let (class, args): (_, Vec<Value<'gc>>) = match filter {
+        Filter::Blur(blur) => (
+            classes.blurfilter,
+            vec![blur.blur_x.into(), blur.blur_y.into(), blur.quality.into()],
+        ),
+        Filter::ColorMatrix(color_matrix) => {
+            let class = classes.colormatrixfilter;
+            let values: Vec<Value<'gc>> = color_matrix.matrix.iter().map(|v| (*v).into()).collect();
+            let matrix = ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?;
+            (class, vec![matrix.into()])
+        }
+        Filter::DropShadow(shadow) => (
+            classes.dropshadowfilter,
+            vec![
+                shadow.distance.into(),
+                shadow.angle.into(),
+                shadow.color.into(),
+                shadow.alpha.into(),
+                shadow.blur_x.into(),
+                shadow.blur_y.into(),
+                shadow.strength.into(),
+                shadow.quality.into(),
+                shadow.inner.into(),
+                shadow.knockout.into(),
+                shadow.hide_object.into(),
+            ],
+        ),
+        Filter::Glow(glow) => (
+            classes.glowfilter,
+            vec![
+                glow.color.into(),
+                glow.alpha.into(),
+                glow.blur_x.into(),
+                glow.blur_y.into(),
+                glow.strength.into(),
+                glow.quality.into(),
+                glow.inner.into(),
+                glow.knockout.into(),
+            ],
+        ),
+        Filter::Unsupported(_) => return Ok(None),
+    };
+
+    Ok(Some(class.construct(activation, &args)?))
+}
+
+/// Construct `BitmapFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.filters"), "BitmapFilter"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<BitmapFilter instance initializer>", mc),
+        Method::from_builtin(class_init, "<BitmapFilter class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("clone", clone)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
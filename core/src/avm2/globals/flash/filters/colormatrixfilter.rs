@@ -0,0 +1,91 @@
+//! `flash.filters.ColorMatrixFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The matrix that leaves every color unchanged.
+const IDENTITY_MATRIX: [f64; 20] = [
+    1.0, 0.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, 0.0, //
+];
+
+/// The properties set by `ColorMatrixFilter`'s constructor, in argument order.
+pub const CONSTRUCTOR_PROPERTIES: &[&str] = &["matrix"];
+
+/// Implements `flash.filters.ColorMatrixFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        // The filter keeps its own copy of the matrix, like Flash does.
+        let matrix: Vec<Value<'gc>> = match args.get(0) {
+            Some(Value::Object(matrix)) if matrix.as_array_storage().is_some() => matrix
+                .as_array_storage()
+                .unwrap()
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect(),
+            _ => IDENTITY_MATRIX.iter().map(|v| (*v).into()).collect(),
+        };
+        let matrix = ArrayObject::from_storage(activation, ArrayStorage::from_args(&matrix))?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "matrix").into(),
+            matrix.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.filters.ColorMatrixFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorMatrixFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.filters"), "ColorMatrixFilter"),
+        Some(QName::new(Namespace::package("flash.filters"), "BitmapFilter").into()),
+        Method::from_builtin(
+            instance_init,
+            "<ColorMatrixFilter instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<ColorMatrixFilter class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "matrix"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+
+    class
+}
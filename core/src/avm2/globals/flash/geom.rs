@@ -1,4 +1,7 @@
 //! `flash.geom` namespace
 
+pub mod colortransform;
+pub mod matrix;
 pub mod point;
 pub mod rectangle;
+pub mod transform;
@@ -0,0 +1,372 @@
+//! `flash.geom.ColorTransform` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::{Activation, Error, Namespace, Object, QName, TObject, Value};
+use crate::bitmap::color_transform_params::ColorTransformParams;
+use crate::color_transform::ColorTransform;
+use crate::string::AvmString;
+use gc_arena::{GcCell, MutationContext};
+use swf::Fixed8;
+
+/// The names of a `ColorTransform`'s components, in constructor order.
+const COMPONENTS: [&str; 8] = [
+    "redMultiplier",
+    "greenMultiplier",
+    "blueMultiplier",
+    "alphaMultiplier",
+    "redOffset",
+    "greenOffset",
+    "blueOffset",
+    "alphaOffset",
+];
+
+fn get_number<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        activation,
+    )?
+    .coerce_to_number(activation)
+}
+
+fn set_number<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+    value: f64,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        value.into(),
+        activation,
+    )
+}
+
+/// Read the components of a `ColorTransform` object.
+pub fn object_to_color_transform_params<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ColorTransformParams, Error> {
+    Ok(ColorTransformParams {
+        red_multiplier: get_number(object, activation, "redMultiplier")?,
+        green_multiplier: get_number(object, activation, "greenMultiplier")?,
+        blue_multiplier: get_number(object, activation, "blueMultiplier")?,
+        alpha_multiplier: get_number(object, activation, "alphaMultiplier")?,
+        red_offset: get_number(object, activation, "redOffset")?,
+        green_offset: get_number(object, activation, "greenOffset")?,
+        blue_offset: get_number(object, activation, "blueOffset")?,
+        alpha_offset: get_number(object, activation, "alphaOffset")?,
+    })
+}
+
+/// Convert a `ColorTransform` object into the transform applied to display
+/// objects at render time.
+pub fn object_to_color_transform<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ColorTransform, Error> {
+    let params = object_to_color_transform_params(object, activation)?;
+
+    Ok(ColorTransform {
+        r_mult: Fixed8::from_f64(params.red_multiplier),
+        g_mult: Fixed8::from_f64(params.green_multiplier),
+        b_mult: Fixed8::from_f64(params.blue_multiplier),
+        a_mult: Fixed8::from_f64(params.alpha_multiplier),
+        r_add: params.red_offset as i16,
+        g_add: params.green_offset as i16,
+        b_add: params.blue_offset as i16,
+        a_add: params.alpha_offset as i16,
+    })
+}
+
+/// Construct a new `ColorTransform` object from a display object's color
+/// transform.
+pub fn color_transform_to_object<'gc>(
+    color_transform: &ColorTransform,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args = [
+        color_transform.r_mult.to_f64().into(),
+        color_transform.g_mult.to_f64().into(),
+        color_transform.b_mult.to_f64().into(),
+        color_transform.a_mult.to_f64().into(),
+        color_transform.r_add.into(),
+        color_transform.g_add.into(),
+        color_transform.b_add.into(),
+        color_transform.a_add.into(),
+    ];
+    let color_transform_class = activation.context.avm2.classes().colortransform;
+
+    color_transform_class.construct(activation, &args)
+}
+
+/// Implements `flash.geom.ColorTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        for (i, name) in COMPONENTS.into_iter().enumerate() {
+            let value = match args.get(i) {
+                Some(value) => value.coerce_to_number(activation)?,
+                // The first four components are multipliers.
+                None if i < 4 => 1.0,
+                None => 0.0,
+            };
+
+            set_number(this, activation, name, value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.ColorTransform`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `color`'s getter
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut rgb = 0;
+        for name in ["redOffset", "greenOffset", "blueOffset"] {
+            let offset = this
+                .get_property(
+                    this,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_i32(activation)?;
+
+            rgb = (rgb << 8) | offset;
+        }
+
+        return Ok((rgb as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `color`'s setter
+///
+/// This replaces the RGB offsets with the given color and clears the RGB
+/// multipliers, leaving alpha untouched.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let color = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let [b, g, r, _] = color.to_le_bytes();
+
+        set_number(this, activation, "redMultiplier", 0.0)?;
+        set_number(this, activation, "greenMultiplier", 0.0)?;
+        set_number(this, activation, "blueMultiplier", 0.0)?;
+        set_number(this, activation, "redOffset", r.into())?;
+        set_number(this, activation, "greenOffset", g.into())?;
+        set_number(this, activation, "blueOffset", b.into())?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concat`
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let second = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let first = object_to_color_transform_params(this, activation)?;
+        let second = object_to_color_transform_params(second, activation)?;
+
+        // The second transform is applied first, so its offsets are scaled by
+        // our multipliers.
+        let concatenated = [
+            first.red_multiplier * second.red_multiplier,
+            first.green_multiplier * second.green_multiplier,
+            first.blue_multiplier * second.blue_multiplier,
+            first.alpha_multiplier * second.alpha_multiplier,
+            first.red_offset + first.red_multiplier * second.red_offset,
+            first.green_offset + first.green_multiplier * second.green_offset,
+            first.blue_offset + first.blue_multiplier * second.blue_offset,
+            first.alpha_offset + first.alpha_multiplier * second.alpha_offset,
+        ];
+
+        for (name, value) in COMPONENTS.into_iter().zip(concatenated) {
+            set_number(this, activation, name, value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut parts = Vec::with_capacity(COMPONENTS.len());
+        for name in COMPONENTS {
+            let value = this
+                .get_property(
+                    this,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new_utf8(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "ColorTransform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<ColorTransform instance initializer>", mc),
+        Method::from_builtin(class_init, "<ColorTransform class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_NUMBER_SLOTS: &[(&str, Option<f64>)] = &[
+        ("redMultiplier", Some(1.0)),
+        ("greenMultiplier", Some(1.0)),
+        ("blueMultiplier", Some(1.0)),
+        ("alphaMultiplier", Some(1.0)),
+        ("redOffset", Some(0.0)),
+        ("greenOffset", Some(0.0)),
+        ("blueOffset", Some(0.0)),
+        ("alphaOffset", Some(0.0)),
+    ];
+    write.define_public_slot_number_instance_traits(PUBLIC_INSTANCE_NUMBER_SLOTS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("color", Some(color), Some(set_color))];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("concat", concat), ("toString", to_string)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::object::Object;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+
+    fn color_transform<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[f64],
+    ) -> Result<Object<'gc>, Error> {
+        let args: Vec<Value<'gc>> = args.iter().map(|&n| n.into()).collect();
+
+        construct(activation, "flash.geom", "ColorTransform", &args)
+    }
+
+    #[test]
+    fn constructors() {
+        with_avm2(|activation| {
+            let ct = color_transform(activation, &[])?;
+            assert_eq!(traced(activation, ct)?, "(redMultiplier=1, greenMultiplier=1, blueMultiplier=1, alphaMultiplier=1, redOffset=0, greenOffset=0, blueOffset=0, alphaOffset=0)");
+            let ct = color_transform(activation, &[0.5])?;
+            assert_eq!(traced(activation, ct)?, "(redMultiplier=0.5, greenMultiplier=1, blueMultiplier=1, alphaMultiplier=1, redOffset=0, greenOffset=0, blueOffset=0, alphaOffset=0)");
+            let ct = color_transform(activation, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0])?;
+            assert_eq!(traced(activation, ct)?, "(redMultiplier=1, greenMultiplier=2, blueMultiplier=3, alphaMultiplier=4, redOffset=5, greenOffset=6, blueOffset=7, alphaOffset=8)");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn color() {
+        with_avm2(|activation| {
+            let ct = color_transform(activation, &[0.5, 0.5, 0.5, 0.5, 1.0, 2.0, 3.0, 4.0])?;
+            let color = get(activation, ct, "color")?;
+            assert_eq!(traced(activation, color)?, "66051");
+
+            set(activation, ct, "color", 0x336699)?;
+            let expected = [
+                ("color", "3368601"),
+                ("redOffset", "51"),
+                ("redMultiplier", "0"),
+                ("greenOffset", "102"),
+                ("blueOffset", "153"),
+                ("alphaMultiplier", "0.5"),
+                ("alphaOffset", "4"),
+            ];
+            for (name, value) in expected {
+                let actual = get(activation, ct, name)?;
+                assert_eq!(traced(activation, actual)?, value, "ct.{}", name);
+            }
+
+            set(activation, ct, "color", 0xFF123456u32)?;
+            let color = get(activation, ct, "color")?;
+            assert_eq!(traced(activation, color)?, "1193046");
+            assert_eq!(traced(activation, ct)?, "(redMultiplier=0, greenMultiplier=0, blueMultiplier=0, alphaMultiplier=0.5, redOffset=18, greenOffset=52, blueOffset=86, alphaOffset=4)");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn concat() {
+        with_avm2(|activation| {
+            let first = color_transform(activation, &[0.5, 2.0, 1.0, 1.0, 10.0, 20.0, 30.0, 40.0])?;
+            let second =
+                color_transform(activation, &[2.0, 0.5, 1.0, 0.5, 4.0, 8.0, -10.0, 100.0])?;
+
+            call(activation, first, "concat", &[second.into()])?;
+            assert_eq!(traced(activation, first)?, "(redMultiplier=1, greenMultiplier=1, blueMultiplier=1, alphaMultiplier=0.5, redOffset=12, greenOffset=36, blueOffset=20, alphaOffset=140)");
+
+            Ok(())
+        })
+    }
+}
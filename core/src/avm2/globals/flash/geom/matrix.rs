@@ -0,0 +1,741 @@
+//! `flash.geom.Matrix` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::point::create_point;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::{Activation, Error, Namespace, Object, QName, TObject, Value};
+use crate::matrix::Matrix;
+use crate::string::AvmString;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// The components of a `Matrix` object, in the order that the constructor
+/// accepts them.
+#[derive(Clone, Copy, Debug)]
+struct Components {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    tx: f64,
+    ty: f64,
+}
+
+impl Components {
+    const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    /// Build a matrix that scales, rotates, and then translates.
+    fn create_box(scale_x: f64, scale_y: f64, rotation: f64, tx: f64, ty: f64) -> Self {
+        if rotation != 0.0 {
+            let (sin, cos) = rotation.sin_cos();
+
+            Self {
+                a: cos * scale_x,
+                b: sin * scale_y,
+                c: -sin * scale_x,
+                d: cos * scale_y,
+                tx,
+                ty,
+            }
+        } else {
+            Self {
+                a: scale_x,
+                b: 0.0,
+                c: 0.0,
+                d: scale_y,
+                tx,
+                ty,
+            }
+        }
+    }
+
+    /// Apply `other` after this matrix.
+    fn concat(self, other: Self) -> Self {
+        Self {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            tx: self.tx * other.a + self.ty * other.c + other.tx,
+            ty: self.tx * other.b + self.ty * other.d + other.ty,
+        }
+    }
+
+    /// Invert this matrix.
+    ///
+    /// Flash special-cases matrices without skew, which means a zero scale
+    /// produces infinite (or NaN) components. Any other singular matrix is
+    /// reset to the identity matrix.
+    fn invert(self) -> Self {
+        if self.b == 0.0 && self.c == 0.0 {
+            let a = 1.0 / self.a;
+            let d = 1.0 / self.d;
+
+            return Self {
+                a,
+                b: 0.0,
+                c: 0.0,
+                d,
+                tx: -a * self.tx,
+                ty: -d * self.ty,
+            };
+        }
+
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 {
+            return Self::IDENTITY;
+        }
+
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+
+        Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        }
+    }
+
+    fn transform_point(self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    fn delta_transform_point(self, (x, y): (f64, f64)) -> (f64, f64) {
+        (self.a * x + self.c * y, self.b * x + self.d * y)
+    }
+}
+
+fn get_number<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::public(), name).into(),
+        activation,
+    )?
+    .coerce_to_number(activation)
+}
+
+fn components<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Components, Error> {
+    Ok(Components {
+        a: get_number(this, activation, "a")?,
+        b: get_number(this, activation, "b")?,
+        c: get_number(this, activation, "c")?,
+        d: get_number(this, activation, "d")?,
+        tx: get_number(this, activation, "tx")?,
+        ty: get_number(this, activation, "ty")?,
+    })
+}
+
+fn set_components<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Components,
+) -> Result<(), Error> {
+    let Components { a, b, c, d, tx, ty } = value;
+
+    for (name, value) in [
+        ("a", a),
+        ("b", b),
+        ("c", c),
+        ("d", d),
+        ("tx", tx),
+        ("ty", ty),
+    ] {
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), name).into(),
+            value.into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Convert a `Matrix` object into the matrix used by display objects.
+pub fn object_to_matrix<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Matrix, Error> {
+    let Components { a, b, c, d, tx, ty } = components(object, activation)?;
+
+    Ok(Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    })
+}
+
+/// Construct a new `Matrix` object from a display object's matrix.
+pub fn matrix_to_object<'gc>(
+    matrix: &Matrix,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let args = [
+        matrix.a.into(),
+        matrix.b.into(),
+        matrix.c.into(),
+        matrix.d.into(),
+        matrix.tx.to_pixels().into(),
+        matrix.ty.to_pixels().into(),
+    ];
+    let matrix_class = activation.context.avm2.classes().matrix;
+
+    matrix_class.construct(activation, &args)
+}
+
+/// Read the `x` and `y` coordinates of a `Point` argument.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(f64, f64), Error> {
+    let point = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    Ok((
+        get_number(point, activation, "x")?,
+        get_number(point, activation, "y")?,
+    ))
+}
+
+/// Coerce an optional numeric argument, using `default` if it is missing.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        Some(value) => value.coerce_to_number(activation),
+        None => Ok(default),
+    }
+}
+
+/// Implements `flash.geom.Matrix`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let _ = set_to(activation, this, args)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let Components { a, b, c, d, tx, ty } = components(this, activation)?;
+        let matrix_class = activation.context.avm2.classes().matrix;
+        let args = [a.into(), b.into(), c.into(), d.into(), tx.into(), ty.into()];
+
+        return Ok(matrix_class.construct(activation, &args)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `concat`
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let other = components(other, activation)?;
+        let matrix = components(this, activation)?.concat(other);
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `copyFrom`
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?;
+        let matrix = components(other, activation)?;
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `createBox`
+pub fn create_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0, f64::NAN)?;
+        let scale_y = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+
+        let matrix = Components::create_box(scale_x, scale_y, rotation, tx, ty);
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `createGradientBox`
+pub fn create_gradient_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let width = number_arg(activation, args, 0, f64::NAN)?;
+        let height = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+
+        // Gradients are defined in a 1638.4px square centered on the origin.
+        let matrix = Components::create_box(
+            width / 1638.4,
+            height / 1638.4,
+            rotation,
+            tx + width / 2.0,
+            ty + height / 2.0,
+        );
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `deltaTransformPoint`
+pub fn delta_transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let point = point_arg(activation, args)?;
+        let matrix = components(this, activation)?;
+
+        return create_point(activation, matrix.delta_transform_point(point));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `identity`
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_components(this, activation, Components::IDENTITY)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `invert`
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let matrix = components(this, activation)?.invert();
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotate`
+pub fn rotate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let angle = number_arg(activation, args, 0, f64::NAN)?;
+
+        if angle != 0.0 {
+            let rotation = Components::create_box(1.0, 1.0, angle, 0.0, 0.0);
+            let matrix = components(this, activation)?.concat(rotation);
+
+            set_components(this, activation, matrix)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale`
+pub fn scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0, f64::NAN)?;
+        let scale_y = number_arg(activation, args, 1, f64::NAN)?;
+
+        let scale = Components::create_box(scale_x, scale_y, 0.0, 0.0, 0.0);
+        let matrix = components(this, activation)?.concat(scale);
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `setTo`
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let matrix = Components {
+            a: number_arg(activation, args, 0, 1.0)?,
+            b: number_arg(activation, args, 1, 0.0)?,
+            c: number_arg(activation, args, 2, 0.0)?,
+            d: number_arg(activation, args, 3, 1.0)?,
+            tx: number_arg(activation, args, 4, 0.0)?,
+            ty: number_arg(activation, args, 5, 0.0)?,
+        };
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut parts = Vec::with_capacity(6);
+        for name in ["a", "b", "c", "d", "tx", "ty"] {
+            let value = this
+                .get_property(
+                    this,
+                    &QName::new(Namespace::public(), name).into(),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new_utf8(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transformPoint`
+pub fn transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let point = point_arg(activation, args)?;
+        let matrix = components(this, activation)?;
+
+        return create_point(activation, matrix.transform_point(point));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `translate`
+pub fn translate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+
+        let mut matrix = components(this, activation)?;
+        matrix.tx += dx;
+        matrix.ty += dy;
+
+        set_components(this, activation, matrix)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Matrix`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<Matrix instance initializer>", mc),
+        Method::from_builtin(class_init, "<Matrix class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_NUMBER_SLOTS: &[(&str, Option<f64>)] = &[
+        ("a", Some(1.0)),
+        ("b", Some(0.0)),
+        ("c", Some(0.0)),
+        ("d", Some(1.0)),
+        ("tx", Some(0.0)),
+        ("ty", Some(0.0)),
+    ];
+    write.define_public_slot_number_instance_traits(PUBLIC_INSTANCE_NUMBER_SLOTS);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("clone", clone),
+        ("concat", concat),
+        ("copyFrom", copy_from),
+        ("createBox", create_box),
+        ("createGradientBox", create_gradient_box),
+        ("deltaTransformPoint", delta_transform_point),
+        ("identity", identity),
+        ("invert", invert),
+        ("rotate", rotate),
+        ("scale", scale),
+        ("setTo", set_to),
+        ("toString", to_string),
+        ("transformPoint", transform_point),
+        ("translate", translate),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::object::Object;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use std::f64::consts::PI;
+
+    fn matrix<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[f64],
+    ) -> Result<Object<'gc>, Error> {
+        let args: Vec<Value<'gc>> = args.iter().map(|&n| n.into()).collect();
+
+        construct(activation, "flash.geom", "Matrix", &args)
+    }
+
+    fn point<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+        construct(activation, "flash.geom", "Point", &[1.into(), 1.into()])
+    }
+
+    /// Print a matrix with its components rounded to three decimal places.
+    fn rounded<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        m: Object<'gc>,
+    ) -> Result<String, Error> {
+        let mut components = Vec::new();
+        for name in ["a", "b", "c", "d", "tx", "ty"] {
+            let n = get(activation, m, name)?.coerce_to_number(activation)?;
+            // Add zero to turn a rounded negative zero into a positive one.
+            components.push((n * 1000.0).round() / 1000.0 + 0.0);
+        }
+
+        let rounded = matrix(activation, &components)?;
+        traced(activation, rounded)
+    }
+
+    #[test]
+    fn constructors() {
+        with_avm2(|activation| {
+            let m = matrix(activation, &[])?;
+            assert_eq!(traced(activation, m)?, "(a=1, b=0, c=0, d=1, tx=0, ty=0)");
+            let m = matrix(activation, &[2.0, 3.0, 4.0, 5.0, 6.0, 7.0])?;
+            assert_eq!(traced(activation, m)?, "(a=2, b=3, c=4, d=5, tx=6, ty=7)");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn create_box() {
+        with_avm2(|activation| {
+            let boxed = matrix(activation, &[])?;
+
+            call(activation, boxed, "createBox", &[2.into(), 3.into()])?;
+            assert_eq!(
+                traced(activation, boxed)?,
+                "(a=2, b=0, c=0, d=3, tx=0, ty=0)"
+            );
+
+            let args = [2.into(), 3.into(), 0.into(), 10.into(), 20.into()];
+            call(activation, boxed, "createBox", &args)?;
+            assert_eq!(
+                traced(activation, boxed)?,
+                "(a=2, b=0, c=0, d=3, tx=10, ty=20)"
+            );
+
+            let args = [2.into(), 3.into(), (PI / 2.0).into(), 10.into(), 20.into()];
+            call(activation, boxed, "createBox", &args)?;
+            assert_eq!(
+                rounded(activation, boxed)?,
+                "(a=0, b=3, c=-2, d=0, tx=10, ty=20)"
+            );
+
+            let args = [100.into(), 200.into(), 0.into(), 10.into(), 20.into()];
+            call(activation, boxed, "createGradientBox", &args)?;
+            assert_eq!(
+                rounded(activation, boxed)?,
+                "(a=0.061, b=0, c=0, d=0.122, tx=60, ty=120)"
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn translate_scale_and_rotate() {
+        with_avm2(|activation| {
+            let m = matrix(activation, &[])?;
+
+            call(activation, m, "translate", &[5.into(), 10.into()])?;
+            assert_eq!(traced(activation, m)?, "(a=1, b=0, c=0, d=1, tx=5, ty=10)");
+            call(activation, m, "scale", &[2.into(), 4.into()])?;
+            assert_eq!(traced(activation, m)?, "(a=2, b=0, c=0, d=4, tx=10, ty=40)");
+
+            let p = point(activation)?;
+            let transformed = call(activation, m, "transformPoint", &[p.into()])?;
+            assert_eq!(traced(activation, transformed)?, "(x=12, y=44)");
+            let transformed = call(activation, m, "deltaTransformPoint", &[p.into()])?;
+            assert_eq!(traced(activation, transformed)?, "(x=2, y=4)");
+
+            call(activation, m, "invert", &[])?;
+            assert_eq!(
+                traced(activation, m)?,
+                "(a=0.5, b=0, c=0, d=0.25, tx=-5, ty=-10)"
+            );
+            call(activation, m, "identity", &[])?;
+            assert_eq!(traced(activation, m)?, "(a=1, b=0, c=0, d=1, tx=0, ty=0)");
+
+            let rotated = matrix(activation, &[1.0, 0.0, 0.0, 1.0, 1.0, 0.0])?;
+            call(activation, rotated, "rotate", &[PI.into()])?;
+            assert_eq!(
+                rounded(activation, rotated)?,
+                "(a=-1, b=0, c=0, d=-1, tx=-1, ty=0)"
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn singular_invert() {
+        with_avm2(|activation| {
+            let skewed = matrix(activation, &[1.0, 2.0, 2.0, 4.0, 3.0, 4.0])?;
+            call(activation, skewed, "invert", &[])?;
+            assert_eq!(
+                traced(activation, skewed)?,
+                "(a=1, b=0, c=0, d=1, tx=0, ty=0)"
+            );
+
+            let flattened = matrix(activation, &[0.0, 0.0, 0.0, 1.0, 0.0, 5.0])?;
+            call(activation, flattened, "invert", &[])?;
+            assert_eq!(
+                traced(activation, flattened)?,
+                "(a=Infinity, b=0, c=0, d=1, tx=NaN, ty=-5)"
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn concat_is_associative() {
+        with_avm2(|activation| {
+            let a = matrix(activation, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])?;
+            let b = matrix(activation, &[2.0, 0.0, 1.0, 1.0, 3.0, -1.0])?;
+            let c = matrix(activation, &[0.0, 1.0, -1.0, 0.0, 2.0, 2.0])?;
+
+            let ab_c = call(activation, a, "clone", &[])?.coerce_to_object(activation)?;
+            call(activation, ab_c, "concat", &[b.into()])?;
+            call(activation, ab_c, "concat", &[c.into()])?;
+
+            let bc = call(activation, b, "clone", &[])?.coerce_to_object(activation)?;
+            call(activation, bc, "concat", &[c.into()])?;
+            let a_bc = call(activation, a, "clone", &[])?.coerce_to_object(activation)?;
+            call(activation, a_bc, "concat", &[bc.into()])?;
+
+            let expected = "(a=-2, b=4, c=-4, d=10, tx=-3, ty=21)";
+            assert_eq!(traced(activation, ab_c)?, expected);
+            assert_eq!(traced(activation, a_bc)?, expected);
+
+            Ok(())
+        })
+    }
+}
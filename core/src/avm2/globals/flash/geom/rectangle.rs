@@ -397,8 +397,14 @@ pub fn contains<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
-        let x = args.get(0).unwrap().coerce_to_number(activation)?;
-        let y = args.get(1).unwrap().coerce_to_number(activation)?;
+        let x = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        let y = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
 
         return Ok(contains_tuple(&this, activation, (x, y))?.into());
     }
@@ -439,9 +445,22 @@ pub fn contains_rect<'gc>(
             let right = get_prop!(rect, activation, "width")? + x;
             let bottom = get_prop!(rect, activation, "height")? + y;
 
-            return Ok((contains_tuple(&this, activation, (x, y))?
-                && contains_tuple(&this, activation, (right, bottom))?)
-            .into());
+            let this_left = get_prop!(this, activation, "x")?;
+            let this_right = this_left + get_prop!(this, activation, "width")?;
+            let this_top = get_prop!(this, activation, "y")?;
+            let this_bottom = this_top + get_prop!(this, activation, "height")?;
+
+            // The contained rect's far edges may touch ours, but an empty
+            // rect can never contain anything.
+            return Ok((x >= this_left
+                && x < this_right
+                && y >= this_top
+                && y < this_bottom
+                && right > this_left
+                && right <= this_right
+                && bottom > this_top
+                && bottom <= this_bottom)
+                .into());
         }
     }
 
@@ -617,11 +636,11 @@ pub fn intersects<'gc>(
             let this_top = get_prop!(this, activation, "y")?;
             let this_bottom = this_top + get_prop!(this, activation, "height")?;
 
-            if (this_left, this_right, this_bottom, this_top) == (0., 0., 0., 0.) {
+            if this_right <= this_left || this_bottom <= this_top {
                 return Ok(false.into());
             }
 
-            if (other_left, other_right, other_bottom, other_top) == (0., 0., 0., 0.) {
+            if other_right <= other_left || other_bottom <= other_top {
                 return Ok(false.into());
             }
 
@@ -923,3 +942,96 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::object::Object;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+
+    fn rect<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Object<'gc>, Error> {
+        let args = [x.into(), y.into(), width.into(), height.into()];
+
+        construct(activation, "flash.geom", "Rectangle", &args)
+    }
+
+    /// Call a method with another rectangle, and return what `trace` would
+    /// print for the result.
+    fn with_rect<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        method: &'static str,
+        other: Object<'gc>,
+    ) -> Result<String, Error> {
+        let value = call(activation, this, method, &[other.into()])?;
+
+        traced(activation, value)
+    }
+
+    #[test]
+    fn empty_rectangles_intersect_nothing() {
+        with_avm2(|activation| {
+            let r = rect(activation, 0, 0, 10, 10)?;
+            let apart = rect(activation, 20, 20, 5, 5)?;
+            let touching = rect(activation, 10, 0, 5, 5)?;
+            let empty = rect(activation, 5, 5, 0, 0)?;
+            let negative = rect(activation, 5, 5, -2, 5)?;
+            let overlapping = rect(activation, 5, 5, 10, 10)?;
+            let nothing = "(x=0, y=0, w=0, h=0)";
+
+            assert_eq!(with_rect(activation, r, "intersection", apart)?, nothing);
+            let intersection = call(activation, r, "intersection", &[apart.into()])?
+                .coerce_to_object(activation)?;
+            assert_eq!(call(activation, intersection, "isEmpty", &[])?, true.into());
+            assert_eq!(with_rect(activation, r, "intersection", touching)?, nothing);
+            assert_eq!(with_rect(activation, r, "intersection", empty)?, nothing);
+            assert_eq!(with_rect(activation, r, "intersection", negative)?, nothing);
+            assert_eq!(
+                with_rect(activation, r, "intersection", overlapping)?,
+                "(x=5, y=5, w=5, h=5)"
+            );
+
+            assert_eq!(with_rect(activation, r, "intersects", apart)?, "false");
+            assert_eq!(with_rect(activation, r, "intersects", touching)?, "false");
+            assert_eq!(with_rect(activation, r, "intersects", empty)?, "false");
+            assert_eq!(with_rect(activation, r, "intersects", negative)?, "false");
+            assert_eq!(with_rect(activation, negative, "intersects", r)?, "false");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn empty_rectangles_contain_nothing() {
+        with_avm2(|activation| {
+            let r = rect(activation, 0, 0, 10, 10)?;
+            let touching = rect(activation, 10, 0, 5, 5)?;
+            let empty = rect(activation, 5, 5, 0, 0)?;
+            let negative = rect(activation, 5, 5, -2, 5)?;
+            let inside = rect(activation, 2, 2, 3, 3)?;
+
+            let contains = call(activation, empty, "contains", &[5.into(), 5.into()])?;
+            assert_eq!(contains, false.into());
+            let contains = call(activation, negative, "contains", &[4.into(), 6.into()])?;
+            assert_eq!(contains, Value::from(false));
+
+            assert_eq!(
+                with_rect(activation, empty, "containsRect", empty)?,
+                "false"
+            );
+            assert_eq!(with_rect(activation, r, "containsRect", r)?, "true");
+            assert_eq!(with_rect(activation, r, "containsRect", inside)?, "true");
+            assert_eq!(with_rect(activation, r, "containsRect", touching)?, "false");
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,236 @@
+//! `flash.geom.Transform` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::colortransform::{
+    color_transform_to_object, object_to_color_transform,
+};
+use crate::avm2::globals::flash::geom::matrix::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, TObject, Value};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Get the display object that a `Transform` object applies to.
+fn display_object<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Option<DisplayObject<'gc>>, Error> {
+    let display_object = this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "displayObject").into(),
+        activation,
+    )?;
+
+    match display_object {
+        Value::Object(o) => Ok(o.as_display_object()),
+        _ => Ok(None),
+    }
+}
+
+/// Coerce the argument of a setter to an object, throwing if it is null.
+fn non_null_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    name: &str,
+) -> Result<Object<'gc>, Error> {
+    match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => Err(format!(
+            "TypeError: Error #2007: Parameter {} must be non-null.",
+            name
+        )
+        .into()),
+        value => value.coerce_to_object(activation),
+    }
+}
+
+/// Implements `flash.geom.Transform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let display_object = non_null_arg(activation, args, "displayObject")?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "displayObject").into(),
+            display_object.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Transform`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `colorTransform`'s getter
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(dobj) = display_object(this, activation)? {
+            let color_transform = *dobj.base().color_transform();
+
+            return Ok(color_transform_to_object(&color_transform, activation)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `colorTransform`'s setter
+pub fn set_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = non_null_arg(activation, args, "colorTransform")?;
+
+        if let Some(dobj) = display_object(this, activation)? {
+            let color_transform = object_to_color_transform(value, activation)?;
+
+            dobj.set_color_transform(activation.context.gc_context, &color_transform);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix`'s getter
+pub fn matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(dobj) = display_object(this, activation)? {
+            let matrix = *dobj.base().matrix();
+
+            return Ok(matrix_to_object(&matrix, activation)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `matrix`'s setter
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = non_null_arg(activation, args, "matrix")?;
+
+        if let Some(dobj) = display_object(this, activation)? {
+            let matrix = object_to_matrix(value, activation)?;
+
+            dobj.set_matrix(activation.context.gc_context, &matrix);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Transform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Transform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<Transform instance initializer>", mc),
+        Method::from_builtin(class_init, "<Transform class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        (
+            "colorTransform",
+            Some(color_transform),
+            Some(set_color_transform),
+        ),
+        ("matrix", Some(matrix), Some(set_matrix)),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    // Slot for the display object this transform applies to.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "displayObject"),
+        QName::new(Namespace::package("flash.display"), "DisplayObject").into(),
+        None,
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+
+    #[test]
+    fn display_object_transform() {
+        with_avm2(|activation| {
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            let transform = get(activation, sprite, "transform")?.coerce_to_object(activation)?;
+
+            let color_transform = get(activation, transform, "colorTransform")?;
+            assert_eq!(traced(activation, color_transform)?, "(redMultiplier=1, greenMultiplier=1, blueMultiplier=1, alphaMultiplier=1, redOffset=0, greenOffset=0, blueOffset=0, alphaOffset=0)");
+
+            let args = [0.5, 1.0, 1.0, 1.0, 10.0, 0.0, 0.0, 0.0].map(Value::from);
+            let color_transform = construct(activation, "flash.geom", "ColorTransform", &args)?;
+            set(activation, transform, "colorTransform", color_transform)?;
+            let color_transform = get(activation, transform, "colorTransform")?;
+            let expected_color_transform = "(redMultiplier=0.5, greenMultiplier=1, blueMultiplier=1, alphaMultiplier=1, redOffset=10, greenOffset=0, blueOffset=0, alphaOffset=0)";
+            assert_eq!(
+                traced(activation, color_transform)?,
+                expected_color_transform
+            );
+
+            let args = [2.0, 0.0, 0.0, 2.0, 10.0, 20.0].map(Value::from);
+            let matrix = construct(activation, "flash.geom", "Matrix", &args)?;
+            set(activation, transform, "matrix", matrix)?;
+            let matrix = get(activation, transform, "matrix")?;
+            let expected_matrix = "(a=2, b=0, c=0, d=2, tx=10, ty=20)";
+            assert_eq!(traced(activation, matrix)?, expected_matrix);
+            assert_eq!(get(activation, sprite, "x")?, 10.into());
+            assert_eq!(get(activation, sprite, "scaleX")?, 2.into());
+
+            let other = construct(activation, "flash.display", "Sprite", &[])?;
+            set(activation, other, "transform", transform)?;
+            let other_transform =
+                get(activation, other, "transform")?.coerce_to_object(activation)?;
+            let matrix = get(activation, other_transform, "matrix")?;
+            assert_eq!(traced(activation, matrix)?, expected_matrix);
+            let color_transform = get(activation, other_transform, "colorTransform")?;
+            assert_eq!(
+                traced(activation, color_transform)?,
+                expected_color_transform
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -1,5 +1,7 @@
 //! `flash.media` namespace
 
+pub mod camera;
+pub mod microphone;
 pub mod sound;
 pub mod soundchannel;
 pub mod soundmixer;
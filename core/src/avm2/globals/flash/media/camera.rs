@@ -0,0 +1,117 @@
+//! `flash.media.Camera` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.Camera`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Camera class cannot be instantiated.".into())
+}
+
+/// Implements `flash.media.Camera`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.isSupported`
+///
+/// Ruffle has no video capture support, so no camera can ever be used.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Camera.names`
+pub fn names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(ArrayObject::empty(activation)?.into())
+}
+
+/// Implements `Camera.getCamera`
+///
+/// This returns `null`, as a host without any cameras does.
+pub fn get_camera<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Construct `Camera`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "Camera"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<Camera instance initializer>", mc),
+        Method::from_builtin(class_init, "<Camera class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    const PUBLIC_CLASS_PROPERTIES: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] =
+        &[
+            ("isSupported", Some(is_supported), None),
+            ("names", Some(names), None),
+        ];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_PROPERTIES);
+
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[("getCamera", get_camera)];
+    write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+
+    #[test]
+    fn no_cameras_are_available() {
+        with_avm2(|activation| {
+            let camera = class(activation, "flash.media", "Camera")?;
+
+            let supported = get(activation, camera, "isSupported")?;
+            assert_eq!(traced(activation, supported)?, "false");
+            let names = get(activation, camera, "names")?.coerce_to_object(activation)?;
+            let count = get(activation, names, "length")?;
+            assert_eq!(traced(activation, count)?, "0");
+
+            let found = call(activation, camera, "getCamera", &[])?;
+            assert_eq!(traced(activation, found)?, "null");
+            let found = call(activation, camera, "getCamera", &["0".into()])?;
+            assert_eq!(traced(activation, found)?, "null");
+
+            let error = construct(activation, "flash.media", "Camera", &[]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #2012: Camera class cannot be instantiated."
+            );
+
+            Ok(())
+        })
+    }
+}
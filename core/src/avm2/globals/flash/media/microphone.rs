@@ -0,0 +1,137 @@
+//! `flash.media.Microphone` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.Microphone`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Microphone class cannot be instantiated.".into())
+}
+
+/// Implements `flash.media.Microphone`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.isSupported`
+///
+/// Ruffle has no audio capture support, so no microphone can ever be used.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Microphone.names`
+pub fn names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(ArrayObject::empty(activation)?.into())
+}
+
+/// Implements `Microphone.getMicrophone`
+///
+/// This returns `null`, as a host without any microphones does.
+pub fn get_microphone<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `Microphone.getEnhancedMicrophone`
+///
+/// This returns `null`, as a host without any microphones does.
+pub fn get_enhanced_microphone<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Construct `Microphone`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "Microphone"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<Microphone instance initializer>", mc),
+        Method::from_builtin(class_init, "<Microphone class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    const PUBLIC_CLASS_PROPERTIES: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] =
+        &[
+            ("isSupported", Some(is_supported), None),
+            ("names", Some(names), None),
+        ];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_PROPERTIES);
+
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("getMicrophone", get_microphone),
+        ("getEnhancedMicrophone", get_enhanced_microphone),
+    ];
+    write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    #[test]
+    fn no_microphones_are_available() {
+        with_avm2(|activation| {
+            let microphone = class(activation, "flash.media", "Microphone")?;
+
+            let supported = get(activation, microphone, "isSupported")?;
+            assert_eq!(traced(activation, supported)?, "false");
+            let names = get(activation, microphone, "names")?.coerce_to_object(activation)?;
+            let count = get(activation, names, "length")?;
+            assert_eq!(traced(activation, count)?, "0");
+
+            let lookups: [(&'static str, &[Value<'_>]); 3] = [
+                ("getMicrophone", &[]),
+                ("getMicrophone", &[0.into()]),
+                ("getEnhancedMicrophone", &[]),
+            ];
+            for (method, args) in lookups {
+                let found = call(activation, microphone, method, args)?;
+                assert_eq!(traced(activation, found)?, "null");
+            }
+
+            let error = construct(activation, "flash.media", "Microphone", &[]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #2012: Microphone class cannot be instantiated."
+            );
+
+            Ok(())
+        })
+    }
+}
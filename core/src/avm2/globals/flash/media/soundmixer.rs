@@ -4,7 +4,7 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::SoundTransform;
@@ -116,13 +116,83 @@ pub fn are_sounds_inaccessible<'gc>(
     Err("SoundMixer.areSoundsInaccessible is a stub".into())
 }
 
-/// Stub `SoundMixer.computeSpectrum`
+/// The number of values `SoundMixer.computeSpectrum` writes per channel.
+const SPECTRUM_VALUES_PER_CHANNEL: usize = 256;
+
+/// Implements `SoundMixer.computeSpectrum`
+///
+/// The output holds 256 values for the left channel followed by 256 for the
+/// right channel, taken from the most recently mixed audio. In FFT mode these
+/// are frequency magnitudes, otherwise they are the raw waveform.
 pub fn compute_spectrum<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("SoundMixer.computeSpectrum is a stub".into())
+    let output_array = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Object(output_array) => *output_array,
+        _ => return Err("TypeError: Error #2007: Parameter outputArray must be non-null.".into()),
+    };
+    let fft_mode = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Bool(false))
+        .coerce_to_boolean();
+    let stretch_factor = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Integer(0))
+        .coerce_to_i32(activation)?;
+
+    // A stretch factor of N takes every (N + 1)th frame of the 44.1KHz mix.
+    let step = stretch_factor.max(0) as usize + 1;
+    let history = activation.context.audio.get_sample_history();
+    let window_len = if fft_mode {
+        SPECTRUM_VALUES_PER_CHANNEL * 2
+    } else {
+        SPECTRUM_VALUES_PER_CHANNEL
+    };
+    let start = history.len().saturating_sub(window_len * step);
+
+    let mut values = Vec::with_capacity(SPECTRUM_VALUES_PER_CHANNEL * 2);
+    for channel in 0..2 {
+        let window: Vec<f32> = (0..window_len)
+            .map(|i| history[(start + i * step).min(history.len() - 1)][channel])
+            .collect();
+
+        if fft_mode {
+            values.extend(spectrum_magnitudes(&window));
+        } else {
+            values.extend(window);
+        }
+    }
+
+    let mut bytearray = output_array
+        .as_bytearray_mut(activation.context.gc_context)
+        .ok_or("TypeError: Error #1034: Type Coercion failed: outputArray is not a ByteArray.")?;
+    bytearray.clear();
+    for value in values {
+        bytearray.write_float(value)?;
+    }
+    bytearray.set_position(0);
+
+    Ok(Value::Undefined)
+}
+
+/// Compute the magnitude of the lower half of the frequency bins of a window
+/// of samples, scaled so that a full-scale sine wave peaks at 1.
+fn spectrum_magnitudes(window: &[f32]) -> impl Iterator<Item = f32> + '_ {
+    let len = window.len() as f32;
+    (0..window.len() / 2).map(move |bin| {
+        let (mut re, mut im) = (0.0f32, 0.0f32);
+        for (i, sample) in window.iter().enumerate() {
+            let angle = -2.0 * std::f32::consts::PI * bin as f32 * i as f32 / len;
+            re += sample * angle.cos();
+            im += sample * angle.sin();
+        }
+
+        (re * re + im * im).sqrt() * 2.0 / len
+    })
 }
 
 /// Construct `SoundMixer`'s class.
@@ -159,3 +229,77 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// The length and position of a ByteArray, and the number of floats
+    /// after its position along with how many of them are zero.
+    fn describe<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        bytes: Object<'gc>,
+    ) -> Result<String, Error> {
+        let length = get(activation, bytes, "length")?;
+        let position = get(activation, bytes, "position")?;
+        let mut samples = 0;
+        let mut silent = 0;
+        while get(activation, bytes, "bytesAvailable")?.coerce_to_u32(activation)? > 0 {
+            if call(activation, bytes, "readFloat", &[])? == 0.into() {
+                silent += 1;
+            }
+            samples += 1;
+        }
+
+        Ok(format!(
+            "{} {} {} {}",
+            traced(activation, length)?,
+            traced(activation, position)?,
+            samples,
+            silent
+        ))
+    }
+
+    #[test]
+    fn spectrum_replaces_the_output() {
+        with_avm2(|activation| {
+            let bytes = construct(activation, "flash.utils", "ByteArray", &[])?;
+            call(activation, bytes, "writeUTF", &["existing contents".into()])?;
+
+            // The null audio backend has no mixed output, so every value is
+            // silent.
+            let args: [&[Value<'_>]; 3] = [
+                &[bytes.into()],
+                &[bytes.into(), true.into()],
+                &[bytes.into(), false.into(), 1.into()],
+            ];
+            for args in args {
+                compute_spectrum(activation, None, args)?;
+                assert_eq!(describe(activation, bytes)?, "2048 0 512 512");
+            }
+
+            let error = compute_spectrum(activation, None, &[Value::Null]).unwrap_err();
+            assert!(error.to_string().starts_with("TypeError: Error #2007"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn spectrum_magnitudes_peak_at_the_frequency() {
+        // A full-scale sine wave with 8 periods in the window.
+        let window: Vec<f32> = (0..512)
+            .map(|i| (2.0 * std::f32::consts::PI * 8.0 * i as f32 / 512.0).sin())
+            .collect();
+        let magnitudes: Vec<f32> = spectrum_magnitudes(&window).collect();
+
+        assert_eq!(magnitudes.len(), 256);
+        assert!((magnitudes[8] - 1.0).abs() < 0.001);
+        for (bin, magnitude) in magnitudes.iter().enumerate() {
+            if bin != 8 {
+                assert!(magnitude.abs() < 0.001, "bin {} is {}", bin, magnitude);
+            }
+        }
+    }
+}
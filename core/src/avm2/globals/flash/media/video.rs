@@ -2,21 +2,46 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{TDisplayObject, Video};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmType;
 use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
 
 /// Implements `flash.media.Video`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let width = args
+                .get(0)
+                .cloned()
+                .unwrap_or_else(|| 320.into())
+                .coerce_to_i32(activation)?;
+            let height = args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| 240.into())
+                .coerce_to_i32(activation)?;
+
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let library = activation.context.library.library_for_movie_mut(movie);
+            library.force_avm_type(AvmType::Avm2);
+
+            let new_do = Video::new_with_avm2(&mut activation.context, this, width, height);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
     }
 
     Ok(Value::Undefined)
@@ -31,6 +56,162 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Video.attachNetStream`
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let net_stream = match args.get(0).unwrap_or(&Value::Null) {
+            Value::Object(net_stream) => Some(*net_stream),
+            _ => None,
+        };
+
+        video.attach_net_stream(activation.context.gc_context, net_stream);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.attachCamera`
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // `Camera.getCamera` never yields a camera, so the only thing that can be
+    // attached here is `null`, which detaches the current source.
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        video.attach_net_stream(activation.context.gc_context, None);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.clear`
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        video.clear(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.smoothing`'s getter
+pub fn smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.smoothing().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.smoothing`'s setter
+pub fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let smoothing = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+        video.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.deblocking`'s getter
+pub fn deblocking<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.deblocking().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.deblocking`'s setter
+pub fn set_deblocking<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        let deblocking = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+        video.set_deblocking(activation.context.gc_context, deblocking);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.videoWidth`
+pub fn video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.video_size().0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.videoHeight`
+pub fn video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(video) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        return Ok(video.video_size().1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Video`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -45,5 +226,126 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.set_attributes(ClassAttributes::SEALED);
 
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("deblocking", Some(deblocking), Some(set_deblocking)),
+        ("smoothing", Some(smoothing), Some(set_smoothing)),
+        ("videoWidth", Some(video_width), None),
+        ("videoHeight", Some(video_height), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("attachCamera", attach_camera),
+        ("attachNetStream", attach_net_stream),
+        ("clear", clear),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// The given properties of an object, as `trace` would print them
+    /// together.
+    fn describe<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        names: &[&'static str],
+    ) -> Result<String, Error> {
+        let mut fields = Vec::new();
+        for name in names {
+            let value = get(activation, object, name)?;
+            fields.push(traced(activation, value)?);
+        }
+
+        Ok(fields.join(" "))
+    }
+
+    #[test]
+    fn constructed_videos() {
+        with_avm2(|activation| {
+            let video = construct(activation, "flash.media", "Video", &[])?;
+            assert_eq!(
+                describe(activation, video, &["width", "height"])?,
+                "320 240"
+            );
+            assert_eq!(
+                describe(activation, video, &["videoWidth", "videoHeight"])?,
+                "0 0"
+            );
+            assert_eq!(
+                describe(activation, video, &["smoothing", "deblocking"])?,
+                "false 0"
+            );
+
+            let small = construct(
+                activation,
+                "flash.media",
+                "Video",
+                &[160.into(), 120.into()],
+            )?;
+            assert_eq!(
+                describe(activation, small, &["width", "height"])?,
+                "160 120"
+            );
+            let sprite = construct(activation, "flash.display", "Sprite", &[])?;
+            call(activation, sprite, "addChild", &[small.into()])?;
+            assert_eq!(get(activation, small, "parent")?, sprite.into());
+
+            set(activation, small, "smoothing", true)?;
+            set(activation, small, "deblocking", 2)?;
+            assert_eq!(
+                describe(activation, small, &["smoothing", "deblocking"])?,
+                "true 2"
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn attaching_sources_leaves_the_size_unchanged() {
+        with_avm2(|activation| {
+            let small = construct(
+                activation,
+                "flash.media",
+                "Video",
+                &[160.into(), 120.into()],
+            )?;
+            let connection = construct(activation, "flash.net", "NetConnection", &[])?;
+            call(activation, connection, "connect", &[Value::Null])?;
+            let stream = construct(activation, "flash.net", "NetStream", &[connection.into()])?;
+            let camera = class(activation, "flash.media", "Camera")?;
+            let camera = call(activation, camera, "getCamera", &[])?;
+
+            let attachments = [
+                ("attachNetStream", stream.into()),
+                ("attachNetStream", Value::Null),
+                ("attachCamera", camera),
+            ];
+            for (method, source) in attachments {
+                call(activation, small, method, &[source])?;
+                assert_eq!(
+                    describe(activation, small, &["videoWidth", "videoHeight"])?,
+                    "0 0"
+                );
+            }
+
+            call(activation, small, "clear", &[])?;
+            assert_eq!(
+                describe(activation, small, &["width", "height"])?,
+                "160 120"
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -1,4 +1,60 @@
 //! `flash.net` namespace
 
+pub mod localconnection;
+pub mod netconnection;
+pub mod netstream;
 pub mod object_encoding;
 pub mod sharedobject;
+pub mod socket;
+pub mod xmlsocket;
+
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `flash.net.registerClassAlias`
+pub fn register_class_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => {
+            return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into())
+        }
+        alias => alias.coerce_to_string(activation)?,
+    };
+    let class = match args.get(1).unwrap_or(&Value::Undefined) {
+        Value::Object(class) => class.as_class_object(),
+        _ => None,
+    };
+    let class = class.ok_or_else(|| {
+        Error::from("TypeError: Error #2007: Parameter classObject must be non-null.")
+    })?;
+
+    activation.avm2().class_aliases.insert(alias, class);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.getClassByAlias`
+pub fn get_class_by_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => {
+            return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into())
+        }
+        alias => alias.coerce_to_string(activation)?,
+    };
+
+    match activation.avm2().class_aliases.get(&alias) {
+        Some(class) => Ok((*class).into()),
+        None => Err(format!(
+            "ReferenceError: Error #1014: Class {} could not be found.",
+            alias
+        )
+        .into()),
+    }
+}
@@ -0,0 +1,400 @@
+//! `flash.net.LocalConnection` builtin/prototype
+
+use crate::avm2::amf::serialize_value;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::TObject;
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use crate::string::AvmString;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::{GcCell, MutationContext};
+
+fn internal_name<'gc>(name: &'static str) -> QName<'gc> {
+    QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name)
+}
+
+/// Get the name this connection is listening on, if it is connected.
+fn connection_name<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Option<AvmString<'gc>>, Error> {
+    match this.get_property(this, &internal_name("connectionName").into(), activation)? {
+        Value::Undefined | Value::Null => Ok(None),
+        name => Ok(Some(name.coerce_to_string(activation)?)),
+    }
+}
+
+fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &internal_name("client").into(),
+            this.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `client`'s getter
+fn client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return this.get_property(this, &internal_name("client").into(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `client`'s setter
+fn set_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let client = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err("TypeError: Error #2007: Parameter client must be non-null.".into())
+            }
+            value => value.coerce_to_object(activation)?,
+        };
+
+        this.set_property(
+            this,
+            &internal_name("client").into(),
+            client.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `domain`'s getter
+fn domain<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok("localhost".into())
+}
+
+/// Implements `LocalConnection.connect`
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if connection_name(this, activation)?.is_some()
+            || !activation
+                .context
+                .avm2
+                .local_connections
+                .connect(name, this)
+        {
+            return Err("ArgumentError: Error #2082: Connect failed because the object is already connected.".into());
+        }
+
+        this.set_property(
+            this,
+            &internal_name("connectionName").into(),
+            name.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name =
+            match connection_name(this, activation)? {
+                Some(name) => name,
+                None => return Err(
+                    "ArgumentError: Error #2083: Close failed because the object is not connected."
+                        .into(),
+                ),
+            };
+
+        activation.context.avm2.local_connections.close(name);
+
+        this.set_property(
+            this,
+            &internal_name("connectionName").into(),
+            Value::Null,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.send`
+///
+/// The call is delivered to the receiving connection on the next frame.
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let connection_name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+        if connection_name.is_empty() {
+            return Err(
+                "ArgumentError: Error #2085: Parameter connectionName must be non-empty string."
+                    .into(),
+            );
+        }
+
+        let method_name = args
+            .get(1)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+        if method_name.is_empty() {
+            return Err(
+                "ArgumentError: Error #2085: Parameter methodName must be non-empty string.".into(),
+            );
+        }
+
+        let mut arguments = Vec::with_capacity(args.len().saturating_sub(2));
+        for arg in args.iter().skip(2) {
+            arguments.push(serialize_value(activation, *arg)?.unwrap_or(AmfValue::Undefined));
+        }
+
+        activation.context.avm2.local_connections.send(
+            this,
+            connection_name,
+            method_name,
+            arguments,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowDomain` and
+/// `LocalConnection.allowInsecureDomain`
+///
+/// All connections are local to this player, so there are no domains to
+/// restrict.
+fn allow_domain<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `LocalConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "LocalConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<LocalConnection instance initializer>", mc),
+        Method::from_builtin(class_init, "<LocalConnection class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("client", Some(client), Some(set_client)),
+        ("domain", Some(domain), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("connect", connect),
+        ("close", close),
+        ("send", send),
+        ("allowDomain", allow_domain),
+        ("allowInsecureDomain", allow_domain),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connectionName"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::Avm2;
+
+    fn greet<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut fields = Vec::new();
+        for arg in &args[..3] {
+            fields.push(traced(activation, arg.clone())?);
+        }
+        let object = args[3].coerce_to_object(activation)?;
+        let a = get(activation, object, "a")?;
+        fields.push(traced(activation, a)?);
+        log(format!("greet: {}", fields.join(" ")));
+
+        Ok(Value::Undefined)
+    }
+
+    fn cyclic<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let object = args[0].coerce_to_object(activation)?;
+        let name = get(activation, object, "name")?;
+        let name = traced(activation, name)?;
+        let has_self = call(activation, object, "hasOwnProperty", &["self".into()])?;
+        let has_self = traced(activation, has_self)?;
+        log(format!("cyclic: {} {}", name, has_self));
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_status<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let code = get(activation, event, "code")?;
+        let code = traced(activation, code)?;
+        let level = get(activation, event, "level")?;
+        let level = traced(activation, level)?;
+        log(format!("status: {} {}", code, level));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn messages_reach_the_client_on_the_next_frame() {
+        with_avm2(|activation| {
+            let receiver = construct(activation, "flash.net", "LocalConnection", &[])?;
+            let client = construct(activation, "", "Object", &[])?;
+            let method = function(activation, greet)?;
+            set(activation, client, "greet", method)?;
+            let method = function(activation, cyclic)?;
+            set(activation, client, "cyclic", method)?;
+            set(activation, receiver, "client", client)?;
+
+            let sender = construct(activation, "flash.net", "LocalConnection", &[])?;
+            let listener = function(activation, on_status)?;
+            let args = ["status".into(), listener.into()];
+            call(activation, sender, "addEventListener", &args)?;
+
+            let domain = get(activation, receiver, "domain")?;
+            assert_eq!(traced(activation, domain)?, "localhost");
+            call(activation, receiver, "connect", &["_test".into()])?;
+
+            // Connection names are case-insensitive.
+            let list = array(activation, &[1.into(), 2.into()])?;
+            let object = construct(activation, "", "Object", &[])?;
+            set(activation, object, "a", true)?;
+            let args = [
+                "_TEST".into(),
+                "greet".into(),
+                "hello".into(),
+                42.into(),
+                list.into(),
+                object.into(),
+            ];
+            call(activation, sender, "send", &args)?;
+
+            let object = construct(activation, "", "Object", &[])?;
+            set(activation, object, "name", "cyclic")?;
+            set(activation, object, "self", object)?;
+            let args = ["_test".into(), "cyclic".into(), object.into()];
+            call(activation, sender, "send", &args)?;
+
+            let args = ["_nobody".into(), "greet".into()];
+            call(activation, sender, "send", &args)?;
+            assert!(take_log().is_empty());
+
+            Avm2::deliver_local_connection_messages(&mut activation.context);
+            assert_eq!(
+                take_log(),
+                [
+                    "greet: hello 42 1,2 true",
+                    "status: null status",
+                    "cyclic: cyclic true",
+                    "status: null status",
+                    "status: null error",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn connecting_to_a_taken_name_throws() {
+        with_avm2(|activation| {
+            let first = construct(activation, "flash.net", "LocalConnection", &[])?;
+            let second = construct(activation, "flash.net", "LocalConnection", &[])?;
+
+            call(activation, first, "connect", &["_test".into()])?;
+            call(activation, first, "close", &[])?;
+            call(activation, second, "connect", &["_test".into()])?;
+
+            let error = call(activation, first, "connect", &["_Test".into()])
+                .expect_err("the name is already taken");
+            assert!(error.to_string().starts_with("ArgumentError: Error #2082"));
+
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,217 @@
+//! `flash.net.NetConnection` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::TObject;
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use gc_arena::{GcCell, MutationContext};
+
+/// Dispatch a `netStatus` event to `target`, with an `info` object holding
+/// the given `code` and `level`.
+///
+/// This is shared with `NetStream`.
+pub fn dispatch_net_status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    code: &'static str,
+    level: &'static str,
+) -> Result<(), Error> {
+    let object_class = activation.avm2().classes().object;
+    let mut info = object_class.construct(activation, &[])?;
+    info.set_property(
+        info,
+        &QName::dynamic_name("code").into(),
+        code.into(),
+        activation,
+    )?;
+    info.set_property(
+        info,
+        &QName::dynamic_name("level").into(),
+        level.into(),
+        activation,
+    )?;
+
+    let class = activation.avm2().classes().netstatusevent;
+    let event = class.construct(
+        activation,
+        &["netStatus".into(), false.into(), false.into(), info.into()],
+    )?;
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Determine if a `NetConnection` is connected.
+pub fn is_connected<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<bool, Error> {
+    Ok(this
+        .get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected").into(),
+            activation,
+        )?
+        .coerce_to_boolean())
+}
+
+/// Implements `flash.net.NetConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Only `null` connections, which are used for progressive playback of local
+/// or HTTP media, are supported. Connecting to a media server fails.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                this.set_property(
+                    this,
+                    &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected").into(),
+                    true.into(),
+                    activation,
+                )?;
+                this.set_property(
+                    this,
+                    &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "uri").into(),
+                    "null".into(),
+                    activation,
+                )?;
+
+                dispatch_net_status(activation, this, "NetConnection.Connect.Success", "status")?;
+            }
+            command => {
+                let command = command.coerce_to_string(activation)?;
+                log::warn!(
+                    "NetConnection.connect: connecting to {} is not yet implemented",
+                    command
+                );
+
+                dispatch_net_status(activation, this, "NetConnection.Connect.Failed", "error")?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if is_connected(this, activation)? {
+            this.set_property(
+                this,
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected").into(),
+                false.into(),
+                activation,
+            )?;
+
+            dispatch_net_status(activation, this, "NetConnection.Connect.Closed", "status")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connected`'s getter.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(is_connected(this, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.uri`'s getter.
+pub fn uri<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "uri").into(),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<NetConnection instance initializer>", mc),
+        Method::from_builtin(class_init, "<NetConnection class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("connect", connect), ("close", close)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("connected", Some(connected), None),
+        ("uri", Some(uri), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "uri"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    class
+}
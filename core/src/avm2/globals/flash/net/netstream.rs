@@ -0,0 +1,569 @@
+//! `flash.net.NetStream` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::net::netconnection::{dispatch_net_status, is_connected};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::net_stream::{NetStreams, Playback};
+use crate::avm2::object::TObject;
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use crate::backend::navigator::RequestOptions;
+use gc_arena::{GcCell, MutationContext};
+
+/// Get one of the internal slots of a `NetStream`.
+fn get_slot<'gc>(
+    this: Object<'gc>,
+    name: &'static str,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name).into(),
+        activation,
+    )
+}
+
+/// Set one of the internal slots of a `NetStream`.
+fn set_slot<'gc>(
+    mut this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name).into(),
+        value,
+        activation,
+    )
+}
+
+/// Get the playback state of a `NetStream`.
+fn playback<'a, 'gc>(
+    activation: &'a mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<&'a mut Playback, Error> {
+    activation
+        .context
+        .avm2
+        .net_streams
+        .playback_mut(this)
+        .ok_or_else(|| "NetStream has no stream".into())
+}
+
+/// Implements `flash.net.NetStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let connection = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Object(connection) => *connection,
+            _ => {
+                return Err("TypeError: Error #2007: Parameter connection must be non-null.".into())
+            }
+        };
+        if !is_connected(connection, activation)? {
+            return Err(
+                "ArgumentError: Error #2126: NetConnection object must be connected.".into(),
+            );
+        }
+
+        activation.context.avm2.net_streams.register(this);
+        set_slot(this, "client", this.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.play`.
+///
+/// Playing `null` starts data generation mode, where FLV data is provided
+/// with `appendBytes`. Otherwise, the FLV file at the given URL is loaded
+/// and played.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                NetStreams::play_appended(activation, this)?;
+            }
+            url => {
+                let url = url.coerce_to_string(activation)?;
+                activation.context.avm2.net_streams.start_loading(this);
+
+                let fetch = activation
+                    .context
+                    .navigator
+                    .fetch(&url.to_utf8_lossy(), RequestOptions::get());
+                let process = activation.context.load_manager.load_net_stream(
+                    activation.context.player.clone().unwrap(),
+                    this,
+                    fetch,
+                );
+
+                activation.context.navigator.spawn_future(process);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.pause`.
+pub fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if playback(activation, this)?.set_paused(true) {
+            dispatch_net_status(activation, this, "NetStream.Pause.Notify", "status")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.resume`.
+pub fn resume<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if playback(activation, this)?.set_paused(false) {
+            dispatch_net_status(activation, this, "NetStream.Unpause.Notify", "status")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.togglePause`.
+pub fn toggle_pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if playback(activation, this)?.is_paused() {
+            resume(activation, Some(this), args)?;
+        } else {
+            pause(activation, Some(this), args)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.seek`.
+///
+/// Playback continues from the keyframe at or before the requested offset.
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let offset = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation)?;
+        let offset = if offset.is_nan() {
+            0.0
+        } else {
+            offset.max(0.0)
+        };
+
+        playback(activation, this)?.seek(offset);
+        dispatch_net_status(activation, this, "NetStream.Seek.Notify", "status")?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        playback(activation, this)?.close();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.appendBytes`.
+///
+/// The data is parsed as an FLV file. Video frames are played as they
+/// arrive, and script data tags such as `onMetaData` are passed on to the
+/// `client`.
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Object(bytes) => bytes
+                .as_bytearray()
+                .map(|bytes| bytes.bytes().to_vec())
+                .unwrap_or_default(),
+            _ => return Err("TypeError: Error #2007: Parameter bytes must be non-null.".into()),
+        };
+
+        NetStreams::append(activation, this, &bytes)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.appendBytesAction`.
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let action = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if &action == b"resetBegin" || &action == b"resetSeek" {
+            playback(activation, this)?.reset_data(&action == b"resetBegin");
+        } else if &action == b"endSequence" {
+            // If frames are still waiting to be played, `NetStream.Play.Stop`
+            // is dispatched once they have been.
+            let playback = playback(activation, this)?;
+            playback.end_of_data();
+            if playback.finish() {
+                dispatch_net_status(activation, this, "NetStream.Play.Stop", "status")?;
+            }
+        } else {
+            return Err(
+                "ArgumentError: Error #2008: Parameter action must be one of the accepted values."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.time`'s getter.
+pub fn time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(playback(activation, this)?.time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bytesLoaded`'s getter.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(playback(activation, this)?.bytes_loaded().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bytesTotal`'s getter.
+pub fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(playback(activation, this)?.bytes_total().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.client`'s getter.
+pub fn client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(this, "client", activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.client`'s setter.
+pub fn set_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let client = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Object(client) => *client,
+            _ => return Err("TypeError: Error #2004: One of the parameters is invalid.".into()),
+        };
+
+        set_slot(this, "client", client.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<NetStream instance initializer>", mc),
+        Method::from_builtin(class_init, "<NetStream class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("play", play),
+        ("pause", pause),
+        ("resume", resume),
+        ("togglePause", toggle_pause),
+        ("seek", seek),
+        ("close", close),
+        ("appendBytes", append_bytes),
+        ("appendBytesAction", append_bytes_action),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("time", Some(time), None),
+        ("bytesLoaded", Some(bytes_loaded), None),
+        ("bytesTotal", Some(bytes_total), None),
+        ("client", Some(client), Some(set_client)),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    fn on_status<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let info = get(activation, event, "info")?.coerce_to_object(activation)?;
+        let code = get(activation, info, "code")?;
+        let level = get(activation, info, "level")?;
+        log(format!(
+            "netStatus: {} ({})",
+            traced(activation, code)?,
+            traced(activation, level)?
+        ));
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_meta_data<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let info = args[0].coerce_to_object(activation)?;
+        let duration = get(activation, info, "duration")?;
+        log(format!("duration: {}", traced(activation, duration)?));
+
+        Ok(Value::Undefined)
+    }
+
+    /// An FLV file holding only an `onMetaData` script data tag, with a
+    /// duration of 2.5 seconds.
+    fn meta_data_flv() -> Vec<u8> {
+        let mut script = vec![2];
+        script.extend_from_slice(b"\x00\x0aonMetaData");
+        script.extend_from_slice(&[8, 0, 0, 0, 1]);
+        script.extend_from_slice(b"\x00\x08duration");
+        script.push(0);
+        script.extend_from_slice(&2.5f64.to_be_bytes());
+        script.extend_from_slice(&[0, 0, 9]);
+
+        let mut flv = b"FLV\x01\x05\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        flv.push(18);
+        flv.extend_from_slice(&(script.len() as u32).to_be_bytes()[1..]);
+        flv.extend_from_slice(&[0; 7]);
+        flv.extend_from_slice(&script);
+        flv.extend_from_slice(&(11 + script.len() as u32).to_be_bytes());
+        flv
+    }
+
+    fn byte_array<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        data: &[u8],
+    ) -> Result<Object<'gc>, Error> {
+        let bytes = construct(activation, "flash.utils", "ByteArray", &[])?;
+        bytes
+            .as_bytearray_mut(activation.context.gc_context)
+            .unwrap()
+            .write_bytes(data)?;
+
+        Ok(bytes)
+    }
+
+    fn traced_property<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+    ) -> Result<String, Error> {
+        let value = get(activation, object, name)?;
+        traced(activation, value)
+    }
+
+    #[test]
+    fn appended_bytes_are_played() {
+        with_avm2(|activation| {
+            let listener = function(activation, on_status)?;
+            let connection = construct(activation, "flash.net", "NetConnection", &[])?;
+            call(
+                activation,
+                connection,
+                "addEventListener",
+                &["netStatus".into(), listener.into()],
+            )?;
+            assert_eq!(
+                traced_property(activation, connection, "connected")?,
+                "false"
+            );
+            call(activation, connection, "connect", &[Value::Null])?;
+            assert_eq!(
+                take_log(),
+                ["netStatus: NetConnection.Connect.Success (status)"]
+            );
+            assert_eq!(
+                traced_property(activation, connection, "connected")?,
+                "true"
+            );
+            assert_eq!(traced_property(activation, connection, "uri")?, "null");
+
+            let stream = construct(activation, "flash.net", "NetStream", &[connection.into()])?;
+            call(
+                activation,
+                stream,
+                "addEventListener",
+                &["netStatus".into(), listener.into()],
+            )?;
+            assert_eq!(get(activation, stream, "client")?, stream.into());
+            let client = construct(activation, "", "Object", &[])?;
+            let handler = function(activation, on_meta_data)?;
+            set(activation, client, "onMetaData", handler)?;
+            set(activation, stream, "client", client)?;
+
+            call(activation, stream, "play", &[Value::Null])?;
+            assert_eq!(take_log(), ["netStatus: NetStream.Play.Start (status)"]);
+
+            // The script data tag is only passed on once all of it arrived.
+            let flv = meta_data_flv();
+            let first_half = byte_array(activation, &flv[..30])?;
+            call(activation, stream, "appendBytes", &[first_half.into()])?;
+            assert!(take_log().is_empty());
+            assert_eq!(traced_property(activation, stream, "bytesLoaded")?, "30");
+            assert_eq!(traced_property(activation, stream, "bytesTotal")?, "30");
+            let second_half = byte_array(activation, &flv[30..])?;
+            call(activation, stream, "appendBytes", &[second_half.into()])?;
+            assert_eq!(take_log(), ["duration: 2.5"]);
+            assert_eq!(traced_property(activation, stream, "bytesLoaded")?, "68");
+            assert_eq!(traced_property(activation, stream, "bytesTotal")?, "68");
+
+            for method in ["pause", "resume", "togglePause"] {
+                call(activation, stream, method, &[])?;
+            }
+            call(activation, stream, "seek", &[1.5.into()])?;
+            assert_eq!(traced_property(activation, stream, "time")?, "1.5");
+            call(
+                activation,
+                stream,
+                "appendBytesAction",
+                &["endSequence".into()],
+            )?;
+            assert_eq!(
+                take_log(),
+                [
+                    "netStatus: NetStream.Pause.Notify (status)",
+                    "netStatus: NetStream.Unpause.Notify (status)",
+                    "netStatus: NetStream.Pause.Notify (status)",
+                    "netStatus: NetStream.Seek.Notify (status)",
+                    "netStatus: NetStream.Play.Stop (status)",
+                ]
+            );
+
+            call(activation, stream, "close", &[])?;
+            assert!(take_log().is_empty());
+            assert_eq!(traced_property(activation, stream, "time")?, "0");
+            assert_eq!(traced_property(activation, stream, "bytesLoaded")?, "0");
+
+            call(activation, connection, "close", &[])?;
+            assert_eq!(
+                take_log(),
+                ["netStatus: NetConnection.Connect.Closed (status)"]
+            );
+            assert_eq!(
+                traced_property(activation, connection, "connected")?,
+                "false"
+            );
+
+            let error =
+                construct(activation, "flash.net", "NetStream", &[connection.into()]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ArgumentError: Error #2126: NetConnection object must be connected."
+            );
+
+            Ok(())
+        })
+    }
+}
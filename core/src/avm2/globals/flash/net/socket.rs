@@ -0,0 +1,613 @@
+//! `flash.net.Socket` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::utils::bytearray;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::{bytearray_allocator, TObject};
+use crate::avm2::socket::{finish_read, SocketKind, Sockets};
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use gc_arena::{GcCell, MutationContext};
+use std::time::Duration;
+use url::Url;
+
+/// The error thrown when using a socket that isn't connected.
+const INVALID_SOCKET: &str = "IOError: Error #2002: Operation attempted on invalid socket.";
+
+/// The shortest connection timeout allowed, in milliseconds.
+const MIN_TIMEOUT: u32 = 250;
+
+/// Get the `timeout` of a `Socket` or `XMLSocket`.
+fn get_timeout<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<u32, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "timeout").into(),
+        activation,
+    )?
+    .coerce_to_u32(activation)
+}
+
+/// Connect a `Socket` or `XMLSocket` to the host and port given as
+/// arguments.
+///
+/// A `null` host refers to the host the movie was loaded from.
+pub fn connect_socket<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    kind: SocketKind,
+    args: &[Value<'gc>],
+) -> Result<(), Error> {
+    let host = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => activation
+            .context
+            .swf
+            .url()
+            .and_then(|url| Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_owned))
+            .unwrap_or_else(|| "localhost".to_owned()),
+        host => host.coerce_to_string(activation)?.to_string(),
+    };
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let port = match u16::try_from(port) {
+        Ok(port) if port > 0 => port,
+        _ => {
+            return Err("SecurityError: Error #2003: Invalid socket port number specified.".into())
+        }
+    };
+    let timeout = Duration::from_millis(get_timeout(this, activation)?.into());
+
+    Sockets::connect(&mut activation.context, this, kind, host, port, timeout);
+
+    Ok(())
+}
+
+/// Implements `connected`'s getter for `Socket` and `XMLSocket`.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation.context.avm2.sockets.is_connected(this).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `timeout`'s getter for `Socket` and `XMLSocket`.
+pub fn timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(get_timeout(this, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `timeout`'s setter for `Socket` and `XMLSocket`.
+pub fn set_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let timeout = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_u32(activation)?
+            .max(MIN_TIMEOUT);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "timeout").into(),
+            timeout.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `close` for `Socket` and `XMLSocket`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !activation.context.avm2.sockets.close(this) {
+            return Err(INVALID_SOCKET.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define the `timeout` slot used by `Socket` and `XMLSocket`.
+pub fn define_timeout_slot(class: &mut Class<'_>) {
+    class.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "timeout"),
+        QName::new(Namespace::public(), "uint").into(),
+        Some(20000.into()),
+    ));
+}
+
+/// Get the buffer that a `Socket`'s writes are collected in until flushed.
+///
+/// The socket object itself holds the received data.
+fn output_buffer<'gc>(
+    this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output").into(),
+        activation,
+    )?
+    .coerce_to_object(activation)
+}
+
+/// Apply a `ByteArray` write method to a connected socket's output buffer.
+fn write_output<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    method: NativeMethodImpl,
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !activation.context.avm2.sockets.is_connected(this) {
+            return Err(INVALID_SOCKET.into());
+        }
+
+        let output = output_buffer(this, activation)?;
+        return method(activation, Some(output), args);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define `Socket` write methods in terms of `ByteArray`'s.
+macro_rules! output_methods {
+    ($($name:ident => $method:path),* $(,)?) => {
+        $(
+            fn $name<'gc>(
+                activation: &mut Activation<'_, 'gc, '_>,
+                this: Option<Object<'gc>>,
+                args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error> {
+                write_output(activation, this, args, $method)
+            }
+        )*
+    };
+}
+
+output_methods! {
+    write_boolean => bytearray::write_boolean,
+    write_byte => bytearray::write_byte,
+    write_bytes => bytearray::write_bytes,
+    write_double => bytearray::write_double,
+    write_float => bytearray::write_float,
+    write_int => bytearray::write_int,
+    write_multibyte => bytearray::write_multibyte,
+    write_short => bytearray::write_short,
+    write_unsigned_int => bytearray::write_unsigned_int,
+    write_utf => bytearray::write_utf,
+    write_utf_bytes => bytearray::write_utf_bytes,
+}
+
+/// Apply a `ByteArray` read method to a socket's received data.
+///
+/// Reads that need more data than has arrived throw an `EOFError` without
+/// consuming anything, so they can be retried once the rest arrives.
+fn read_input<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    method: NativeMethodImpl,
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let start = match this.as_bytearray() {
+            Some(input) => input.position(),
+            None => return Ok(Value::Undefined),
+        };
+
+        let result = method(activation, Some(this), args);
+        if let Some(input) = this.as_bytearray() {
+            return finish_read(&input, start, result);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define `Socket` read methods in terms of `ByteArray`'s.
+macro_rules! input_methods {
+    ($($name:ident => $method:path),* $(,)?) => {
+        $(
+            fn $name<'gc>(
+                activation: &mut Activation<'_, 'gc, '_>,
+                this: Option<Object<'gc>>,
+                args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error> {
+                read_input(activation, this, args, $method)
+            }
+        )*
+    };
+}
+
+input_methods! {
+    read_boolean => bytearray::read_boolean,
+    read_byte => bytearray::read_byte,
+    read_bytes => bytearray::read_bytes,
+    read_double => bytearray::read_double,
+    read_float => bytearray::read_float,
+    read_int => bytearray::read_int,
+    read_multibyte => bytearray::read_multibyte,
+    read_object => bytearray::read_object,
+    read_short => bytearray::read_short,
+    read_unsigned_byte => bytearray::read_unsigned_byte,
+    read_unsigned_int => bytearray::read_unsigned_int,
+    read_unsigned_short => bytearray::read_unsigned_short,
+    read_utf => bytearray::read_utf,
+    read_utf_bytes => bytearray::read_utf_bytes,
+}
+
+/// Implements `flash.net.Socket`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let bytearray_class = activation.context.avm2.classes().bytearray;
+        let output = bytearray_class.construct(activation, &[])?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output").into(),
+            output.into(),
+            activation,
+        )?;
+
+        if let Some(Value::Null) | Some(Value::Undefined) | None = args.get(0) {
+            return Ok(Value::Undefined);
+        }
+
+        connect_socket(activation, this, SocketKind::Binary, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.Socket`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        connect_socket(activation, this, SocketKind::Binary, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.flush`
+///
+/// This sends everything written since the last flush.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !activation.context.avm2.sockets.is_connected(this) {
+            return Err(INVALID_SOCKET.into());
+        }
+
+        let output = output_buffer(this, activation)?;
+        if let Some(mut output) = output.as_bytearray_mut(activation.context.gc_context) {
+            let data = output.bytes().to_vec();
+            output.clear();
+
+            activation.context.avm2.sockets.send(this, data);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bytesPending`'s getter
+pub fn bytes_pending<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let output = output_buffer(this, activation)?;
+        return bytearray::length(activation, Some(output), &[]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `endian`'s setter
+///
+/// Both received data and pending writes use the same byte order.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        bytearray::set_endian(activation, Some(this), args)?;
+
+        let output = output_buffer(this, activation)?;
+        bytearray::set_endian(activation, Some(output), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `objectEncoding`'s setter
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        bytearray::set_object_encoding(activation, Some(this), args)?;
+
+        let output = output_buffer(this, activation)?;
+        bytearray::set_object_encoding(activation, Some(output), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Socket`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "Socket"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<Socket instance initializer>", mc),
+        Method::from_builtin(class_init, "<Socket class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+    // Received data is read straight out of the socket's own storage.
+    write.set_instance_allocator(bytearray_allocator);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("connect", connect),
+        ("close", close),
+        ("flush", flush),
+        ("readBoolean", read_boolean),
+        ("readByte", read_byte),
+        ("readBytes", read_bytes),
+        ("readDouble", read_double),
+        ("readFloat", read_float),
+        ("readInt", read_int),
+        ("readMultiByte", read_multibyte),
+        ("readObject", read_object),
+        ("readShort", read_short),
+        ("readUnsignedByte", read_unsigned_byte),
+        ("readUnsignedInt", read_unsigned_int),
+        ("readUnsignedShort", read_unsigned_short),
+        ("readUTF", read_utf),
+        ("readUTFBytes", read_utf_bytes),
+        ("writeBoolean", write_boolean),
+        ("writeByte", write_byte),
+        ("writeBytes", write_bytes),
+        ("writeDouble", write_double),
+        ("writeFloat", write_float),
+        ("writeInt", write_int),
+        ("writeMultiByte", write_multibyte),
+        ("writeShort", write_short),
+        ("writeUnsignedInt", write_unsigned_int),
+        ("writeUTF", write_utf),
+        ("writeUTFBytes", write_utf_bytes),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("bytesAvailable", Some(bytearray::bytes_available), None),
+        ("bytesPending", Some(bytes_pending), None),
+        ("connected", Some(connected), None),
+        ("endian", Some(bytearray::endian), Some(set_endian)),
+        (
+            "objectEncoding",
+            Some(bytearray::object_encoding),
+            Some(set_object_encoding),
+        ),
+        ("timeout", Some(timeout), Some(set_timeout)),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output"),
+        QName::new(Namespace::package("flash.utils"), "ByteArray").into(),
+        None,
+    ));
+    define_timeout_slot(&mut write);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    fn on_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let mut fields = Vec::new();
+        for name in ["type", "errorID", "text"] {
+            let value = get(activation, event, name)?;
+            fields.push(traced(activation, value)?);
+        }
+        log(fields.join(" "));
+
+        Ok(Value::Undefined)
+    }
+
+    fn traced_property<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+    ) -> Result<String, Error> {
+        let value = get(activation, object, name)?;
+        traced(activation, value)
+    }
+
+    #[test]
+    fn properties_of_an_unconnected_socket() {
+        with_avm2(|activation| {
+            let socket = construct(activation, "flash.net", "Socket", &[])?;
+            assert_eq!(traced_property(activation, socket, "connected")?, "false");
+            assert_eq!(traced_property(activation, socket, "bytesAvailable")?, "0");
+            assert_eq!(traced_property(activation, socket, "bytesPending")?, "0");
+            assert_eq!(traced_property(activation, socket, "endian")?, "bigEndian");
+            assert_eq!(traced_property(activation, socket, "timeout")?, "20000");
+
+            // Timeouts are at least 250ms.
+            set(activation, socket, "timeout", 100)?;
+            assert_eq!(traced_property(activation, socket, "timeout")?, "250");
+            set(activation, socket, "endian", "littleEndian")?;
+            assert_eq!(
+                traced_property(activation, socket, "endian")?,
+                "littleEndian"
+            );
+
+            let xml_socket = construct(activation, "flash.net", "XMLSocket", &[])?;
+            assert_eq!(
+                traced_property(activation, xml_socket, "connected")?,
+                "false"
+            );
+            assert_eq!(traced_property(activation, xml_socket, "timeout")?, "20000");
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn connections_without_a_policy_fail_with_a_security_error() {
+        with_avm2(|activation| {
+            let listener = function(activation, on_event)?;
+            let socket = construct(activation, "flash.net", "Socket", &[])?;
+            let xml_socket = construct(activation, "flash.net", "XMLSocket", &[])?;
+            for target in [socket, xml_socket] {
+                for event_type in ["connect", "ioError", "securityError"] {
+                    let args = [event_type.into(), listener.into()];
+                    call(activation, target, "addEventListener", &args)?;
+                }
+            }
+
+            call(
+                activation,
+                socket,
+                "connect",
+                &["localhost".into(), 8080.into()],
+            )?;
+            assert_eq!(traced_property(activation, socket, "connected")?, "false");
+            call(
+                activation,
+                xml_socket,
+                "connect",
+                &["localhost".into(), 8081.into()],
+            )?;
+            assert_eq!(
+                traced_property(activation, xml_socket, "connected")?,
+                "false"
+            );
+
+            // Without a master policy on port 843, the policy is requested
+            // from the destination port before giving up.
+            Sockets::update_sockets(&mut activation.context);
+            assert!(take_log().is_empty());
+            Sockets::update_sockets(&mut activation.context);
+            assert_eq!(
+                take_log(),
+                [
+                    "securityError 2048 Error #2048: Security sandbox violation: cannot load data from localhost:8080.",
+                    "securityError 2048 Error #2048: Security sandbox violation: cannot load data from localhost:8081.",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn closing_twice_throws() {
+        with_avm2(|activation| {
+            let socket = construct(activation, "flash.net", "Socket", &[])?;
+            call(
+                activation,
+                socket,
+                "connect",
+                &["localhost".into(), 8080.into()],
+            )?;
+            call(activation, socket, "close", &[])?;
+
+            let error = call(activation, socket, "close", &[]).expect_err("the socket is closed");
+            assert_eq!(error.to_string(), INVALID_SOCKET);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn short_reads_throw_without_consuming_data() {
+        with_avm2(|activation| {
+            let socket = construct(activation, "flash.net", "Socket", &[])?;
+            if let Some(mut input) = socket.as_bytearray_mut(activation.context.gc_context) {
+                input.write_at(&[0, 5, b'h', b'e'], 0)?;
+            }
+
+            let error = call(activation, socket, "readUTF", &[]).expect_err("the data is short");
+            assert!(error.to_string().starts_with("EOFError: Error #2030"));
+            assert_eq!(traced_property(activation, socket, "bytesAvailable")?, "4");
+
+            if let Some(mut input) = socket.as_bytearray_mut(activation.context.gc_context) {
+                input.write_at(b"llo", 4)?;
+            }
+            let text = call(activation, socket, "readUTF", &[])?;
+            assert_eq!(traced(activation, text)?, "hello");
+            assert_eq!(traced_property(activation, socket, "bytesAvailable")?, "0");
+
+            Ok(())
+        });
+    }
+}
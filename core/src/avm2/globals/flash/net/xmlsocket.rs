@@ -0,0 +1,109 @@
+//! `flash.net.XMLSocket` builtin/prototype
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::net::socket::{
+    close, connect_socket, connected, define_timeout_slot, set_timeout, timeout,
+};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::socket::SocketKind;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.XMLSocket`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if let Some(Value::Null) | Some(Value::Undefined) | None = args.get(0) {
+            return Ok(Value::Undefined);
+        }
+
+        connect_socket(activation, this, SocketKind::Xml, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.XMLSocket`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        connect_socket(activation, this, SocketKind::Xml, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.send`
+///
+/// The value is sent as a string, terminated by a zero byte.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let message = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let mut data = message.to_utf8_lossy().into_owned().into_bytes();
+        data.push(0);
+
+        if !activation.context.avm2.sockets.send(this, data) {
+            return Err("IOError: Error #2002: Operation attempted on invalid socket.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `XMLSocket`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "XMLSocket"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<XMLSocket instance initializer>", mc),
+        Method::from_builtin(class_init, "<XMLSocket class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("connect", connect), ("close", close), ("send", send)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("connected", Some(connected), None),
+        ("timeout", Some(timeout), Some(set_timeout)),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    define_timeout_slot(&mut write);
+
+    class
+}
@@ -0,0 +1,198 @@
+//! `flash.sampler` namespace
+//!
+//! Ruffle has no sampling profiler, so everything here except `getSize`
+//! reports that nothing has been sampled.
+
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error, Object};
+use crate::string::AvmString;
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let size = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) | Value::Unsigned(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(s) => {
+            let char_size = if s.is_wide() { 2 } else { 1 };
+            std::mem::size_of::<AvmString<'gc>>() + s.len() * char_size
+        }
+        Value::Object(o) => {
+            let mut size = o.base().approximate_size();
+
+            if let Some(array) = o.as_array_storage() {
+                size += array.length() * std::mem::size_of::<Option<Value<'gc>>>();
+            }
+            if let Some(vector) = o.as_vector_storage() {
+                size += vector.length() * std::mem::size_of::<Value<'gc>>();
+            }
+            if let Some(bytearray) = o.as_bytearray() {
+                size += bytearray.len();
+            }
+
+            size
+        }
+    };
+
+    Ok((size as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples` and `getMemberNames`, which always
+/// yield an empty iterable.
+pub fn empty_iterable<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(ArrayObject::empty(activation)?.into())
+}
+
+/// Implements `flash.sampler.getSampleCount` and the invocation counters,
+/// which always report zero.
+pub fn zero<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(0.0.into())
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `flash.sampler.getLexicalScopes`, `getMasterString` and
+/// `getSavedThis`, none of which have anything to report.
+pub fn null<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements the sampler's control functions, such as `startSampling`,
+/// which have nothing to control.
+pub fn no_op<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::test_utils::*;
+
+    /// Call one of the functions of the `flash.sampler` package.
+    fn sampler_call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let domain = activation.avm2().global_domain();
+        let name = QName::new(Namespace::package("flash.sampler"), name);
+        let function = domain
+            .get_defined_value(activation, name)?
+            .coerce_to_object(activation)?;
+
+        function.call(None, args, activation)
+    }
+
+    fn size_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: impl Into<Value<'gc>>,
+    ) -> Result<f64, Error> {
+        sampler_call(activation, "getSize", &[value.into()])?.coerce_to_number(activation)
+    }
+
+    /// A dynamic object with a property for each of the given names.
+    fn object_with<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        names: &[&'static str],
+    ) -> Result<Object<'gc>, Error> {
+        let object = construct(activation, "", "Object", &[])?;
+        for name in names {
+            set(activation, object, name, 1)?;
+        }
+
+        Ok(object)
+    }
+
+    #[test]
+    fn sizes_depend_on_the_shape_of_objects() {
+        with_avm2(|activation| {
+            let small = object_with(activation, &["a"])?;
+            let same_shape = object_with(activation, &["b"])?;
+            let larger = object_with(activation, &["a", "b", "c", "d"])?;
+
+            assert!(size_of(activation, small)? > 0.0);
+            assert_eq!(size_of(activation, small)?, size_of(activation, small)?);
+            assert_eq!(
+                size_of(activation, small)?,
+                size_of(activation, same_shape)?
+            );
+            assert!(size_of(activation, larger)? > size_of(activation, small)?);
+
+            let values = [1.into(), 2.into(), 3.into(), 4.into()];
+            let full = array(activation, &values)?;
+            let empty = array(activation, &[])?;
+            assert!(size_of(activation, full)? > size_of(activation, empty)?);
+
+            assert_eq!(size_of(activation, Value::Null)?, 0.0);
+            assert_eq!(size_of(activation, 1)?, 4.0);
+            assert_eq!(size_of(activation, 1.5)?, 8.0);
+            assert!(size_of(activation, "longer")? > size_of(activation, "short")?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn nothing_is_sampled() {
+        with_avm2(|activation| {
+            for control in [
+                "startSampling",
+                "pauseSampling",
+                "stopSampling",
+                "clearSamples",
+            ] {
+                sampler_call(activation, control, &[])?;
+            }
+            assert_eq!(sampler_call(activation, "getSampleCount", &[])?, 0.into());
+            let samples = sampler_call(activation, "getSamples", &[])?;
+            let samples = samples.coerce_to_object(activation)?;
+            assert_eq!(get(activation, samples, "length")?, 0.into());
+
+            let this = construct(activation, "", "Object", &[])?;
+            let args = [this.into(), Value::Null];
+            assert_eq!(
+                sampler_call(activation, "getInvocationCount", &args)?,
+                0.into()
+            );
+            assert_eq!(
+                sampler_call(activation, "isGetterSetter", &args)?,
+                false.into()
+            );
+            assert_eq!(
+                sampler_call(activation, "getSavedThis", &[this.into()])?,
+                Value::Null
+            );
+
+            Ok(())
+        })
+    }
+}
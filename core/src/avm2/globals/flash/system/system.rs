@@ -28,7 +28,22 @@ pub fn class_init<'gc>(
 }
 
 /// Implements `System.gc`
+///
+/// Like the release Flash Player, this only does something in debug builds.
 pub fn gc<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if cfg!(feature = "avm_debug") {
+        *activation.context.needs_gc = true;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `System.pauseForGCIfCollectionImminent`
+pub fn pause_for_gc_if_collection_imminent<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
@@ -36,6 +51,46 @@ pub fn gc<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `System.totalMemory`
+pub fn total_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let total = activation.context.gc_total_allocated.min(u32::MAX as usize);
+
+    Ok((total as u32).into())
+}
+
+/// Implements `System.totalMemoryNumber` and `System.privateMemory`
+///
+/// We only track memory owned by the garbage collector, so both of these
+/// report the same value.
+pub fn total_memory_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((activation.context.gc_total_allocated as f64).into())
+}
+
+/// Implements `System.freeMemory`
+///
+/// Memory allocated since the last collection has not been proven live yet,
+/// so it is reported as free, like memory Flash has reserved but not used.
+pub fn free_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let free = activation
+        .context
+        .gc_total_allocated
+        .saturating_sub(activation.context.gc_remembered_size);
+
+    Ok((free as f64).into())
+}
+
 /// Construct `System`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -48,8 +103,51 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     let mut write = class.write(mc);
 
-    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[("gc", gc)];
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("gc", gc),
+        (
+            "pauseForGCIfCollectionImminent",
+            pause_for_gc_if_collection_imminent,
+        ),
+    ];
     write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
 
+    const PUBLIC_CLASS_TRAITS: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] = &[
+        ("freeMemory", Some(free_memory), None),
+        ("privateMemory", Some(total_memory_number), None),
+        ("totalMemory", Some(total_memory), None),
+        ("totalMemoryNumber", Some(total_memory_number), None),
+    ];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_TRAITS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+
+    #[test]
+    fn memory_properties() {
+        with_avm2(|activation| {
+            let system = class(activation, "flash.system", "System")?;
+            let total = GC_TOTAL_ALLOCATED as f64;
+
+            for name in ["totalMemory", "totalMemoryNumber", "privateMemory"] {
+                let memory = get(activation, system, name)?.coerce_to_number(activation)?;
+                assert_eq!(memory, total, "System.{}", name);
+            }
+
+            let free = get(activation, system, "freeMemory")?.coerce_to_number(activation)?;
+            assert_eq!(free, (GC_TOTAL_ALLOCATED - GC_REMEMBERED_SIZE) as f64);
+
+            call(activation, system, "gc", &[])?;
+            call(activation, system, "pauseForGCIfCollectionImminent", &[])?;
+
+            let memory = get(activation, system, "totalMemory")?.coerce_to_number(activation)?;
+            assert!(memory > 0.0);
+
+            Ok(())
+        })
+    }
+}
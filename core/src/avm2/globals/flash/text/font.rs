@@ -1,10 +1,11 @@
 //! `flash.text.Font` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::{Class, ClassAttributes};
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::character::Character;
@@ -153,22 +154,77 @@ pub fn has_glyphs<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Stub `Font.enumerateFonts`
+/// Implements `Font.enumerateFonts`
+///
+/// Only fonts registered with `registerFont` are listed; there is no way to
+/// enumerate the fonts installed on the system.
 pub fn enumerate_fonts<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Font.enumerateFonts is a stub".into())
+    let font_classes = activation.context.avm2.registered_fonts.clone();
+    let mut fonts = Vec::with_capacity(font_classes.len());
+    for font_class in font_classes {
+        fonts.push(font_class.construct(activation, &[])?.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&fonts))?.into())
 }
 
-/// Stub `Font.registerFont`
+/// Implements `Font.registerFont`
 pub fn register_font<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Font.registerFont is a stub".into())
+    let font_class = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?
+        .as_class_object();
+    let font = font_class
+        .and_then(|class| {
+            activation
+                .context
+                .library
+                .avm2_class_registry()
+                .class_symbol(class)
+        })
+        .and_then(|(movie, character_id)| {
+            match activation
+                .context
+                .library
+                .library_for_movie_mut(movie)
+                .character_by_id(character_id)
+            {
+                Some(Character::Font(font)) => Some(*font),
+                _ => None,
+            }
+        });
+
+    match (font_class, font) {
+        (Some(font_class), Some(font)) => {
+            activation.context.library.register_font(font);
+
+            let registered_fonts = &mut activation.context.avm2.registered_fonts;
+            if !registered_fonts
+                .iter()
+                .any(|class| Object::ptr_eq(*class, font_class))
+            {
+                registered_fonts.push(font_class);
+            }
+        }
+        _ => {
+            return Err(
+                "ArgumentError: Error #1508: The value specified for argument font is invalid."
+                    .into(),
+            )
+        }
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Construct `Font`'s class.
@@ -207,3 +263,101 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::ClassObject;
+    use crate::avm2::test_utils::*;
+    use crate::player::DEVICE_FONT_TAG;
+    use swf::{CharacterId, SwfStr};
+
+    /// Define a `Font` subclass linked to a copy of the device font, like a
+    /// font embedded in a movie and exported for ActionScript.
+    fn embedded_font<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &'static str,
+        id: CharacterId,
+        font_name: &'static str,
+        is_bold: bool,
+    ) -> Result<ClassObject<'gc>, Error> {
+        let mut reader = swf::read::Reader::new(DEVICE_FONT_TAG, 8);
+        let mut tag = reader.read_define_font_2(3)?;
+        tag.id = id;
+        tag.name = SwfStr::from_utf8_str(font_name);
+        tag.is_bold = is_bold;
+        let font = crate::font::Font::from_swf_tag(
+            activation.context.gc_context,
+            activation.context.renderer,
+            &tag,
+            reader.encoding(),
+        )?;
+
+        let movie = activation.context.swf.clone();
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .register_character(id, Character::Font(font));
+        let font_class = class(activation, "flash.text", "Font")?;
+        let class = define_class(activation, class_name, Some(font_class), &[], &[])?;
+        activation
+            .context
+            .library
+            .avm2_class_registry_mut()
+            .set_class_symbol(class, movie, id);
+
+        Ok(class)
+    }
+
+    /// Call `Font.enumerateFonts`.
+    fn enumerate<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let font_class = class(activation, "flash.text", "Font")?;
+
+        call(activation, font_class, "enumerateFonts", args)?.coerce_to_object(activation)
+    }
+
+    #[test]
+    fn registered_fonts_are_enumerated() {
+        with_avm2(|activation| {
+            let fonts = enumerate(activation, &[])?;
+            assert_eq!(get(activation, fonts, "length")?, 0.into());
+
+            let regular = embedded_font(activation, "NotoSansRegular", 1, "Noto Sans", false)?;
+            let font_class = class(activation, "flash.text", "Font")?;
+            call(activation, font_class, "registerFont", &[regular.into()])?;
+            let fonts = enumerate(activation, &[])?;
+            assert_eq!(get(activation, fonts, "length")?, 1.into());
+            let font = get(activation, fonts, "0")?.coerce_to_object(activation)?;
+            assert!(font.is_of_type(regular, activation)?);
+            assert_eq!(get(activation, font, "fontName")?, "Noto Sans".into());
+            assert_eq!(get(activation, font, "fontStyle")?, "regular".into());
+            assert_eq!(get(activation, font, "fontType")?, "embedded".into());
+
+            // Registering a font twice lists it once.
+            call(activation, font_class, "registerFont", &[regular.into()])?;
+            let fonts = enumerate(activation, &[])?;
+            assert_eq!(get(activation, fonts, "length")?, 1.into());
+
+            let bold = embedded_font(activation, "NotoSansBold", 2, "Noto Sans Bold", true)?;
+            call(activation, font_class, "registerFont", &[bold.into()])?;
+            let fonts = enumerate(activation, &[true.into()])?;
+            assert_eq!(get(activation, fonts, "length")?, 2.into());
+            let font = get(activation, fonts, "1")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, font, "fontName")?, "Noto Sans Bold".into());
+            assert_eq!(get(activation, font, "fontStyle")?, "bold".into());
+
+            let plain = define_class(activation, "NotAFont", None, &[], &[])?;
+            let error = call(activation, font_class, "registerFont", &[plain.into()]);
+            assert!(error
+                .unwrap_err()
+                .to_string()
+                .starts_with("ArgumentError: Error #1508"));
+
+            Ok(())
+        })
+    }
+}
@@ -206,6 +206,21 @@ pub fn set_border_color<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn caret_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.selection().map(|s| s.to()).unwrap_or(0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn default_text_format<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -369,6 +384,72 @@ pub fn length<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_max_chars(max_chars, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll_h<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.maxhscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.maxscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn multiline<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -405,6 +486,152 @@ pub fn set_multiline<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.restrict() {
+            Some(restrict) => AvmString::new(activation.context.gc_context, restrict).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                this.set_restrict(None, &mut activation.context);
+            }
+            value => {
+                let restrict = value.coerce_to_string(activation)?;
+                this.set_restrict(Some(restrict.as_wstr()), &mut activation.context);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll_h<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll_h<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let hscroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)? as f64;
+
+        this.set_hscroll(
+            hscroll.clamp(0.0, this.maxhscroll()),
+            &mut activation.context,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll_v<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let scroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        this.set_scroll(scroll, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn selection_begin_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.selection().map(|s| s.start()).unwrap_or(0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn selection_end_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.selection().map(|s| s.end()).unwrap_or(0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn selectable<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -654,14 +881,8 @@ pub fn append_text<'gc>(
             .cloned()
             .unwrap_or(Value::Undefined)
             .coerce_to_string(activation)?;
-        let existing_length = this.text_length();
 
-        this.replace_text(
-            existing_length,
-            existing_length,
-            &new_text,
-            &mut activation.context,
-        );
+        this.append_text(&new_text, &mut activation.context);
     }
 
     Ok(Value::Undefined)
@@ -850,6 +1071,115 @@ pub fn set_text_format<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn num_lines<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.num_lines().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let line_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        return match usize::try_from(line_index)
+            .ok()
+            .and_then(|line| this.line_offset(line))
+        {
+            Some(offset) => Ok(offset.into()),
+            None => Err("RangeError: Error #2006: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let line_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        return match usize::try_from(line_index)
+            .ok()
+            .and_then(|line| this.line_text(line))
+        {
+            Some(text) => Ok(AvmString::new(activation.context.gc_context, text).into()),
+            None => Err("RangeError: Error #2006: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let char_index = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        let bounds = match usize::try_from(char_index)
+            .ok()
+            .and_then(|index| this.char_boundaries(index))
+        {
+            Some(bounds) => bounds,
+            None => return Ok(Value::Null),
+        };
+
+        let rectangle_class = activation.context.avm2.classes().rectangle;
+        return Ok(rectangle_class
+            .construct(
+                activation,
+                &[
+                    bounds.offset_x().to_pixels().into(),
+                    bounds.offset_y().to_pixels().into(),
+                    bounds.width().to_pixels().into(),
+                    bounds.height().to_pixels().into(),
+                ],
+            )?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `TextField`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -877,6 +1207,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ),
         ("border", Some(border), Some(set_border)),
         ("borderColor", Some(border_color), Some(set_border_color)),
+        ("caretIndex", Some(caret_index), None),
         (
             "defaultTextFormat",
             Some(default_text_format),
@@ -890,8 +1221,17 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("embedFonts", Some(embed_fonts), Some(set_embed_fonts)),
         ("htmlText", Some(html_text), Some(set_html_text)),
         ("length", Some(length), None),
+        ("maxChars", Some(max_chars), Some(set_max_chars)),
+        ("maxScrollH", Some(max_scroll_h), None),
+        ("maxScrollV", Some(max_scroll_v), None),
         ("multiline", Some(multiline), Some(set_multiline)),
+        ("numLines", Some(num_lines), None),
+        ("restrict", Some(restrict), Some(set_restrict)),
+        ("scrollH", Some(scroll_h), Some(set_scroll_h)),
+        ("scrollV", Some(scroll_v), Some(set_scroll_v)),
         ("selectable", Some(selectable), Some(set_selectable)),
+        ("selectionBeginIndex", Some(selection_begin_index), None),
+        ("selectionEndIndex", Some(selection_end_index), None),
         ("text", Some(text), Some(set_text)),
         ("textColor", Some(text_color), Some(set_text_color)),
         ("textHeight", Some(text_height), None),
@@ -903,6 +1243,9 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("appendText", append_text),
+        ("getCharBoundaries", get_char_boundaries),
+        ("getLineOffset", get_line_offset),
+        ("getLineText", get_line_text),
         ("getTextFormat", get_text_format),
         ("replaceSelectedText", replace_selected_text),
         ("replaceText", replace_text),
@@ -913,3 +1256,301 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use crate::display_object::TInteractiveObject;
+    use crate::events::ClipEvent;
+
+    /// Call a method of a text field and trace its result.
+    fn traced_call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        field: Object<'gc>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<String, Error> {
+        let value = call(activation, field, name, args)?;
+
+        traced(activation, value)
+    }
+
+    /// The difference between two numeric properties of an object, rounded.
+    fn rounded_difference<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        minuend: &'static str,
+        subtrahend: &'static str,
+    ) -> Result<f64, Error> {
+        let minuend = get(activation, object, minuend)?.coerce_to_number(activation)?;
+        let subtrahend = get(activation, object, subtrahend)?.coerce_to_number(activation)?;
+
+        Ok((minuend - subtrahend).round())
+    }
+
+    /// A numeric property of an object.
+    fn number<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+    ) -> Result<f64, Error> {
+        get(activation, object, name)?.coerce_to_number(activation)
+    }
+
+    #[test]
+    fn lines() {
+        with_avm2(|activation| {
+            let field = construct(activation, "flash.text", "TextField", &[])?;
+            set(activation, field, "width", 80)?;
+            set(activation, field, "wordWrap", true)?;
+            assert_eq!(get(activation, field, "numLines")?, 1.into());
+
+            call(activation, field, "appendText", &["example".into()])?;
+            call(activation, field, "appendText", &[" example".into()])?;
+            let text = get(activation, field, "text")?;
+            assert_eq!(traced(activation, text)?, "example example");
+            assert_eq!(get(activation, field, "length")?, 15.into());
+            assert_eq!(get(activation, field, "numLines")?, 2.into());
+            let line = traced_call(activation, field, "getLineText", &[0.into()])?;
+            assert_eq!(line, "example ");
+            let line = traced_call(activation, field, "getLineText", &[1.into()])?;
+            assert_eq!(line, "example");
+
+            call(activation, field, "appendText", &[" sample".into()])?;
+            assert_eq!(get(activation, field, "numLines")?, 3.into());
+            let line = traced_call(activation, field, "getLineText", &[2.into()])?;
+            assert_eq!(line, "sample");
+            let offset = call(activation, field, "getLineOffset", &[1.into()])?;
+            assert_eq!(offset, 8.into());
+            let offset = call(activation, field, "getLineOffset", &[2.into()])?;
+            assert_eq!(offset, 16.into());
+
+            let first = call(activation, field, "getCharBoundaries", &[0.into()])?
+                .coerce_to_object(activation)?;
+            assert_eq!(get(activation, first, "x")?, 2.into());
+            assert_eq!(get(activation, first, "y")?, 2.into());
+            let missing = call(activation, field, "getCharBoundaries", &[100.into()])?;
+            assert_eq!(missing, Value::Null);
+
+            let args = [0.into(), 8.into(), "".into()];
+            call(activation, field, "replaceText", &args)?;
+            let text = get(activation, field, "text")?;
+            assert_eq!(traced(activation, text)?, "example sample");
+            assert_eq!(get(activation, field, "numLines")?, 2.into());
+
+            set(activation, field, "wordWrap", false)?;
+            assert_eq!(get(activation, field, "numLines")?, 1.into());
+            let line = traced_call(activation, field, "getLineText", &[0.into()])?;
+            assert_eq!(line, "example sample");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn autosize_includes_the_gutter() {
+        with_avm2(|activation| {
+            let field = construct(activation, "flash.text", "TextField", &[])?;
+            set(activation, field, "multiline", true)?;
+            set(activation, field, "autoSize", "left")?;
+            set(activation, field, "text", "one")?;
+            let single_height = number(activation, field, "height")?;
+            let single_text_height = number(activation, field, "textHeight")?;
+            assert_eq!(
+                rounded_difference(activation, field, "width", "textWidth")?,
+                4.0
+            );
+            assert_eq!(
+                rounded_difference(activation, field, "height", "textHeight")?,
+                4.0
+            );
+
+            set(activation, field, "text", "one\rtwo\rthree")?;
+            assert_eq!(get(activation, field, "numLines")?, 3.into());
+            assert!(number(activation, field, "height")? > single_height);
+            assert_eq!(
+                rounded_difference(activation, field, "height", "textHeight")?,
+                4.0
+            );
+            assert!(number(activation, field, "textHeight")? > single_text_height * 2.0);
+
+            let wrapped = construct(activation, "flash.text", "TextField", &[])?;
+            set(activation, wrapped, "width", 80)?;
+            set(activation, wrapped, "wordWrap", true)?;
+            set(activation, wrapped, "autoSize", "left")?;
+            set(activation, wrapped, "text", "example example example")?;
+            assert_eq!(get(activation, wrapped, "width")?, 80.into());
+            assert_eq!(get(activation, wrapped, "numLines")?, 3.into());
+            let gutter = rounded_difference(activation, wrapped, "height", "textHeight")?;
+            assert_eq!(gutter, 4.0);
+
+            // Turning autosizing off keeps the size it grew to.
+            set(activation, field, "autoSize", "none")?;
+            set(activation, field, "text", "one")?;
+            assert!(number(activation, field, "height")? > single_height);
+
+            Ok(())
+        })
+    }
+
+    /// Log the given properties of an event, and the name of its target.
+    fn log_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        event: Value<'gc>,
+        names: &[&'static str],
+    ) -> Result<(), Error> {
+        let event = event.coerce_to_object(activation)?;
+        let mut fields = Vec::new();
+        for name in names {
+            let value = get(activation, event, name)?;
+            fields.push(traced(activation, value)?);
+        }
+        let target = get(activation, event, "target")?.coerce_to_object(activation)?;
+        let target_name = get(activation, target, "name")?;
+        fields.push(traced(activation, target_name)?);
+        log(fields.join(" "));
+
+        Ok(())
+    }
+
+    fn on_link<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let text_event = class(activation, "flash.events", "TextEvent")?;
+        assert!(args[0].is_of_type(activation, text_event)?);
+        log_event(
+            activation,
+            args[0],
+            &["type", "bubbles", "cancelable", "text"],
+        )?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_scroll<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log_event(activation, args[0], &["type", "bubbles", "cancelable"])?;
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn clicking_event_links_dispatches_link_events() {
+        with_avm2(|activation| {
+            let link = construct(activation, "flash.text", "TextField", &[])?;
+            set(activation, link, "name", "link")?;
+            set(activation, link, "width", 200)?;
+            set(activation, link, "height", 40)?;
+            let html = r#"<a href="event:hello world">Click this link</a>"#;
+            set(activation, link, "htmlText", html)?;
+            let listener = function(activation, on_link)?;
+            let args = ["link".into(), listener.into()];
+            call(activation, link, "addEventListener", &args)?;
+
+            // The mouse is over the top left corner of the field.
+            let field = link
+                .as_display_object()
+                .and_then(|field| field.as_interactive())
+                .unwrap();
+            field.handle_clip_event(&mut activation.context, ClipEvent::Release);
+            assert_eq!(take_log(), ["link true false hello world link"]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn scrolling_dispatches_scroll_events() {
+        with_avm2(|activation| {
+            let scrolling = construct(activation, "flash.text", "TextField", &[])?;
+            set(activation, scrolling, "name", "scrolling")?;
+            set(activation, scrolling, "width", 200)?;
+            set(activation, scrolling, "height", 20)?;
+            set(activation, scrolling, "multiline", true)?;
+            set(activation, scrolling, "text", "one\ntwo\nthree\nfour")?;
+            let listener = function(activation, on_scroll)?;
+            let args = ["scroll".into(), listener.into()];
+            call(activation, scrolling, "addEventListener", &args)?;
+
+            set(activation, scrolling, "scrollV", 3)?;
+            assert_eq!(take_log(), ["scroll false false scrolling"]);
+            assert_eq!(get(activation, scrolling, "scrollV")?, 3.into());
+
+            // Nothing is dispatched if the value is unchanged.
+            set(activation, scrolling, "scrollV", 3)?;
+            assert!(take_log().is_empty());
+
+            Ok(())
+        })
+    }
+
+    fn on_change<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let input = get(activation, event, "target")?.coerce_to_object(activation)?;
+        let mut fields = Vec::new();
+        for name in [
+            "text",
+            "caretIndex",
+            "selectionBeginIndex",
+            "selectionEndIndex",
+        ] {
+            let value = get(activation, input, name)?;
+            fields.push(traced(activation, value)?);
+        }
+        log(fields.join(" "));
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn typing_is_restricted() {
+        with_avm2(|activation| {
+            let input = construct(activation, "flash.text", "TextField", &[])?;
+            assert_eq!(get(activation, input, "restrict")?, Value::Null);
+            assert_eq!(get(activation, input, "maxChars")?, 0.into());
+
+            set(activation, input, "type", "input")?;
+            set(activation, input, "restrict", "a-z0-9\\-^q")?;
+            set(activation, input, "maxChars", 5)?;
+            let restrict = get(activation, input, "restrict")?;
+            assert_eq!(traced(activation, restrict)?, "a-z0-9\\-^q");
+            assert_eq!(get(activation, input, "maxChars")?, 5.into());
+
+            let listener = function(activation, on_change)?;
+            let args = ["change".into(), listener.into()];
+            call(activation, input, "addEventListener", &args)?;
+
+            // Clicking the field puts the caret at the start.
+            call(activation, input, "setSelection", &[0.into(), 0.into()])?;
+            let field = input
+                .as_display_object()
+                .and_then(|field| field.as_edit_text())
+                .unwrap();
+            for character in "Hq-1ab cdefg".chars() {
+                field.text_input(character, &mut activation.context);
+            }
+            assert_eq!(
+                take_log(),
+                [
+                    "- 1 1 1",
+                    "-1 2 2 2",
+                    "-1a 3 3 3",
+                    "-1ab 4 4 4",
+                    "-1abc 5 5 5",
+                ]
+            );
+
+            Ok(())
+        })
+    }
+}
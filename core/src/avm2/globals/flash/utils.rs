@@ -1,5 +1,6 @@
 //! `flash.utils` namespace
 
+use crate::avm1::TimerCallback;
 use crate::avm2::object::TObject;
 use crate::avm2::QName;
 use crate::avm2::{Activation, Error, Object, Value};
@@ -8,6 +9,9 @@ pub mod bytearray;
 pub mod compression_algorithm;
 pub mod dictionary;
 pub mod endian;
+pub mod idatainput;
+pub mod idataoutput;
+pub mod iexternalizable;
 pub mod proxy;
 
 /// `flash.utils.flash_proxy` namespace
@@ -22,6 +26,71 @@ pub fn get_timer<'gc>(
     Ok((activation.context.navigator.time_since_launch().as_millis() as u32).into())
 }
 
+/// Register a timer that calls a closure, as `setInterval` and `setTimeout`
+/// do.
+fn create_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    is_timeout: bool,
+) -> Result<Value<'gc>, Error> {
+    let closure = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let interval = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let params = args.get(2..).unwrap_or_default().to_vec();
+
+    let id = activation.context.timers.add_timer(
+        TimerCallback::Avm2Callback { closure, params },
+        interval,
+        Vec::new(),
+        is_timeout,
+    );
+
+    Ok((id as u32).into())
+}
+
+/// Implements `flash.utils.setInterval`
+pub fn set_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    create_timer(activation, args, false)
+}
+
+/// Implements `flash.utils.setTimeout`
+pub fn set_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    create_timer(activation, args, true)
+}
+
+/// Implements `flash.utils.clearInterval` and `flash.utils.clearTimeout`
+///
+/// Clearing a timer that has already fired or been cleared does nothing.
+pub fn clear_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    activation.context.timers.remove(id as i32);
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `flash.utils.getQualifiedClassName`
 pub fn get_qualified_class_name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -94,3 +163,65 @@ pub fn get_definition_by_name<'gc>(
     let qname = QName::from_qualified_name(name, activation.context.gc_context);
     appdomain.get_defined_value(activation, qname)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::Timers;
+    use crate::avm2::test_utils::*;
+
+    /// Log the arguments, joined by commas.
+    fn log_args<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut values = Vec::new();
+        for arg in args {
+            values.push(traced(activation, arg.clone())?);
+        }
+        log(values.join(", "));
+
+        Ok(Value::Undefined)
+    }
+
+    /// Advance the timers by the given number of milliseconds.
+    fn advance(activation: &mut Activation<'_, '_, '_>, milliseconds: f64) {
+        Timers::update_timers(&mut activation.context, milliseconds);
+    }
+
+    #[test]
+    fn timers_fire_until_cleared() {
+        with_avm2(|activation| {
+            let callback = function(activation, log_args)?;
+            let args = [callback.into(), 10.into(), "tick".into()];
+            let interval_id = set_interval(activation, None, &args)?;
+            assert!(matches!(interval_id, Value::Unsigned(_)));
+
+            let cancelled_id = set_timeout(activation, None, &[callback.into(), 20.into()])?;
+            clear_interval(activation, None, &[cancelled_id])?;
+            let args = [callback.into(), 100.into(), "first".into(), 2.into()];
+            set_timeout(activation, None, &args)?;
+
+            // Clearing an unknown id does nothing.
+            clear_interval(activation, None, &[12345.into()])?;
+            assert!(take_log().is_empty());
+
+            advance(activation, 15.0);
+            assert_eq!(take_log(), ["tick"]);
+            advance(activation, 10.0);
+            assert_eq!(take_log(), ["tick"]);
+            advance(activation, 10.0);
+            assert_eq!(take_log(), ["tick"]);
+            clear_interval(activation, None, &[interval_id])?;
+
+            // The timeout fires once, with its arguments.
+            advance(activation, 100.0);
+            assert_eq!(take_log(), ["first, 2"]);
+            advance(activation, 100.0);
+            assert!(take_log().is_empty());
+
+            Ok(())
+        })
+    }
+}
@@ -1,10 +1,10 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::array::ArrayStorage;
-use crate::avm2::bytearray::{ByteArrayStorage, CompressionAlgorithm, Endian, ObjectEncoding};
+use crate::avm2::amf::{deserialize_value, read_amf3_externalizable, write_amf3_value};
+use crate::avm2::bytearray::{CompressionAlgorithm, Endian, ObjectEncoding};
 use crate::avm2::class::{Class, ClassAttributes};
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{bytearray_allocator, ArrayObject, ByteArrayObject, Object, TObject};
+use crate::avm2::object::{bytearray_allocator, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::character::Character;
@@ -13,80 +13,8 @@ use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::amf3::read::AMF3Decoder;
-use flash_lso::types::Value as AmfValue;
 use gc_arena::{GcCell, MutationContext};
 
-pub fn deserialize_value<'gc>(
-    activation: &mut Activation<'_, 'gc, '_>,
-    value: &AmfValue,
-) -> Result<Value<'gc>, Error> {
-    Ok(match value {
-        AmfValue::Undefined => Value::Undefined,
-        AmfValue::Null => Value::Null,
-        AmfValue::Bool(b) => Value::Bool(*b),
-        AmfValue::Integer(i) => Value::Integer(*i),
-        AmfValue::Number(n) => Value::Number(*n),
-        AmfValue::String(s) => Value::String(AvmString::new_utf8(activation.context.gc_context, s)),
-        AmfValue::ByteArray(bytes) => {
-            let storage = ByteArrayStorage::from_vec(bytes.clone());
-            let bytearray = ByteArrayObject::from_storage(activation, storage)?;
-            bytearray.into()
-        }
-        AmfValue::StrictArray(values) => {
-            let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
-            for value in values {
-                arr.push(Some(deserialize_value(activation, value)?));
-            }
-            let storage = ArrayStorage::from_storage(arr);
-            let array = ArrayObject::from_storage(activation, storage)?;
-            array.into()
-        }
-        AmfValue::ECMAArray(values, elements, _) => {
-            // First lets create an array out of `values` (dense portion), then we add the elements onto it.
-            let mut arr: Vec<Option<Value<'gc>>> = Vec::with_capacity(values.len());
-            for value in values {
-                arr.push(Some(deserialize_value(activation, value)?));
-            }
-            let storage = ArrayStorage::from_storage(arr);
-            let mut array = ArrayObject::from_storage(activation, storage)?;
-            // Now lets add each element as a property
-            for element in elements {
-                array.set_property(
-                    array,
-                    &QName::new(
-                        Namespace::public(),
-                        AvmString::new_utf8(activation.context.gc_context, element.name()),
-                    )
-                    .into(),
-                    deserialize_value(activation, element.value())?,
-                    activation,
-                )?;
-            }
-            array.into()
-        }
-        AmfValue::Object(properties, _class_definition) => {
-            let obj_class = activation.avm2().classes().object;
-            let mut obj = obj_class.construct(activation, &[])?;
-            for property in properties {
-                obj.set_property(
-                    obj,
-                    &QName::new(
-                        Namespace::public(),
-                        AvmString::new_utf8(activation.context.gc_context, property.name()),
-                    )
-                    .into(),
-                    deserialize_value(activation, property.value())?,
-                    activation,
-                )?;
-            }
-            obj.into()
-            // TODO: Handle class_defintion
-        }
-        // TODO: Dictionary, Vector, XML, Date, etc...
-        _ => Value::Undefined,
-    })
-}
-
 /// Implements `flash.utils.ByteArray`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -839,9 +767,20 @@ pub fn read_object<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
+        let object_encoding = match this.as_bytearray() {
+            Some(bytearray) => bytearray.object_encoding(),
+            None => return Ok(Value::Undefined),
+        };
+
+        if object_encoding == ObjectEncoding::Amf3 {
+            if let Some(value) = read_amf3_externalizable(activation, this)? {
+                return Ok(value);
+            }
+        }
+
         if let Some(bytearray) = this.as_bytearray() {
             let bytes = bytearray.read_at(bytearray.bytes_available(), bytearray.position())?;
-            let (bytes_left, value) = match bytearray.object_encoding() {
+            let (bytes_left, value) = match object_encoding {
                 ObjectEncoding::Amf0 => {
                     let mut decoder = AMF0Decoder::default();
                     let (extra, amf) = decoder
@@ -866,6 +805,29 @@ pub fn read_object<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let object_encoding = match this.as_bytearray() {
+            Some(bytearray) => bytearray.object_encoding(),
+            None => return Ok(Value::Undefined),
+        };
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+        match object_encoding {
+            ObjectEncoding::Amf0 => {
+                log::warn!("ByteArray.writeObject: AMF0 encoding is not yet implemented")
+            }
+            ObjectEncoding::Amf3 => write_amf3_value(activation, this, value, &mut Vec::new())?,
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn object_encoding<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -916,6 +878,9 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     write.set_attributes(ClassAttributes::SEALED);
     write.set_instance_allocator(bytearray_allocator);
 
+    write.implements(QName::new(Namespace::package("flash.utils"), "IDataInput").into());
+    write.implements(QName::new(Namespace::package("flash.utils"), "IDataOutput").into());
+
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("writeByte", write_byte),
         ("writeBytes", write_bytes),
@@ -948,6 +913,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("writeUTFBytes", write_utf_bytes),
         ("readUTFBytes", read_utf_bytes),
         ("readObject", read_object),
+        ("writeObject", write_object),
     ];
     write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
 
@@ -975,3 +941,141 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::globals::flash::net::{get_class_by_alias, register_class_alias};
+    use crate::avm2::object::ClassObject;
+    use crate::avm2::test_utils::*;
+
+    /// `Point(x:int = 0, y:int = 0)`
+    fn point_init<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let this = this.unwrap();
+        activation.super_init(this, &[])?;
+        set(
+            activation,
+            this,
+            "x",
+            args.get(0).cloned().unwrap_or_else(|| 0.into()),
+        )?;
+        set(
+            activation,
+            this,
+            "y",
+            args.get(1).cloned().unwrap_or_else(|| 0.into()),
+        )?;
+
+        Ok(Value::Undefined)
+    }
+
+    fn point_class_init<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// Write `x` and `y` to the output as ints.
+    fn write_external<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let output = args[0].coerce_to_object(activation)?;
+        for name in ["x", "y"] {
+            let value = get(activation, this.unwrap(), name)?;
+            call(activation, output, "writeInt", &[value])?;
+        }
+
+        Ok(Value::Undefined)
+    }
+
+    /// Read `x` and `y` from the input as ints.
+    fn read_external<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let input = args[0].coerce_to_object(activation)?;
+        for name in ["x", "y"] {
+            let value = call(activation, input, "readInt", &[])?;
+            set(activation, this.unwrap(), name, value)?;
+        }
+
+        Ok(Value::Undefined)
+    }
+
+    /// Define a `Point` class that implements `IExternalizable` by writing
+    /// its coordinates as two ints.
+    fn define_point<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<ClassObject<'gc>, Error> {
+        let mc = activation.context.gc_context;
+        let object = activation.avm2().classes().object;
+        let point = Class::new(
+            QName::new(Namespace::public(), "Point"),
+            Some(QName::new(Namespace::public(), "Object").into()),
+            Method::from_builtin(point_init, "Point", mc),
+            Method::from_builtin(point_class_init, "Point", mc),
+            mc,
+        );
+        let mut write = point.write(mc);
+        write.implements(QName::new(Namespace::package("flash.utils"), "IExternalizable").into());
+        write.define_public_builtin_instance_methods(
+            mc,
+            &[
+                ("writeExternal", write_external),
+                ("readExternal", read_external),
+            ],
+        );
+        drop(write);
+
+        ClassObject::from_class(activation, point, Some(object))
+    }
+
+    #[test]
+    fn externalizable_objects_round_trip() {
+        with_avm2(|activation| {
+            let point = define_point(activation)?;
+            register_class_alias(activation, None, &["test.Point".into(), point.into()])?;
+            let aliased = get_class_by_alias(activation, None, &["test.Point".into()])?;
+            assert_eq!(aliased, point.into());
+
+            let bytes = construct(activation, "flash.utils", "ByteArray", &[])?;
+            for interface in ["IDataInput", "IDataOutput"] {
+                let interface = class(activation, "flash.utils", interface)?;
+                assert!(bytes.is_of_type(interface, activation)?);
+            }
+
+            set(activation, bytes, "objectEncoding", 3)?;
+            let original = point.construct(activation, &[12.into(), (-345).into()])?;
+            call(activation, bytes, "writeObject", &[original.into()])?;
+            assert_eq!(get(activation, bytes, "length")?, 21.into());
+
+            // An object marker, externalizable traits and the alias length.
+            set(activation, bytes, "position", 0)?;
+            let mut header = Vec::new();
+            for _ in 0..3 {
+                header.push(call(activation, bytes, "readUnsignedByte", &[])?);
+            }
+            assert_eq!(header, [10.into(), 7.into(), 21.into()]);
+
+            set(activation, bytes, "position", 0)?;
+            let copy = call(activation, bytes, "readObject", &[])?.coerce_to_object(activation)?;
+            assert!(copy.is_of_type(point, activation)?);
+            let externalizable = class(activation, "flash.utils", "IExternalizable")?;
+            assert!(copy.is_of_type(externalizable, activation)?);
+            assert_eq!(get(activation, copy, "x")?, 12.into());
+            assert_eq!(get(activation, copy, "y")?, (-345).into());
+            assert_eq!(get(activation, bytes, "bytesAvailable")?, 0.into());
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,63 @@
+//! `flash.utils.IDataInput` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Emulates attempts to execute bodiless methods.
+pub fn bodiless_method<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Cannot execute non-native method without body".into())
+}
+
+/// Implements `flash.utils.IDataInput`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `IDataInput`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "IDataInput"),
+        None,
+        Method::from_builtin(bodiless_method, "<IDataInput instance initializer>", mc),
+        Method::from_builtin(class_init, "<IDataInput interface initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::INTERFACE);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("readBoolean", bodiless_method),
+        ("readByte", bodiless_method),
+        ("readBytes", bodiless_method),
+        ("readDouble", bodiless_method),
+        ("readFloat", bodiless_method),
+        ("readInt", bodiless_method),
+        ("readMultiByte", bodiless_method),
+        ("readObject", bodiless_method),
+        ("readShort", bodiless_method),
+        ("readUnsignedByte", bodiless_method),
+        ("readUnsignedInt", bodiless_method),
+        ("readUnsignedShort", bodiless_method),
+        ("readUTF", bodiless_method),
+        ("readUTFBytes", bodiless_method),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
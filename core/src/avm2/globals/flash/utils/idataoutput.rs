@@ -0,0 +1,61 @@
+//! `flash.utils.IDataOutput` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Emulates attempts to execute bodiless methods.
+pub fn bodiless_method<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Cannot execute non-native method without body".into())
+}
+
+/// Implements `flash.utils.IDataOutput`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `IDataOutput`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "IDataOutput"),
+        None,
+        Method::from_builtin(bodiless_method, "<IDataOutput instance initializer>", mc),
+        Method::from_builtin(class_init, "<IDataOutput interface initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::INTERFACE);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("writeBoolean", bodiless_method),
+        ("writeByte", bodiless_method),
+        ("writeBytes", bodiless_method),
+        ("writeDouble", bodiless_method),
+        ("writeFloat", bodiless_method),
+        ("writeInt", bodiless_method),
+        ("writeMultiByte", bodiless_method),
+        ("writeObject", bodiless_method),
+        ("writeShort", bodiless_method),
+        ("writeUnsignedInt", bodiless_method),
+        ("writeUTF", bodiless_method),
+        ("writeUTFBytes", bodiless_method),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
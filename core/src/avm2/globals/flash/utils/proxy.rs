@@ -32,7 +32,7 @@ pub fn get_property<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("getproperty is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement getProperty. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.setProperty`
@@ -41,7 +41,7 @@ pub fn set_property<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("setproperty is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement setProperty. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.deleteProperty`
@@ -50,7 +50,7 @@ pub fn delete_property<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("deleteproperty is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement deleteProperty. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.callProperty`
@@ -59,7 +59,7 @@ pub fn call_property<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("callproperty is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement callProperty. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.hasProperty`
@@ -68,7 +68,7 @@ pub fn has_property<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("hasproperty is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement hasProperty. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.isAttribute`
@@ -77,7 +77,7 @@ pub fn is_attribute<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("isattribute is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement isAttribute. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.getDescendants`
@@ -86,7 +86,7 @@ pub fn get_descendants<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("getdescendants is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement getDescendants. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.nextNameIndex`
@@ -95,7 +95,7 @@ pub fn next_name_index<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("hasnext/nextNameIndex is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement nextNameIndex. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.nextName`
@@ -104,7 +104,7 @@ pub fn next_name<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("nextname is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement nextName. It must be overridden by a subclass.".into())
 }
 
 /// Implements `Proxy.nextValue`
@@ -113,7 +113,7 @@ pub fn next_value<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("nextvalue is not implemented for this Proxy".into())
+    Err("Error: Error #2088: The Proxy class does not implement nextValue. It must be overridden by a subclass.".into())
 }
 
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
@@ -147,3 +147,163 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::names::Multiname;
+    use crate::avm2::object::TObject;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::traits::Trait;
+    use crate::string::AvmString;
+
+    /// A `flash_proxy::getProperty` trap, which returns `"trapped " + name`.
+    fn trap_get<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = traced(activation, args[0])?;
+        log(format!("getProperty {}", name));
+        let trapped = format!("trapped {}", name);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, trapped).into())
+    }
+
+    /// A `flash_proxy::callProperty` trap, which returns the number of
+    /// arguments of the call.
+    fn trap_call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = traced(activation, args[0])?;
+        log(format!("callProperty {}", name));
+
+        Ok((args.len() - 1).into())
+    }
+
+    /// A `flash_proxy::getDescendants` trap, which returns
+    /// `"descendants of " + name.localName`.
+    fn trap_descendants<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = args[0].coerce_to_object(activation)?;
+        let qname_class = class(activation, "", "QName")?;
+        let is_qname = name.is_of_type(qname_class, activation)?;
+        let local_name = get(activation, name, "localName")?;
+        let local_name = traced(activation, local_name)?;
+        log(format!("getDescendants {} {}", is_qname, local_name));
+        let descendants = format!("descendants of {}", local_name);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, descendants).into())
+    }
+
+    /// A public method named like the trap, which is not one.
+    fn not_a_trap<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("not a trap".into())
+    }
+
+    /// An override of a trap in the `flash_proxy` namespace.
+    fn trap<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        method: NativeMethodImpl,
+    ) -> Trait<'gc> {
+        Trait::from_method(
+            QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), name),
+            Method::from_builtin(method, name, activation.context.gc_context),
+        )
+        .with_override()
+    }
+
+    #[test]
+    fn traps_are_in_the_flash_proxy_namespace() {
+        with_avm2(|activation| {
+            let domain = activation.avm2().global_domain();
+            let flash_proxy = QName::new(Namespace::package("flash.utils"), "flash_proxy");
+            let flash_proxy = domain.get_defined_value(activation, flash_proxy)?;
+            assert_eq!(
+                traced(activation, flash_proxy)?,
+                "http://www.adobe.com/2006/actionscript/flash/proxy"
+            );
+            let namespace_class = class(activation, "", "Namespace")?;
+            assert!(flash_proxy.is_of_type(activation, namespace_class)?);
+            let as3 = QName::new(Namespace::public(), "AS3");
+            let as3 = domain.get_defined_value(activation, as3)?;
+            assert_eq!(
+                traced(activation, as3)?,
+                "http://adobe.com/AS3/2006/builtin"
+            );
+
+            let proxy_class = class(activation, "flash.utils", "Proxy")?;
+            let traps = vec![
+                trap(activation, "getProperty", trap_get),
+                trap(activation, "callProperty", trap_call),
+            ];
+            let correct_class =
+                define_class_with_traits(activation, "CorrectProxy", Some(proxy_class), traps)?;
+            let correct = correct_class.construct(activation, &[])?;
+            let value = get(activation, correct, "foo")?;
+            assert_eq!(traced(activation, value)?, "trapped foo");
+            let args = [1.into(), 2.into()];
+            assert_eq!(call(activation, correct, "bar", &args)?, 2.into());
+            assert_eq!(take_log(), ["getProperty foo", "callProperty bar"]);
+
+            // A public method is not a trap, so the default one throws.
+            let methods: &[(&'static str, NativeMethodImpl)] = &[("getProperty", not_a_trap)];
+            let wrong_class =
+                define_class(activation, "WrongProxy", Some(proxy_class), methods, &[])?;
+            let wrong = wrong_class.construct(activation, &[])?;
+            let args = ["direct".into()];
+            let value = call(activation, wrong, "getProperty", &args)?;
+            assert_eq!(traced(activation, value)?, "not a trap");
+            let error = get(activation, wrong, "foo").unwrap_err();
+            assert!(error.to_string().starts_with("Error: Error #2088"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn descendants_are_trapped() {
+        with_avm2(|activation| {
+            let proxy_class = class(activation, "flash.utils", "Proxy")?;
+            let traps = vec![trap(activation, "getDescendants", trap_descendants)];
+            let descendant_class =
+                define_class_with_traits(activation, "DescendantProxy", Some(proxy_class), traps)?;
+            let proxy = descendant_class.construct(activation, &[])?;
+
+            for (name, local_name) in [
+                (public_name("foo"), "foo"),
+                (public_name("bar"), "bar"),
+                (Multiname::any(), "*"),
+            ] {
+                let descendants = proxy.get_descendants(&name, activation)?;
+                assert_eq!(
+                    traced(activation, descendants)?,
+                    format!("descendants of {}", local_name)
+                );
+                assert_eq!(take_log(), [format!("getDescendants true {}", local_name)]);
+            }
+
+            let plain_class = define_class(activation, "PlainProxy", Some(proxy_class), &[], &[])?;
+            let plain = plain_class.construct(activation, &[])?;
+            let error = plain
+                .get_descendants(&public_name("foo"), activation)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Error: Error #2088: The Proxy class does not implement getDescendants. It must be overridden by a subclass."
+            );
+
+            Ok(())
+        })
+    }
+}
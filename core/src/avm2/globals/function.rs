@@ -3,7 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::globals::array::resolve_array_hole;
-use crate::avm2::method::Method;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{FunctionObject, Object, TObject};
 use crate::avm2::value::Value;
@@ -150,6 +150,21 @@ fn apply<'gc>(
     }
 }
 
+/// Implements `Function.length`
+fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(exec) = this.as_executable() {
+            return Ok((exec.num_parameters() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Function`'s class.
 pub fn create_class<'gc>(gc_context: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let function_class = Class::new(
@@ -160,5 +175,14 @@ pub fn create_class<'gc>(gc_context: MutationContext<'gc, '_>) -> GcCell<'gc, Cl
         gc_context,
     );
 
+    let mut write = function_class.write(gc_context);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("length", Some(length), None)];
+    write.define_public_builtin_instance_properties(gc_context, PUBLIC_INSTANCE_PROPERTIES);
+
     function_class
 }
@@ -2,20 +2,89 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{namespace_allocator, Object};
+use crate::avm2::object::{namespace_allocator, FunctionObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::string::AvmString;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `Namespace`'s instance initializer.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Namespace constructor is a stub.".into())
+    if let Some(this) = this.and_then(|t| t.as_namespace_object()) {
+        let (uri, prefix) = match args {
+            [] => ("".into(), Some("".into())),
+            [uri_arg] => match uri_arg {
+                Value::Object(o) if o.as_namespace_object().is_some() => {
+                    let ns = o.as_namespace_object().unwrap();
+
+                    (ns.namespace().as_uri(), ns.prefix())
+                }
+                uri_arg => {
+                    let uri = uri_to_string(activation, uri_arg)?;
+                    let prefix = if uri.is_empty() {
+                        Some("".into())
+                    } else {
+                        None
+                    };
+
+                    (uri, prefix)
+                }
+            },
+            [prefix_arg, uri_arg, ..] => {
+                let uri = uri_to_string(activation, uri_arg)?;
+                let prefix = match prefix_arg {
+                    Value::Undefined => None,
+                    prefix_arg => Some(prefix_arg.coerce_to_string(activation)?),
+                };
+
+                let has_prefix = prefix.map(|p| !p.is_empty()).unwrap_or(false);
+                if uri.is_empty() && has_prefix {
+                    return Err(format!(
+                        "TypeError: Error #1098: Illegal prefix {} for no namespace.",
+                        prefix.unwrap_or_default()
+                    )
+                    .into());
+                }
+
+                (uri, prefix)
+            }
+        };
+
+        this.init_namespace(
+            activation.context.gc_context,
+            Namespace::Namespace(uri),
+            prefix,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Convert the URI argument of the `Namespace` constructor to a string.
+///
+/// `QName`s contribute their URI; anything else is coerced to a string.
+fn uri_to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    uri_arg: &Value<'gc>,
+) -> Result<AvmString<'gc>, Error> {
+    match uri_arg {
+        Value::Object(o) if o.as_qname_object().is_some() => {
+            let qname = o.as_qname_object().unwrap();
+            let uri = qname.qname().map(|q| match q.namespace() {
+                Namespace::Any => "*".into(),
+                ns => ns.as_uri(),
+            });
+
+            Ok(uri.unwrap_or_default())
+        }
+        uri_arg => uri_arg.coerce_to_string(activation),
+    }
 }
 
 /// Implements `Namespace`'s native instance initializer.
@@ -33,13 +102,77 @@ pub fn native_instance_init<'gc>(
 
 /// Implements `Namespace`'s class initializer.
 pub fn class_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this.unwrap();
+    let scope = activation.create_scopechain();
+    let mut namespace_proto = this
+        .get_property(this, &QName::dynamic_name("prototype").into(), activation)?
+        .coerce_to_object(activation)?;
+    let this_class = this.as_class_object().unwrap();
+
+    const PROTOTYPE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("toString", to_string), ("valueOf", to_string)];
+
+    for &(name, method) in PROTOTYPE_METHODS {
+        namespace_proto.set_property(
+            namespace_proto,
+            &QName::dynamic_name(name).into(),
+            FunctionObject::from_method(
+                activation,
+                Method::from_builtin(method, name, activation.context.gc_context),
+                scope,
+                None,
+                Some(this_class),
+            )
+            .into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Namespace.prefix`'s getter
+pub fn prefix<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_namespace_object()) {
+        if let Some(prefix) = this.prefix() {
+            return Ok(prefix.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Namespace.uri`'s getter
+pub fn uri<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_namespace_object()) {
+        return Ok(this.namespace().as_uri().into());
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `Namespace.AS3::toString`, `Namespace.AS3::valueOf`, and their
+/// prototype equivalents.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    uri(activation, this, args)
+}
+
 /// Construct `Namespace`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -58,5 +191,47 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         mc,
     ));
 
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("prefix", Some(prefix), None), ("uri", Some(uri), None)];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const AS3_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("toString", to_string), ("valueOf", to_string)];
+    write.define_as3_builtin_instance_methods(mc, AS3_INSTANCE_METHODS);
+
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+
+    #[test]
+    fn constructor_sets_prefix_and_uri() {
+        with_avm2(|activation| {
+            let ns = construct(
+                activation,
+                "",
+                "Namespace",
+                &["p".into(), "http://example.com/p".into()],
+            )?;
+            assert_eq!(get(activation, ns, "prefix")?, "p".into());
+            assert_eq!(get(activation, ns, "uri")?, "http://example.com/p".into());
+
+            let unprefixed = construct(
+                activation,
+                "",
+                "Namespace",
+                &["http://example.com/q".into()],
+            )?;
+            assert_eq!(get(activation, unprefixed, "prefix")?, Value::Undefined);
+            assert_eq!(traced(activation, unprefixed)?, "http://example.com/q");
+
+            Ok(())
+        })
+    }
+}
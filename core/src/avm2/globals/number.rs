@@ -123,6 +123,20 @@ fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Number`'s call handler.
+///
+/// `Number()` with no arguments is `0`, not `NaN`.
+fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0) {
+        Some(arg) => Ok(arg.coerce_to_number(activation)?.into()),
+        None => Ok(0.0.into()),
+    }
+}
+
 /// Implements `Number.toLocaleString`
 fn to_locale_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -357,6 +371,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     let mut write = class.write(mc);
     write.set_instance_allocator(primitive_allocator);
+    write.set_call_handler(Method::from_builtin(
+        call_handler,
+        "<Number call handler>",
+        mc,
+    ));
     write.set_native_instance_init(Method::from_builtin(
         native_instance_init,
         "<Number native instance initializer>",
@@ -365,7 +384,8 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const CLASS_CONSTANTS: &[(&str, f64)] = &[
         ("MAX_VALUE", f64::MAX),
-        ("MIN_VALUE", f64::MIN_POSITIVE),
+        // The smallest positive subnormal value, not `f64::MIN_POSITIVE`.
+        ("MIN_VALUE", 5e-324),
         ("NaN", f64::NAN),
         ("NEGATIVE_INFINITY", f64::NEG_INFINITY),
         ("POSITIVE_INFINITY", f64::INFINITY),
@@ -387,3 +407,58 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Read a constant of a numeric class, like `int.MAX_VALUE`.
+    fn constant<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &'static str,
+        name: &'static str,
+    ) -> Result<Value<'gc>, Error> {
+        let numeric_class = class(activation, "", class_name)?;
+
+        get(activation, numeric_class, name)
+    }
+
+    #[test]
+    fn class_constants() {
+        with_avm2(|activation| {
+            let max = constant(activation, "Number", "MAX_VALUE")?;
+            assert_eq!(max, 1.7976931348623157e308.into());
+            assert_eq!(max.coerce_to_number(activation)? * 2.0, f64::INFINITY);
+
+            // `MIN_VALUE` is the smallest subnormal number.
+            let min = constant(activation, "Number", "MIN_VALUE")?;
+            let min = min.coerce_to_number(activation)?;
+            assert_eq!(min, f64::from_bits(1));
+            assert!(min > 0.0);
+            assert_eq!(min / 2.0, 0.0);
+
+            let nan = constant(activation, "Number", "NaN")?;
+            assert_eq!(traced(activation, nan)?, "NaN");
+            let negative_infinity = constant(activation, "Number", "NEGATIVE_INFINITY")?;
+            assert_eq!(traced(activation, negative_infinity)?, "-Infinity");
+            let positive_infinity = constant(activation, "Number", "POSITIVE_INFINITY")?;
+            assert_eq!(traced(activation, positive_infinity)?, "Infinity");
+
+            let int_max = constant(activation, "int", "MAX_VALUE")?;
+            assert_eq!(traced(activation, int_max)?, "2147483647");
+            let int_min = constant(activation, "int", "MIN_VALUE")?;
+            assert_eq!(traced(activation, int_min)?, "-2147483648");
+            let uint_max = constant(activation, "uint", "MAX_VALUE")?;
+            assert_eq!(traced(activation, uint_max)?, "4294967295");
+            let uint_min = constant(activation, "uint", "MIN_VALUE")?;
+            assert_eq!(traced(activation, uint_min)?, "0");
+
+            let number_class = class(activation, "", "Number")?;
+            let error = set(activation, number_class, "MAX_VALUE", 1).unwrap_err();
+            assert!(error.to_string().starts_with("ReferenceError: Error #1074"));
+
+            Ok(())
+        })
+    }
+}
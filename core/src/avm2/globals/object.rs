@@ -126,6 +126,26 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Object`'s call handler.
+///
+/// Calling `Object` as a function returns its argument as an object, or a new
+/// empty object if it was `null` or `undefined`.
+pub fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => {
+            let object_class = activation.avm2().classes().object;
+
+            Ok(object_class.construct(activation, &[])?.into())
+        }
+        // AS3 has no wrapper objects, so `Object(5)` is just `5`.
+        value => Ok(value),
+    }
+}
+
 /// Implements `Object.prototype.toString`
 fn to_string<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -219,10 +239,13 @@ pub fn set_property_is_enumerable<'gc>(
     let name: Result<&Value<'gc>, Error> = args.get(0).ok_or_else(|| "No name specified".into());
     let name = name?.coerce_to_string(activation)?;
 
-    if let Some(Value::Bool(is_enum)) = args.get(1) {
-        let qname = QName::dynamic_name(name);
-        this.set_local_property_is_enumerable(activation.context.gc_context, qname, *is_enum)?;
-    }
+    let is_enum = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Bool(true))
+        .coerce_to_boolean();
+    let qname = QName::dynamic_name(name);
+    this.set_local_property_is_enumerable(activation.context.gc_context, qname, is_enum)?;
 
     Ok(Value::Undefined)
 }
@@ -238,6 +261,12 @@ pub fn create_class<'gc>(gc_context: MutationContext<'gc, '_>) -> GcCell<'gc, Cl
     );
     let mut write = object_class.write(gc_context);
 
+    write.set_call_handler(Method::from_builtin(
+        call_handler,
+        "<Object call handler>",
+        gc_context,
+    ));
+
     write.define_class_trait(Trait::from_const(
         QName::new(Namespace::public(), "length"),
         QName::new(Namespace::public(), "int").into(),
@@ -254,3 +283,185 @@ pub fn create_class<'gc>(gc_context: MutationContext<'gc, '_>) -> GcCell<'gc, Cl
 
     object_class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use crate::string::AvmString;
+
+    /// The names a `for..in` loop over an object would visit, joined by
+    /// commas.
+    fn enumerated<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<String, Error> {
+        let mut names = Vec::new();
+        let mut index = 0;
+        while let Some(next_index) = object.get_next_enumerant(index, activation)? {
+            let name = object.get_enumerant_name(next_index, activation)?;
+            names.push(traced(activation, name)?);
+            index = next_index;
+        }
+
+        Ok(names.join(","))
+    }
+
+    /// Join the first argument and `this.name`, like a method added to a
+    /// class prototype.
+    fn greet<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let greeting = traced(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        let name = get(activation, this.unwrap(), "name")?;
+        let name = traced(activation, name)?;
+        let greeting = format!("{}, {}", greeting, name);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, greeting).into())
+    }
+
+    /// Prefix the first argument, like a method set on an instance.
+    fn greet_own<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let greeting = traced(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        let greeting = format!("own {}", greeting);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, greeting).into())
+    }
+
+    /// Return `this[1]`.
+    fn second<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        get(activation, this.unwrap(), "1")
+    }
+
+    /// Return `this.toUpperCase() + "!"`.
+    fn shout<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let upper = call(activation, this.unwrap(), "toUpperCase", &[])?;
+        let shouted = format!("{}!", traced(activation, upper)?);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, shouted).into())
+    }
+
+    #[test]
+    fn prototype_methods() {
+        with_avm2(|activation| {
+            let greeter = define_class(activation, "Greeter", None, &[], &[])?;
+            let early = greeter.construct(activation, &[])?;
+            set(activation, early, "name", "early")?;
+
+            // Instances made before the method was added see it too.
+            let prototype = get(activation, greeter, "prototype")?.coerce_to_object(activation)?;
+            let method = function(activation, greet)?;
+            set(activation, prototype, "greet", method)?;
+            let late = greeter.construct(activation, &[])?;
+            set(activation, late, "name", "late")?;
+
+            let greeting = call(activation, early, "greet", &["Hello".into()])?;
+            assert_eq!(traced(activation, greeting)?, "Hello, early");
+            let greeting = call(activation, late, "greet", &["Hi".into()])?;
+            assert_eq!(traced(activation, greeting)?, "Hi, late");
+            let name = QName::new(Namespace::public(), "greet");
+            assert!(late.has_property_via_in(activation, name)?);
+            let args = ["greet".into()];
+            assert_eq!(
+                call(activation, late, "hasOwnProperty", &args)?,
+                false.into()
+            );
+
+            // Own properties shadow the prototype.
+            let method = function(activation, greet_own)?;
+            set(activation, late, "greet", method)?;
+            let greeting = call(activation, late, "greet", &["Hey".into()])?;
+            assert_eq!(traced(activation, greeting)?, "own Hey");
+            let greeting = call(activation, early, "greet", &["Hey".into()])?;
+            assert_eq!(traced(activation, greeting)?, "Hey, early");
+
+            prototype.delete_property(activation, &public_name("greet"))?;
+            assert_eq!(get(activation, early, "greet")?, Value::Undefined);
+
+            // Builtin prototypes can be extended as well.
+            let array_prototype = activation.avm2().prototypes().array;
+            let method = function(activation, second)?;
+            set(activation, array_prototype, "second", method)?;
+            let numbers = array(activation, &[1.into(), 2.into(), 3.into()])?;
+            assert_eq!(call(activation, numbers, "second", &[])?, 2.into());
+
+            let string_prototype = activation.avm2().prototypes().string;
+            let method = function(activation, shout)?;
+            set(activation, string_prototype, "shout", method)?;
+            let hello = Value::from("hello").coerce_to_object(activation)?;
+            let shouted = call(activation, hello, "shout", &[])?;
+            assert_eq!(traced(activation, shouted)?, "HELLO!");
+
+            let object_prototype = activation.avm2().prototypes().object;
+            set(activation, object_prototype, "everywhere", "inherited")?;
+            assert_eq!(get(activation, early, "everywhere")?, "inherited".into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_property_is_enumerable() {
+        with_avm2(|activation| {
+            let o = construct(activation, "", "Object", &[])?;
+            set(activation, o, "a", 1)?;
+            set(activation, o, "b", 2)?;
+            set(activation, o, "c", 3)?;
+
+            let args = ["b".into(), false.into()];
+            call(activation, o, "setPropertyIsEnumerable", &args)?;
+            assert_eq!(enumerated(activation, o)?, "a,c");
+            let args = ["b".into()];
+            assert_eq!(
+                call(activation, o, "propertyIsEnumerable", &args)?,
+                false.into()
+            );
+            assert_eq!(get(activation, o, "b")?, 2.into());
+
+            // Writing the property keeps it hidden.
+            set(activation, o, "b", 5)?;
+            assert_eq!(enumerated(activation, o)?, "a,c");
+            assert_eq!(
+                call(activation, o, "propertyIsEnumerable", &args)?,
+                false.into()
+            );
+            assert_eq!(get(activation, o, "b")?, 5.into());
+
+            // Missing properties are not created.
+            let args = ["missing".into(), true.into()];
+            call(activation, o, "setPropertyIsEnumerable", &args)?;
+            let args = ["missing".into()];
+            assert_eq!(
+                call(activation, o, "propertyIsEnumerable", &args)?,
+                false.into()
+            );
+            assert_eq!(call(activation, o, "hasOwnProperty", &args)?, false.into());
+
+            // The flag defaults to true, and shown properties are enumerated
+            // last.
+            call(activation, o, "setPropertyIsEnumerable", &["b".into()])?;
+            assert_eq!(enumerated(activation, o)?, "a,c,b");
+            let args = ["b".into()];
+            assert_eq!(
+                call(activation, o, "propertyIsEnumerable", &args)?,
+                true.into()
+            );
+
+            Ok(())
+        })
+    }
+}
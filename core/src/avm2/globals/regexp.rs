@@ -301,3 +301,93 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// The source, flags and `lastIndex` of a regular expression, joined by
+    /// spaces.
+    fn describe<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        re: Object<'gc>,
+    ) -> Result<String, Error> {
+        let mut described = Vec::new();
+        for name in [
+            "source",
+            "global",
+            "ignoreCase",
+            "multiline",
+            "dotall",
+            "extended",
+            "lastIndex",
+        ] {
+            let value = get(activation, re, name)?;
+            described.push(traced(activation, value)?);
+        }
+
+        Ok(described.join(" "))
+    }
+
+    /// Call `re.exec(text)` and return the match and the new `lastIndex`.
+    fn exec_from<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        re: Object<'gc>,
+        text: &'static str,
+    ) -> Result<(String, Value<'gc>), Error> {
+        let found = call(activation, re, "exec", &[text.into()])?;
+        let found = traced(activation, found)?;
+
+        Ok((found, get(activation, re, "lastIndex")?))
+    }
+
+    #[test]
+    fn flags_and_last_index() {
+        with_avm2(|activation| {
+            let re = construct(activation, "", "RegExp", &["a+b".into(), "g".into()])?;
+            assert_eq!(
+                describe(activation, re)?,
+                "a+b true false false false false 0"
+            );
+            let re = construct(activation, "", "RegExp", &["x y".into(), "imsx".into()])?;
+            assert_eq!(describe(activation, re)?, "x y false true true true true 0");
+            let re = construct(activation, "", "RegExp", &["abc".into()])?;
+            assert_eq!(
+                describe(activation, re)?,
+                "abc false false false false false 0"
+            );
+
+            let text = "12 345 6789";
+            let re = construct(activation, "", "RegExp", &["\\d+".into(), "g".into()])?;
+            assert_eq!(exec_from(activation, re, text)?, ("12".into(), 2.into()));
+            set(activation, re, "lastIndex", 6)?;
+            assert_eq!(exec_from(activation, re, text)?, ("6789".into(), 11.into()));
+            set(activation, re, "lastIndex", 3)?;
+            assert_eq!(exec_from(activation, re, text)?, ("345".into(), 6.into()));
+            assert_eq!(exec_from(activation, re, text)?, ("6789".into(), 11.into()));
+
+            // Running out of matches starts over.
+            assert_eq!(exec_from(activation, re, text)?, ("null".into(), 0.into()));
+            assert_eq!(exec_from(activation, re, text)?, ("12".into(), 2.into()));
+
+            set(activation, re, "lastIndex", 8)?;
+            assert_eq!(call(activation, re, "test", &[text.into()])?, true.into());
+            assert_eq!(get(activation, re, "lastIndex")?, 11.into());
+            set(activation, re, "lastIndex", 100)?;
+            assert_eq!(call(activation, re, "test", &[text.into()])?, false.into());
+            assert_eq!(get(activation, re, "lastIndex")?, 0.into());
+
+            // Searches that are not global ignore `lastIndex`.
+            let re = construct(activation, "", "RegExp", &["\\d+".into()])?;
+            set(activation, re, "lastIndex", 5)?;
+            assert_eq!(exec_from(activation, re, text)?, ("12".into(), 5.into()));
+
+            // The `x` flag ignores unescaped whitespace.
+            let re = construct(activation, "", "RegExp", &["a b \\ c".into(), "x".into()])?;
+            assert_eq!(exec_from(activation, re, "ab c")?.0, "ab c");
+
+            Ok(())
+        })
+    }
+}
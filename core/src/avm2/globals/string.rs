@@ -9,9 +9,10 @@ use crate::avm2::regexp::RegExpFlags;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::{ArrayObject, ArrayStorage};
-use crate::string::{AvmString, WString};
+use crate::string::{AvmString, WStr, WString};
 use gc_arena::{GcCell, MutationContext};
 use std::iter;
+use std::ops::Range;
 
 /// Implements `String`'s instance initializer.
 pub fn instance_init<'gc>(
@@ -45,6 +46,21 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `String`'s call handler.
+///
+/// Unlike coercion to `String`, this converts `null` and `undefined` to the
+/// strings `"null"` and `"undefined"`.
+pub fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0) {
+        Some(arg) => Ok(arg.coerce_to_string(activation)?.into()),
+        None => Ok("".into()),
+    }
+}
+
 /// Implements `length` property's getter
 fn length<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -279,6 +295,157 @@ fn match_s<'gc>(
     Ok(Value::Null)
 }
 
+/// Implements `String.replace`
+fn replace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let this = Value::from(this).coerce_to_string(activation)?;
+        let pattern = args.get(0).unwrap_or(&Value::Undefined);
+        let replacement = args.get(1).unwrap_or(&Value::Undefined);
+
+        // Matches are collected up front, so that a replacement function is
+        // free to use the regexp itself.
+        let regexp_matches = match pattern {
+            Value::Object(pattern) => pattern
+                .as_regexp_mut(activation.context.gc_context)
+                .map(|mut regexp| regexp.replace_matches(this)),
+            _ => None,
+        };
+        let matches = match regexp_matches {
+            Some(matches) => matches,
+            None => {
+                // String patterns are matched literally, and only once.
+                let pattern = pattern.coerce_to_string(activation)?;
+                this.find(&pattern)
+                    .map(|start| (start..start + pattern.len(), Vec::new()))
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        let replace_function = match replacement {
+            Value::Object(function) if function.as_executable().is_some() => Some(*function),
+            _ => None,
+        };
+        let replacement = match replace_function {
+            Some(_) => AvmString::default(),
+            None => replacement.coerce_to_string(activation)?,
+        };
+
+        let mut out = WString::new();
+        let mut last_end = 0;
+        for (range, captures) in matches {
+            out.push_str(&this[last_end..range.start]);
+
+            if let Some(function) = replace_function {
+                let mc = activation.context.gc_context;
+                let mut function_args = vec![AvmString::new(mc, &this[range.clone()]).into()];
+                for capture in &captures {
+                    function_args.push(match capture {
+                        Some(capture) => AvmString::new(mc, &this[capture.clone()]).into(),
+                        None => Value::Undefined,
+                    });
+                }
+                function_args.push(Value::Number(range.start as f64));
+                function_args.push(this.into());
+
+                let replaced = function
+                    .call(None, &function_args, activation)?
+                    .coerce_to_string(activation)?;
+                out.push_str(&replaced);
+            } else {
+                expand_replacement(&mut out, &replacement, &this, range.clone(), &captures);
+            }
+
+            last_end = range.end;
+        }
+        out.push_str(&this[last_end..]);
+
+        return Ok(AvmString::new(activation.context.gc_context, out).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Append a `String.replace` replacement string to `out`, expanding its `$`
+/// patterns against the current match.
+///
+/// References to capture groups that don't exist in the pattern are copied
+/// literally, while groups that didn't participate in the match expand to
+/// nothing.
+fn expand_replacement(
+    out: &mut WString,
+    replacement: &WStr,
+    text: &WStr,
+    matched: Range<usize>,
+    captures: &[Option<Range<usize>>],
+) {
+    let mut i = 0;
+    while i < replacement.len() {
+        let c = replacement.at(i);
+        if c != u16::from(b'$') {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        let digit_at = |i: usize| {
+            replacement
+                .get(i)
+                .and_then(|c| u8::try_from(c).ok())
+                .filter(u8::is_ascii_digit)
+                .map(|d| usize::from(d - b'0'))
+        };
+
+        match replacement.get(i + 1).and_then(|c| u8::try_from(c).ok()) {
+            Some(b'$') => {
+                out.push(c);
+                i += 2;
+            }
+            Some(b'&') => {
+                out.push_str(&text[matched.clone()]);
+                i += 2;
+            }
+            Some(b'`') => {
+                out.push_str(&text[..matched.start]);
+                i += 2;
+            }
+            Some(b'\'') => {
+                out.push_str(&text[matched.end..]);
+                i += 2;
+            }
+            _ => {
+                // Prefer a two-digit group number, if there are that many
+                // groups.
+                let group = digit_at(i + 1).and_then(|first| {
+                    let two_digits = digit_at(i + 2).map(|second| (first * 10 + second, 3));
+                    two_digits
+                        .filter(|(n, _)| (1..=captures.len()).contains(n))
+                        .or_else(|| {
+                            Some((first, 2)).filter(|(n, _)| (1..=captures.len()).contains(n))
+                        })
+                });
+
+                match group {
+                    Some((n, len)) => {
+                        if let Some(capture) = &captures[n - 1] {
+                            out.push_str(&text[capture.clone()]);
+                        }
+                        i += len;
+                    }
+                    None => {
+                        out.push(c);
+                        i += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Implements `String.slice`
 fn slice<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -327,20 +494,36 @@ fn split<'gc>(
                     .into(),
             );
         }
-        if delimiter
-            .coerce_to_object(activation)?
-            .as_regexp()
-            .is_some()
-        {
-            log::warn!("string.split(regex) - not implemented");
-        }
         let this = Value::from(this).coerce_to_string(activation)?;
-        let delimiter = delimiter.coerce_to_string(activation)?;
         let limit = match args.get(1).unwrap_or(&Value::Undefined) {
             Value::Undefined => usize::MAX,
             limit => limit.coerce_to_i32(activation)?.max(0) as usize,
         };
 
+        let regexp_pieces = match delimiter {
+            Value::Object(delimiter) => delimiter
+                .as_regexp_mut(activation.context.gc_context)
+                .map(|mut regexp| regexp.split(this, limit)),
+            _ => None,
+        };
+
+        if let Some(pieces) = regexp_pieces {
+            let storage = pieces
+                .into_iter()
+                .map(|piece| match piece {
+                    Some(range) => {
+                        AvmString::new(activation.context.gc_context, &this[range]).into()
+                    }
+                    None => Value::Undefined,
+                })
+                .collect();
+
+            return Ok(ArrayObject::from_storage(activation, storage)
+                .unwrap()
+                .into());
+        }
+
+        let delimiter = delimiter.coerce_to_string(activation)?;
         let storage = if delimiter.is_empty() {
             // When using an empty delimiter, Str::split adds an extra beginning and trailing item, but Flash does not.
             // e.g., split("foo", "") returns ["", "f", "o", "o", ""] in Rust but ["f, "o", "o"] in Flash.
@@ -459,6 +642,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     let mut write = class.write(mc);
     write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
     write.set_instance_allocator(primitive_allocator);
+    write.set_call_handler(Method::from_builtin(
+        call_handler,
+        "<String call handler>",
+        mc,
+    ));
 
     const PUBLIC_INSTANCE_PROPERTIES: &[(
         &str,
@@ -474,6 +662,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("indexOf", index_of),
         ("lastIndexOf", last_index_of),
         ("match", match_s),
+        ("replace", replace),
         ("slice", slice),
         ("split", split),
         ("substr", substr),
@@ -516,3 +705,282 @@ fn string_wrapping_index(i: f64, len: usize) -> usize {
         (i as usize).min(len)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Call a method of a string, like `"text".name(args)`.
+    fn string_call<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        text: impl Into<Value<'gc>>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let text: Value<'gc> = text.into();
+        let text = text.coerce_to_object(activation)?;
+
+        call(activation, text, name, args)
+    }
+
+    /// Construct a regular expression, like a `/pattern/flags` literal.
+    fn regexp<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        pattern: &'static str,
+        flags: &'static str,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(construct(activation, "", "RegExp", &[pattern.into(), flags.into()])?.into())
+    }
+
+    /// Show the length and elements of an array, joined by `|`.
+    fn traced_join<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        parts: Object<'gc>,
+    ) -> Result<String, Error> {
+        let length = get(activation, parts, "length")?;
+        let joined = call(activation, parts, "join", &["|".into()])?;
+
+        Ok(format!(
+            "{}: {}",
+            traced(activation, length)?,
+            traced(activation, joined)?
+        ))
+    }
+
+    /// Split a string and show the result like `traced_join`.
+    fn traced_split<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        text: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<String, Error> {
+        let parts = string_call(activation, text, "split", args)?.coerce_to_object(activation)?;
+
+        traced_join(activation, parts)
+    }
+
+    #[test]
+    fn split_by_strings_and_patterns() {
+        with_avm2(|activation| {
+            assert_eq!(
+                traced_split(activation, "a,b,c", &[",".into(), 2.into()])?,
+                "2: a|b"
+            );
+            assert_eq!(
+                traced_split(activation, "a,b,c", &[",".into(), 0.into()])?,
+                "0: "
+            );
+            assert_eq!(traced_split(activation, "abc", &["".into()])?, "3: a|b|c");
+
+            let digit = regexp(activation, "\\d", "")?;
+            assert_eq!(traced_split(activation, "a1b2c", &[digit])?, "3: a|b|c");
+            assert_eq!(traced_split(activation, "1a2", &[digit])?, "3: |a|");
+            let digits = regexp(activation, "\\d+", "")?;
+            assert_eq!(
+                traced_split(activation, "a1b22c", &[digits, 2.into()])?,
+                "2: a|b"
+            );
+
+            // Captured groups are spliced into the result.
+            let captured = regexp(activation, "(\\d)", "")?;
+            assert_eq!(
+                traced_split(activation, "a1b2c", &[captured])?,
+                "5: a|1|b|2|c"
+            );
+            let args = [captured, 4.into()];
+            assert_eq!(traced_split(activation, "a1b2c", &args)?, "4: a|1|b|2");
+            let either = regexp(activation, "(-)|(_)", "")?;
+            let groups = string_call(activation, "a-b_c", "split", &[either])?;
+            let groups = groups.coerce_to_object(activation)?;
+            assert_eq!(traced_join(activation, groups)?, "7: a|-||b||_|c");
+            assert_eq!(get(activation, groups, "2")?, Value::Undefined);
+            assert_eq!(get(activation, groups, "4")?, Value::Undefined);
+
+            // Empty matches split between characters.
+            let empty = regexp(activation, "(?:)", "")?;
+            assert_eq!(traced_split(activation, "abc", &[empty])?, "3: a|b|c");
+            let spaces = regexp(activation, " *", "")?;
+            assert_eq!(traced_split(activation, "a b  c", &[spaces])?, "3: a|b|c");
+            let x = regexp(activation, "x", "")?;
+            assert_eq!(traced_split(activation, "", &[x])?, "1: ");
+            let xs = regexp(activation, "x*", "")?;
+            assert_eq!(traced_split(activation, "", &[xs])?, "0: ");
+
+            // Splitting ignores and keeps `lastIndex`.
+            let global = regexp(activation, ",", "g")?;
+            let global_object = global.coerce_to_object(activation)?;
+            set(activation, global_object, "lastIndex", 3)?;
+            assert_eq!(traced_split(activation, "x,y", &[global])?, "2: x|y");
+            assert_eq!(get(activation, global_object, "lastIndex")?, 3.into());
+
+            Ok(())
+        })
+    }
+
+    /// Call `String.fromCharCode`.
+    fn from_char_codes<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        codes: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let string_class = activation.avm2().classes().string;
+
+        call(activation, string_class, "fromCharCode", codes)
+    }
+
+    /// Read the length of a string.
+    fn length_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        text: Value<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        let text = text.coerce_to_object(activation)?;
+
+        get(activation, text, "length")
+    }
+
+    #[test]
+    fn surrogate_pairs_are_two_code_units() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let emoji = from_char_codes(activation, &[0xD83D.into(), 0xDE00.into()])?;
+            assert_eq!(length_of(activation, emoji)?, 2.into());
+            assert_eq!(emoji, AvmString::new_utf8(mc, "\u{1F600}").into());
+            let code = string_call(activation, emoji, "charCodeAt", &[0.into()])?;
+            assert_eq!(code, 55357.into());
+            let code = string_call(activation, emoji, "charCodeAt", &[1.into()])?;
+            assert_eq!(code, 56832.into());
+            for index in [2, -1] {
+                let code = string_call(activation, emoji, "charCodeAt", &[index.into()])?;
+                assert!(code.coerce_to_number(activation)?.is_nan());
+            }
+
+            // Each half of the pair is a string of its own.
+            let high = string_call(activation, emoji, "charAt", &[0.into()])?;
+            let code = string_call(activation, high, "charCodeAt", &[0.into()])?;
+            assert_eq!(code, 55357.into());
+            let low = string_call(activation, emoji, "charAt", &[1.into()])?;
+            assert_eq!(length_of(activation, low)?, 1.into());
+            let past_the_end = string_call(activation, emoji, "charAt", &[2.into()])?;
+            assert_eq!(past_the_end, "".into());
+
+            let args = [55357.into(), 56832.into()];
+            assert_eq!(from_char_codes(activation, &args)?, emoji);
+            let mixed = AvmString::new_utf8(mc, "a\u{1F600}\u{4E2D}").into();
+            assert_eq!(length_of(activation, mixed)?, 4.into());
+            let code = string_call(activation, mixed, "charCodeAt", &[3.into()])?;
+            assert_eq!(code, 20013.into());
+
+            // Codes are truncated to 16 bits.
+            let truncated = from_char_codes(activation, &[0x1F600.into()])?;
+            assert_eq!(length_of(activation, truncated)?, 1.into());
+            let code = string_call(activation, truncated, "charCodeAt", &[0.into()])?;
+            assert_eq!(code, 62976.into());
+
+            Ok(())
+        })
+    }
+
+    /// Call `text.replace(pattern, replacement)` and trace the result.
+    fn traced_replace<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        text: &'static str,
+        pattern: Value<'gc>,
+        replacement: impl Into<Value<'gc>>,
+    ) -> Result<String, Error> {
+        let args = [pattern, replacement.into()];
+        let replaced = string_call(activation, text, "replace", &args)?;
+
+        traced(activation, replaced)
+    }
+
+    /// Log the match, index and input, and replace the match with its
+    /// length in angle brackets.
+    fn bracket_length<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut logged = Vec::new();
+        for arg in args {
+            logged.push(traced(activation, *arg)?);
+        }
+        log(logged.join(", "));
+        let replacement = format!("<{}>", logged[0].len());
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, replacement).into())
+    }
+
+    /// Swap the two captured groups around an `=`.
+    fn swap_groups<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = traced(activation, args[1])?;
+        let value = traced(activation, args[2])?;
+        let swapped = format!("{}={}", value, name);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, swapped).into())
+    }
+
+    #[test]
+    fn replacement_patterns_and_functions() {
+        with_avm2(|activation| {
+            let l = regexp(activation, "l", "")?;
+            assert_eq!(traced_replace(activation, "hello", l, "[$&]")?, "he[l]lo");
+            let all_l = regexp(activation, "l", "g")?;
+            assert_eq!(
+                traced_replace(activation, "hello", all_l, "[$&]")?,
+                "he[l][l]o"
+            );
+
+            // String patterns are literal and replace the first match.
+            assert_eq!(
+                traced_replace(activation, "hello", "l".into(), "L")?,
+                "heLlo"
+            );
+            assert_eq!(
+                traced_replace(activation, "a.b.c", ".".into(), "-")?,
+                "a-b.c"
+            );
+            assert_eq!(
+                traced_replace(activation, "hello", "z".into(), "y")?,
+                "hello"
+            );
+
+            let words = regexp(activation, "(\\w+)\\s(\\w+)", "")?;
+            let swapped = traced_replace(activation, "John Smith", words, "$2, $1")?;
+            assert_eq!(swapped, "Smith, John");
+            assert_eq!(
+                traced_replace(activation, "price", "price".into(), "$$5")?,
+                "$5"
+            );
+            let around = traced_replace(activation, "abc", "b".into(), "[$`|$']")?;
+            assert_eq!(around, "a[a|c]c");
+
+            // References to missing groups are kept as written, and groups
+            // that did not participate are empty.
+            let b = regexp(activation, "(b)", "")?;
+            assert_eq!(traced_replace(activation, "abc", b, "$2$1$0")?, "a$2b$0c");
+            assert_eq!(traced_replace(activation, "abc", b, "$01$10")?, "abb0c");
+            let optional = regexp(activation, "(x)?b", "")?;
+            assert_eq!(traced_replace(activation, "abc", optional, "[$1]")?, "a[]c");
+            let plain_b = regexp(activation, "b", "")?;
+            assert_eq!(traced_replace(activation, "abc", plain_b, "$")?, "a$c");
+            let empty = regexp(activation, "(?:)", "g")?;
+            assert_eq!(traced_replace(activation, "abc", empty, "-")?, "-a-b-c-");
+
+            // Functions are called with the match, the groups, the index and
+            // the input.
+            let digits = regexp(activation, "\\d+", "g")?;
+            let callback = function(activation, bracket_length)?;
+            let replaced = traced_replace(activation, "a1b22", digits, callback)?;
+            assert_eq!(replaced, "a<1>b<2>");
+            assert_eq!(take_log(), ["1, 1, a1b22", "22, 3, a1b22"]);
+            let pair = regexp(activation, "(\\w)=(\\d)", "")?;
+            let callback = function(activation, swap_groups)?;
+            assert_eq!(traced_replace(activation, "x=1", pair, callback)?, "1=x");
+
+            Ok(())
+        })
+    }
+}
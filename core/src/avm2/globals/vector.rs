@@ -181,6 +181,64 @@ pub fn specialized_class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Vector`'s call handler.
+///
+/// Calling a specialized `Vector` class as a function converts an `Array`,
+/// another `Vector`, or any other array-like object into a new vector of that
+/// class, coercing each element to the vector's type.
+pub fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let vector_class = activation
+        .subclass_object()
+        .ok_or("TypeError: Vector conversion called without a Vector class")?;
+    let value_type = vector_class
+        .as_class_params()
+        .flatten()
+        .unwrap_or_else(|| activation.avm2().classes().object);
+    let source = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    let source_object = match source {
+        Value::Object(object) => object,
+        _ => {
+            let value_type_name = value_type.inner_class_definition().read().name();
+            let namespace = value_type_name.namespace().as_uri();
+            let value_type_name = if namespace.is_empty() {
+                value_type_name.local_name().to_string()
+            } else {
+                format!("{}.{}", namespace, value_type_name.local_name())
+            };
+
+            return Err(format!(
+                "TypeError: Error #1034: Type Coercion failed: cannot convert {} to __AS3__.vec.Vector.<{}>.",
+                source.coercion_error_description(activation),
+                value_type_name
+            )
+            .into());
+        }
+    };
+
+    // A vector of the requested type converts to itself.
+    if let Some(source_class) = source_object.instance_of() {
+        if Object::ptr_eq(source_class, vector_class) {
+            return Ok(source);
+        }
+    }
+
+    let mut new_storage = VectorStorage::new(0, false, value_type, activation);
+    let mut iter = ArrayIter::new(activation, source_object)?;
+
+    while let Some(r) = iter.next(activation) {
+        let (_, item) = r?;
+
+        new_storage.push(item.coerce_to_type(activation, value_type)?)?;
+    }
+
+    Ok(VectorObject::from_vector(new_storage, activation)?.into())
+}
+
 /// `Vector.length` getter
 pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -334,16 +392,37 @@ where
 
     if let Some(this) = this {
         if let Some(vector) = this.as_vector_storage() {
+            // A vector that contains itself joins as an empty string the
+            // second time around.
+            if activation
+                .avm2()
+                .join_stack
+                .iter()
+                .any(|joining| Object::ptr_eq(*joining, this))
+            {
+                return Ok("".into());
+            }
+
             let string_separator = separator.coerce_to_string(activation)?;
             let mut accum = Vec::with_capacity(vector.length());
 
-            for (_, item) in vector.iter().enumerate() {
-                if matches!(item, Value::Undefined) || matches!(item, Value::Null) {
-                    accum.push("".into());
+            activation.avm2().join_stack.push(this);
+            for item in vector.iter() {
+                let item = if matches!(item, Value::Undefined) || matches!(item, Value::Null) {
+                    Ok("".into())
                 } else {
-                    accum.push(conv(item, activation)?.coerce_to_string(activation)?);
+                    conv(item, activation).and_then(|v| v.coerce_to_string(activation))
+                };
+
+                match item {
+                    Ok(item) => accum.push(item),
+                    Err(e) => {
+                        activation.avm2().join_stack.pop();
+                        return Err(e);
+                    }
                 }
             }
+            activation.avm2().join_stack.pop();
 
             return Ok(AvmString::new(
                 activation.context.gc_context,
@@ -692,16 +771,14 @@ pub fn push<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
-        if let Some(mut vs) = this.as_vector_storage_mut(activation.context.gc_context) {
-            let value_type = vs.value_type();
-
-            for arg in args {
-                let coerced_arg = arg.coerce_to_type(activation, value_type)?;
+        if let Some(items) = coerce_new_items(activation, this, args)? {
+            if let Some(mut vs) = this.as_vector_storage_mut(activation.context.gc_context) {
+                for item in items {
+                    vs.push(item)?;
+                }
 
-                vs.push(coerced_arg)?;
+                return Ok(vs.length().into());
             }
-
-            return Ok(vs.length().into());
         }
     }
 
@@ -730,22 +807,48 @@ pub fn unshift<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
-        if let Some(mut vs) = this.as_vector_storage_mut(activation.context.gc_context) {
-            let value_type = vs.value_type();
-
-            for arg in args.iter().rev() {
-                let coerced_arg = arg.coerce_to_type(activation, value_type)?;
+        if let Some(items) = coerce_new_items(activation, this, args)? {
+            if let Some(mut vs) = this.as_vector_storage_mut(activation.context.gc_context) {
+                for item in items.into_iter().rev() {
+                    vs.unshift(item)?;
+                }
 
-                vs.unshift(coerced_arg)?;
+                return Ok(vs.length().into());
             }
-
-            return Ok(vs.length().into());
         }
     }
 
     Ok(Value::Undefined)
 }
 
+/// Coerce items about to be added to a vector to its element type.
+///
+/// Fixed vectors are rejected before anything is coerced. Coercion may call
+/// back into user code, so it must finish before the vector is borrowed
+/// mutably to store the results. Yields `None` if `this` is not a vector.
+fn coerce_new_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<Vec<Value<'gc>>>, Error> {
+    let value_type = match this.as_vector_storage() {
+        Some(vs) if vs.is_fixed() => {
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            )
+        }
+        Some(vs) => vs.value_type(),
+        None => return Ok(None),
+    };
+
+    let mut items = Vec::with_capacity(args.len());
+    for arg in args {
+        items.push(arg.coerce_to_type(activation, value_type)?);
+    }
+
+    Ok(Some(items))
+}
+
 /// Implements `Vector.insertAt`
 pub fn insert_at<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -1008,6 +1111,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         "<Vector specialized class initializer>",
         mc,
     ));
+    write.set_call_handler(Method::from_builtin(
+        call_handler,
+        "<Vector call handler>",
+        mc,
+    ));
 
     const PUBLIC_INSTANCE_PROPERTIES: &[(
         &str,
@@ -1046,3 +1154,127 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::ClassObject;
+    use crate::avm2::test_utils::*;
+
+    /// The `Vector` class specialized for a type, like `Vector.<T>`.
+    fn vector_class<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value_type: Value<'gc>,
+    ) -> Result<ClassObject<'gc>, Error> {
+        let vector = activation.avm2().classes().vector;
+
+        vector.apply(activation, &[value_type])
+    }
+
+    #[test]
+    fn added_items_are_coerced() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let ints = vector_class(activation, int.into())?.construct(activation, &[])?;
+
+            let args = [3.7.into(), (-2.9).into(), "12".into()];
+            assert_eq!(call(activation, ints, "push", &args)?, 3.into());
+            assert_eq!(traced(activation, ints)?, "3,-2,12");
+            let args = [Value::Null, Value::Undefined];
+            assert_eq!(call(activation, ints, "push", &args)?, 5.into());
+            assert_eq!(traced(activation, ints)?, "3,-2,12,0,0");
+            let args = [1.5.into(), 2.5.into()];
+            assert_eq!(call(activation, ints, "unshift", &args)?, 7.into());
+            assert_eq!(traced(activation, ints)?, "1,2,3,-2,12,0,0");
+
+            let number = activation.avm2().classes().number;
+            let numbers = vector_class(activation, number.into())?.construct(activation, &[])?;
+            let args = [Value::Null, Value::Undefined, "1.5".into()];
+            assert_eq!(call(activation, numbers, "push", &args)?, 3.into());
+            assert_eq!(traced(activation, numbers)?, "0,NaN,1.5");
+
+            let string = activation.avm2().classes().string;
+            let strings = vector_class(activation, string.into())?.construct(activation, &[])?;
+            let args = [1.into(), Value::Null];
+            assert_eq!(call(activation, strings, "push", &args)?, 2.into());
+            assert_eq!(get(activation, strings, "0")?, "1".into());
+            assert_eq!(get(activation, strings, "1")?, Value::Null);
+
+            assert_eq!(call(activation, ints, "pop", &[])?, 0.into());
+            assert_eq!(call(activation, ints, "shift", &[])?, 1.into());
+            assert_eq!(traced(activation, ints)?, "2,3,-2,12,0");
+
+            let args = [2.into(), true.into()];
+            let fixed = vector_class(activation, int.into())?.construct(activation, &args)?;
+            let error = call(activation, fixed, "push", &[1.into()]).unwrap_err();
+            assert!(error.to_string().starts_with("RangeError: Error #1126"));
+            assert_eq!(get(activation, fixed, "length")?, 2.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn conversion_coerces_elements() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let int_vector = vector_class(activation, int.into())?;
+            let source = array(activation, &[1.9.into(), 2.1.into(), 3.into()])?;
+            let ints = int_vector.call(None, &[source.into()], activation)?;
+            assert_eq!(traced(activation, ints)?, "1,2,3");
+            let ints = ints.coerce_to_object(activation)?;
+            assert_eq!(get(activation, ints, "length")?, 3.into());
+
+            // Converting to another type copies the elements.
+            let number = activation.avm2().classes().number;
+            let number_vector = vector_class(activation, number.into())?;
+            let numbers = number_vector.call(None, &[ints.into()], activation)?;
+            assert_eq!(traced(activation, numbers)?, "1,2,3");
+            let numbers = numbers.coerce_to_object(activation)?;
+            set(activation, numbers, "0", 0.5)?;
+            assert_eq!(traced(activation, numbers)?, "0.5,2,3");
+            assert_eq!(traced(activation, ints)?, "1,2,3");
+
+            let string = activation.avm2().classes().string;
+            let string_vector = vector_class(activation, string.into())?;
+            let source = array(activation, &[1.into(), "two".into(), Value::Null])?;
+            let strings = string_vector.call(None, &[source.into()], activation)?;
+            let strings = strings.coerce_to_object(activation)?;
+            assert_eq!(get(activation, strings, "length")?, 3.into());
+            assert_eq!(get(activation, strings, "0")?, "1".into());
+            assert_eq!(get(activation, strings, "1")?, "two".into());
+            assert_eq!(get(activation, strings, "2")?, Value::Null);
+
+            // A vector of the requested type converts to itself.
+            let same = int_vector.call(None, &[ints.into()], activation)?;
+            assert_eq!(same, ints.into());
+
+            let error = int_vector.call(None, &[5.into()], activation).unwrap_err();
+            assert!(error.to_string().starts_with("TypeError: Error #1034"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn cycles_print_as_empty() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let ints = vector_class(activation, int.into())?.construct(activation, &[])?;
+            call(activation, ints, "push", &[4.into(), 5.into(), 6.into()])?;
+            assert_eq!(traced(activation, ints)?, "4,5,6");
+
+            let object = activation.avm2().classes().object;
+            let objects = vector_class(activation, object.into())?;
+            let self_vector = objects.construct(activation, &[])?;
+            let args = ["a".into(), self_vector.into(), "b".into()];
+            call(activation, self_vector, "push", &args)?;
+            assert_eq!(traced(activation, self_vector)?, "a,,b");
+
+            let both = array(activation, &[self_vector.into(), self_vector.into()])?;
+            assert_eq!(traced(activation, both)?, "a,,b,a,,b");
+
+            Ok(())
+        })
+    }
+}
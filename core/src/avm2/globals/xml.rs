@@ -1,29 +1,377 @@
 //! XML builtin and prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
-use crate::avm2::method::{Method, ParamConfig};
+use crate::avm2::e4x::{E4XNamespace, E4XNode, E4XSettings};
+use crate::avm2::method::{Method, NativeMethodImpl, ParamConfig};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{xml_allocator, Object};
+use crate::avm2::object::{
+    xml_allocator, ArrayObject, FunctionObject, NamespaceObject, Object, TObject,
+};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::string::AvmString;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `XML`'s instance initializer.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_xml_object()) {
+        let mc = activation.context.gc_context;
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+        let node = match value {
+            Value::Object(o) if o.as_xml_object().and_then(|x| x.node()).is_some() => {
+                o.as_xml_object().unwrap().node().unwrap().deep_copy(mc)
+            }
+            Value::Undefined | Value::Null => E4XNode::text(mc, "".into()),
+            value => {
+                let string = value.coerce_to_string(activation)?;
+                let settings = E4XSettings::from_xml_class(activation)?;
+                let mut nodes = E4XNode::parse(&string, activation, &settings)?;
+
+                match nodes.len() {
+                    0 => E4XNode::text(mc, "".into()),
+                    1 => nodes.pop().unwrap(),
+                    _ => return Err("TypeError: Error #1088: The markup in the document following the root element must be well-formed.".into()),
+                }
+            }
+        };
+
+        this.set_node(mc, node);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML`'s native instance initializer.
+///
+/// Native XML objects are created with their node already in place, so this
+/// does not reset it.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Implements `XML`'s class initializer
 pub fn class_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this.unwrap();
+    let scope = activation.create_scopechain();
+    let mut xml_proto = this
+        .get_property(this, &QName::dynamic_name("prototype").into(), activation)?
+        .coerce_to_object(activation)?;
+    let this_class = this.as_class_object().unwrap();
+
+    const PROTOTYPE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("addNamespace", add_namespace),
+        ("inScopeNamespaces", in_scope_namespaces),
+        ("namespace", namespace),
+        ("namespaceDeclarations", namespace_declarations),
+        ("removeNamespace", remove_namespace),
+        ("setNamespace", set_namespace),
+        ("toString", to_string),
+        ("toXMLString", to_xml_string),
+    ];
+
+    for &(name, method) in PROTOTYPE_METHODS {
+        xml_proto.set_property(
+            xml_proto,
+            &QName::dynamic_name(name).into(),
+            FunctionObject::from_method(
+                activation,
+                Method::from_builtin(method, name, activation.context.gc_context),
+                scope,
+                None,
+                Some(this_class),
+            )
+            .into(),
+            activation,
+        )?;
+        xml_proto.set_local_property_is_enumerable(
+            activation.context.gc_context,
+            QName::dynamic_name(name),
+            false,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.AS3::toString` and `XML.prototype.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(node) = this.and_then(|t| t.as_xml_object()).and_then(|x| x.node()) {
+        let settings = E4XSettings::from_xml_class(activation)?;
+        let string = node.xml_to_string(&settings);
+
+        return Ok(AvmString::new_utf8(activation.context.gc_context, string).into());
+    }
+
+    Ok("".into())
+}
+
+/// Implements `XML.AS3::toXMLString` and `XML.prototype.toXMLString`
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(node) = this.and_then(|t| t.as_xml_object()).and_then(|x| x.node()) {
+        let settings = E4XSettings::from_xml_class(activation)?;
+        let string = node.xml_to_xml_string(&settings);
+
+        return Ok(AvmString::new_utf8(activation.context.gc_context, string).into());
+    }
+
+    Ok("".into())
+}
+
+/// Box an E4X namespace into a `Namespace` object.
+fn namespace_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    namespace: &E4XNamespace<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let prefix = namespace.prefix.unwrap_or_else(|| "".into());
+
+    Ok(NamespaceObject::from_namespace_and_prefix(
+        activation,
+        Namespace::Namespace(namespace.uri),
+        Some(prefix),
+    )?
+    .into())
+}
+
+/// Coerce a value to a namespace in the same way as the `Namespace`
+/// constructor, yielding its prefix (if not `undefined`) and URI.
+fn namespace_from_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(Option<AvmString<'gc>>, AvmString<'gc>), Error> {
+    let namespace = match value {
+        Value::Object(o) if o.as_namespace_object().is_some() => o,
+        value => activation
+            .avm2()
+            .classes()
+            .namespace
+            .construct(activation, &[value])?,
+    };
+    let namespace = namespace
+        .as_namespace_object()
+        .ok_or("TypeError: Error #1034: Type Coercion failed: cannot convert to Namespace.")?;
+
+    Ok((namespace.prefix(), namespace.namespace().as_uri()))
+}
+
+/// Build an array of `Namespace` objects from a list of E4X namespaces.
+fn namespaces_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    namespaces: &[E4XNamespace<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut values = Vec::with_capacity(namespaces.len());
+    for namespace in namespaces {
+        values.push(namespace_to_object(activation, namespace)?);
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?.into())
+}
+
+/// Implements `XML.AS3::namespace` and `XML.prototype.namespace`
+pub fn namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(node) = this.and_then(|t| t.as_xml_object()).and_then(|x| x.node()) {
+        match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined => {
+                if node.local_name().is_none() {
+                    return Ok(Value::Null);
+                }
+
+                let namespace = node.namespace().unwrap_or(E4XNamespace {
+                    prefix: None,
+                    uri: "".into(),
+                });
+
+                return namespace_to_object(activation, &namespace);
+            }
+            prefix => {
+                let prefix = prefix.coerce_to_string(activation)?;
+                let namespace = node
+                    .in_scope_namespaces()
+                    .into_iter()
+                    .find(|ns| ns.prefix.unwrap_or_default() == prefix);
+
+                if let Some(namespace) = namespace {
+                    return namespace_to_object(activation, &namespace);
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.AS3::namespaceDeclarations` and
+/// `XML.prototype.namespaceDeclarations`
+pub fn namespace_declarations<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut declarations = Vec::new();
+
+    if let Some(node) = this.and_then(|t| t.as_xml_object()).and_then(|x| x.node()) {
+        // Redeclarations of namespaces that are already in scope are omitted.
+        let ancestor_namespaces = node
+            .parent()
+            .map(|p| p.in_scope_namespaces())
+            .unwrap_or_default();
+
+        declarations = node
+            .namespace_declarations()
+            .into_iter()
+            .filter(|ns| {
+                !ancestor_namespaces
+                    .iter()
+                    .any(|a| a.is_same_declaration(ns))
+            })
+            .collect();
+    }
+
+    namespaces_to_array(activation, &declarations)
+}
+
+/// Implements `XML.AS3::inScopeNamespaces` and
+/// `XML.prototype.inScopeNamespaces`
+pub fn in_scope_namespaces<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let namespaces = this
+        .and_then(|t| t.as_xml_object())
+        .and_then(|x| x.node())
+        .map(|node| node.in_scope_namespaces())
+        .unwrap_or_default();
+
+    namespaces_to_array(activation, &namespaces)
+}
+
+/// Implements `XML.AS3::addNamespace` and `XML.prototype.addNamespace`
+pub fn add_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(node) = this.as_xml_object().and_then(|x| x.node()) {
+            let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+            let (prefix, uri) = namespace_from_value(activation, value)?;
+
+            // Namespaces without a prefix can't be declared.
+            if let Some(prefix) = prefix {
+                node.add_in_scope_namespace(
+                    activation.context.gc_context,
+                    E4XNamespace {
+                        prefix: (!prefix.is_empty()).then(|| prefix),
+                        uri,
+                    },
+                );
+            }
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.AS3::removeNamespace` and `XML.prototype.removeNamespace`
+pub fn remove_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(node) = this.as_xml_object().and_then(|x| x.node()) {
+            let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+            let (prefix, uri) = namespace_from_value(activation, value)?;
+
+            node.remove_namespace(activation.context.gc_context, prefix, uri);
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.AS3::setNamespace` and `XML.prototype.setNamespace`
+pub fn set_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(node) = this.and_then(|t| t.as_xml_object()).and_then(|x| x.node()) {
+        if node.local_name().is_none() {
+            return Ok(Value::Undefined);
+        }
+
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let (prefix, uri) = namespace_from_value(activation, value)?;
+
+        // Attributes declare their namespaces on the element that owns them.
+        let declaring_element = if node.is_attribute() {
+            node.parent()
+        } else {
+            Some(node)
+        };
+
+        let namespace = match prefix {
+            Some(prefix) => Some(E4XNamespace {
+                prefix: (!prefix.is_empty()).then(|| prefix),
+                uri,
+            }),
+            // Namespaces without a prefix reuse one that is already in scope.
+            None if !uri.is_empty() => Some(
+                declaring_element
+                    .map(|e| e.in_scope_namespaces())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|ns| ns.uri == uri)
+                    .unwrap_or(E4XNamespace { prefix: None, uri }),
+            ),
+            None => None,
+        };
+
+        let mc = activation.context.gc_context;
+        node.set_namespace(mc, namespace.clone());
+
+        if let (Some(namespace), Some(element)) = (namespace, declaring_element) {
+            element.add_in_scope_namespace(mc, namespace);
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -48,6 +396,254 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     let mut write = class.write(mc);
     write.set_instance_allocator(xml_allocator);
+    write.set_native_instance_init(Method::from_builtin(
+        native_instance_init,
+        "<XML native instance initializer>",
+        mc,
+    ));
+
+    const PUBLIC_CLASS_BOOLEAN_SETTINGS: &[(&str, bool)] = &[
+        ("ignoreComments", true),
+        ("ignoreProcessingInstructions", true),
+        ("ignoreWhitespace", true),
+        ("prettyPrinting", true),
+    ];
+    for &(name, default) in PUBLIC_CLASS_BOOLEAN_SETTINGS {
+        write.define_class_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "Boolean").into(),
+            Some(default.into()),
+        ));
+    }
+
+    write.define_class_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "prettyIndent"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(Value::Integer(2)),
+    ));
+
+    const AS3_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("addNamespace", add_namespace),
+        ("inScopeNamespaces", in_scope_namespaces),
+        ("namespace", namespace),
+        ("namespaceDeclarations", namespace_declarations),
+        ("removeNamespace", remove_namespace),
+        ("setNamespace", set_namespace),
+        ("toString", to_string),
+        ("toXMLString", to_xml_string),
+    ];
+    write.define_as3_builtin_instance_methods(mc, AS3_INSTANCE_METHODS);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::names::{Multiname, Namespace, QName};
+    use crate::avm2::object::{Object, TObject};
+    use crate::avm2::test_utils::*;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+
+    const COMPLEX: &str = "<root xmlns:p=\"http://example.com/p\" b=\"1\" a=\"x &quot;y&quot; &lt;z &amp;\"><p:child>text &lt; more</p:child><other/></root>";
+    const COMPLEX_OPEN_TAG: &str =
+        "<root xmlns:p=\"http://example.com/p\" b=\"1\" a=\"x &quot;y&quot; &lt;z &amp;\">";
+
+    #[test]
+    fn simple_content_prints_as_text() {
+        with_avm2(|activation| {
+            let simple = construct(
+                activation,
+                "",
+                "XML",
+                &["<a>hello &amp; goodbye</a>".into()],
+            )?;
+
+            let string = call(activation, simple, "toString", &[])?;
+            assert_eq!(traced(activation, string)?, "hello & goodbye");
+            let xml_string = call(activation, simple, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, xml_string)?,
+                "<a>hello &amp; goodbye</a>"
+            );
+            assert_eq!(traced(activation, simple)?, "hello & goodbye");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn complex_content_is_pretty_printed() {
+        with_avm2(|activation| {
+            let xml = class(activation, "", "XML")?;
+            let complex = construct(activation, "", "XML", &[COMPLEX.into()])?;
+            let pretty = format!(
+                "{}\n  <p:child>text &lt; more</p:child>\n  <other/>\n</root>",
+                COMPLEX_OPEN_TAG
+            );
+
+            let string = call(activation, complex, "toString", &[])?;
+            assert_eq!(traced(activation, string)?, pretty);
+            let xml_string = call(activation, complex, "toXMLString", &[])?;
+            assert_eq!(traced(activation, xml_string)?, pretty);
+
+            set(activation, xml, "prettyIndent", 4)?;
+            let xml_string = call(activation, complex, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, xml_string)?,
+                format!(
+                    "{}\n    <p:child>text &lt; more</p:child>\n    <other/>\n</root>",
+                    COMPLEX_OPEN_TAG
+                )
+            );
+            set(activation, xml, "prettyIndent", 2)?;
+
+            set(activation, xml, "prettyPrinting", false)?;
+            let xml_string = call(activation, complex, "toXMLString", &[])?;
+            assert_eq!(traced(activation, xml_string)?, COMPLEX);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn whitespace_handling() {
+        with_avm2(|activation| {
+            let xml = class(activation, "", "XML")?;
+            let source = "<a>\n  <b>x</b>\n</a>";
+
+            let spaced = construct(activation, "", "XML", &[source.into()])?;
+            let xml_string = call(activation, spaced, "toXMLString", &[])?;
+            assert_eq!(traced(activation, xml_string)?, source);
+
+            set(activation, xml, "ignoreWhitespace", false)?;
+            set(activation, xml, "prettyPrinting", false)?;
+            let unspaced = construct(activation, "", "XML", &[source.into()])?;
+            let xml_string = call(activation, unspaced, "toXMLString", &[])?;
+            assert_eq!(traced(activation, xml_string)?, source);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn empty_xml_prints_nothing() {
+        with_avm2(|activation| {
+            let empty = construct(activation, "", "XML", &[])?;
+
+            let xml_string = call(activation, empty, "toXMLString", &[])?;
+            assert_eq!(xml_string, Value::from(""));
+            let string = call(activation, empty, "toString", &[])?;
+            assert_eq!(string, Value::from(""));
+
+            Ok(())
+        })
+    }
+
+    const P_URI: &str = "http://example.com/p";
+
+    /// Evaluate `object.ns::name`, where `ns` is the namespace `P_URI`.
+    fn get_in_p<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+    ) -> Result<Value<'gc>, Error> {
+        let name = Multiname::from(QName::new(Namespace::Namespace(P_URI.into()), name));
+
+        object.get_property(object, &name, activation)
+    }
+
+    /// Evaluate `list.length()`.
+    fn list_length<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        list: Value<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        let list = list.coerce_to_object(activation)?;
+
+        call(activation, list, "length", &[])
+    }
+
+    #[test]
+    fn children_are_looked_up_by_namespace() {
+        with_avm2(|activation| {
+            let parsed = construct(
+                activation,
+                "",
+                "XML",
+                &["<root xmlns:p=\"http://example.com/p\"><p:item>prefixed</p:item><item>plain</item></root>".into()],
+            )?;
+
+            let prefixed = get_in_p(activation, parsed, "item")?;
+            assert_eq!(traced(activation, prefixed)?, "prefixed");
+            let plain = get(activation, parsed, "item")?;
+            assert_eq!(traced(activation, plain)?, "plain");
+
+            let ns = call(activation, parsed, "namespace", &["p".into()])?;
+            assert_eq!(traced(activation, ns)?, P_URI);
+            let ns = call(activation, parsed, "namespace", &["q".into()])?;
+            assert_eq!(ns, Value::Undefined);
+
+            let prefixed = prefixed.coerce_to_object(activation)?;
+            let item = get(activation, prefixed, "0")?.coerce_to_object(activation)?;
+            let ns = call(activation, item, "namespace", &[])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, ns, "prefix")?, "p".into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn namespace_declarations_can_change() {
+        with_avm2(|activation| {
+            let doc = construct(
+                activation,
+                "",
+                "XML",
+                &["<root><item>plain</item></root>".into()],
+            )?;
+            let ns = construct(activation, "", "Namespace", &["p".into(), P_URI.into()])?;
+
+            let declarations = call(activation, doc, "namespaceDeclarations", &[])?
+                .coerce_to_object(activation)?;
+            assert_eq!(get(activation, declarations, "length")?, 0.into());
+
+            call(activation, doc, "addNamespace", &[ns.into()])?;
+            let declarations = call(activation, doc, "namespaceDeclarations", &[])?
+                .coerce_to_object(activation)?;
+            assert_eq!(get(activation, declarations, "length")?, 1.into());
+            let declaration = get(activation, declarations, "0")?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, declaration, "prefix")?, "p".into());
+            let in_scope =
+                call(activation, doc, "inScopeNamespaces", &[])?.coerce_to_object(activation)?;
+            assert_eq!(get(activation, in_scope, "length")?, 1.into());
+            let xml_string = call(activation, doc, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, xml_string)?,
+                "<root xmlns:p=\"http://example.com/p\">\n  <item>plain</item>\n</root>"
+            );
+
+            let items = get(activation, doc, "item")?.coerce_to_object(activation)?;
+            let item = get(activation, items, "0")?.coerce_to_object(activation)?;
+            call(activation, item, "setNamespace", &[ns.into()])?;
+            let prefixed = get_in_p(activation, doc, "item")?;
+            assert_eq!(traced(activation, prefixed)?, "plain");
+            let plain = get(activation, doc, "item")?;
+            assert_eq!(list_length(activation, plain)?, 0.into());
+            let xml_string = call(activation, doc, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, xml_string)?,
+                "<root xmlns:p=\"http://example.com/p\">\n  <p:item>plain</p:item>\n</root>"
+            );
+
+            call(activation, doc, "removeNamespace", &[ns.into()])?;
+            let xml_string = call(activation, doc, "toXMLString", &[])?;
+            assert_eq!(
+                traced(activation, xml_string)?,
+                "<root>\n  <p:item xmlns:p=\"http://example.com/p\">plain</p:item>\n</root>"
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -2,31 +2,172 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::method::{Method, ParamConfig};
+use crate::avm2::e4x::{E4XNode, E4XNodeKind, E4XSettings};
+use crate::avm2::method::{Method, NativeMethodImpl, ParamConfig};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{xml_allocator, Object};
+use crate::avm2::object::{xml_list_allocator, FunctionObject, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::string::AvmString;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `XMLList`'s instance initializer.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_xml_list_object()) {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+        let nodes = match value {
+            Value::Object(o) if o.as_xml_list_object().is_some() => {
+                o.as_xml_list_object().unwrap().nodes()
+            }
+            Value::Object(o) if o.as_xml_object().and_then(|x| x.node()).is_some() => {
+                vec![o.as_xml_object().unwrap().node().unwrap()]
+            }
+            Value::Undefined | Value::Null => Vec::new(),
+            value => {
+                let string = value.coerce_to_string(activation)?;
+                let settings = E4XSettings::from_xml_class(activation)?;
+
+                E4XNode::parse(&string, activation, &settings)?
+            }
+        };
+
+        this.set_nodes(activation.context.gc_context, nodes);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList`'s native instance initializer.
+///
+/// Native XMLList objects are created with their nodes already in place, so
+/// this does not reset them.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
     Ok(Value::Undefined)
 }
 
 /// Implements `XMLList`'s class initializer
 pub fn class_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    let this = this.unwrap();
+    let scope = activation.create_scopechain();
+    let mut xml_list_proto = this
+        .get_property(this, &QName::dynamic_name("prototype").into(), activation)?
+        .coerce_to_object(activation)?;
+    let this_class = this.as_class_object().unwrap();
+
+    const PROTOTYPE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("length", length),
+        ("toString", to_string),
+        ("toXMLString", to_xml_string),
+    ];
+
+    for &(name, method) in PROTOTYPE_METHODS {
+        xml_list_proto.set_property(
+            xml_list_proto,
+            &QName::dynamic_name(name).into(),
+            FunctionObject::from_method(
+                activation,
+                Method::from_builtin(method, name, activation.context.gc_context),
+                scope,
+                None,
+                Some(this_class),
+            )
+            .into(),
+            activation,
+        )?;
+        xml_list_proto.set_local_property_is_enumerable(
+            activation.context.gc_context,
+            QName::dynamic_name(name),
+            false,
+        )?;
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `XMLList.AS3::length` and `XMLList.prototype.length`
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_xml_list_object()) {
+        return Ok(this.nodes().len().into());
+    }
+
+    Ok(Value::Integer(0))
+}
+
+/// Implements `XMLList.AS3::toString` and `XMLList.prototype.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_xml_list_object()) {
+        let nodes = this.nodes();
+        let has_simple_content = match nodes.as_slice() {
+            [node] => node.has_simple_content(),
+            nodes => !nodes.iter().any(|n| n.is_element()),
+        };
+
+        if !has_simple_content {
+            return to_xml_string(activation, Some(this.into()), args);
+        }
+
+        let settings = E4XSettings::from_xml_class(activation)?;
+        let mut string = String::new();
+        for node in nodes {
+            if !matches!(
+                *node.kind(),
+                E4XNodeKind::Comment(_) | E4XNodeKind::ProcessingInstruction(_)
+            ) {
+                string.push_str(&node.xml_to_string(&settings));
+            }
+        }
+
+        return Ok(AvmString::new_utf8(activation.context.gc_context, string).into());
+    }
+
+    Ok("".into())
+}
+
+/// Implements `XMLList.AS3::toXMLString` and `XMLList.prototype.toXMLString`
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_xml_list_object()) {
+        let settings = E4XSettings::from_xml_class(activation)?;
+        let strings: Vec<_> = this
+            .nodes()
+            .into_iter()
+            .map(|node| node.xml_to_xml_string(&settings))
+            .collect();
+
+        return Ok(AvmString::new_utf8(activation.context.gc_context, strings.join("\n")).into());
+    }
+
+    Ok("".into())
+}
+
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
         QName::new(Namespace::public(), "XMLList"),
@@ -47,7 +188,19 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     );
 
     let mut write = class.write(mc);
-    write.set_instance_allocator(xml_allocator);
+    write.set_instance_allocator(xml_list_allocator);
+    write.set_native_instance_init(Method::from_builtin(
+        native_instance_init,
+        "<XMLList native instance initializer>",
+        mc,
+    ));
+
+    const AS3_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("length", length),
+        ("toString", to_string),
+        ("toXMLString", to_xml_string),
+    ];
+    write.define_as3_builtin_instance_methods(mc, AS3_INSTANCE_METHODS);
 
     class
 }
@@ -0,0 +1,172 @@
+//! Registry of `LocalConnection` names and their pending messages
+
+use crate::avm2::amf::deserialize_value;
+use crate::avm2::{Activation, Avm2, Error, Event, Namespace, Object, QName, TObject, Value};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use flash_lso::types::Value as AmfValue;
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use std::collections::hash_map::Entry;
+
+/// A method call sent with `LocalConnection.send`, waiting to be delivered.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Message<'gc> {
+    /// The `LocalConnection` that sent this message.
+    sender: Object<'gc>,
+
+    /// The normalized name of the connection this message was sent to.
+    connection_name: String,
+
+    /// The name of the method to call on the receiver's `client`.
+    method_name: AvmString<'gc>,
+
+    /// The AMF-serialized arguments of the call.
+    #[collect(require_static)]
+    arguments: Vec<AmfValue>,
+}
+
+/// All `LocalConnection` names listened on in this player.
+///
+/// Messages are queued when sent, and delivered to the receiving connection
+/// at the start of the next frame.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    /// The receiving `LocalConnection` object for each connected name.
+    connections: FnvHashMap<String, Object<'gc>>,
+
+    /// Messages waiting to be delivered.
+    messages: Vec<Message<'gc>>,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    /// Connection names are not case sensitive.
+    fn normalize_name(name: AvmString<'gc>) -> String {
+        name.to_utf8_lossy().to_lowercase()
+    }
+
+    /// Start listening for messages sent to `name`.
+    ///
+    /// Returns `false` if another connection is already listening on that
+    /// name.
+    pub fn connect(&mut self, name: AvmString<'gc>, connection: Object<'gc>) -> bool {
+        match self.connections.entry(Self::normalize_name(name)) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(connection);
+                true
+            }
+        }
+    }
+
+    /// Stop listening for messages sent to `name`.
+    pub fn close(&mut self, name: AvmString<'gc>) {
+        self.connections.remove(&Self::normalize_name(name));
+    }
+
+    /// Queue a method call to be delivered to the connection named
+    /// `connection_name`.
+    pub fn send(
+        &mut self,
+        sender: Object<'gc>,
+        connection_name: AvmString<'gc>,
+        method_name: AvmString<'gc>,
+        arguments: Vec<AmfValue>,
+    ) {
+        self.messages.push(Message {
+            sender,
+            connection_name: Self::normalize_name(connection_name),
+            method_name,
+            arguments,
+        });
+    }
+
+    /// Deliver all messages sent since the last delivery.
+    ///
+    /// Each sender is notified with a `StatusEvent`, whose level is `error`
+    /// if nothing was listening on the connection name.
+    pub fn deliver_messages(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let messages = std::mem::take(&mut context.avm2.local_connections.messages);
+
+        for message in messages {
+            let receiver = context
+                .avm2
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+                .copied();
+
+            let level = if let Some(receiver) = receiver {
+                let mut activation = Activation::from_nothing(context.reborrow());
+                if let Err(e) = Self::invoke(&mut activation, receiver, &message) {
+                    log::error!(
+                        "Unhandled error in LocalConnection method {}: {}",
+                        message.method_name,
+                        e
+                    );
+                }
+
+                "status"
+            } else {
+                "error"
+            };
+
+            let mut event = Event::new("status");
+            event.set_bubbles(false);
+            event.set_cancelable(false);
+
+            let properties = [("code", Value::Null), ("level", level.into())];
+            let event_class = context.avm2.classes().statusevent;
+
+            if let Err(e) = Avm2::dispatch_event_with_properties(
+                context,
+                event,
+                event_class,
+                &properties,
+                message.sender,
+            ) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
+    /// Call a message's method on the receiving connection's `client`.
+    fn invoke(
+        activation: &mut Activation<'_, 'gc, '_>,
+        receiver: Object<'gc>,
+        message: &Message<'gc>,
+    ) -> Result<(), Error> {
+        let client = receiver
+            .get_property(
+                receiver,
+                &QName::new(Namespace::public(), "client").into(),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+
+        let mut arguments = Vec::with_capacity(message.arguments.len());
+        for argument in &message.arguments {
+            arguments.push(deserialize_value(activation, argument)?);
+        }
+
+        let method = client.get_property(
+            client,
+            &QName::dynamic_name(message.method_name).into(),
+            activation,
+        )?;
+
+        if let Value::Object(method) = method {
+            method.call(Some(client), &arguments, activation)?;
+
+            Ok(())
+        } else {
+            Err(format!(
+                "Error #2095: flash.net.LocalConnection was unable to invoke callback {}.",
+                message.method_name
+            )
+            .into())
+        }
+    }
+}
@@ -368,6 +368,24 @@ impl<'gc> Method<'gc> {
         }
     }
 
+    /// Access the parameter signature of this method.
+    pub fn signature(&self) -> &[ParamConfig<'gc>] {
+        match self {
+            Method::Native(nm) => &nm.signature,
+            Method::Bytecode(bm) => bm.signature(),
+        }
+    }
+
+    /// Get the return type of this method.
+    ///
+    /// Native methods are not typed, and always return `*`.
+    pub fn return_type(&self) -> Multiname<'gc> {
+        match self {
+            Method::Native { .. } => Multiname::any(),
+            Method::Bytecode(bm) => bm.return_type.clone(),
+        }
+    }
+
     /// Check if this method needs `arguments`.
     pub fn needs_arguments_object(&self) -> bool {
         match self {
@@ -1,6 +1,7 @@
 //! AVM2 names & namespacing
 
 use crate::avm2::activation::Activation;
+use crate::avm2::object::TObject;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -236,6 +237,17 @@ impl<'gc> QName<'gc> {
         })
     }
 
+    /// Converts this `QName` to the dotted form used in error messages,
+    /// such as `flash.display.MovieClip`.
+    pub fn to_error_name(self) -> String {
+        let uri = self.namespace().as_uri();
+        if uri.is_empty() {
+            self.local_name().to_string()
+        } else {
+            format!("{}.{}", uri, self.local_name())
+        }
+    }
+
     pub fn local_name(&self) -> AvmString<'gc> {
         self.name
     }
@@ -288,9 +300,24 @@ pub struct Multiname<'gc> {
     /// The type parameters required to satisfy this multiname. If empty, then
     /// this multiname is satisfied by any type parameters in any amount.
     params: Vec<Multiname<'gc>>,
+
+    /// Whether this multiname names an XML attribute rather than a property.
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
+    /// Determine if an ABC multiname is one of the attribute (`...A`) kinds.
+    fn abc_is_attribute(abc_multiname: &AbcMultiname) -> bool {
+        matches!(
+            abc_multiname,
+            AbcMultiname::QNameA { .. }
+                | AbcMultiname::RTQNameA { .. }
+                | AbcMultiname::RTQNameLA
+                | AbcMultiname::MultinameA { .. }
+                | AbcMultiname::MultinameLA { .. }
+        )
+    }
+
     /// Read a namespace set from the ABC constant pool, and return a list of
     /// copied namespaces.
     fn abc_namespace_set(
@@ -335,6 +362,20 @@ impl<'gc> Multiname<'gc> {
         name: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Self, Error> {
+        // A `QName` used as a late-bound name carries its own namespace.
+        if let Value::Object(object) = name {
+            if let Some(qname_object) = object.as_qname_object() {
+                if let Some(qname) = qname_object.qname().as_deref().copied() {
+                    return Ok(Self {
+                        ns: vec![qname.namespace()],
+                        name: Some(qname.local_name()),
+                        params: Vec::new(),
+                        is_attribute: Self::abc_is_attribute(abc_multiname),
+                    });
+                }
+            }
+        }
+
         match abc_multiname {
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => Ok(Self {
@@ -345,6 +386,7 @@ impl<'gc> Multiname<'gc> {
                 )?,
                 name: Some(name.coerce_to_string(activation)?),
                 params: Vec::new(),
+                is_attribute: Self::abc_is_attribute(abc_multiname),
             }),
             _ => Err("Cannot assemble early-bound multinames using from_multiname_late".into()),
         }
@@ -374,6 +416,7 @@ impl<'gc> Multiname<'gc> {
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
                     params: Vec::new(),
+                    is_attribute: Self::abc_is_attribute(abc_multiname),
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -384,6 +427,7 @@ impl<'gc> Multiname<'gc> {
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
                     params: Vec::new(),
+                    is_attribute: Self::abc_is_attribute(abc_multiname),
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -394,6 +438,7 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![*ns],
                     name: Some(name),
                     params: Vec::new(),
+                    is_attribute: Self::abc_is_attribute(abc_multiname),
                 }
             }
             AbcMultiname::Multiname {
@@ -411,6 +456,7 @@ impl<'gc> Multiname<'gc> {
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
                 params: Vec::new(),
+                is_attribute: Self::abc_is_attribute(abc_multiname),
             },
             AbcMultiname::MultinameL { .. } | AbcMultiname::MultinameLA { .. } => {
                 let name = activation.avm2().pop();
@@ -507,7 +553,10 @@ impl<'gc> Multiname<'gc> {
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let is_attribute = Self::abc_is_attribute(abc_multiname);
+
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -517,6 +566,7 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
                     params: Vec::new(),
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -530,6 +580,7 @@ impl<'gc> Multiname<'gc> {
                 ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
                 params: Vec::new(),
+                is_attribute,
             },
             AbcMultiname::TypeName {
                 base_type,
@@ -568,6 +619,19 @@ impl<'gc> Multiname<'gc> {
             ns: vec![Namespace::Any],
             name: None,
             params: Vec::new(),
+            is_attribute: false,
+        }
+    }
+
+    /// Construct a multiname satisfied by a name in any of the given
+    /// namespaces.
+    #[cfg(test)]
+    pub fn with_namespaces(ns: Vec<Namespace<'gc>>, name: impl Into<AvmString<'gc>>) -> Self {
+        Self {
+            ns,
+            name: Some(name.into()),
+            params: Vec::new(),
+            is_attribute: false,
         }
     }
 
@@ -605,6 +669,46 @@ impl<'gc> Multiname<'gc> {
         ns_match && name_match
     }
 
+    /// Indicates if this multiname names an XML attribute.
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
+    /// Replace the public namespace in this multiname with an XML namespace.
+    ///
+    /// Unqualified E4X element names resolve in the default XML namespace
+    /// rather than the empty one. Attribute names are never affected.
+    pub fn with_default_xml_namespace(&self, uri: AvmString<'gc>) -> Self {
+        let mut multiname = self.clone();
+        if !self.is_attribute {
+            for ns in multiname.ns.iter_mut() {
+                if ns.is_public() {
+                    *ns = Namespace::Namespace(uri);
+                }
+            }
+        }
+
+        multiname
+    }
+
+    /// Apply E4X's rules for names that were only known at runtime.
+    ///
+    /// A name of `*` selects every name, and a name starting with `@` selects
+    /// attributes, as in `xml["@id"]` or `xml.@["*"]`.
+    pub fn as_xml_name(&self, mc: MutationContext<'gc, '_>) -> Self {
+        let mut multiname = self.clone();
+        if let Some(name) = self.name {
+            if &name == b"*" {
+                multiname.name = None;
+            } else if !self.is_attribute && name.starts_with(b'@') {
+                multiname.name = Some(AvmString::new(mc, &name[1..]));
+                multiname.is_attribute = true;
+            }
+        }
+
+        multiname
+    }
+
     /// List the parameters that the selected class must match.
     pub fn params(&self) -> &[Multiname<'gc>] {
         &self.params[..]
@@ -617,6 +721,36 @@ impl<'gc> From<QName<'gc>> for Multiname<'gc> {
             ns: vec![q.ns],
             name: Some(q.name),
             params: Vec::new(),
+            is_attribute: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_names_follow_e4x_string_rules() {
+        gc_arena::rootless_arena(|mc| {
+            let attribute = Multiname::from(QName::new(Namespace::public(), "@id")).as_xml_name(mc);
+            assert!(attribute.is_attribute());
+            assert_eq!(attribute.local_name(), Some("id".into()));
+
+            let mut any_attribute = Multiname::from(QName::new(Namespace::public(), "*"));
+            any_attribute.is_attribute = true;
+            let any_attribute = any_attribute.as_xml_name(mc);
+            assert!(any_attribute.is_attribute());
+            assert_eq!(any_attribute.local_name(), None);
+
+            let mut at_attribute = Multiname::from(QName::new(Namespace::public(), "@id"));
+            at_attribute.is_attribute = true;
+            let at_attribute = at_attribute.as_xml_name(mc);
+            assert_eq!(at_attribute.local_name(), Some("@id".into()));
+
+            let element = Multiname::from(QName::new(Namespace::public(), "item")).as_xml_name(mc);
+            assert!(!element.is_attribute());
+            assert_eq!(element.local_name(), Some("item".into()));
+        });
+    }
+}
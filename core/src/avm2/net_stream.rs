@@ -0,0 +1,803 @@
+//! Registry of `NetStream` objects and the FLV video they play
+
+use crate::avm2::amf::deserialize_value;
+use crate::avm2::globals::flash::net::netconnection::dispatch_net_status;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, TObject, Value};
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use crate::backend::video::{EncodedFrame, VideoBackend, VideoStreamHandle};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::Collect;
+use swf::{VideoCodec, VideoDeblocking};
+
+/// The FLV tag type of video frames.
+const FLV_TAG_VIDEO: u8 = 9;
+
+/// The FLV tag type of script data, such as `onMetaData`.
+const FLV_TAG_SCRIPT_DATA: u8 = 18;
+
+/// The length of an FLV tag header.
+const FLV_TAG_HEADER_LEN: usize = 11;
+
+/// The length of the size of the previous tag that follows every FLV tag.
+const FLV_PREVIOUS_TAG_SIZE_LEN: usize = 4;
+
+/// The frame type of FLV video tags that hold a keyframe.
+const FLV_FRAME_TYPE_KEYFRAME: u8 = 1;
+
+/// A frame of video split off an FLV file.
+#[derive(Debug, PartialEq)]
+struct VideoTag {
+    /// The time at which this frame is shown, in milliseconds.
+    timestamp: u32,
+
+    codec: VideoCodec,
+
+    keyframe: bool,
+
+    /// The displayed size of the video, if this frame declares it.
+    size: Option<(u16, u16)>,
+
+    /// The bitstream of the frame, in the form embedded in SWF files.
+    data: Vec<u8>,
+}
+
+impl VideoTag {
+    /// Parse the body of an FLV video tag.
+    ///
+    /// Returns the codec ID instead if the codec is not one SWF files can
+    /// embed.
+    fn parse(timestamp: u32, body: &[u8]) -> Result<Option<Self>, u8> {
+        let (flags, data) = match body.split_first() {
+            Some((flags, data)) => (*flags, data),
+            None => return Ok(None),
+        };
+        let codec = VideoCodec::from_u8(flags & 0x0F).ok_or(flags & 0x0F)?;
+        let keyframe = flags >> 4 == FLV_FRAME_TYPE_KEYFRAME;
+
+        // FLV files store VP6 frames behind an extra byte holding the number
+        // of pixels to crop off the encoded macroblocks.
+        let (size, data) = match codec {
+            VideoCodec::Vp6 | VideoCodec::Vp6WithAlpha => match data.split_first() {
+                Some((adjustment, data)) => {
+                    let header = if codec == VideoCodec::Vp6WithAlpha {
+                        data.get(3..).unwrap_or_default()
+                    } else {
+                        data
+                    };
+                    (vp6_display_size(*adjustment, header), data)
+                }
+                None => return Ok(None),
+            },
+            _ => (None, data),
+        };
+
+        Ok(Some(Self {
+            timestamp,
+            codec,
+            keyframe,
+            size,
+            data: data.to_vec(),
+        }))
+    }
+}
+
+/// Read the displayed size of a VP6 keyframe.
+///
+/// The frame header holds the encoded size in macroblocks, and the FLV
+/// adjustment byte the number of pixels cropped off the right and bottom.
+fn vp6_display_size(adjustment: u8, header: &[u8]) -> Option<(u16, u16)> {
+    let (&frame_mode, rest) = header.split_first()?;
+    if frame_mode & 0x80 != 0 {
+        // Interframes do not repeat the frame size.
+        return None;
+    }
+
+    let separated_coefficients = frame_mode & 0x01 != 0;
+    let filter_header = rest.first()? & 0x06;
+    // The header may hold the offset of the coefficient data first.
+    let offset = if separated_coefficients || filter_header == 0 {
+        4
+    } else {
+        2
+    };
+    let rows = *header.get(offset)? as u16;
+    let columns = *header.get(offset + 1)? as u16;
+
+    Some((
+        (columns * 16).saturating_sub((adjustment >> 4) as u16),
+        (rows * 16).saturating_sub((adjustment & 0x0F) as u16),
+    ))
+}
+
+/// A complete tag split off an FLV file.
+#[derive(Debug, PartialEq)]
+enum FlvTag {
+    Video(VideoTag),
+
+    /// A video frame in a codec that cannot be decoded, such as H.264.
+    UnsupportedVideo(u8),
+
+    /// The body of a script data tag, such as `onMetaData`.
+    ScriptData(Vec<u8>),
+}
+
+/// Splits FLV data into tags as it arrives.
+#[derive(Default)]
+struct FlvParser {
+    /// Data received that does not yet form a complete tag.
+    buffer: Vec<u8>,
+
+    header_read: bool,
+
+    /// The latest timestamp of any tag, in milliseconds.
+    last_timestamp: u32,
+}
+
+impl FlvParser {
+    /// Append data to the stream, and split off all tags it completes.
+    ///
+    /// Returns `None` if the data is not an FLV file.
+    fn push(&mut self, data: &[u8]) -> Option<Vec<FlvTag>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut position = 0;
+        if !self.header_read {
+            if self.buffer.len() < 3 {
+                return Some(Vec::new());
+            }
+            if &self.buffer[..3] != b"FLV" {
+                return None;
+            }
+            if self.buffer.len() < 9 {
+                return Some(Vec::new());
+            }
+
+            let header = &self.buffer[5..9];
+            let header_len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            position = header_len as usize + FLV_PREVIOUS_TAG_SIZE_LEN;
+            if self.buffer.len() < position {
+                return Some(Vec::new());
+            }
+
+            self.header_read = true;
+        }
+
+        let mut tags = Vec::new();
+        while let Some(header) = self.buffer.get(position..position + FLV_TAG_HEADER_LEN) {
+            let tag_type = header[0] & 0x1F;
+            let data_len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+            // The 24-bit timestamp is extended by an upper byte that follows it.
+            let timestamp = u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+            let body_start = position + FLV_TAG_HEADER_LEN;
+            let body_end = body_start + data_len;
+            if self.buffer.len() < body_end + FLV_PREVIOUS_TAG_SIZE_LEN {
+                break;
+            }
+
+            let body = &self.buffer[body_start..body_end];
+            match tag_type {
+                FLV_TAG_VIDEO => match VideoTag::parse(timestamp, body) {
+                    Ok(Some(tag)) => tags.push(FlvTag::Video(tag)),
+                    Ok(None) => {}
+                    Err(codec_id) => tags.push(FlvTag::UnsupportedVideo(codec_id)),
+                },
+                FLV_TAG_SCRIPT_DATA => tags.push(FlvTag::ScriptData(body.to_vec())),
+                _ => {}
+            }
+
+            self.last_timestamp = self.last_timestamp.max(timestamp);
+            position = body_end + FLV_PREVIOUS_TAG_SIZE_LEN;
+        }
+
+        self.buffer.drain(..position);
+
+        Some(tags)
+    }
+}
+
+/// The playback state of a `NetStream`.
+#[derive(Default)]
+pub struct Playback {
+    parser: FlvParser,
+
+    /// Every video frame received so far, in display order.
+    video_tags: Vec<VideoTag>,
+
+    /// The index of the next video frame to decode.
+    next_tag: usize,
+
+    /// The playback position, in seconds.
+    time: f64,
+
+    playing: bool,
+
+    paused: bool,
+
+    /// Whether the stream's URL is being loaded.
+    loading: bool,
+
+    /// Whether all of the stream's data has been received.
+    complete: bool,
+
+    bytes_loaded: u32,
+
+    bytes_total: u32,
+
+    /// The decoder stream that video frames are decoded with.
+    stream: Option<VideoStreamHandle>,
+
+    /// Whether the video backend has failed to decode this stream.
+    decoding_failed: bool,
+
+    /// The last decoded frame, and the index of its video tag.
+    frame: Option<(u32, BitmapInfo)>,
+
+    /// Whether a frame in a codec that cannot be decoded has been reported.
+    reported_unsupported_codec: bool,
+}
+
+impl Playback {
+    /// Discard all data and start playing anew.
+    fn restart(&mut self) {
+        *self = Self {
+            playing: true,
+            ..Default::default()
+        };
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn bytes_loaded(&self) -> u32 {
+        self.bytes_loaded
+    }
+
+    pub fn bytes_total(&self) -> u32 {
+        self.bytes_total
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause or resume playback, returning `false` if nothing changed.
+    pub fn set_paused(&mut self, paused: bool) -> bool {
+        let changed = self.paused != paused;
+        self.paused = paused;
+        changed
+    }
+
+    /// Move the playback position to the keyframe at or before `offset`
+    /// seconds.
+    pub fn seek(&mut self, offset: f64) {
+        let target = (offset * 1000.0) as u32;
+        let keyframe = self
+            .video_tags
+            .iter()
+            .rposition(|tag| tag.keyframe && tag.timestamp <= target);
+
+        if let Some(keyframe) = keyframe {
+            self.next_tag = keyframe;
+            self.time = self.video_tags[keyframe].timestamp as f64 / 1000.0;
+        } else {
+            self.next_tag = 0;
+            self.time = offset;
+        }
+    }
+
+    /// Discard the data that has not been split into tags yet, as
+    /// `appendBytesAction` does. `expect_header` starts a new FLV file.
+    pub fn reset_data(&mut self, expect_header: bool) {
+        self.parser.buffer.clear();
+        if expect_header {
+            self.parser.header_read = false;
+        }
+    }
+
+    /// Stop playing and discard all data.
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Mark all of the stream's data as received.
+    pub fn end_of_data(&mut self) {
+        self.complete = true;
+    }
+
+    /// Append FLV data to the stream.
+    ///
+    /// Video frames are queued for playback, and the bodies of the script
+    /// data tags are returned. `None` is returned if the data is not an FLV
+    /// file.
+    fn append(&mut self, data: &[u8]) -> Option<Vec<Vec<u8>>> {
+        self.bytes_loaded = self.bytes_loaded.saturating_add(data.len() as u32);
+        self.bytes_total = self.bytes_total.max(self.bytes_loaded);
+
+        let mut script_tags = Vec::new();
+        for tag in self.parser.push(data)? {
+            match tag {
+                FlvTag::Video(tag) => self.video_tags.push(tag),
+                FlvTag::UnsupportedVideo(codec_id) => {
+                    if !self.reported_unsupported_codec {
+                        log::warn!("NetStream: FLV video codec {} is not supported", codec_id);
+                        self.reported_unsupported_codec = true;
+                    }
+                }
+                FlvTag::ScriptData(body) => script_tags.push(body),
+            }
+        }
+
+        Some(script_tags)
+    }
+
+    /// Advance the playback position by `seconds`, decoding every video
+    /// frame that has become due.
+    fn advance(
+        &mut self,
+        seconds: f64,
+        video: &mut dyn VideoBackend,
+        renderer: &mut dyn RenderBackend,
+    ) {
+        if !self.playing || self.paused {
+            return;
+        }
+
+        self.time += seconds;
+        let now = (self.time * 1000.0) as u32;
+        while let Some(tag) = self.video_tags.get(self.next_tag) {
+            if tag.timestamp > now {
+                break;
+            }
+
+            if !self.decoding_failed {
+                if let Err(e) = self.decode(self.next_tag, video, renderer) {
+                    log::error!("NetStream: could not decode video frame: {}", e);
+                    self.decoding_failed = true;
+                }
+            }
+            self.next_tag += 1;
+        }
+    }
+
+    /// Decode the video frame at `index` into the video backend.
+    fn decode(
+        &mut self,
+        index: usize,
+        video: &mut dyn VideoBackend,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<(), crate::backend::video::Error> {
+        let tag = &self.video_tags[index];
+        let stream = match self.stream {
+            Some(stream) => stream,
+            None => {
+                // Without a declared size, frames are left uncropped.
+                let size = self
+                    .video_tags
+                    .iter()
+                    .find_map(|tag| tag.size)
+                    .unwrap_or((u16::MAX, u16::MAX));
+                let stream = video.register_video_stream(
+                    self.video_tags.len() as u32,
+                    size,
+                    tag.codec,
+                    VideoDeblocking::UseVideoPacketValue,
+                )?;
+                self.stream = Some(stream);
+                stream
+            }
+        };
+
+        let frame_id = index as u32;
+        let bitmap = video.decode_video_stream_frame(
+            stream,
+            EncodedFrame {
+                codec: tag.codec,
+                data: &tag.data,
+                frame_id,
+            },
+            renderer,
+        )?;
+        self.frame = Some((frame_id, bitmap));
+
+        Ok(())
+    }
+
+    /// Stop playing if the end of the stream has been reached.
+    ///
+    /// Returns `true` if playback stopped.
+    pub fn finish(&mut self) -> bool {
+        let ended = self.playing
+            && self.complete
+            && self.next_tag >= self.video_tags.len()
+            && self.time * 1000.0 >= self.parser.last_timestamp as f64;
+
+        if ended {
+            self.playing = false;
+        }
+
+        ended
+    }
+}
+
+/// A `NetStream` object and the stream it plays.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct NetStream<'gc> {
+    /// The object that status events for this stream are dispatched to.
+    target: Object<'gc>,
+
+    #[collect(require_static)]
+    playback: Playback,
+}
+
+/// All `NetStream` objects constructed in this player.
+///
+/// Every frame, playing streams advance by the frame's duration, and the
+/// video frames that have become due are decoded into the video backend.
+/// Audio is not played.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct NetStreams<'gc> {
+    streams: Vec<NetStream<'gc>>,
+}
+
+impl<'gc> NetStreams<'gc> {
+    /// Start tracking the stream of a newly constructed `NetStream`.
+    pub fn register(&mut self, target: Object<'gc>) {
+        self.streams.push(NetStream {
+            target,
+            playback: Playback::default(),
+        });
+    }
+
+    pub fn playback(&self, target: Object<'gc>) -> Option<&Playback> {
+        self.streams
+            .iter()
+            .find(|stream| Object::ptr_eq(stream.target, target))
+            .map(|stream| &stream.playback)
+    }
+
+    pub fn playback_mut(&mut self, target: Object<'gc>) -> Option<&mut Playback> {
+        self.streams
+            .iter_mut()
+            .find(|stream| Object::ptr_eq(stream.target, target))
+            .map(|stream| &mut stream.playback)
+    }
+
+    /// The frame that a `Video` attached to `target` should display.
+    pub fn current_frame(&self, target: Object<'gc>) -> Option<(u32, BitmapInfo)> {
+        self.playback(target).and_then(|playback| playback.frame)
+    }
+
+    /// Start playing the data appended with `appendBytes`.
+    pub fn play_appended(
+        activation: &mut Activation<'_, 'gc, '_>,
+        target: Object<'gc>,
+    ) -> Result<(), Error> {
+        if let Some(playback) = activation.context.avm2.net_streams.playback_mut(target) {
+            playback.restart();
+        }
+
+        dispatch_net_status(activation, target, "NetStream.Play.Start", "status")
+    }
+
+    /// Discard the current stream while its URL is loaded.
+    pub fn start_loading(&mut self, target: Object<'gc>) {
+        if let Some(playback) = self.playback_mut(target) {
+            playback.close();
+            playback.loading = true;
+        }
+    }
+
+    /// Play the data loaded for a `NetStream`, or report that it could not
+    /// be loaded.
+    pub fn load_complete(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), Error> {
+        match context.avm2.net_streams.playback_mut(target) {
+            // The stream was closed or replaced while loading.
+            Some(playback) if playback.loading => {
+                if data.is_some() {
+                    playback.restart();
+                    playback.end_of_data();
+                } else {
+                    playback.close();
+                }
+            }
+            _ => return Ok(()),
+        }
+
+        let mut activation = Activation::from_nothing(context.reborrow());
+        match data {
+            Some(data) => {
+                dispatch_net_status(&mut activation, target, "NetStream.Play.Start", "status")?;
+                Self::append(&mut activation, target, &data)
+            }
+            None => dispatch_net_status(
+                &mut activation,
+                target,
+                "NetStream.Play.StreamNotFound",
+                "error",
+            ),
+        }
+    }
+
+    /// Append FLV data to a stream, passing its script data tags such as
+    /// `onMetaData` on to the stream's `client`.
+    pub fn append(
+        activation: &mut Activation<'_, 'gc, '_>,
+        target: Object<'gc>,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let script_tags = match activation.context.avm2.net_streams.playback_mut(target) {
+            Some(playback) => match playback.append(data) {
+                Some(script_tags) => script_tags,
+                None => {
+                    playback.close();
+
+                    return dispatch_net_status(
+                        activation,
+                        target,
+                        "NetStream.Play.FileStructureInvalid",
+                        "error",
+                    );
+                }
+            },
+            None => return Ok(()),
+        };
+
+        for tag in script_tags {
+            Self::call_script_data_handler(activation, target, &tag)?;
+        }
+
+        Ok(())
+    }
+
+    /// Advance all playing streams by one frame.
+    ///
+    /// `NetStream.Play.Stop` is dispatched to streams that reach their end.
+    pub fn update(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let frame_duration = 1.0 / *context.frame_rate;
+
+        let mut stopped = Vec::new();
+        for stream in context.avm2.net_streams.streams.iter_mut() {
+            stream
+                .playback
+                .advance(frame_duration, context.video, context.renderer);
+            if stream.playback.finish() {
+                stopped.push(stream.target);
+            }
+        }
+
+        for target in stopped {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            if let Err(e) =
+                dispatch_net_status(&mut activation, target, "NetStream.Play.Stop", "status")
+            {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
+    /// Call the `client` handler named by an FLV script data tag, such as
+    /// `onMetaData`, with the value the tag holds.
+    fn call_script_data_handler(
+        activation: &mut Activation<'_, 'gc, '_>,
+        target: Object<'gc>,
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let mut decoder = AMF0Decoder::default();
+        let (rest, name) = match decoder.parse_single_element(tag) {
+            Ok((rest, AmfValue::String(name))) => (rest, name),
+            _ => return Ok(()),
+        };
+        let value = match decoder.parse_single_element(rest) {
+            Ok((_, value)) => deserialize_value(activation, &value)?,
+            Err(_) => Value::Undefined,
+        };
+
+        let client = target
+            .get_property(
+                target,
+                &QName::new(Namespace::public(), "client").into(),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let name = QName::new(
+            Namespace::public(),
+            AvmString::new_utf8(activation.context.gc_context, name),
+        );
+        if client.has_property(name)? {
+            client.call_property(&name.into(), &[value], activation)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::render::{BitmapHandle, NullRenderer};
+    use crate::backend::video::FrameDependency;
+    use generational_arena::Arena;
+
+    /// A video backend that records which frames it was asked to decode.
+    #[derive(Default)]
+    struct StubVideoBackend {
+        streams: Arena<()>,
+        decoded: Vec<u32>,
+    }
+
+    impl VideoBackend for StubVideoBackend {
+        fn register_video_stream(
+            &mut self,
+            _num_frames: u32,
+            _size: (u16, u16),
+            _codec: VideoCodec,
+            _filter: VideoDeblocking,
+        ) -> Result<VideoStreamHandle, crate::backend::video::Error> {
+            Ok(self.streams.insert(()))
+        }
+
+        fn preload_video_stream_frame(
+            &mut self,
+            _stream: VideoStreamHandle,
+            _encoded_frame: EncodedFrame<'_>,
+        ) -> Result<FrameDependency, crate::backend::video::Error> {
+            Ok(FrameDependency::None)
+        }
+
+        fn decode_video_stream_frame(
+            &mut self,
+            _stream: VideoStreamHandle,
+            encoded_frame: EncodedFrame<'_>,
+            _renderer: &mut dyn RenderBackend,
+        ) -> Result<BitmapInfo, crate::backend::video::Error> {
+            self.decoded.push(encoded_frame.frame_id);
+
+            Ok(BitmapInfo {
+                handle: BitmapHandle(0),
+                width: 320,
+                height: 240,
+            })
+        }
+    }
+
+    /// Build an FLV file out of `(tag type, timestamp, body)` tags.
+    fn flv(tags: &[(u8, u32, &[u8])]) -> Vec<u8> {
+        let mut data = b"FLV\x01\x01\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        for (tag_type, timestamp, body) in tags {
+            let len = body.len() as u32;
+            data.push(*tag_type);
+            data.extend_from_slice(&len.to_be_bytes()[1..]);
+            data.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+            data.push((timestamp >> 24) as u8);
+            data.extend_from_slice(&[0, 0, 0]);
+            data.extend_from_slice(body);
+            data.extend_from_slice(&(len + FLV_TAG_HEADER_LEN as u32).to_be_bytes());
+        }
+        data
+    }
+
+    /// H.263 keyframes and interframes, at 8 frames per second.
+    fn h263_flv() -> Vec<u8> {
+        flv(&[
+            (FLV_TAG_VIDEO, 0, &[0x12, 0]),
+            (FLV_TAG_VIDEO, 125, &[0x22, 1]),
+            (FLV_TAG_VIDEO, 250, &[0x12, 2]),
+            (FLV_TAG_VIDEO, 375, &[0x22, 3]),
+        ])
+    }
+
+    fn h263_tag(timestamp: u32, keyframe: bool, data: u8) -> FlvTag {
+        FlvTag::Video(VideoTag {
+            timestamp,
+            codec: VideoCodec::H263,
+            keyframe,
+            size: None,
+            data: vec![data],
+        })
+    }
+
+    #[test]
+    fn parses_tags_split_across_appends() {
+        let data = flv(&[
+            (FLV_TAG_SCRIPT_DATA, 0, b"meta"),
+            (FLV_TAG_VIDEO, 0, &[0x12, 0]),
+            (8, 0, &[0x2f, 0xff]),
+            (FLV_TAG_VIDEO, 0x0100_0000, &[0x27, 1]),
+            (FLV_TAG_VIDEO, 0x0100_0040, &[0x22, 1]),
+        ]);
+
+        let mut parser = FlvParser::default();
+        let mut tags = Vec::new();
+        for byte in data {
+            tags.extend(parser.push(&[byte]).unwrap());
+        }
+
+        assert_eq!(
+            tags,
+            vec![
+                FlvTag::ScriptData(b"meta".to_vec()),
+                h263_tag(0, true, 0),
+                FlvTag::UnsupportedVideo(7),
+                h263_tag(0x0100_0040, false, 1),
+            ]
+        );
+        assert_eq!(parser.last_timestamp, 0x0100_0040);
+        assert!(parser.buffer.is_empty());
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_flv() {
+        assert_eq!(FlvParser::default().push(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn reads_vp6_keyframe_size() {
+        // 20x15 macroblocks, with 4 columns and 8 rows of pixels cropped off.
+        let body = [0x14, 0x48, 0x00, 0x46, 15, 20, 15, 20];
+        let tag = VideoTag::parse(0, &body).unwrap().unwrap();
+
+        assert_eq!(tag.codec, VideoCodec::Vp6);
+        assert_eq!(tag.size, Some((316, 232)));
+        assert_eq!(tag.data, &body[2..]);
+
+        let interframe = VideoTag::parse(40, &[0x24, 0x48, 0x80, 0])
+            .unwrap()
+            .unwrap();
+        assert_eq!(interframe.size, None);
+    }
+
+    #[test]
+    fn plays_frames_as_they_become_due() {
+        let mut video = StubVideoBackend::default();
+        let mut renderer = NullRenderer::new();
+        let mut playback = Playback::default();
+        playback.restart();
+        playback.append(&h263_flv()).unwrap();
+
+        playback.advance(0.125, &mut video, &mut renderer);
+        assert_eq!(video.decoded, vec![0, 1]);
+        assert_eq!(playback.frame.map(|(frame_id, _)| frame_id), Some(1));
+
+        playback.advance(0.0625, &mut video, &mut renderer);
+        assert_eq!(video.decoded, vec![0, 1]);
+
+        playback.set_paused(true);
+        playback.advance(0.5, &mut video, &mut renderer);
+        assert_eq!(video.decoded, vec![0, 1]);
+        assert_eq!(playback.time(), 0.1875);
+
+        playback.set_paused(false);
+        playback.advance(0.1875, &mut video, &mut renderer);
+        assert_eq!(video.decoded, vec![0, 1, 2, 3]);
+        assert!(!playback.finish());
+
+        playback.end_of_data();
+        assert!(playback.finish());
+        assert!(!playback.finish());
+    }
+
+    #[test]
+    fn seek_snaps_to_the_previous_keyframe() {
+        let mut video = StubVideoBackend::default();
+        let mut renderer = NullRenderer::new();
+        let mut playback = Playback::default();
+        playback.restart();
+        playback.append(&h263_flv()).unwrap();
+
+        playback.seek(0.4);
+        assert_eq!(playback.time(), 0.25);
+
+        playback.advance(0.0, &mut video, &mut renderer);
+        assert_eq!(video.decoded, vec![2]);
+    }
+}
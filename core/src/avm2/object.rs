@@ -48,6 +48,7 @@ mod soundchannel_object;
 mod stage_object;
 mod textformat_object;
 mod vector_object;
+mod xml_list_object;
 mod xml_object;
 
 pub use crate::avm2::object::array_object::{array_allocator, ArrayObject};
@@ -74,6 +75,7 @@ pub use crate::avm2::object::soundchannel_object::{soundchannel_allocator, Sound
 pub use crate::avm2::object::stage_object::{stage_allocator, StageObject};
 pub use crate::avm2::object::textformat_object::{textformat_allocator, TextFormatObject};
 pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject};
+pub use crate::avm2::object::xml_list_object::{xml_list_allocator, XmlListObject};
 pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
 
 /// Represents an object that can be directly interacted with by the AVM2
@@ -93,6 +95,7 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         EventObject(EventObject<'gc>),
         DispatchObject(DispatchObject<'gc>),
         XmlObject(XmlObject<'gc>),
+        XmlListObject(XmlListObject<'gc>),
         RegExpObject(RegExpObject<'gc>),
         ByteArrayObject(ByteArrayObject<'gc>),
         LoaderInfoObject(LoaderInfoObject<'gc>),
@@ -153,10 +156,22 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             .map(|c| c.read().is_sealed())
             .unwrap_or(false)
         {
+            // `__proto__` exposes the prototype of dynamic objects.
+            if is_proto_name(multiname) {
+                return Ok(self.proto().map(Value::from).unwrap_or(Value::Null));
+            }
+
             return Ok(Value::Undefined);
         }
 
-        return Err(format!("Cannot get undefined property {:?}", multiname.local_name()).into());
+        Err(format!(
+            "ReferenceError: Error #1069: Property {} not found on {} and there is no default value.",
+            multiname.local_name().unwrap_or_else(|| "*".into()),
+            self.instance_of_class_definition()
+                .map(|class| class.read().name().to_error_name())
+                .unwrap_or_else(|| "Object".to_string())
+        )
+        .into())
     }
 
     /// Retrieve a property by Multiname lookup.
@@ -201,9 +216,13 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             }
         }
 
-        let is_set_only = self.base().has_own_virtual_set_only_property(name);
+        // Reading a property with only a setter yields `undefined`, rather
+        // than whatever a prototype might hold under the same name.
+        if self.base().has_own_virtual_set_only_property(name) {
+            return Ok(Value::Undefined);
+        }
 
-        if self.has_own_property(name)? && !is_set_only {
+        if self.has_own_property(name)? {
             return self.get_property_local(receiver, name, activation);
         }
 
@@ -214,6 +233,25 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         Ok(Value::Undefined)
     }
 
+    /// Retrieve all descendants of this object matching a multiname.
+    ///
+    /// This corresponds directly to the AVM2 operation `getdescendants`,
+    /// which implements the E4X descendant accessor (`..`). Only XML values
+    /// have descendants; all other objects yield an error.
+    fn get_descendants(
+        self,
+        multiname: &Multiname<'gc>,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Err(format!(
+            "TypeError: Error #1016: Descendants operator (..) not supported on type {}.",
+            self.instance_of_class_definition()
+                .map(|class| class.read().name().to_error_name())
+                .unwrap_or_else(|| "Object".to_string())
+        )
+        .into())
+    }
+
     /// Set a property by QName, after multiname resolution and all other
     /// considerations have been taken.
     ///
@@ -253,8 +291,8 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         &mut self,
         _receiver: Object<'gc>,
         multiname: &Multiname<'gc>,
-        _value: Value<'gc>,
-        _activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<QName<'gc>>, Error> {
         // Special case: Unresolvable properties on dynamic classes are treated
         // as initializing a new dynamic property on namespace Public("").
@@ -263,6 +301,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             .map(|c| c.read().is_sealed())
             .unwrap_or(false)
         {
+            // Writing `__proto__` replaces the prototype of dynamic objects.
+            // Non-object values, and prototypes that would form a cycle, are
+            // ignored.
+            if is_proto_name(multiname) {
+                if let Value::Object(proto) = value {
+                    let this: Object<'gc> = (*self).into();
+                    if !Object::ptr_eq(proto, this) && !proto.has_prototype_in_chain(this)? {
+                        self.set_proto(activation.context.gc_context, proto);
+                    }
+                }
+
+                return Ok(None);
+            }
+
             let local_name: Result<AvmString<'gc>, Error> = multiname
                 .local_name()
                 .ok_or_else(|| "Cannot set undefined property using any name".into());
@@ -319,6 +371,23 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             }
         }
 
+        // Getter-only accessors and `const` traits (once initialized by
+        // `initproperty`) are read-only.
+        let is_read_only = {
+            let base = self.base();
+            base.has_own_virtual_get_only_property(name) || base.has_own_const_property(name)
+        };
+        if is_read_only {
+            return Err(format!(
+                "ReferenceError: Error #1074: Illegal write to read-only property {} on {}.",
+                name.local_name(),
+                self.instance_of_class_definition()
+                    .map(|class| class.read().name().to_error_name())
+                    .unwrap_or_else(|| "Object".to_string())
+            )
+            .into());
+        }
+
         self.set_property_local(receiver, name, value, activation)
     }
 
@@ -431,7 +500,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             if let Some((superclass, method_trait)) = class.instance_method(name)? {
                 let method = method_trait.as_method().unwrap();
                 if !method.needs_arguments_object() {
-                    let scope = class.instance_scope();
+                    let scope = superclass.instance_scope();
 
                     return Executable::from_method(method, scope, None, Some(superclass)).exec(
                         Some(self.into()),
@@ -545,7 +614,13 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// match.
     ///
     /// If multiple namespaces could match, this yields an error.
+    ///
+    /// Attribute multinames never resolve to ordinary properties.
     fn resolve_multiname(self, multiname: &Multiname<'gc>) -> Result<Option<QName<'gc>>, Error> {
+        if multiname.is_attribute() {
+            return Ok(None);
+        }
+
         let matching_set = if let Some(local_name) = multiname.local_name() {
             self.resolve_ns(local_name)?
         } else {
@@ -553,10 +628,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         };
 
         if let Some(name) = multiname.local_name() {
-            for ns in matching_set.iter() {
-                if multiname.namespace_set().any(|n| n == ns) {
-                    return Ok(Some(QName::new(*ns, name)));
+            let mut matches = matching_set
+                .iter()
+                .filter(|ns| multiname.namespace_set().any(|n| n == *ns));
+
+            if let Some(ns) = matches.next() {
+                if matches.next().is_some() {
+                    return Err(format!(
+                        "TypeError: Error #1008: {} is ambiguous; Found more than one matching binding.",
+                        name
+                    )
+                    .into());
                 }
+
+                return Ok(Some(QName::new(*ns, name)));
             }
         }
 
@@ -595,6 +680,9 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     ///
     /// By default, this just calls `has_property`, but may be overridden by
     /// other object types to change the behavior of the `in` operator only.
+    ///
+    /// Lazy-bound methods are considered present even if they have not been
+    /// bound yet, so that this agrees with what `get_property` would return.
     fn has_property_via_in(
         self,
         _activation: &mut Activation<'_, 'gc, '_>,
@@ -603,12 +691,13 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         self.has_property(name)
     }
 
-    /// Indicates whether or not a property exists on an object.
+    /// Indicates whether or not a property exists on an object or anywhere in
+    /// its prototype chain.
     fn has_property(self, name: QName<'gc>) -> Result<bool, Error> {
         if self.has_own_property(name)? {
             Ok(true)
         } else if let Some(proto) = self.proto() {
-            Ok(proto.has_own_property(name)?)
+            proto.has_property(name)
         } else {
             Ok(false)
         }
@@ -647,10 +736,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// methods.
     fn delete_property_local(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
     ) -> Result<bool, Error> {
-        let mut base = self.base_mut(gc_context);
+        let mut base = self.base_mut(activation.context.gc_context);
 
         Ok(base.delete_property(name))
     }
@@ -714,7 +803,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
             }
         }
 
-        self.delete_property_local(activation.context.gc_context, name)
+        self.delete_property_local(activation, name)
     }
 
     /// Retrieve the `__proto__` of a given object.
@@ -1299,11 +1388,26 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Unwrap this object as a `NamespaceObject`
+    fn as_namespace_object(self) -> Option<NamespaceObject<'gc>> {
+        None
+    }
+
     /// Unwrap this object as a `QNameObject`
     fn as_qname_object(self) -> Option<QNameObject<'gc>> {
         None
     }
 
+    /// Unwrap this object as an `XmlObject`
+    fn as_xml_object(self) -> Option<XmlObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as an `XmlListObject`
+    fn as_xml_list_object(self) -> Option<XmlListObject<'gc>> {
+        None
+    }
+
     /// Unwrap this object as array storage.
     fn as_array_storage(&self) -> Option<Ref<ArrayStorage<'gc>>> {
         None
@@ -1488,3 +1592,510 @@ impl<'gc> Hash for Object<'gc> {
         self.as_ptr().hash(state);
     }
 }
+
+/// Determine if a multiname names the public `__proto__` property.
+fn is_proto_name(multiname: &Multiname<'_>) -> bool {
+    !multiname.is_attribute()
+        && multiname
+            .local_name()
+            .map(|name| &name == b"__proto__")
+            .unwrap_or(false)
+        && multiname.namespace_set().any(|ns| ns.is_public())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::class::ClassAttributes;
+    use crate::avm2::method::Method;
+    use crate::avm2::test_utils::*;
+
+    fn test_method<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// Evaluate `name in object`.
+    fn is_in<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        object: impl Into<Object<'gc>>,
+    ) -> Result<bool, Error> {
+        object
+            .into()
+            .has_property_via_in(activation, QName::new(Namespace::public(), name))
+    }
+
+    /// Read `"read " + this.stored`.
+    fn read_stored<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let stored = get(activation, this.unwrap(), "stored")?;
+        let read = format!("read {}", traced(activation, stored)?);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, read).into())
+    }
+
+    /// Log the value and write it to `this.stored`.
+    fn write_stored<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        log(format!("write {}", traced(activation, value.clone())?));
+        set(activation, this.unwrap(), "stored", value)?;
+
+        Ok(Value::Undefined)
+    }
+
+    /// Read `this.stored`.
+    fn stored<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        get(activation, this.unwrap(), "stored")
+    }
+
+    /// Log the value and write `"derived " + value` to `this.stored`, like
+    /// an overriding setter.
+    fn write_derived<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let value = traced(activation, value)?;
+        log(format!("write derived {}", value));
+        let stored =
+            AvmString::new_utf8(activation.context.gc_context, format!("derived {}", value));
+        set(activation, this.unwrap(), "stored", stored)?;
+
+        Ok(Value::Undefined)
+    }
+
+    /// Return `"hello from " + this.name`.
+    fn hello_from<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = get(activation, this.unwrap(), "name")?;
+        let hello = format!("hello from {}", traced(activation, name)?);
+
+        Ok(AvmString::new_utf8(activation.context.gc_context, hello).into())
+    }
+
+    #[test]
+    fn in_walks_the_prototype_chain() {
+        with_avm2(|activation| {
+            let class = define_class(
+                activation,
+                "DynamicClass",
+                None,
+                &[("test_method", test_method)],
+                &[("test_static_method", test_method)],
+            )?;
+            let subclass = define_class(activation, "DynamicSubclass", Some(class), &[], &[])?;
+            let obj = class.construct(activation, &[])?;
+            let sub = subclass.construct(activation, &[])?;
+
+            assert!(is_in(activation, "test_method", obj)?);
+            assert!(is_in(activation, "test_static_method", class)?);
+            assert!(is_in(activation, "test_method", sub)?);
+            assert!(is_in(activation, "toString", obj)?);
+            assert!(is_in(activation, "toString", sub)?);
+            assert!(!is_in(activation, "dynProp", obj)?);
+
+            set(activation, obj, "dynProp", 1)?;
+            assert!(is_in(activation, "dynProp", obj)?);
+            assert!(!is_in(activation, "dynProp", sub)?);
+
+            let proto = get(activation, class, "prototype")?.coerce_to_object(activation)?;
+            set(activation, proto, "protoProp", 2)?;
+            assert!(is_in(activation, "protoProp", sub)?);
+
+            let method = get(activation, obj, "test_method")?.coerce_to_object(activation)?;
+            assert!(method.as_executable().is_some());
+            assert!(is_in(activation, "test_method", obj)?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn getter_only_properties_reject_writes() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let mut traits = accessor(mc, "readOnly", Some(read_stored), None);
+            traits.extend(accessor(mc, "writeOnly", None, Some(write_stored)));
+            let class = define_class_with_traits(activation, "Accessors", None, traits)?;
+            let accessors = class.construct(activation, &[])?;
+            set(activation, accessors, "stored", "initial")?;
+
+            let value = get(activation, accessors, "readOnly")?;
+            assert_eq!(traced(activation, value)?, "read initial");
+            set(activation, accessors, "writeOnly", "changed")?;
+            assert_eq!(take_log(), ["write changed"]);
+            assert_eq!(get(activation, accessors, "writeOnly")?, Value::Undefined);
+            let value = get(activation, accessors, "readOnly")?;
+            assert_eq!(traced(activation, value)?, "read changed");
+
+            let error = set(activation, accessors, "readOnly", "overwritten").unwrap_err();
+            assert!(error.to_string().starts_with(
+                "ReferenceError: Error #1074: Illegal write to read-only property readOnly"
+            ));
+            let value = get(activation, accessors, "readOnly")?;
+            assert_eq!(traced(activation, value)?, "read changed");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn setter_only_properties_read_as_undefined() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let mut traits = accessor(mc, "both", Some(stored), Some(write_stored));
+            traits.extend(accessor(mc, "getOnly", Some(read_stored), None));
+            traits.extend(accessor(mc, "setOnly", None, Some(write_stored)));
+            let base_class = define_class_with_traits(activation, "Base", None, traits)?;
+            let traits = vec![Trait::from_setter(
+                QName::new(Namespace::public(), "both"),
+                Method::from_builtin(write_derived, "both", mc),
+            )
+            .with_override()];
+            let derived_class =
+                define_class_with_traits(activation, "Derived", Some(base_class), traits)?;
+
+            // The prototype value is never read through the accessor.
+            let prototype =
+                get(activation, base_class, "prototype")?.coerce_to_object(activation)?;
+            set(activation, prototype, "setOnly", "prototype value")?;
+
+            let base = base_class.construct(activation, &[])?;
+            set(activation, base, "stored", "stored")?;
+            assert_eq!(get(activation, base, "both")?, "stored".into());
+            set(activation, base, "both", "a")?;
+            assert_eq!(get(activation, base, "both")?, "a".into());
+            let value = get(activation, base, "getOnly")?;
+            assert_eq!(traced(activation, value)?, "read a");
+            set(activation, base, "setOnly", "b")?;
+            assert_eq!(get(activation, base, "setOnly")?, Value::Undefined);
+            assert_eq!(get(activation, base, "both")?, "b".into());
+            assert_eq!(take_log(), ["write a", "write b"]);
+
+            // An overridden setter pairs with the inherited getter.
+            let derived = derived_class.construct(activation, &[])?;
+            set(activation, derived, "both", "c")?;
+            assert_eq!(take_log(), ["write derived c"]);
+            let value = get(activation, derived, "both")?;
+            assert_eq!(traced(activation, value)?, "derived c");
+            assert_eq!(get(activation, derived, "setOnly")?, Value::Undefined);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn consts_are_written_once() {
+        with_avm2(|activation| {
+            let string_type = QName::new(Namespace::public(), "String").into();
+            let int_type = QName::new(Namespace::public(), "int").into();
+            let traits = vec![
+                Trait::from_const(QName::new(Namespace::public(), "value"), string_type, None),
+                Trait::from_const(
+                    QName::new(Namespace::public(), "withDefault"),
+                    int_type,
+                    Some(7.into()),
+                ),
+            ];
+            let class = define_class_with_traits(activation, "Constants", None, traits)?;
+
+            // Constructors assign consts with `initproperty`.
+            let mut constants = class.construct(activation, &[])?;
+            constants.init_property(
+                constants,
+                &public_name("value"),
+                "first".into(),
+                activation,
+            )?;
+            assert_eq!(get(activation, constants, "value")?, "first".into());
+            assert_eq!(get(activation, constants, "withDefault")?, 7.into());
+
+            let mut other = class.construct(activation, &[])?;
+            other.init_property(other, &public_name("value"), "second".into(), activation)?;
+            assert_eq!(get(activation, other, "value")?, "second".into());
+            assert_eq!(get(activation, constants, "value")?, "first".into());
+
+            let error = set(activation, constants, "value", "overwritten").unwrap_err();
+            assert!(error.to_string().starts_with(
+                "ReferenceError: Error #1074: Illegal write to read-only property value"
+            ));
+            assert_eq!(get(activation, constants, "value")?, "first".into());
+
+            // Builtin class constants are read-only too.
+            let int_class = activation.avm2().classes().int;
+            assert_eq!(get(activation, int_class, "MAX_VALUE")?, i32::MAX.into());
+            let error = set(activation, int_class, "MAX_VALUE", 0).unwrap_err();
+            assert!(error.to_string().starts_with("ReferenceError: Error #1074"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn instanceof_follows_prototypes_and_is_follows_classes() {
+        with_avm2(|activation| {
+            let base_class = define_class(activation, "Base", None, &[], &[])?;
+            let derived_class = define_class(activation, "Derived", Some(base_class), &[], &[])?;
+            let base_prototype =
+                get(activation, base_class, "prototype")?.coerce_to_object(activation)?;
+
+            // A function constructor borrowing a class prototype.
+            let impostor_function = function(activation, test_method)?;
+            set(activation, impostor_function, "prototype", base_prototype)?;
+            let impostor = impostor_function.construct(activation, &[])?;
+            assert!(impostor.is_instance_of(activation, base_class.into())?);
+            assert!(!impostor.is_of_type(base_class, activation)?);
+            assert!(impostor.is_instance_of(activation, impostor_function)?);
+            let object_class = activation.avm2().classes().object;
+            assert!(impostor.is_of_type(object_class, activation)?);
+
+            let derived = derived_class.construct(activation, &[])?;
+            assert!(derived.is_instance_of(activation, base_class.into())?);
+            assert!(derived.is_of_type(base_class, activation)?);
+            assert!(derived.is_instance_of(activation, impostor_function)?);
+
+            // A prototype is not an instance of its own class.
+            assert!(!base_prototype.is_instance_of(activation, base_class.into())?);
+            assert!(!base_prototype.is_of_type(base_class, activation)?);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn proto_property_reads_and_replaces_the_prototype() {
+        with_avm2(|activation| {
+            let object_prototype = activation.avm2().prototypes().object;
+            let mixin = construct(activation, "", "Object", &[])?;
+            let greet = function(activation, hello_from)?;
+            set(activation, mixin, "greet", greet)?;
+            let target = construct(activation, "", "Object", &[])?;
+            set(activation, target, "name", "target")?;
+
+            assert_eq!(
+                get(activation, target, "__proto__")?,
+                object_prototype.into()
+            );
+            assert_eq!(get(activation, target, "greet")?, Value::Undefined);
+
+            set(activation, target, "__proto__", mixin)?;
+            assert_eq!(get(activation, target, "__proto__")?, mixin.into());
+            let hello = call(activation, target, "greet", &[])?;
+            assert_eq!(traced(activation, hello)?, "hello from target");
+            let args = ["greet".into()];
+            assert_eq!(
+                call(activation, target, "hasOwnProperty", &args)?,
+                false.into()
+            );
+
+            // Values that are not objects, and cycles, are ignored.
+            set(activation, target, "__proto__", "not an object")?;
+            assert_eq!(get(activation, target, "__proto__")?, mixin.into());
+            set(activation, mixin, "__proto__", target)?;
+            assert_eq!(
+                get(activation, mixin, "__proto__")?,
+                object_prototype.into()
+            );
+
+            assert_eq!(get(activation, object_prototype, "__proto__")?, Value::Null);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn sealed_classes_throw_on_undefined_reads() {
+        with_avm2(|activation| {
+            let open_class = define_class(activation, "Open", None, &[], &[])?;
+            let open = open_class.construct(activation, &[])?;
+            assert_eq!(get(activation, open, "bogus")?, Value::Undefined);
+
+            let traits = vec![Trait::from_slot(
+                QName::new(Namespace::public(), "present"),
+                QName::new(Namespace::public(), "String").into(),
+                Some("here".into()),
+            )];
+            let sealed_class = define_class_with_traits(activation, "Sealed", None, traits)?;
+            sealed_class
+                .inner_class_definition()
+                .write(activation.context.gc_context)
+                .set_attributes(ClassAttributes::SEALED);
+            let sealed = sealed_class.construct(activation, &[])?;
+            assert_eq!(get(activation, sealed, "present")?, "here".into());
+            let error = get(activation, sealed, "bogus").unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "ReferenceError: Error #1069: Property bogus not found on Sealed and there is no default value."
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn names_in_several_open_namespaces_are_ambiguous() {
+        with_avm2(|activation| {
+            let ns1 = Namespace::Namespace("http://example.com/ns1".into());
+            let ns2 = Namespace::Namespace("http://example.com/ns2".into());
+            let holder = construct(activation, "", "Object", &[])?;
+            let properties = [
+                (ns1, "shared", "ns1 shared"),
+                (ns2, "shared", "ns2 shared"),
+                (ns1, "onlyOne", "ns1 only"),
+            ];
+            for (ns, name, value) in properties {
+                holder.set_property_local(
+                    holder,
+                    QName::new(ns, name),
+                    value.into(),
+                    activation,
+                )?;
+            }
+
+            // `use namespace ns1; use namespace ns2;`
+            let open = |name| Multiname::with_namespaces(vec![ns1, ns2], name);
+            for (name, value) in [
+                (QName::new(ns1, "shared").into(), "ns1 shared"),
+                (QName::new(ns2, "shared").into(), "ns2 shared"),
+                (open("onlyOne"), "ns1 only"),
+            ] {
+                let found = holder.get_property(holder, &name, activation)?;
+                assert_eq!(traced(activation, found)?, value);
+            }
+
+            let error = holder
+                .get_property(holder, &open("shared"), activation)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "TypeError: Error #1008: shared is ambiguous; Found more than one matching binding."
+            );
+
+            Ok(())
+        })
+    }
+
+    fn named_method<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("method".into())
+    }
+
+    /// Delete a property of an object, like `delete object.name`.
+    fn delete<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        name: &'static str,
+    ) -> Result<bool, Error> {
+        object.delete_property(activation, &public_name(name))
+    }
+
+    /// A typed vector holding the given values, like `new <T>[values]`.
+    fn vector_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value_type: ClassObject<'gc>,
+        values: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let vector = activation.avm2().classes().vector;
+        let vector = vector
+            .apply(activation, &[value_type.into()])?
+            .construct(activation, &[])?;
+        call(activation, vector, "push", values)?;
+
+        Ok(vector)
+    }
+
+    #[test]
+    fn deleting_each_kind_of_property() {
+        with_avm2(|activation| {
+            let mc = activation.context.gc_context;
+            let field = || {
+                Trait::from_slot(
+                    QName::new(Namespace::public(), "field"),
+                    Multiname::any(),
+                    Some("field".into()),
+                )
+            };
+            let traits = vec![
+                field(),
+                Trait::from_method(
+                    QName::new(Namespace::public(), "method"),
+                    Method::from_builtin(named_method, "method", mc),
+                ),
+            ];
+            let sealed_class = define_class_with_traits(activation, "Sealed", None, traits)?;
+            sealed_class
+                .inner_class_definition()
+                .write(mc)
+                .set_attributes(ClassAttributes::SEALED);
+            let sealed = sealed_class.construct(activation, &[])?;
+            assert!(!delete(activation, sealed, "method")?);
+            assert_eq!(call(activation, sealed, "method", &[])?, "method".into());
+            assert!(!delete(activation, sealed, "field")?);
+            assert_eq!(get(activation, sealed, "field")?, "field".into());
+
+            let dynamic_class =
+                define_class_with_traits(activation, "Dynamic", None, vec![field()])?;
+            let dynamic = dynamic_class.construct(activation, &[])?;
+            set(activation, dynamic, "extra", "extra")?;
+            assert!(delete(activation, dynamic, "extra")?);
+            assert_eq!(get(activation, dynamic, "extra")?, Value::Undefined);
+            assert!(!delete(activation, dynamic, "field")?);
+            assert_eq!(get(activation, dynamic, "field")?, "field".into());
+            assert!(delete(activation, dynamic, "missing")?);
+
+            // Deleted elements of arrays leave holes.
+            let values = [1.into(), 2.into(), 3.into(), 4.into()];
+            let arr = array(activation, &values)?;
+            assert!(delete(activation, arr, "2")?);
+            assert_eq!(get(activation, arr, "length")?, 4.into());
+            assert_eq!(get(activation, arr, "2")?, Value::Undefined);
+            assert_eq!(
+                call(activation, arr, "hasOwnProperty", &[2.into()])?,
+                false.into()
+            );
+            assert_eq!(traced(activation, arr)?, "1,2,,4");
+            assert!(delete(activation, arr, "10")?);
+            assert_eq!(get(activation, arr, "length")?, 4.into());
+            assert!(!delete(activation, arr, "length")?);
+            assert_eq!(get(activation, arr, "length")?, 4.into());
+
+            // Vectors cannot have holes, so deleted elements are reset.
+            let int = activation.avm2().classes().int;
+            let values = [5.into(), 6.into(), 7.into()];
+            let ints = vector_of(activation, int, &values)?;
+            assert!(delete(activation, ints, "1")?);
+            assert_eq!(get(activation, ints, "length")?, 3.into());
+            assert_eq!(traced(activation, ints)?, "5,0,7");
+
+            let string = activation.avm2().classes().string;
+            let strings = vector_of(activation, string, &["a".into(), "b".into()])?;
+            assert!(delete(activation, strings, "0")?);
+            assert_eq!(get(activation, strings, "length")?, 2.into());
+            assert_eq!(get(activation, strings, "0")?, Value::Null);
+
+            Ok(())
+        })
+    }
+}
@@ -167,17 +167,24 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
 
     fn delete_property_local(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
     ) -> Result<bool, Error> {
         if name.namespace().is_public() {
             if let Ok(index) = name.local_name().parse::<usize>() {
-                self.0.write(gc_context).array.delete(index);
+                self.0
+                    .write(activation.context.gc_context)
+                    .array
+                    .delete(index);
                 return Ok(true);
             }
         }
 
-        Ok(self.0.write(gc_context).base.delete_property(name))
+        Ok(self
+            .0
+            .write(activation.context.gc_context)
+            .base
+            .delete_property(name))
     }
 
     fn has_own_property(self, name: QName<'gc>) -> Result<bool, Error> {
@@ -217,8 +224,15 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         let last_enumerant = read.base.get_last_enumerant();
         let array_length = read.array.length() as u32;
 
-        if last_index < last_enumerant + array_length {
-            Ok(Some(last_index.saturating_add(1)))
+        // Holes are not enumerable, so skip ahead to the next element that
+        // actually exists.
+        let mut next_index = last_index;
+        while next_index < array_length && read.array.get(next_index as usize).is_none() {
+            next_index += 1;
+        }
+
+        if next_index < last_enumerant + array_length {
+            Ok(Some(next_index.saturating_add(1)))
         } else {
             Ok(None)
         }
@@ -246,7 +260,7 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
     fn property_is_enumerable(&self, name: QName<'gc>) -> bool {
         name.local_name()
             .parse::<u32>()
-            .map(|index| self.0.read().array.length() as u32 >= index)
+            .map(|index| self.0.read().array.get(index as usize).is_some())
             .unwrap_or(false)
             || self.base().property_is_enumerable(name)
     }
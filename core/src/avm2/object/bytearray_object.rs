@@ -166,17 +166,24 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
 
     fn delete_property_local(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
     ) -> Result<bool, Error> {
         if name.namespace().is_public() {
             if let Ok(index) = name.local_name().parse::<usize>() {
-                self.0.write(gc_context).storage.delete(index);
+                self.0
+                    .write(activation.context.gc_context)
+                    .storage
+                    .delete(index);
                 return Ok(true);
             }
         }
 
-        Ok(self.0.write(gc_context).base.delete_property(name))
+        Ok(self
+            .0
+            .write(activation.context.gc_context)
+            .base
+            .delete_property(name))
     }
 
     fn has_own_property(self, name: QName<'gc>) -> Result<bool, Error> {
@@ -715,7 +715,7 @@ impl<'gc> ClassObject<'gc> {
             self.lookup_instance_traits(name, |t| matches!(t.kind(), TraitKind::Method { .. }));
 
         if let Some((superclass_object, method_trait)) = lookup_result {
-            let scope = superclass_object.class_scope();
+            let scope = superclass_object.instance_scope();
             let method = method_trait.as_method().unwrap();
             let callee = FunctionObject::from_method(
                 activation,
@@ -776,7 +776,7 @@ impl<'gc> ClassObject<'gc> {
             self.lookup_instance_traits(name, |t| matches!(t.kind(), TraitKind::Getter { .. }));
 
         if let Some((superclass_object, method_trait)) = lookup_result {
-            let scope = superclass_object.class_scope();
+            let scope = superclass_object.instance_scope();
             let method = method_trait.as_method().unwrap();
             let callee = FunctionObject::from_method(
                 activation,
@@ -839,7 +839,7 @@ impl<'gc> ClassObject<'gc> {
             self.lookup_instance_traits(name, |t| matches!(t.kind(), TraitKind::Setter { .. }));
 
         if let Some((superclass_object, method_trait)) = lookup_result {
-            let scope = superclass_object.class_scope();
+            let scope = superclass_object.instance_scope();
             let method = method_trait.as_method().unwrap();
             let callee = FunctionObject::from_method(
                 activation,
@@ -886,7 +886,7 @@ impl<'gc> ClassObject<'gc> {
         if let Some((superclass, method_trait)) = self.instance_method(name)? {
             let method = method_trait.as_method().unwrap();
             let disp_id = method_trait.disp_id().unwrap();
-            let scope = self.instance_scope();
+            let scope = superclass.instance_scope();
 
             Ok(Some((
                 FunctionObject::from_method(
@@ -929,7 +929,7 @@ impl<'gc> ClassObject<'gc> {
             }) {
                 let name = method_trait.name();
                 let method = method_trait.as_method().unwrap();
-                let scope = self.instance_scope();
+                let scope = superclass.instance_scope();
 
                 Ok(Some((
                     FunctionObject::from_method(
@@ -1129,10 +1129,18 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
 
     fn call(
         self,
-        _receiver: Option<Object<'gc>>,
+        receiver: Option<Object<'gc>>,
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
+        let call_handler = self.inner_class_definition().read().call_handler();
+        if let Some(call_handler) = call_handler {
+            let scope = self.0.read().class_scope;
+            let handler = Executable::from_method(call_handler, scope, None, Some(self));
+
+            return handler.exec(receiver, arguments, activation, self.into());
+        }
+
         arguments
             .get(0)
             .cloned()
@@ -1350,3 +1358,480 @@ impl<'gc> Hash for ClassObject<'gc> {
         self.as_ptr().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::method::NativeMethodImpl;
+    use crate::avm2::test_utils::*;
+
+    /// Call a top-level class as a conversion function, and return what
+    /// `trace` would print for the result.
+    fn convert<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<String, Error> {
+        let value = class(activation, "", name)?.call(None, args, activation)?;
+
+        traced(activation, value)
+    }
+
+    #[test]
+    fn array_conversion_function() {
+        with_avm2(|activation| {
+            let array = class(activation, "", "Array")?
+                .call(None, &[3.into()], activation)?
+                .coerce_to_object(activation)?;
+            let length = get(activation, array, "length")?;
+            assert_eq!(length, 3.into());
+
+            let array = construct(activation, "", "Array", &[3.into()])?;
+            let length = get(activation, array, "length")?;
+            assert_eq!(length, 3.into());
+
+            assert_eq!(
+                convert(activation, "Array", &[1.into(), 2.into(), 3.into()])?,
+                "1,2,3"
+            );
+            assert_eq!(convert(activation, "Array", &[])?, "");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn object_conversion_function() {
+        with_avm2(|activation| {
+            let object = class(activation, "", "Object")?;
+            let from_null = object.call(None, &[Value::Null], activation)?;
+            let from_undefined = object.call(None, &[Value::Undefined], activation)?;
+            let other_from_null = object.call(None, &[Value::Null], activation)?;
+            assert!(matches!(from_null, Value::Object(_)));
+            assert!(matches!(from_undefined, Value::Object(_)));
+            assert_ne!(from_null, other_from_null);
+
+            let o = construct(activation, "", "Object", &[])?;
+            assert_eq!(object.call(None, &[o.into()], activation)?, o.into());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn primitive_conversion_functions() {
+        with_avm2(|activation| {
+            assert_eq!(
+                convert(activation, "String", &[Value::Undefined])?,
+                "undefined"
+            );
+            assert_eq!(convert(activation, "String", &[Value::Null])?, "null");
+            assert_eq!(convert(activation, "String", &[])?, "");
+            assert_eq!(convert(activation, "String", &[123.into()])?, "123");
+
+            assert_eq!(convert(activation, "Number", &[])?, "0");
+            assert_eq!(convert(activation, "Number", &[Value::Undefined])?, "NaN");
+            assert_eq!(convert(activation, "Number", &[Value::Null])?, "0");
+            assert_eq!(convert(activation, "Number", &["12.5".into()])?, "12.5");
+
+            assert_eq!(convert(activation, "int", &[4294967297.0.into()])?, "1");
+            assert_eq!(convert(activation, "int", &[(-1.5).into()])?, "-1");
+            assert_eq!(
+                convert(activation, "int", &["2147483648".into()])?,
+                "-2147483648"
+            );
+            assert_eq!(convert(activation, "uint", &[(-1).into()])?, "4294967295");
+            assert_eq!(convert(activation, "uint", &[4294967297.0.into()])?, "1");
+            assert_eq!(convert(activation, "uint", &[Value::Undefined])?, "0");
+
+            assert_eq!(convert(activation, "Boolean", &[])?, "false");
+            assert_eq!(convert(activation, "Boolean", &["".into()])?, "false");
+            assert_eq!(convert(activation, "Boolean", &["false".into()])?, "true");
+            assert_eq!(convert(activation, "Boolean", &[f64::NAN.into()])?, "false");
+
+            Ok(())
+        })
+    }
+
+    /// Returns `"Grandparent"`, so that the class that defines it can be
+    /// found on the scope chain by name.
+    fn prefix<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("Grandparent".into())
+    }
+
+    /// Returns the name of the class that `prefix` resolves to from the
+    /// scope this method runs in.
+    fn describe<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let definition = activation.find_definition(&public_name("prefix"))?;
+
+        Ok(definition
+            .and_then(|definition| definition.as_class_object())
+            .map(|class| {
+                class
+                    .inner_class_definition()
+                    .read()
+                    .name()
+                    .local_name()
+                    .into()
+            })
+            .unwrap_or(Value::Undefined))
+    }
+
+    #[test]
+    fn methods_run_in_their_defining_class_scope() {
+        with_avm2(|activation| {
+            let grandparent = define_class(
+                activation,
+                "Grandparent",
+                None,
+                &[("describe", describe)],
+                &[("prefix", prefix)],
+            )?;
+            let parent = define_class(activation, "Parent", Some(grandparent), &[], &[])?;
+            let child = define_class(activation, "Child", Some(parent), &[], &[])?;
+            let instance = child.construct(activation, &[])?;
+
+            // An inherited method called on a subclass instance.
+            let described = call(activation, instance, "describe", &[])?;
+            assert_eq!(described, "Grandparent".into());
+
+            // `super.describe()` from a method of `Child`.
+            let described =
+                parent.call_super(&public_name("describe"), instance, &[], activation)?;
+            assert_eq!(described, "Grandparent".into());
+
+            Ok(())
+        })
+    }
+
+    /// Define a class whose instance initializer is the given native
+    /// function, like a class with an explicit constructor.
+    fn define_with_constructor<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+        superclass: ClassObject<'gc>,
+        constructor: NativeMethodImpl,
+    ) -> Result<ClassObject<'gc>, Error> {
+        let mc = activation.context.gc_context;
+        let class = Class::new(
+            QName::new(Namespace::public(), name),
+            Some(superclass.inner_class_definition().read().name().into()),
+            Method::from_builtin(constructor, name, mc),
+            Method::from_builtin(class_initializer, name, mc),
+            mc,
+        );
+
+        ClassObject::from_class(activation, class, Some(superclass))
+    }
+
+    fn class_initializer<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    /// `Base(name:String = "default")`
+    fn base<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let name = match args.get(0) {
+            Some(name) => name.coerce_to_string(activation)?.to_string(),
+            None => "default".to_string(),
+        };
+        log(format!("Base({})", name));
+        activation.super_init(this.unwrap(), &[])?;
+
+        Ok(Value::Undefined)
+    }
+
+    /// `Explicit(name:String)`, which calls `super(name.toUpperCase())`.
+    fn explicit<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        log("Explicit before super");
+        let name = args[0].coerce_to_string(activation)?.to_string();
+        let name = AvmString::new_utf8(activation.context.gc_context, name.to_uppercase());
+        activation.super_init(this.unwrap(), &[name.into()])?;
+        log("Explicit after super");
+
+        Ok(Value::Undefined)
+    }
+
+    /// `Implicit()`, which the compiler gives an implicit `super()` call.
+    fn implicit<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        activation.super_init(this.unwrap(), &[])?;
+        log("Implicit");
+
+        Ok(Value::Undefined)
+    }
+
+    /// `Deeper()`, which calls `super("deeper")`.
+    fn deeper<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        activation.super_init(this.unwrap(), &["deeper".into()])?;
+        log("Deeper");
+
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn super_constructors() {
+        with_avm2(|activation| {
+            let object = activation.avm2().classes().object;
+            let base_class = define_with_constructor(activation, "Base", object, base)?;
+            let explicit_class =
+                define_with_constructor(activation, "Explicit", base_class, explicit)?;
+            let implicit_class =
+                define_with_constructor(activation, "Implicit", base_class, implicit)?;
+            let deeper_class =
+                define_with_constructor(activation, "Deeper", explicit_class, deeper)?;
+
+            explicit_class.construct(activation, &["explicit".into()])?;
+            assert_eq!(
+                take_log(),
+                [
+                    "Explicit before super",
+                    "Base(EXPLICIT)",
+                    "Explicit after super"
+                ]
+            );
+
+            implicit_class.construct(activation, &[])?;
+            assert_eq!(take_log(), ["Base(default)", "Implicit"]);
+
+            deeper_class.construct(activation, &[])?;
+            assert_eq!(
+                take_log(),
+                [
+                    "Explicit before super",
+                    "Base(DEEPER)",
+                    "Explicit after super",
+                    "Deeper"
+                ]
+            );
+
+            Ok(())
+        })
+    }
+
+    fn do_nothing<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok(Value::Undefined)
+    }
+
+    #[test]
+    fn functions_and_classes_coerce_to_strings() {
+        with_avm2(|activation| {
+            let logger_class =
+                define_class(activation, "Logger", None, &[("log", do_nothing)], &[])?;
+            let logger = logger_class.construct(activation, &[])?;
+
+            // Every kind of function prints the same placeholder.
+            let free = function(activation, do_nothing)?;
+            assert_eq!(traced(activation, free)?, "function Function() {}");
+            let method = get(activation, logger, "log")?;
+            assert_eq!(traced(activation, method)?, "function Function() {}");
+            let math = class(activation, "", "Math")?;
+            let max = get(activation, math, "max")?;
+            assert_eq!(traced(activation, max)?, "function Function() {}");
+
+            assert_eq!(traced(activation, logger_class)?, "[class Logger]");
+            let constructor = get(activation, logger, "constructor")?;
+            assert_eq!(traced(activation, constructor)?, "[class Logger]");
+            let sprite = class(activation, "flash.display", "Sprite")?;
+            assert_eq!(traced(activation, sprite)?, "[class Sprite]");
+
+            let both = array(activation, &[logger_class.into(), free.into()])?;
+            let joined = call(activation, both, "join", &[])?;
+            assert_eq!(
+                traced(activation, joined)?,
+                "[class Logger],function Function() {}"
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn instances_know_their_constructor() {
+        with_avm2(|activation| {
+            let base_class = define_class(activation, "Base", None, &[], &[])?;
+            let derived_class = define_class(activation, "Derived", Some(base_class), &[], &[])?;
+            let base = base_class.construct(activation, &[])?;
+            let derived = derived_class.construct(activation, &[])?;
+            assert_eq!(get(activation, base, "constructor")?, base_class.into());
+            let constructor = get(activation, derived, "constructor")?;
+            assert_eq!(constructor, derived_class.into());
+            assert_ne!(constructor, base_class.into());
+            let prototype =
+                get(activation, base_class, "prototype")?.coerce_to_object(activation)?;
+            assert_eq!(
+                get(activation, prototype, "constructor")?,
+                base_class.into()
+            );
+
+            // Builtin classes and primitives.
+            let object_class = activation.avm2().classes().object;
+            let plain = construct(activation, "", "Object", &[])?;
+            assert_eq!(get(activation, plain, "constructor")?, object_class.into());
+            let array_class = activation.avm2().classes().array;
+            let empty = array(activation, &[])?;
+            assert_eq!(get(activation, empty, "constructor")?, array_class.into());
+            let sprite_class = class(activation, "flash.display", "Sprite")?;
+            let sprite = sprite_class.construct(activation, &[])?;
+            assert_eq!(get(activation, sprite, "constructor")?, sprite_class.into());
+            let string_class = activation.avm2().classes().string;
+            let text = Value::from("text").coerce_to_object(activation)?;
+            assert_eq!(get(activation, text, "constructor")?, string_class.into());
+
+            // The constructor can make more instances.
+            assert_eq!(traced(activation, constructor)?, "[class Derived]");
+            let another = constructor
+                .coerce_to_object(activation)?
+                .construct(activation, &[])?;
+            assert!(another.is_of_type(derived_class, activation)?);
+
+            Ok(())
+        })
+    }
+
+    /// The `prototype` property of a class.
+    fn prototype_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: ClassObject<'gc>,
+    ) -> Result<Object<'gc>, Error> {
+        get(activation, class, "prototype")?.coerce_to_object(activation)
+    }
+
+    fn is_prototype_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        prototype: Object<'gc>,
+        object: Object<'gc>,
+    ) -> Result<bool, Error> {
+        Ok(call(activation, prototype, "isPrototypeOf", &[object.into()])?.coerce_to_boolean())
+    }
+
+    fn hello_from_base<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("hello from Base.prototype".into())
+    }
+
+    fn hello_from_derived<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Ok("hello from Derived.prototype".into())
+    }
+
+    #[test]
+    fn each_class_has_a_prototype_chained_to_its_superclass() {
+        with_avm2(|activation| {
+            let base_class = define_class(activation, "Base", None, &[], &[])?;
+            let derived_class = define_class(activation, "Derived", Some(base_class), &[], &[])?;
+            let sibling_class = define_class(activation, "Sibling", Some(base_class), &[], &[])?;
+            let base = prototype_of(activation, base_class)?;
+            let derived = prototype_of(activation, derived_class)?;
+            let sibling = prototype_of(activation, sibling_class)?;
+            let object = activation.avm2().prototypes().object;
+
+            assert_ne!(Value::from(base), derived.into());
+            assert_ne!(Value::from(base), sibling.into());
+            assert!(is_prototype_of(activation, base, derived)?);
+            assert!(!is_prototype_of(activation, derived, base)?);
+            assert!(is_prototype_of(activation, object, base)?);
+            assert_eq!(get(activation, derived, "__proto__")?, base.into());
+            assert_eq!(get(activation, sibling, "__proto__")?, base.into());
+            assert_eq!(get(activation, base, "__proto__")?, object.into());
+            assert_eq!(
+                get(activation, derived, "constructor")?,
+                derived_class.into()
+            );
+
+            let instance = derived_class.construct(activation, &[])?;
+            assert_eq!(get(activation, instance, "__proto__")?, derived.into());
+            assert!(is_prototype_of(activation, derived, instance)?);
+            assert!(is_prototype_of(activation, base, instance)?);
+            assert!(!is_prototype_of(activation, sibling, instance)?);
+
+            // Methods added to a prototype are found through the chain.
+            let greet = function(activation, hello_from_base)?;
+            set(activation, base, "greet", greet)?;
+            let shout = function(activation, hello_from_derived)?;
+            set(activation, derived, "shout", shout)?;
+            let greeting = call(activation, instance, "greet", &[])?;
+            assert_eq!(traced(activation, greeting)?, "hello from Base.prototype");
+            let shouted = call(activation, instance, "shout", &[])?;
+            assert_eq!(traced(activation, shouted)?, "hello from Derived.prototype");
+            let other = sibling_class.construct(activation, &[])?;
+            let greeting = call(activation, other, "greet", &[])?;
+            assert_eq!(traced(activation, greeting)?, "hello from Base.prototype");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn builtin_classes_have_their_own_prototypes() {
+        with_avm2(|activation| {
+            let pairs = [
+                (("flash.display", "Sprite"), ("flash.display", "MovieClip")),
+                (
+                    ("flash.events", "Event"),
+                    ("flash.events", "NetStatusEvent"),
+                ),
+            ];
+            for ((super_package, super_name), (package, name)) in pairs {
+                let superclass = class(activation, super_package, super_name)?;
+                let subclass = class(activation, package, name)?;
+                let super_prototype = prototype_of(activation, superclass)?;
+                let prototype = prototype_of(activation, subclass)?;
+
+                assert_ne!(Value::from(super_prototype), prototype.into());
+                assert!(is_prototype_of(activation, super_prototype, prototype)?);
+                assert_eq!(
+                    get(activation, prototype, "__proto__")?,
+                    super_prototype.into()
+                );
+            }
+
+            let vector = activation.avm2().classes().vector;
+            let int = activation.avm2().classes().int;
+            let number = activation.avm2().classes().number;
+            let int_vector = vector.apply(activation, &[int.into()])?;
+            let number_vector = vector.apply(activation, &[number.into()])?;
+            let int_prototype = prototype_of(activation, int_vector)?;
+            let number_prototype = prototype_of(activation, number_vector)?;
+            assert_ne!(Value::from(int_prototype), number_prototype.into());
+
+            Ok(())
+        })
+    }
+}
@@ -6,6 +6,7 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::string::AvmString;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::cell::{Ref, RefMut};
 
@@ -22,6 +23,7 @@ pub fn namespace_allocator<'gc>(
         NamespaceObjectData {
             base,
             namespace: Namespace::public(),
+            prefix: None,
         },
     ))
     .into())
@@ -40,6 +42,12 @@ pub struct NamespaceObjectData<'gc> {
 
     /// The namespace name this object is associated with.
     namespace: Namespace<'gc>,
+
+    /// The prefix this namespace is bound to, if it was given one.
+    ///
+    /// Only E4X namespaces have prefixes; a prefix of `None` corresponds to
+    /// an `undefined` prefix in ActionScript.
+    prefix: Option<AvmString<'gc>>,
 }
 
 impl<'gc> NamespaceObject<'gc> {
@@ -47,6 +55,15 @@ impl<'gc> NamespaceObject<'gc> {
     pub fn from_namespace(
         activation: &mut Activation<'_, 'gc, '_>,
         namespace: Namespace<'gc>,
+    ) -> Result<Object<'gc>, Error> {
+        Self::from_namespace_and_prefix(activation, namespace, None)
+    }
+
+    /// Box a namespace and the prefix it is bound to into an object.
+    pub fn from_namespace_and_prefix(
+        activation: &mut Activation<'_, 'gc, '_>,
+        namespace: Namespace<'gc>,
+        prefix: Option<AvmString<'gc>>,
     ) -> Result<Object<'gc>, Error> {
         let class = activation.avm2().classes().namespace;
         let proto = activation.avm2().prototypes().namespace;
@@ -54,7 +71,11 @@ impl<'gc> NamespaceObject<'gc> {
 
         let mut this: Object<'gc> = NamespaceObject(GcCell::allocate(
             activation.context.gc_context,
-            NamespaceObjectData { base, namespace },
+            NamespaceObjectData {
+                base,
+                namespace,
+                prefix,
+            },
         ))
         .into();
         this.install_instance_traits(activation, class)?;
@@ -63,6 +84,28 @@ impl<'gc> NamespaceObject<'gc> {
 
         Ok(this)
     }
+
+    pub fn namespace(self) -> Namespace<'gc> {
+        self.0.read().namespace
+    }
+
+    pub fn prefix(self) -> Option<AvmString<'gc>> {
+        self.0.read().prefix
+    }
+
+    /// Set the namespace and prefix of an object created by the `Namespace`
+    /// constructor.
+    pub fn init_namespace(
+        self,
+        mc: MutationContext<'gc, '_>,
+        namespace: Namespace<'gc>,
+        prefix: Option<AvmString<'gc>>,
+    ) {
+        let mut write = self.0.write(mc);
+
+        write.namespace = namespace;
+        write.prefix = prefix;
+    }
 }
 
 impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
@@ -90,6 +133,10 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
         Some(Ref::map(self.0.read(), |s| &s.namespace))
     }
 
+    fn as_namespace_object(self) -> Option<NamespaceObject<'gc>> {
+        Some(self)
+    }
+
     fn derive(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
         let this: Object<'gc> = Object::NamespaceObject(*self);
         let base = ScriptObjectData::base_new(Some(this), None);
@@ -99,6 +146,7 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
             NamespaceObjectData {
                 base,
                 namespace: Namespace::public(),
+                prefix: None,
             },
         ))
         .into())
@@ -65,10 +65,20 @@ impl<'gc> PrimitiveObject<'gc> {
             return Err("Cannot box a null value".into());
         }
 
+        // Numbers are boxed by the class Flash infers for their value rather
+        // than by their representation: integral values in range of `int` box
+        // as `int`, and everything else boxes as `Number`.
+        let primitive = match primitive {
+            Value::Number(_) | Value::Unsigned(_) if is_int_valued(primitive) => {
+                Value::Integer(primitive.coerce_to_i32(activation)?)
+            }
+            Value::Unsigned(u) => Value::Number(u as f64),
+            primitive => primitive,
+        };
+
         let proto = match primitive {
             Value::Bool(_) => activation.avm2().prototypes().boolean,
             Value::Number(_) => activation.avm2().prototypes().number,
-            Value::Unsigned(_) => activation.avm2().prototypes().uint,
             Value::Integer(_) => activation.avm2().prototypes().int,
             Value::String(_) => activation.avm2().prototypes().string,
             _ => unreachable!(),
@@ -76,7 +86,6 @@ impl<'gc> PrimitiveObject<'gc> {
         let class = match primitive {
             Value::Bool(_) => activation.avm2().classes().boolean,
             Value::Number(_) => activation.avm2().classes().number,
-            Value::Unsigned(_) => activation.avm2().classes().uint,
             Value::Integer(_) => activation.avm2().classes().int,
             Value::String(_) => activation.avm2().classes().string,
             _ => unreachable!(),
@@ -99,6 +108,16 @@ impl<'gc> PrimitiveObject<'gc> {
     }
 }
 
+/// Determine if a numeric value would be represented as an `int` by Flash.
+///
+/// Negative zero is not, since `int` cannot represent its sign.
+fn is_int_valued(value: Value<'_>) -> bool {
+    match value {
+        Value::Number(n) => value.is_i32() && !(n == 0.0 && n.is_sign_negative()),
+        value => value.is_i32(),
+    }
+}
+
 impl<'gc> TObject<'gc> for PrimitiveObject<'gc> {
     fn base(&self) -> Ref<ScriptObjectData<'gc>> {
         Ref::map(self.0.read(), |read| &read.base)
@@ -156,3 +175,61 @@ impl<'gc> TObject<'gc> for PrimitiveObject<'gc> {
         Some(Ref::map(self.0.read(), |pod| &pod.primitive))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+    use crate::avm2::{Activation, Error, TObject, Value};
+
+    /// The name of the class a value is boxed as.
+    fn boxed_class_name<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<String, Error> {
+        let boxed = value.coerce_to_object(activation)?;
+        let class = boxed.instance_of().unwrap().inner_class_definition();
+        let name = class.read().name().local_name().to_string();
+
+        Ok(name)
+    }
+
+    #[test]
+    fn numbers_box_by_their_value() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let uint = activation.avm2().classes().uint;
+            let number = activation.avm2().classes().number;
+
+            let cases = [
+                (Value::Number(5.0), [true, true, true], "int"),
+                (Value::Number(5.5), [false, false, true], "Number"),
+                (Value::Number(-5.0), [true, false, true], "int"),
+                (Value::Number(4294967296.0), [false, false, true], "Number"),
+                (Value::Unsigned(u32::MAX), [false, true, true], "Number"),
+                (Value::Unsigned(7), [true, true, true], "int"),
+            ];
+            for (value, is, class_name) in cases {
+                let is_int = value.is_of_type(activation, int)?;
+                let is_uint = value.is_of_type(activation, uint)?;
+                let is_number = value.is_of_type(activation, number)?;
+                assert_eq!([is_int, is_uint, is_number], is, "{:?}", value);
+                assert_eq!(boxed_class_name(activation, value)?, class_name);
+            }
+
+            let boxed = Value::Number(5.0).coerce_to_object(activation)?;
+            let string = call(activation, boxed, "toString", &[])?;
+            assert_eq!(traced(activation, string)?, "5");
+            let boxed = Value::Number(5.5).coerce_to_object(activation)?;
+            let string = call(activation, boxed, "toFixed", &[2.into()])?;
+            assert_eq!(traced(activation, string)?, "5.50");
+            let boxed = Value::Unsigned(u32::MAX).coerce_to_object(activation)?;
+            let string = call(activation, boxed, "toString", &[16.into()])?;
+            assert_eq!(traced(activation, string)?, "ffffffff");
+            let boxed = Value::Unsigned(255).coerce_to_object(activation)?;
+            let string = call(activation, boxed, "toString", &[16.into()])?;
+            assert_eq!(traced(activation, string)?, "ff");
+
+            Ok(())
+        })
+    }
+}
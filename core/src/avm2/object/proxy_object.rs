@@ -230,6 +230,31 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
             .coerce_to_boolean())
     }
 
+    fn get_descendants(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        // `proxy..*` has no local name; Flash hands the trap a wildcard name.
+        let local_name = multiname.local_name().unwrap_or_else(|| "*".into());
+        let namespace = multiname
+            .namespace_set()
+            .find(|ns| ns.is_any() || ns.is_public() || ns.is_namespace())
+            .copied()
+            .unwrap_or(Namespace::Any);
+        let qname = QNameObject::from_qname(activation, QName::new(namespace, local_name))?;
+
+        self.call_property(
+            &QName::new(
+                Namespace::Namespace(NS_FLASH_PROXY.into()),
+                "getDescendants",
+            )
+            .into(),
+            &[qname.into()],
+            activation,
+        )
+    }
+
     fn get_next_enumerant(
         self,
         last_index: u32,
@@ -222,6 +222,7 @@ impl<'gc> ScriptObjectData<'gc> {
 
         if can_delete {
             self.values.remove(name);
+            self.enumerants.retain(|enumerant| *enumerant != name);
         }
 
         can_delete
@@ -306,6 +307,31 @@ impl<'gc> ScriptObjectData<'gc> {
         self.values.get(name).is_some()
     }
 
+    pub fn has_own_virtual_get_only_property(&self, name: QName<'gc>) -> bool {
+        matches!(
+            self.values.get(name),
+            Some(Property::Virtual {
+                get: Some(_),
+                set: None,
+                ..
+            })
+        )
+    }
+
+    /// Determine if a property is a `const` that may only be written by
+    /// `initproperty`.
+    pub fn has_own_const_property(&self, name: QName<'gc>) -> bool {
+        match self.values.get(name) {
+            Some(Property::Slot { slot_id, .. }) => self
+                .slots
+                .get(*slot_id as usize)
+                .map(|slot| slot.is_const())
+                .unwrap_or(false),
+            Some(prop @ Property::Stored { .. }) => !prop.is_overwritable(),
+            _ => false,
+        }
+    }
+
     pub fn has_own_virtual_set_only_property(&self, name: QName<'gc>) -> bool {
         matches!(
             self.values.get(name),
@@ -364,17 +390,8 @@ impl<'gc> ScriptObjectData<'gc> {
 
         if is_enumerable && self.values.contains_key(name) && !self.enumerants.contains(&name) {
             self.enumerants.push(name);
-        } else if !is_enumerable && self.enumerants.contains(&name) {
-            let mut index = None;
-            for (i, other_name) in self.enumerants.iter().enumerate() {
-                if *other_name == name {
-                    index = Some(i);
-                }
-            }
-
-            if let Some(index) = index {
-                self.enumerants.remove(index);
-            }
+        } else if !is_enumerable {
+            self.enumerants.retain(|enumerant| *enumerant != name);
         }
 
         Ok(())
@@ -541,4 +558,16 @@ impl<'gc> ScriptObjectData<'gc> {
     pub fn set_instance_of(&mut self, instance_of: ClassObject<'gc>) {
         self.instance_of = Some(instance_of);
     }
+
+    /// Estimate how many bytes this object's own storage occupies.
+    ///
+    /// This only accounts for slots, methods, and enumerable dynamic
+    /// properties; it is stable for a given object shape, but is not an exact
+    /// measurement of heap usage.
+    pub fn approximate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.slots.len() * std::mem::size_of::<Slot<'gc>>()
+            + self.methods.len() * std::mem::size_of::<Option<Object<'gc>>>()
+            + self.enumerants.len() * std::mem::size_of::<(QName<'gc>, Property<'gc>)>()
+    }
 }
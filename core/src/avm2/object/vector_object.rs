@@ -139,7 +139,7 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
                 self.0
                     .write(activation.context.gc_context)
                     .vector
-                    .set(index, value, activation)?;
+                    .set(index, value)?;
 
                 return Ok(());
             }
@@ -177,7 +177,7 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
                 self.0
                     .write(activation.context.gc_context)
                     .vector
-                    .set(index, value, activation)?;
+                    .set(index, value)?;
 
                 return Ok(());
             }
@@ -198,14 +198,28 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
 
     fn delete_property_local(
         &self,
-        gc_context: MutationContext<'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
         name: QName<'gc>,
     ) -> Result<bool, Error> {
-        if name.namespace().is_package("") && name.local_name().parse::<usize>().is_ok() {
-            return Ok(true);
+        if name.namespace().is_package("") {
+            if let Ok(index) = name.local_name().parse::<usize>() {
+                // Vectors cannot have holes, so deleting an element resets it
+                // to the default value instead.
+                let mut write = self.0.write(activation.context.gc_context);
+                if write.vector.is_in_range(index) {
+                    let default = write.vector.default(activation);
+                    write.vector.set(index, default)?;
+                }
+
+                return Ok(true);
+            }
         }
 
-        Ok(self.0.write(gc_context).base.delete_property(name))
+        Ok(self
+            .0
+            .write(activation.context.gc_context)
+            .base
+            .delete_property(name))
     }
 
     fn has_own_property(self, name: QName<'gc>) -> Result<bool, Error> {
@@ -306,3 +320,75 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
         Some(RefMut::map(self.0.write(mc), |vod| &mut vod.vector))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+
+    /// Construct a vector object holding the given values.
+    fn vector<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value_type: ClassObject<'gc>,
+        values: &[Value<'gc>],
+        is_fixed: bool,
+    ) -> Result<Object<'gc>, Error> {
+        let storage = VectorStorage::from_values(values.to_vec(), is_fixed, value_type);
+
+        VectorObject::from_vector(storage, activation)
+    }
+
+    #[test]
+    fn index_set_overwrites_and_appends() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let a = vector(activation, int, &[1.into(), 2.into(), 3.into()], false)?;
+
+            set(activation, a, "1", 5)?;
+            assert_eq!(traced(activation, a)?, "1,5,3");
+            assert_eq!(get(activation, a, "length")?, 3.into());
+
+            set(activation, a, "3", 4)?;
+            assert_eq!(traced(activation, a)?, "1,5,3,4");
+            assert_eq!(get(activation, a, "length")?, 4.into());
+
+            set(activation, a, "4", "6")?;
+            assert_eq!(traced(activation, a)?, "1,5,3,4,6");
+
+            let string = activation.avm2().classes().string;
+            let b = vector(activation, string, &["x".into(), "y".into()], false)?;
+            set(activation, b, "2", Value::Null)?;
+            assert_eq!(get(activation, b, "length")?, 3.into());
+            assert_eq!(get(activation, b, "2")?, Value::Null);
+
+            let number = activation.avm2().classes().number;
+            let c = vector(activation, number, &[1.5.into()], false)?;
+            set(activation, c, "1", Value::Undefined)?;
+            assert_eq!(traced(activation, c)?, "1.5,NaN");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn index_set_past_the_end_throws() {
+        with_avm2(|activation| {
+            let int = activation.avm2().classes().int;
+            let fixed = vector(activation, int, &[0.into(), 0.into(), 0.into()], true)?;
+
+            set(activation, fixed, "2", 7)?;
+            assert_eq!(traced(activation, fixed)?, "0,0,7");
+
+            let error = set(activation, fixed, "3", 8).unwrap_err();
+            assert!(error.to_string().starts_with("RangeError: Error #1125"));
+            assert_eq!(traced(activation, fixed)?, "0,0,7");
+
+            let a = vector(activation, int, &[1.into(), 2.into(), 3.into()], false)?;
+            let error = set(activation, a, "5", 6).unwrap_err();
+            assert!(error.to_string().starts_with("RangeError: Error #1125"));
+            assert_eq!(get(activation, a, "length")?, 3.into());
+
+            Ok(())
+        })
+    }
+}
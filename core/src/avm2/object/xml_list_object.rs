@@ -0,0 +1,247 @@
+//! Object representation for XMLList objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Multiname, QName};
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject, XmlObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// A class instance allocator that allocates XMLList objects.
+pub fn xml_list_allocator<'gc>(
+    class: ClassObject<'gc>,
+    proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let base = ScriptObjectData::base_new(Some(proto), Some(class));
+
+    Ok(XmlListObject(GcCell::allocate(
+        activation.context.gc_context,
+        XmlListObjectData {
+            base,
+            nodes: Vec::new(),
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct XmlListObject<'gc>(GcCell<'gc, XmlListObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct XmlListObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The E4X nodes in this list.
+    nodes: Vec<E4XNode<'gc>>,
+}
+
+impl<'gc> XmlListObject<'gc> {
+    /// Construct an XMLList object that holds a list of existing E4X nodes.
+    pub fn from_nodes(
+        activation: &mut Activation<'_, 'gc, '_>,
+        nodes: Vec<E4XNode<'gc>>,
+    ) -> Result<Object<'gc>, Error> {
+        let class = activation.avm2().classes().xml_list;
+        let proto = activation.avm2().prototypes().xml_list;
+        let base = ScriptObjectData::base_new(Some(proto), Some(class));
+
+        let mut this: Object<'gc> = XmlListObject(GcCell::allocate(
+            activation.context.gc_context,
+            XmlListObjectData { base, nodes },
+        ))
+        .into();
+        this.install_instance_traits(activation, class)?;
+
+        class.call_native_init(Some(this), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn nodes(self) -> Vec<E4XNode<'gc>> {
+        self.0.read().nodes.clone()
+    }
+
+    /// Determine if any member of this list has child elements or attributes
+    /// named by a multiname, such as when it is the target of a `with` scope.
+    pub fn has_xml_property(self, multiname: &Multiname<'gc>) -> bool {
+        self.0
+            .read()
+            .nodes
+            .iter()
+            .any(|node| !node.properties(multiname).is_empty())
+    }
+
+    pub fn set_nodes(self, mc: MutationContext<'gc, '_>, nodes: Vec<E4XNode<'gc>>) {
+        self.0.write(mc).nodes = nodes;
+    }
+}
+
+impl<'gc> TObject<'gc> for XmlListObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        Ref::map(self.0.read(), |read| &read.base)
+    }
+
+    fn base_mut(&self, mc: MutationContext<'gc, '_>) -> RefMut<ScriptObjectData<'gc>> {
+        RefMut::map(self.0.write(mc), |write| &mut write.base)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn derive(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XmlListObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), None);
+
+        Ok(XmlListObject(GcCell::allocate(
+            activation.context.gc_context,
+            XmlListObjectData {
+                base,
+                nodes: Vec::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn get_property_undef(
+        self,
+        _receiver: Object<'gc>,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        if let Some(index) = multiname
+            .local_name()
+            .and_then(|name| name.to_utf8_lossy().parse::<usize>().ok())
+        {
+            return match self.0.read().nodes.get(index).copied() {
+                Some(node) => Ok(XmlObject::from_node(activation, node)?.into()),
+                None => Ok(Value::Undefined),
+            };
+        }
+
+        let multiname = activation.xml_name(multiname);
+        let properties = self
+            .nodes()
+            .into_iter()
+            .flat_map(|node| node.properties(&multiname))
+            .collect();
+
+        Ok(XmlListObject::from_nodes(activation, properties)?.into())
+    }
+
+    fn set_property_undef(
+        &mut self,
+        _receiver: Object<'gc>,
+        multiname: &Multiname<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Option<QName<'gc>>, Error> {
+        let local_name: Result<AvmString<'gc>, Error> = multiname
+            .local_name()
+            .ok_or_else(|| "Cannot set undefined property using any name".into());
+        let local_name = local_name?;
+
+        let index = match local_name.to_utf8_lossy().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => return Ok(Some(QName::dynamic_name(local_name))),
+        };
+
+        let mut nodes = match value {
+            Value::Object(object) => {
+                if let Some(xml) = object.as_xml_object() {
+                    xml.node().into_iter().collect()
+                } else if let Some(list) = object.as_xml_list_object() {
+                    list.nodes()
+                } else {
+                    vec![E4XNode::text(
+                        activation.context.gc_context,
+                        value.coerce_to_string(activation)?,
+                    )]
+                }
+            }
+            value => vec![E4XNode::text(
+                activation.context.gc_context,
+                value.coerce_to_string(activation)?,
+            )],
+        };
+
+        // Assigning past the end of the list appends, as the filter operator
+        // does when collecting matching members.
+        let mut write = self.0.write(activation.context.gc_context);
+        if index < write.nodes.len() && nodes.len() == 1 {
+            write.nodes[index] = nodes.pop().unwrap();
+        } else {
+            write.nodes.extend(nodes);
+        }
+
+        Ok(None)
+    }
+
+    fn get_descendants(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let multiname = activation.xml_name(multiname);
+        let mut descendants = Vec::new();
+        for node in self.nodes() {
+            node.descendants(&multiname, &mut descendants);
+        }
+
+        Ok(XmlListObject::from_nodes(activation, descendants)?.into())
+    }
+
+    fn get_next_enumerant(
+        self,
+        last_index: u32,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Option<u32>, Error> {
+        Ok(if (last_index as usize) < self.0.read().nodes.len() {
+            Some(last_index + 1)
+        } else {
+            None
+        })
+    }
+
+    fn get_enumerant_name(
+        self,
+        index: u32,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(match index.checked_sub(1) {
+            Some(index) if (index as usize) < self.0.read().nodes.len() => index.into(),
+            _ => Value::Undefined,
+        })
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let node = index
+            .checked_sub(1)
+            .and_then(|index| self.0.read().nodes.get(index as usize).copied());
+
+        match node {
+            Some(node) => Ok(XmlObject::from_node(activation, node)?.into()),
+            None => Ok(Value::Undefined),
+        }
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_xml_list_object(self) -> Option<XmlListObject<'gc>> {
+        Some(self)
+    }
+}
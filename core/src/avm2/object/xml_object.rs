@@ -1,8 +1,10 @@
 //! Object representation for XML objects
 
 use crate::avm2::activation::Activation;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::Multiname;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject, XmlListObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{Collect, GcCell, MutationContext};
@@ -18,7 +20,7 @@ pub fn xml_allocator<'gc>(
 
     Ok(XmlObject(GcCell::allocate(
         activation.context.gc_context,
-        XmlObjectData { base },
+        XmlObjectData { base, node: None },
     ))
     .into())
 }
@@ -32,6 +34,53 @@ pub struct XmlObject<'gc>(GcCell<'gc, XmlObjectData<'gc>>);
 pub struct XmlObjectData<'gc> {
     /// Base script object
     base: ScriptObjectData<'gc>,
+
+    /// The E4X node this object represents.
+    ///
+    /// This is `None` until the object has been initialized.
+    node: Option<E4XNode<'gc>>,
+}
+
+impl<'gc> XmlObject<'gc> {
+    /// Construct an XML object that represents an existing E4X node.
+    pub fn from_node(
+        activation: &mut Activation<'_, 'gc, '_>,
+        node: E4XNode<'gc>,
+    ) -> Result<Object<'gc>, Error> {
+        let class = activation.avm2().classes().xml;
+        let proto = activation.avm2().prototypes().xml;
+        let base = ScriptObjectData::base_new(Some(proto), Some(class));
+
+        let mut this: Object<'gc> = XmlObject(GcCell::allocate(
+            activation.context.gc_context,
+            XmlObjectData {
+                base,
+                node: Some(node),
+            },
+        ))
+        .into();
+        this.install_instance_traits(activation, class)?;
+
+        class.call_native_init(Some(this), &[], activation)?;
+
+        Ok(this)
+    }
+
+    pub fn node(self) -> Option<E4XNode<'gc>> {
+        self.0.read().node
+    }
+
+    /// Determine if this XML value has child elements or attributes named by
+    /// a multiname, such as when it is the target of a `with` scope.
+    pub fn has_xml_property(self, multiname: &Multiname<'gc>) -> bool {
+        self.node()
+            .map(|node| !node.properties(multiname).is_empty())
+            .unwrap_or(false)
+    }
+
+    pub fn set_node(self, mc: MutationContext<'gc, '_>, node: E4XNode<'gc>) {
+        self.0.write(mc).node = Some(node);
+    }
 }
 
 impl<'gc> TObject<'gc> for XmlObject<'gc> {
@@ -53,12 +102,78 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
 
         Ok(XmlObject(GcCell::allocate(
             activation.context.gc_context,
-            XmlObjectData { base },
+            XmlObjectData { base, node: None },
         ))
         .into())
     }
 
+    fn get_property_undef(
+        self,
+        _receiver: Object<'gc>,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let multiname = activation.xml_name(multiname);
+        let properties = self
+            .node()
+            .map(|node| node.properties(&multiname))
+            .unwrap_or_default();
+
+        Ok(XmlListObject::from_nodes(activation, properties)?.into())
+    }
+
+    fn get_descendants(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let multiname = activation.xml_name(multiname);
+        let mut descendants = Vec::new();
+        if let Some(node) = self.node() {
+            node.descendants(&multiname, &mut descendants);
+        }
+
+        Ok(XmlListObject::from_nodes(activation, descendants)?.into())
+    }
+
+    fn get_next_enumerant(
+        self,
+        last_index: u32,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Option<u32>, Error> {
+        // A lone XML value enumerates as a list containing only itself.
+        Ok(if last_index == 0 { Some(1) } else { None })
+    }
+
+    fn get_enumerant_name(
+        self,
+        index: u32,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(if index == 1 {
+            0.into()
+        } else {
+            Value::Undefined
+        })
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        Ok(if index == 1 {
+            Object::from(self).into()
+        } else {
+            Value::Undefined
+        })
+    }
+
     fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
         Ok(Value::Object(Object::from(*self)))
     }
+
+    fn as_xml_object(self) -> Option<XmlObject<'gc>> {
+        Some(self)
+    }
 }
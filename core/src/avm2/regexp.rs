@@ -1,6 +1,7 @@
 //! RegExp Structure
 
 use std::borrow::Cow;
+use std::ops::Range;
 
 use crate::string::{AvmString, Units, WStrToUtf8};
 use bitflags::bitflags;
@@ -90,8 +91,13 @@ impl<'gc> RegExp<'gc> {
         F: FnOnce(&mut CachedText<'gc>, regress::Match) -> T,
     {
         if self.cached_regex.is_none() {
+            let mut source = self.source.to_utf8_lossy();
+            if self.flags.contains(RegExpFlags::EXTENDED) {
+                source = Cow::Owned(strip_extended_whitespace(&source));
+            }
+
             let re = regress::Regex::with_flags(
-                &self.source.to_utf8_lossy(),
+                &source,
                 regress::Flags {
                     icase: self.flags.contains(RegExpFlags::IGNORE_CASE),
                     multiline: self.flags.contains(RegExpFlags::MULTILINE),
@@ -140,14 +146,120 @@ impl<'gc> RegExp<'gc> {
                 true
             }
             Some(None) => true,
-            None => false,
+            None => {
+                // A failed global search starts over from the beginning.
+                if global {
+                    self.last_index = 0;
+                }
+
+                false
+            }
         }
     }
 
     pub fn exec(&mut self, text: AvmString<'gc>) -> Option<regress::Match> {
         let global = self.flags.contains(RegExpFlags::GLOBAL);
         let start = if global { self.last_index } else { 0 };
-        let re_match = self.find_utf8_match_at(text, start, |text, mut re_match| {
+        let re_match = self.find_utf16_match_at(text, start);
+
+        if global {
+            self.last_index = re_match.as_ref().map(|m| m.end()).unwrap_or(0);
+        }
+
+        re_match
+    }
+
+    /// Split a string around the matches of this regexp, as `String.split`
+    /// does, yielding the range of each piece.
+    ///
+    /// Capture groups in the pattern are spliced into the result after the
+    /// piece that precedes them; groups that did not participate in a match
+    /// are `None`. Empty matches split between each character. At most
+    /// `limit` pieces are returned, and `lastIndex` is not affected.
+    pub fn split(&mut self, text: AvmString<'gc>, limit: usize) -> Vec<Option<Range<usize>>> {
+        let mut pieces = Vec::new();
+        if limit == 0 {
+            return pieces;
+        }
+
+        if text.is_empty() {
+            if self.find_utf16_match_at(text, 0).is_none() {
+                pieces.push(Some(0..0));
+            }
+
+            return pieces;
+        }
+
+        let mut piece_start = 0;
+        let mut search_start = 0;
+        while search_start < text.len() {
+            let re_match = match self.find_utf16_match_at(text, search_start) {
+                Some(re_match) if re_match.start() < text.len() => re_match,
+                _ => break,
+            };
+
+            // An empty match where the last piece ended doesn't split
+            // anything; try again one character further on.
+            if re_match.end() == piece_start {
+                search_start = re_match.start() + 1;
+                continue;
+            }
+
+            pieces.push(Some(piece_start..re_match.start()));
+            if pieces.len() >= limit {
+                return pieces;
+            }
+
+            for capture in re_match.captures.iter().cloned() {
+                pieces.push(capture);
+                if pieces.len() >= limit {
+                    return pieces;
+                }
+            }
+
+            piece_start = re_match.end();
+            search_start = re_match.end();
+        }
+
+        pieces.push(Some(piece_start..text.len()));
+        pieces
+    }
+
+    /// Find the matches that `String.replace` substitutes: every match for a
+    /// global regexp, or only the first one otherwise.
+    ///
+    /// Each match is given as its range along with the ranges of its capture
+    /// groups. `lastIndex` is not affected.
+    pub fn replace_matches(&mut self, text: AvmString<'gc>) -> Vec<RangeWithCaptures> {
+        let global = self.flags.contains(RegExpFlags::GLOBAL);
+        let mut matches = Vec::new();
+        let mut start = 0;
+
+        while let Some(re_match) = self.find_utf16_match_at(text, start) {
+            // Step past empty matches so that the search makes progress.
+            start = if re_match.range.is_empty() {
+                re_match.end() + 1
+            } else {
+                re_match.end()
+            };
+            matches.push((re_match.range, re_match.captures));
+
+            if !global || start > text.len() {
+                break;
+            }
+        }
+
+        matches
+    }
+
+    /// Find the first match at or after a UTF-16 index, with the bounds of
+    /// the match and its captures also given as UTF-16 indices.
+    fn find_utf16_match_at(
+        &mut self,
+        text: AvmString<'gc>,
+        start: usize,
+    ) -> Option<regress::Match> {
+        self.find_utf8_match_at(text, start, |text, mut re_match| {
             // Sort the capture endpoints by increasing index, so that CachedText::utf16_index is efficient.
             let mut utf8_indices = re_match
                 .captures
@@ -164,16 +276,34 @@ impl<'gc> RegExp<'gc> {
             }
 
             re_match
-        })?;
+        })
+    }
+}
 
-        if global {
-            self.last_index = re_match.end();
+/// Remove the whitespace that the `x` (extended) flag tells the pattern to
+/// ignore. Escaped whitespace is kept as a literal.
+fn strip_extended_whitespace(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut chars = source.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            stripped.push(c);
+            if let Some(escaped) = chars.next() {
+                stripped.push(escaped);
+            }
+        } else if !c.is_whitespace() {
+            stripped.push(c);
         }
-
-        Some(re_match)
     }
+
+    stripped
 }
 
+/// The range of a match, and the ranges of its capture groups (or `None` for
+/// groups that did not participate in the match).
+pub type RangeWithCaptures = (Range<usize>, Vec<Option<Range<usize>>>);
+
 #[derive(Collect, Debug)]
 #[collect(no_drop)]
 struct CachedText<'gc> {
@@ -126,6 +126,10 @@ impl<'gc> ScopeChain<'gc> {
         if let Some(scopes) = self.scopes {
             for (depth, scope) in scopes.iter().enumerate().rev() {
                 let values = scope.values();
+                if scope.with() && has_xml_property(values, name) {
+                    return Ok(Some(values));
+                }
+
                 if let Some(qname) = values.resolve_multiname(name)? {
                     // We search the dynamic properties if either conditions are met:
                     // 1. Scope is a `with` scope
@@ -198,6 +202,10 @@ impl<'gc> ScopeStack<'gc> {
     pub fn find(&self, name: &Multiname<'gc>, global: bool) -> Result<Option<Object<'gc>>, Error> {
         for (depth, scope) in self.scopes.iter().enumerate().rev() {
             let values = scope.values();
+            if scope.with() && has_xml_property(values, name) {
+                return Ok(Some(values));
+            }
+
             if let Some(qname) = values.resolve_multiname(name)? {
                 // We search the dynamic properties if either conditions are met:
                 // 1. Scope is a `with` scope
@@ -212,3 +220,16 @@ impl<'gc> ScopeStack<'gc> {
         Ok(None)
     }
 }
+
+/// Determine if a `with` scope's XML or XMLList object has children or
+/// attributes matching a name, so that E4X filter predicates can refer to
+/// them unqualified.
+fn has_xml_property<'gc>(values: Object<'gc>, name: &Multiname<'gc>) -> bool {
+    if let Some(xml) = values.as_xml_object() {
+        xml.has_xml_property(name)
+    } else if let Some(list) = values.as_xml_list_object() {
+        list.has_xml_property(name)
+    } else {
+        false
+    }
+}
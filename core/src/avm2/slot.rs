@@ -55,6 +55,14 @@ impl<'gc> Slot<'gc> {
         }
     }
 
+    /// Determine if this slot holds a `const` that cannot be overwritten.
+    pub fn is_const(&self) -> bool {
+        match self {
+            Self::Unoccupied => false,
+            Self::Occupied { attributes, .. } => attributes.contains(Attribute::READ_ONLY),
+        }
+    }
+
     /// Write the value of this slot.
     pub fn set(&mut self, new_value: impl Into<Value<'gc>>) -> Result<(), Error> {
         match self {
@@ -0,0 +1,579 @@
+//! Registry of open `Socket` and `XMLSocket` connections
+
+use crate::avm2::bytearray::{ByteArrayStorage, EofError};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::{Activation, ClassObject, Error, Object, TObject, Value};
+use crate::backend::navigator::{ConnectionState, SocketAction, SocketHandle};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use quick_xml::events::Event as XmlEvent;
+use quick_xml::Reader;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+use url::Url;
+
+/// The port that socket policy files are requested from.
+const POLICY_PORT: u16 = 843;
+
+/// The request sent to a policy server.
+const POLICY_FILE_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// The kind of object that owns a socket.
+#[derive(Collect, Clone, Copy, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum SocketKind {
+    /// A `flash.net.Socket`, which receives raw bytes into its input buffer.
+    Binary,
+
+    /// A `flash.net.XMLSocket`, which receives zero-terminated strings.
+    Xml,
+}
+
+/// A connection requested by a `Socket` or `XMLSocket` object.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct Socket<'gc> {
+    /// The object that events for this socket are dispatched to.
+    target: Object<'gc>,
+
+    kind: SocketKind,
+
+    #[collect(require_static)]
+    host: String,
+
+    port: u16,
+
+    #[collect(require_static)]
+    timeout: Duration,
+
+    /// Sink for data written to the connection.
+    ///
+    /// This is `None` until the host's policy file allows the connection.
+    #[collect(require_static)]
+    sender: Option<Sender<Vec<u8>>>,
+
+    /// Whether the connection has been established.
+    connected: bool,
+
+    /// Received data that does not yet form a complete `XMLSocket` message.
+    #[collect(require_static)]
+    partial_message: Vec<u8>,
+}
+
+/// A request for a host's socket policy file, made before connecting to it.
+struct PolicyCheck {
+    /// The socket waiting for this policy file.
+    socket: SocketHandle,
+
+    /// The port the policy file was requested from.
+    port: u16,
+
+    /// The policy file received so far.
+    response: Vec<u8>,
+
+    /// Keeps the policy connection open until the policy file is received.
+    _sender: Sender<Vec<u8>>,
+}
+
+/// All sockets opened by AVM2 objects in this player.
+///
+/// Before connecting to a host, its policy file is requested from port 843,
+/// falling back to the destination port itself if that doesn't allow the
+/// connection. The connection fails with a `SecurityErrorEvent` unless one of
+/// the policies allows it. Events reported by the navigator backend are dispatched to the
+/// owning objects once per frame.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Sockets<'gc> {
+    sockets: FnvHashMap<SocketHandle, Socket<'gc>>,
+
+    #[collect(require_static)]
+    policy_checks: FnvHashMap<SocketHandle, PolicyCheck>,
+
+    next_handle: SocketHandle,
+
+    /// The sender given to the navigator backend for each connection.
+    #[collect(require_static)]
+    action_sender: Sender<SocketAction>,
+
+    #[collect(require_static)]
+    action_receiver: Receiver<SocketAction>,
+}
+
+impl<'gc> Default for Sockets<'gc> {
+    fn default() -> Self {
+        let (action_sender, action_receiver) = channel();
+
+        Self {
+            sockets: Default::default(),
+            policy_checks: Default::default(),
+            next_handle: 0,
+            action_sender,
+            action_receiver,
+        }
+    }
+}
+
+impl<'gc> Sockets<'gc> {
+    fn allocate_handle(&mut self) -> SocketHandle {
+        let handle = self.next_handle;
+        self.next_handle = self.next_handle.wrapping_add(1);
+        handle
+    }
+
+    /// Find the socket owned by `target`.
+    fn find(&self, target: Object<'gc>) -> Option<SocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| *handle)
+    }
+
+    /// Start connecting `target` to the given host and port, closing any
+    /// socket it already owns.
+    ///
+    /// The connection is only opened once the host's policy file has been
+    /// received; the outcome is reported to `target` as an event.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        kind: SocketKind,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) {
+        let sockets = &mut context.avm2.sockets;
+        sockets.close(target);
+
+        let handle = sockets.allocate_handle();
+        sockets.sockets.insert(
+            handle,
+            Socket {
+                target,
+                kind,
+                host: host.clone(),
+                port,
+                timeout,
+                sender: None,
+                connected: false,
+                partial_message: Vec::new(),
+            },
+        );
+
+        Self::request_policy(context, handle, host, POLICY_PORT, timeout);
+    }
+
+    /// Request the policy file of a socket's host from the given port.
+    fn request_policy(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        socket: SocketHandle,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) {
+        let sockets = &mut context.avm2.sockets;
+        let policy_handle = sockets.allocate_handle();
+
+        let (sender, receiver) = channel();
+        let _ = sender.send(POLICY_FILE_REQUEST.to_vec());
+        sockets.policy_checks.insert(
+            policy_handle,
+            PolicyCheck {
+                socket,
+                port,
+                response: Vec::new(),
+                _sender: sender,
+            },
+        );
+
+        let action_sender = sockets.action_sender.clone();
+        context.navigator.connect_socket(
+            host,
+            port,
+            timeout,
+            policy_handle,
+            receiver,
+            action_sender,
+        );
+    }
+
+    /// Close the socket owned by `target`, without notifying it.
+    ///
+    /// Returns `false` if `target` has no open or pending socket.
+    pub fn close(&mut self, target: Object<'gc>) -> bool {
+        match self.find(target) {
+            Some(handle) => {
+                self.sockets.remove(&handle);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the socket owned by `target` has connected and is still open.
+    pub fn is_connected(&self, target: Object<'gc>) -> bool {
+        self.find(target)
+            .and_then(|handle| self.sockets.get(&handle))
+            .map(|socket| socket.connected)
+            .unwrap_or(false)
+    }
+
+    /// Write data to the socket owned by `target`.
+    ///
+    /// Returns `false` if `target` has no connected socket.
+    pub fn send(&self, target: Object<'gc>, data: Vec<u8>) -> bool {
+        let socket = self
+            .find(target)
+            .and_then(|handle| self.sockets.get(&handle));
+
+        match socket {
+            Some(Socket {
+                connected: true,
+                sender: Some(sender),
+                ..
+            }) => sender.send(data).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Dispatch events for everything that happened to our sockets since the
+    /// last update.
+    pub fn update_sockets(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let actions: Vec<_> = context.avm2.sockets.action_receiver.try_iter().collect();
+
+        for action in actions {
+            let handle = match action {
+                SocketAction::Connect(handle, _)
+                | SocketAction::Data(handle, _)
+                | SocketAction::Close(handle) => handle,
+            };
+
+            let result = if context.avm2.sockets.policy_checks.contains_key(&handle) {
+                Self::update_policy_check(context, handle, action)
+            } else {
+                Self::update_socket(context, handle, action)
+            };
+
+            if let Err(e) = result {
+                log::error!(
+                    "Encountered AVM2 error when dispatching socket event: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    fn update_policy_check(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handle: SocketHandle,
+        action: SocketAction,
+    ) -> Result<(), Error> {
+        let policy_checks = &mut context.avm2.sockets.policy_checks;
+        let is_complete = match action {
+            SocketAction::Connect(_, ConnectionState::Connected) => false,
+            SocketAction::Connect(_, _) | SocketAction::Close(_) => true,
+            SocketAction::Data(_, data) => {
+                let check = policy_checks.get_mut(&handle).unwrap();
+                check.response.extend(data);
+                check.response.contains(&0)
+            }
+        };
+
+        if !is_complete {
+            return Ok(());
+        }
+
+        // Dropping the check closes the policy connection.
+        let check = policy_checks.remove(&handle).unwrap();
+        let socket = match context.avm2.sockets.sockets.get(&check.socket) {
+            Some(socket) => socket,
+            // The socket was closed while waiting for the policy file.
+            None => return Ok(()),
+        };
+
+        let movie_host = context
+            .swf
+            .url()
+            .and_then(|url| Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_owned));
+        let response = check.response.split(|&b| b == 0).next().unwrap_or(&[]);
+
+        if policy_allows(response, movie_host.as_deref(), socket.port) {
+            let (sender, receiver) = channel();
+            let (host, port, timeout) = (socket.host.clone(), socket.port, socket.timeout);
+            let action_sender = context.avm2.sockets.action_sender.clone();
+
+            if let Some(socket) = context.avm2.sockets.sockets.get_mut(&check.socket) {
+                socket.sender = Some(sender);
+            }
+            context.navigator.connect_socket(
+                host,
+                port,
+                timeout,
+                check.socket,
+                receiver,
+                action_sender,
+            );
+
+            return Ok(());
+        }
+
+        if check.port != socket.port {
+            // Hosts without a master policy server may serve their policy
+            // from the destination port instead.
+            let (host, port, timeout) = (socket.host.clone(), socket.port, socket.timeout);
+            Self::request_policy(context, check.socket, host, port, timeout);
+
+            return Ok(());
+        }
+
+        let socket = context.avm2.sockets.sockets.remove(&check.socket).unwrap();
+        let text = format!(
+            "Error #2048: Security sandbox violation: cannot load data from {}:{}.",
+            socket.host, socket.port
+        );
+        let class = context.avm2.classes().securityerrorevent;
+
+        Self::dispatch_error(context, socket.target, class, "securityError", text, 2048)
+    }
+
+    fn update_socket(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handle: SocketHandle,
+        action: SocketAction,
+    ) -> Result<(), Error> {
+        let socket = match context.avm2.sockets.sockets.get_mut(&handle) {
+            Some(socket) => socket,
+            // Ignore anything that happens after a socket is closed.
+            None => return Ok(()),
+        };
+        let target = socket.target;
+
+        match action {
+            SocketAction::Connect(_, ConnectionState::Connected) => {
+                socket.connected = true;
+
+                let class = context.avm2.classes().event;
+                Self::dispatch(context, target, class, &["connect".into()])
+            }
+            SocketAction::Connect(_, _) => {
+                let socket = context.avm2.sockets.sockets.remove(&handle).unwrap();
+                let text = format!("Error #2031: Socket Error. URL: {}", socket.host);
+                let class = context.avm2.classes().ioerrorevent;
+
+                Self::dispatch_error(context, target, class, "ioError", text, 2031)
+            }
+            SocketAction::Data(_, data) => match socket.kind {
+                SocketKind::Binary => {
+                    let length = data.len();
+                    if let Some(mut input) = target.as_bytearray_mut(context.gc_context) {
+                        // Unread data is kept, so that messages split across
+                        // several packets can be read once they're complete.
+                        if input.bytes_available() == 0 {
+                            input.clear();
+                        }
+                        let end = input.len();
+                        input.write_at(&data, end)?;
+                    }
+
+                    let class = context.avm2.classes().progressevent;
+                    Self::dispatch(
+                        context,
+                        target,
+                        class,
+                        &[
+                            "socketData".into(),
+                            false.into(),
+                            false.into(),
+                            (length as f64).into(),
+                            0.0.into(),
+                        ],
+                    )
+                }
+                SocketKind::Xml => {
+                    socket.partial_message.extend(data);
+
+                    let mut messages = Vec::new();
+                    while let Some(end) = socket.partial_message.iter().position(|&b| b == 0) {
+                        let message: Vec<u8> = socket.partial_message.drain(..=end).collect();
+                        messages.push(String::from_utf8_lossy(&message[..end]).into_owned());
+                    }
+
+                    let class = context.avm2.classes().dataevent;
+                    for message in messages {
+                        let message = AvmString::new_utf8(context.gc_context, message);
+                        Self::dispatch(
+                            context,
+                            target,
+                            class,
+                            &["data".into(), false.into(), false.into(), message.into()],
+                        )?;
+                    }
+
+                    Ok(())
+                }
+            },
+            SocketAction::Close(_) => {
+                context.avm2.sockets.sockets.remove(&handle);
+
+                let class = context.avm2.classes().event;
+                Self::dispatch(context, target, class, &["close".into()])
+            }
+        }
+    }
+
+    /// Construct an event of the given class and dispatch it to `target`.
+    fn dispatch(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        class: ClassObject<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let event = class.construct(&mut activation, args)?;
+
+        dispatch_event(&mut activation, target, event)?;
+
+        Ok(())
+    }
+
+    fn dispatch_error(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        class: ClassObject<'gc>,
+        event_type: &'static str,
+        text: String,
+        error_id: i32,
+    ) -> Result<(), Error> {
+        let text = AvmString::new_utf8(context.gc_context, text);
+
+        Self::dispatch(
+            context,
+            target,
+            class,
+            &[
+                event_type.into(),
+                false.into(),
+                false.into(),
+                text.into(),
+                error_id.into(),
+            ],
+        )
+    }
+}
+
+/// Check whether a socket policy file allows a movie hosted at `movie_host`
+/// to connect to `port`.
+fn policy_allows(policy: &[u8], movie_host: Option<&str>, port: u16) -> bool {
+    let mut reader = Reader::from_reader(policy);
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(XmlEvent::Start(ref e)) | Ok(XmlEvent::Empty(ref e))
+                if e.name() == b"allow-access-from" =>
+            {
+                let attribute = |name: &[u8]| {
+                    e.attributes().with_checks(false).find_map(|attribute| {
+                        let attribute = attribute.ok()?;
+                        (attribute.key == name)
+                            .then(|| String::from_utf8_lossy(&attribute.value).into_owned())
+                    })
+                };
+
+                let domain_allowed = match attribute(b"domain") {
+                    Some(domain) => domain == "*" || Some(domain.as_str()) == movie_host,
+                    None => false,
+                };
+                let port_allowed = match attribute(b"to-ports") {
+                    Some(ports) => ports_allow(&ports, port),
+                    None => false,
+                };
+
+                if domain_allowed && port_allowed {
+                    return true;
+                }
+            }
+            Ok(XmlEvent::Eof) | Err(_) => return false,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Finish a read from a `Socket`'s received data that started at `start`.
+///
+/// A read that runs past the data received so far throws an `EOFError`, but
+/// consumes none of the data. It stays buffered, so the read succeeds once
+/// the next `socketData` event reports that the rest has arrived.
+pub fn finish_read<T>(
+    input: &ByteArrayStorage,
+    start: usize,
+    result: Result<T, Error>,
+) -> Result<T, Error> {
+    if let Err(error) = &result {
+        if error.is::<EofError>() {
+            input.set_position(start);
+        }
+    }
+
+    result
+}
+
+/// Check whether a policy's `to-ports` list, such as `507,516-523`, contains
+/// `port`.
+fn ports_allow(ports: &str, port: u16) -> bool {
+    ports.split(',').map(str::trim).any(|range| {
+        if range == "*" {
+            return true;
+        }
+
+        match range.split_once('-') {
+            Some((start, end)) => match (start.parse::<u16>(), end.parse::<u16>()) {
+                (Ok(start), Ok(end)) => (start..=end).contains(&port),
+                _ => false,
+            },
+            None => range.parse() == Ok(port),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_read_waits_for_the_rest() {
+        let mut input = ByteArrayStorage::new();
+        input.write_bytes(&[0, 5, b'h', b'e']).unwrap();
+        input.set_position(0);
+
+        let result = input.read_utf();
+        let error = finish_read(&input, 0, result).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "EOFError: Error #2030: End of file was encountered."
+        );
+        assert_eq!(input.position(), 0);
+        assert_eq!(input.bytes_available(), 4);
+
+        let end = input.len();
+        input.write_at(b"llo", end).unwrap();
+
+        let result = input.read_utf();
+        assert_eq!(finish_read(&input, 0, result).unwrap(), "hello");
+        assert_eq!(input.bytes_available(), 0);
+    }
+
+    #[test]
+    fn other_read_errors_consume_the_data() {
+        let input = ByteArrayStorage::from_vec(vec![1, 2, 3]);
+        input.set_position(2);
+        let result: Result<(), Error> = Err("Error: Invalid object".into());
+
+        assert!(finish_read(&input, 0, result).is_err());
+        assert_eq!(input.position(), 2);
+    }
+}
@@ -0,0 +1,619 @@
+//! Helpers for running AVM2 builtins in unit tests
+
+use crate::avm1::globals::system::SystemProperties;
+use crate::avm1::{Avm1, Timers};
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{ArrayObject, ClassObject, FunctionObject, Object, TObject};
+use crate::avm2::script::TranslationUnit;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::audio::{AudioManager, NullAudioBackend};
+use crate::backend::locale::NullLocaleBackend;
+use crate::backend::log::NullLogBackend;
+use crate::backend::navigator::NullNavigatorBackend;
+use crate::backend::render::NullRenderer;
+use crate::backend::storage::MemoryStorageBackend;
+use crate::backend::ui::NullUiBackend;
+use crate::backend::video::NullVideoBackend;
+use crate::context::{ActionQueue, UpdateContext};
+use crate::display_object::{MovieClip, Stage, TDisplayObject};
+use crate::focus_tracker::FocusTracker;
+use crate::library::Library;
+use crate::loader::LoadManager;
+use crate::player::{Player, DEVICE_FONT_TAG};
+use crate::prelude::*;
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::Instantiator;
+use gc_arena::{rootless_arena, GcCell, MutationContext};
+use instant::Instant;
+use rand::{rngs::SmallRng, SeedableRng};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use swf::avm2::types::{
+    AbcFile, ConstantPool, DefaultValue, Index, Method as AbcMethod, MethodBody, MethodParam,
+    Multiname as AbcMultiname, Namespace as AbcNamespace,
+};
+
+/// The garbage collector statistics reported to tests.
+pub const GC_TOTAL_ALLOCATED: usize = 3 * 1024 * 1024;
+pub const GC_REMEMBERED_SIZE: usize = 2 * 1024 * 1024;
+
+thread_local! {
+    /// Messages recorded by native callbacks during the current test.
+    static LOG: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// Record a message from a native callback, like `trace` would.
+pub fn log(message: impl Into<String>) {
+    LOG.with(|log| log.borrow_mut().push(message.into()));
+}
+
+/// Take the messages recorded since the last call.
+pub fn take_log() -> Vec<String> {
+    LOG.with(|log| log.take())
+}
+
+/// Build an uncompressed AS3 movie whose main timeline has the given number
+/// of empty frames.
+fn movie_data(num_frames: u16) -> Vec<u8> {
+    // The header has an empty stage and a 24fps frame rate. The tags are a
+    // `FileAttributes` tag marking the movie as AS3, a `ShowFrame` tag per
+    // frame and an `End` tag.
+    let length = 13 + 6 + 2 * (num_frames as u32 + 1);
+    let mut data = b"FWS\x0a".to_vec();
+    data.extend_from_slice(&length.to_le_bytes());
+    data.extend_from_slice(&[0x00, 0x00, 24]);
+    data.extend_from_slice(&num_frames.to_le_bytes());
+    data.extend_from_slice(&[0x44, 0x11, 0x08, 0x00, 0x00, 0x00]);
+    for _ in 0..num_frames {
+        data.extend_from_slice(&[0x40, 0x00]);
+    }
+    data.extend_from_slice(&[0x00, 0x00]);
+
+    data
+}
+
+/// Run a test against a player with the AVM2 globals loaded and an AVM2
+/// stage, and panic if it returns an error.
+pub fn with_avm2<F>(test: F)
+where
+    F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>) -> Result<(), Error>,
+{
+    fn in_the_arena<'gc, F>(test: F, gc_context: MutationContext<'gc, '_>)
+    where
+        F: FnOnce(&mut Activation<'_, 'gc, '_>) -> Result<(), Error>,
+    {
+        let mut avm1 = Avm1::new(gc_context, NEWEST_PLAYER_VERSION);
+        let mut avm2 = Avm2::new(gc_context);
+        let swf = Arc::new(SwfMovie::from_data(&movie_data(1), None, None).unwrap());
+        let root: DisplayObject<'gc> = MovieClip::from_movie(gc_context, swf.clone()).into();
+        root.set_depth(gc_context, 0);
+        let stage = Stage::empty(gc_context, 550, 400);
+        let mut frame_rate = 24.0;
+
+        // Text fields are laid out with the device font.
+        let mut renderer = NullRenderer::new();
+        let mut library = Library::empty(gc_context);
+        let device_font = Player::load_device_font(gc_context, DEVICE_FONT_TAG, &mut renderer);
+        library.set_device_font(device_font.ok());
+
+        let mut context = UpdateContext {
+            gc_context,
+            player_version: 32,
+            swf: &swf,
+            stage,
+            rng: &mut SmallRng::from_seed([0u8; 32]),
+            audio: &mut NullAudioBackend::new(),
+            ui: &mut NullUiBackend::new(),
+            action_queue: &mut ActionQueue::new(),
+            library: &mut library,
+            navigator: &mut NullNavigatorBackend::new(),
+            renderer: &mut renderer,
+            locale: &mut NullLocaleBackend::new(),
+            log: &mut NullLogBackend::new(),
+            video: &mut NullVideoBackend::new(),
+            mouse_over_object: None,
+            mouse_down_object: None,
+            avm2_mouse_over_object: None,
+            mouse_position: &(Twips::ZERO, Twips::ZERO),
+            drag_object: &mut None,
+            player: None,
+            load_manager: &mut LoadManager::new(),
+            system: &mut SystemProperties::default(),
+            instance_counter: &mut 0,
+            storage: &mut MemoryStorageBackend::default(),
+            shared_objects: &mut HashMap::new(),
+            unbound_text_fields: &mut Vec::new(),
+            timers: &mut Timers::new(),
+            current_context_menu: &mut None,
+            needs_render: &mut false,
+            avm1: &mut avm1,
+            avm2: &mut avm2,
+            external_interface: &mut Default::default(),
+            update_start: Instant::now(),
+            max_execution_duration: Duration::from_secs(15),
+            focus_tracker: FocusTracker::new(gc_context),
+            times_get_time_called: 0,
+            time_offset: &mut 0,
+            audio_manager: &mut AudioManager::new(),
+            frame_rate: &mut frame_rate,
+            gc_total_allocated: GC_TOTAL_ALLOCATED,
+            gc_remembered_size: GC_REMEMBERED_SIZE,
+            needs_gc: &mut false,
+        };
+
+        root.post_instantiation(&mut context, root, None, Instantiator::Movie, false);
+        context.stage.replace_at_depth(&mut context, root, 0);
+
+        if let Err(e) = Avm2::load_player_globals(&mut context) {
+            panic!("Could not load the AVM2 globals: {}", e);
+        }
+
+        let stage = context.stage;
+        stage.post_instantiation(&mut context, stage.into(), None, Instantiator::Movie, false);
+
+        let mut activation = Activation::from_nothing(context.reborrow());
+        if let Err(e) = test(&mut activation) {
+            panic!("Encountered exception during test: {}", e);
+        }
+    }
+
+    take_log();
+    rootless_arena(|gc_context| in_the_arena(test, gc_context))
+}
+
+/// Look up a class defined by the player globals.
+///
+/// Top-level classes use an empty package name.
+pub fn class<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+) -> Result<ClassObject<'gc>, Error> {
+    let namespace = if package.is_empty() {
+        Namespace::public()
+    } else {
+        Namespace::package(package)
+    };
+    let domain = activation.avm2().global_domain();
+
+    domain
+        .get_defined_value(activation, QName::new(namespace, name))?
+        .coerce_to_object(activation)?
+        .as_class_object()
+        .ok_or_else(|| format!("{} is not a class", name).into())
+}
+
+/// Implements the instance initializer of classes made by `define_class`.
+fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the class initializer of classes made by `define_class`.
+fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Define a dynamic class with public methods, like a `dynamic class`
+/// declaration in a script would.
+///
+/// Classes without a superclass extend `Object`.
+pub fn define_class<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+    superclass: Option<ClassObject<'gc>>,
+    instance_methods: &[(&'static str, NativeMethodImpl)],
+    class_methods: &[(&'static str, NativeMethodImpl)],
+) -> Result<ClassObject<'gc>, Error> {
+    let mc = activation.context.gc_context;
+    let superclass = superclass.unwrap_or_else(|| activation.avm2().classes().object);
+    let class = new_class(mc, name, superclass);
+
+    let mut write = class.write(mc);
+    write.define_public_builtin_instance_methods(mc, instance_methods);
+    write.define_public_builtin_class_methods(mc, class_methods);
+    drop(write);
+
+    ClassObject::from_class(activation, class, Some(superclass))
+}
+
+/// Define a dynamic class with the given instance traits, such as accessors
+/// or overrides of its superclass.
+///
+/// Classes without a superclass extend `Object`.
+pub fn define_class_with_traits<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &'static str,
+    superclass: Option<ClassObject<'gc>>,
+    instance_traits: Vec<Trait<'gc>>,
+) -> Result<ClassObject<'gc>, Error> {
+    let mc = activation.context.gc_context;
+    let superclass = superclass.unwrap_or_else(|| activation.avm2().classes().object);
+    let class = new_class(mc, name, superclass);
+
+    let mut write = class.write(mc);
+    for instance_trait in instance_traits {
+        write.define_instance_trait(instance_trait);
+    }
+    drop(write);
+
+    ClassObject::from_class(activation, class, Some(superclass))
+}
+
+/// A public getter or setter trait implemented by a native function.
+pub fn accessor<'gc>(
+    mc: MutationContext<'gc, '_>,
+    name: &'static str,
+    getter: Option<NativeMethodImpl>,
+    setter: Option<NativeMethodImpl>,
+) -> Vec<Trait<'gc>> {
+    let mut traits = Vec::new();
+    if let Some(getter) = getter {
+        traits.push(Trait::from_getter(
+            QName::new(Namespace::public(), name),
+            Method::from_builtin(getter, name, mc),
+        ));
+    }
+    if let Some(setter) = setter {
+        traits.push(Trait::from_setter(
+            QName::new(Namespace::public(), name),
+            Method::from_builtin(setter, name, mc),
+        ));
+    }
+
+    traits
+}
+
+/// Create the definition of a test class with no traits.
+fn new_class<'gc>(
+    mc: MutationContext<'gc, '_>,
+    name: &'static str,
+    superclass: ClassObject<'gc>,
+) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::public(), name),
+        Some(superclass.inner_class_definition().read().name().into()),
+        Method::from_builtin(instance_init, "<test instance initializer>", mc),
+        Method::from_builtin(class_init, "<test class initializer>", mc),
+        mc,
+    )
+}
+
+/// Wrap a native function in a function object, like a function expression
+/// in a script would.
+pub fn function<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    function: NativeMethodImpl,
+) -> Result<Object<'gc>, Error> {
+    let method = Method::from_builtin(function, "<test function>", activation.context.gc_context);
+    let scope = activation.create_scopechain();
+
+    FunctionObject::from_function(activation, method, scope)
+}
+
+/// Construct an array holding the given values, like an array literal would.
+pub fn array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    ArrayObject::from_storage(activation, ArrayStorage::from_args(values))
+}
+
+/// Load a movie whose main timeline has the given number of empty frames
+/// as the root of the stage, and run its first frame like the player does.
+///
+/// This returns the `MovieClip` object of that timeline.
+pub fn timeline<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    num_frames: u16,
+) -> Result<Object<'gc>, Error> {
+    let movie = Arc::new(SwfMovie::from_data(&movie_data(num_frames), None, None)?);
+    let clip = MovieClip::from_movie(activation.context.gc_context, movie);
+    clip.preload(&mut activation.context, &mut Default::default());
+
+    let stage = activation.context.stage;
+    stage.replace_at_depth(&mut activation.context, clip.into(), 0);
+    run_frame(activation);
+
+    clip.object2().coerce_to_object(activation)
+}
+
+/// Run the frame phases of the stage, like the player does for each frame of
+/// an AS3 movie.
+pub fn run_frame(activation: &mut Activation<'_, '_, '_>) {
+    let context = &mut activation.context;
+    let stage = context.stage;
+
+    stage.exit_frame(context);
+    stage.enter_frame(context);
+    stage.construct_frame(context);
+    stage.frame_constructed(context);
+    stage.run_frame_avm2(context);
+    stage.run_frame_scripts(context);
+    stage.fire_render_event(context);
+}
+
+/// Construct an instance of a class defined by the player globals, like
+/// `new` would.
+pub fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    class(activation, package, name)?.construct(activation, args)
+}
+
+/// The multiname compiled code uses for a plain property access, which
+/// searches both the public and the `AS3` namespaces.
+pub fn public_name<'gc>(name: &'static str) -> Multiname<'gc> {
+    Multiname::with_namespaces(vec![Namespace::public(), Namespace::as3_namespace()], name)
+}
+
+/// Call a method of an object, like `object.name(args)`.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: impl Into<Object<'gc>>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    object
+        .into()
+        .call_property(&public_name(name), args, activation)
+}
+
+/// Read a property of an object, like `object.name`.
+pub fn get<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: impl Into<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    let object = object.into();
+
+    object.get_property(object, &public_name(name), activation)
+}
+
+/// Write a property of an object, like `object.name = value`.
+pub fn set<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: impl Into<Object<'gc>>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error> {
+    let mut object = object.into();
+
+    object.set_property(object, &public_name(name), value.into(), activation)
+}
+
+/// Convert a value to the string `trace` would print for it.
+pub fn traced<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: impl Into<Value<'gc>>,
+) -> Result<String, Error> {
+    Ok(value.into().coerce_to_string(activation)?.to_string())
+}
+
+/// Builds an ABC file of free-standing functions, like the compiler does for
+/// function declarations in a script.
+///
+/// Method bodies are raw bytecode.
+pub struct AbcBuilder {
+    abc: AbcFile,
+}
+
+impl AbcBuilder {
+    pub fn new() -> Self {
+        Self {
+            abc: AbcFile {
+                major_version: 46,
+                minor_version: 16,
+                constant_pool: ConstantPool {
+                    ints: Vec::new(),
+                    uints: Vec::new(),
+                    doubles: Vec::new(),
+                    strings: Vec::new(),
+                    namespaces: Vec::new(),
+                    namespace_sets: Vec::new(),
+                    multinames: Vec::new(),
+                },
+                methods: Vec::new(),
+                metadata: Vec::new(),
+                instances: Vec::new(),
+                classes: Vec::new(),
+                scripts: Vec::new(),
+                method_bodies: Vec::new(),
+            },
+        }
+    }
+
+    /// Add a value to a constant pool, whose indices start at 1.
+    fn pool_index<T: PartialEq>(pool: &mut Vec<T>, value: T) -> u32 {
+        let index = match pool.iter().position(|other| *other == value) {
+            Some(index) => index,
+            None => {
+                pool.push(value);
+                pool.len() - 1
+            }
+        };
+
+        index as u32 + 1
+    }
+
+    pub fn string(&mut self, value: &str) -> Index<String> {
+        let strings = &mut self.abc.constant_pool.strings;
+        Index::new(Self::pool_index(strings, value.to_string()))
+    }
+
+    pub fn int(&mut self, value: i32) -> Index<i32> {
+        Index::new(Self::pool_index(&mut self.abc.constant_pool.ints, value))
+    }
+
+    pub fn uint(&mut self, value: u32) -> Index<u32> {
+        Index::new(Self::pool_index(&mut self.abc.constant_pool.uints, value))
+    }
+
+    pub fn double(&mut self, value: f64) -> Index<f64> {
+        Index::new(Self::pool_index(&mut self.abc.constant_pool.doubles, value))
+    }
+
+    /// The multiname of a public definition in a package, such as a class
+    /// used as a type annotation.
+    ///
+    /// Top-level definitions use an empty package name.
+    pub fn name(&mut self, package: &str, name: &str) -> Index<AbcMultiname> {
+        let package = self.string(package);
+        let namespaces = &mut self.abc.constant_pool.namespaces;
+        let namespace = Index::new(Self::pool_index(namespaces, AbcNamespace::Package(package)));
+        let name = self.string(name);
+        let multinames = &mut self.abc.constant_pool.multinames;
+
+        Index::new(Self::pool_index(
+            multinames,
+            AbcMultiname::QName { namespace, name },
+        ))
+    }
+
+    /// The multiname of every public property, as in `xml.*`.
+    pub fn any_name(&mut self) -> Index<AbcMultiname> {
+        let package = self.string("");
+        let namespaces = &mut self.abc.constant_pool.namespaces;
+        let namespace = Index::new(Self::pool_index(namespaces, AbcNamespace::Package(package)));
+        let multinames = &mut self.abc.constant_pool.multinames;
+
+        Index::new(Self::pool_index(
+            multinames,
+            AbcMultiname::QName {
+                namespace,
+                name: Index::new(0),
+            },
+        ))
+    }
+
+    /// The multiname of a public attribute, as in `xml.@name`.
+    pub fn attribute_name(&mut self, name: &str) -> Index<AbcMultiname> {
+        let package = self.string("");
+        let namespaces = &mut self.abc.constant_pool.namespaces;
+        let namespace = Index::new(Self::pool_index(namespaces, AbcNamespace::Package(package)));
+        let name = self.string(name);
+        let multinames = &mut self.abc.constant_pool.multinames;
+
+        Index::new(Self::pool_index(
+            multinames,
+            AbcMultiname::QNameA { namespace, name },
+        ))
+    }
+
+    /// The multiname of a public property whose name is on the stack, such
+    /// as an array index.
+    pub fn runtime_name(&mut self) -> Index<AbcMultiname> {
+        let package = self.string("");
+        let namespaces = &mut self.abc.constant_pool.namespaces;
+        let namespace = Index::new(Self::pool_index(namespaces, AbcNamespace::Package(package)));
+        let namespace_sets = &mut self.abc.constant_pool.namespace_sets;
+        let namespace_set = Index::new(Self::pool_index(namespace_sets, vec![namespace]));
+        let multinames = &mut self.abc.constant_pool.multinames;
+
+        Index::new(Self::pool_index(
+            multinames,
+            AbcMultiname::MultinameL { namespace_set },
+        ))
+    }
+
+    /// The multiname of a public attribute whose name is on the stack, as in
+    /// `xml.@[name]`.
+    pub fn runtime_attribute_name(&mut self) -> Index<AbcMultiname> {
+        let package = self.string("");
+        let namespaces = &mut self.abc.constant_pool.namespaces;
+        let namespace = Index::new(Self::pool_index(namespaces, AbcNamespace::Package(package)));
+        let namespace_sets = &mut self.abc.constant_pool.namespace_sets;
+        let namespace_set = Index::new(Self::pool_index(namespace_sets, vec![namespace]));
+        let multinames = &mut self.abc.constant_pool.multinames;
+
+        Index::new(Self::pool_index(
+            multinames,
+            AbcMultiname::MultinameLA { namespace_set },
+        ))
+    }
+
+    /// Add a method with the given parameters and bytecode.
+    ///
+    /// The returned method can be changed to set its flags.
+    pub fn method(&mut self, name: &str, params: Vec<MethodParam>, code: &[u8]) -> &mut AbcMethod {
+        let name = self.string(name);
+        let index = self.abc.methods.len() as u32;
+        // The registers hold `this`, the parameters and the rest array, and
+        // scratch registers after those.
+        let num_locals = params.len() as u32 + 4;
+
+        self.abc.method_bodies.push(MethodBody {
+            method: Index::new(index),
+            max_stack: 16,
+            num_locals,
+            init_scope_depth: 0,
+            max_scope_depth: 1,
+            code: code.to_vec(),
+            exceptions: Vec::new(),
+            traits: Vec::new(),
+        });
+        self.abc.methods.push(AbcMethod {
+            name,
+            params,
+            return_type: Index::new(0),
+            needs_arguments_object: false,
+            needs_activation: false,
+            needs_rest: false,
+            needs_dxns: false,
+        });
+
+        self.abc.methods.last_mut().unwrap()
+    }
+
+    /// Load the methods as functions, in the order they were added.
+    pub fn functions<'gc>(
+        self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Vec<Object<'gc>>, Error> {
+        let num_methods = self.abc.methods.len() as u32;
+        let domain = activation.avm2().global_domain();
+        let unit =
+            TranslationUnit::from_abc(Rc::new(self.abc), domain, activation.context.gc_context);
+        let scope = activation.create_scopechain();
+
+        let mut functions = Vec::new();
+        for index in 0..num_methods {
+            let method = unit.load_method(index, true, activation)?;
+            functions.push(FunctionObject::from_function(activation, method, scope)?);
+        }
+
+        Ok(functions)
+    }
+}
+
+/// A method parameter of the given type, where index 0 is the any type.
+pub fn param(kind: Index<AbcMultiname>, default_value: Option<DefaultValue>) -> MethodParam {
+    MethodParam {
+        name: None,
+        kind,
+        default_value,
+    }
+}
@@ -7,10 +7,12 @@ use crate::avm2::names::{Multiname, QName};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
 use crate::avm2::Error;
+use crate::string::AvmString;
 use bitflags::bitflags;
-use gc_arena::{Collect, GcCell};
+use gc_arena::{Collect, GcCell, MutationContext};
 use swf::avm2::types::{
-    DefaultValue as AbcDefaultValue, Trait as AbcTrait, TraitKind as AbcTraitKind,
+    DefaultValue as AbcDefaultValue, Index, Metadata as AbcMetadata, Trait as AbcTrait,
+    TraitKind as AbcTraitKind,
 };
 
 bitflags! {
@@ -48,6 +50,57 @@ pub struct Trait<'gc> {
 
     /// The kind of trait in use.
     kind: TraitKind<'gc>,
+
+    /// The metadata tags attached to this trait.
+    metadata: Vec<Metadata<'gc>>,
+}
+
+/// A metadata tag attached to a trait, such as `[Event(name="change")]`.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct Metadata<'gc> {
+    /// The name of the tag.
+    pub name: AvmString<'gc>,
+
+    /// The key-value pairs of the tag.
+    pub items: Vec<MetadataItem<'gc>>,
+}
+
+/// A single key-value pair of a metadata tag.
+///
+/// Values given without a key have an empty key.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct MetadataItem<'gc> {
+    pub key: AvmString<'gc>,
+    pub value: AvmString<'gc>,
+}
+
+impl<'gc> Metadata<'gc> {
+    /// Load a metadata tag from an ABC file.
+    pub fn from_abc_index(
+        unit: TranslationUnit<'gc>,
+        index: &Index<AbcMetadata>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<Self, Error> {
+        let abc = unit.abc();
+        let abc_metadata: Result<&AbcMetadata, Error> = abc
+            .metadata
+            .get(index.0 as usize)
+            .ok_or_else(|| "LoadError: Metadata index not valid".into());
+        let abc_metadata = abc_metadata?;
+
+        let name = unit.pool_string(abc_metadata.name.0, mc)?;
+        let mut items = Vec::with_capacity(abc_metadata.items.len());
+        for item in &abc_metadata.items {
+            items.push(MetadataItem {
+                key: unit.pool_string(item.key.0, mc)?,
+                value: unit.pool_string(item.value.0, mc)?,
+            });
+        }
+
+        Ok(Self { name, items })
+    }
 }
 
 fn trait_attribs_from_abc_traits(abc_trait: &AbcTrait) -> TraitAttributes {
@@ -107,6 +160,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Class { slot_id: 0, class },
         }
     }
@@ -115,6 +169,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Method { disp_id: 0, method },
         }
     }
@@ -123,6 +178,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Getter { disp_id: 0, method },
         }
     }
@@ -131,6 +187,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Setter { disp_id: 0, method },
         }
     }
@@ -139,6 +196,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Function {
                 slot_id: 0,
                 function,
@@ -154,6 +212,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
+            metadata: Vec::new(),
             kind: TraitKind::Slot {
                 slot_id: 0,
                 default_value: default_value.unwrap_or_else(|| default_value_for_type(&type_name)),
@@ -170,7 +229,8 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: TraitAttributes::empty(),
-            kind: TraitKind::Slot {
+            metadata: Vec::new(),
+            kind: TraitKind::Const {
                 slot_id: 0,
                 default_value: default_value.unwrap_or_else(|| default_value_for_type(&type_name)),
                 type_name,
@@ -187,7 +247,12 @@ impl<'gc> Trait<'gc> {
         let mc = activation.context.gc_context;
         let name = QName::from_abc_multiname(unit, abc_trait.name.clone(), mc)?;
 
-        Ok(match &abc_trait.kind {
+        let mut metadata = Vec::with_capacity(abc_trait.metadata.len());
+        for index in &abc_trait.metadata {
+            metadata.push(Metadata::from_abc_index(unit, index, mc)?);
+        }
+
+        let mut loaded_trait = match &abc_trait.kind {
             AbcTraitKind::Slot {
                 slot_id,
                 type_name,
@@ -202,6 +267,7 @@ impl<'gc> Trait<'gc> {
                 Trait {
                     name,
                     attributes: trait_attribs_from_abc_traits(abc_trait),
+                    metadata: Vec::new(),
                     kind: TraitKind::Slot {
                         slot_id: *slot_id,
                         type_name,
@@ -212,6 +278,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Method { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Method {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, false, activation)?,
@@ -220,6 +287,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Getter { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Getter {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, false, activation)?,
@@ -228,22 +296,30 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Setter { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Setter {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, false, activation)?,
                 },
             },
-            AbcTraitKind::Class { slot_id, class } => Trait {
-                name,
-                attributes: trait_attribs_from_abc_traits(abc_trait),
-                kind: TraitKind::Class {
-                    slot_id: *slot_id,
-                    class: unit.load_class(class.0, activation)?,
-                },
-            },
+            AbcTraitKind::Class { slot_id, class } => {
+                let class = unit.load_class(class.0, activation)?;
+                class.write(mc).set_metadata(metadata.clone());
+
+                Trait {
+                    name,
+                    attributes: trait_attribs_from_abc_traits(abc_trait),
+                    metadata: Vec::new(),
+                    kind: TraitKind::Class {
+                        slot_id: *slot_id,
+                        class,
+                    },
+                }
+            }
             AbcTraitKind::Function { slot_id, function } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Function {
                     slot_id: *slot_id,
                     function: unit.load_method(function.0, true, activation)?,
@@ -263,6 +339,7 @@ impl<'gc> Trait<'gc> {
                 Trait {
                     name,
                     attributes: trait_attribs_from_abc_traits(abc_trait),
+                    metadata: Vec::new(),
                     kind: TraitKind::Const {
                         slot_id: *slot_id,
                         type_name,
@@ -270,13 +347,21 @@ impl<'gc> Trait<'gc> {
                     },
                 }
             }
-        })
+        };
+
+        loaded_trait.metadata = metadata;
+
+        Ok(loaded_trait)
     }
 
     pub fn name(&self) -> QName<'gc> {
         self.name
     }
 
+    pub fn metadata(&self) -> &[Metadata<'gc>] {
+        &self.metadata
+    }
+
     pub fn kind(&self) -> &TraitKind<'gc> {
         &self.kind
     }
@@ -250,6 +250,21 @@ impl<'gc> Value<'gc> {
         }
     }
 
+    /// Get the value as an array index, if it is a non-negative integer.
+    ///
+    /// This function performs no coercion: strings, boxed numbers, and numbers
+    /// with a fractional part are never indices.
+    pub fn as_array_index(&self) -> Option<usize> {
+        match self {
+            Value::Integer(num) if *num >= 0 => Some(*num as usize),
+            Value::Unsigned(num) => Some(*num as usize),
+            Value::Number(num) if *num >= 0.0 && *num <= u32::MAX as f64 && num.fract() == 0.0 => {
+                Some(*num as usize)
+            }
+            _ => None,
+        }
+    }
+
     /// Yields `true` if the given value is an unboxed primitive value.
     ///
     /// Note: Boxed primitive values are not considered primitive - it is
@@ -617,15 +632,48 @@ impl<'gc> Value<'gc> {
             }
         }
 
-        let static_class = class.inner_class_definition();
         Err(format!(
-            "Cannot coerce {:?} to an {:?}",
-            self,
-            static_class.read().name()
+            "TypeError: Error #1034: Type Coercion failed: cannot convert {} to {}.",
+            self.coercion_error_description(activation),
+            class.inner_class_definition().read().name().to_error_name()
         )
         .into())
     }
 
+    /// Describe this value the way Flash Player does in type coercion errors.
+    ///
+    /// Objects are described by their qualified class name, and strings are
+    /// quoted. Flash Player also prints the object's address, which we leave
+    /// out so that the messages are stable.
+    pub fn coercion_error_description(&self, activation: &mut Activation<'_, 'gc, '_>) -> String {
+        if let Value::Object(object) = self {
+            let primitive = object.as_primitive().map(|primitive| *primitive);
+            if let Some(primitive) = primitive {
+                return primitive.coercion_error_description(activation);
+            }
+
+            return object
+                .instance_of()
+                .map(|class| {
+                    class
+                        .inner_class_definition()
+                        .read()
+                        .name()
+                        .to_qualified_name(activation.context.gc_context)
+                        .to_string()
+                })
+                .unwrap_or_else(|| "Object".to_string());
+        }
+
+        match self {
+            Value::String(string) => format!("\"{}\"", string),
+            value => match value.coerce_to_string(activation) {
+                Ok(string) => string.to_string(),
+                Err(_) => format!("{:?}", value),
+            },
+        }
+    }
+
     /// Determine if this value is any kind of number.
     pub fn is_number(&self) -> bool {
         match self {
@@ -758,8 +806,9 @@ impl<'gc> Value<'gc> {
                 Value::String(_) | Value::Number(_) | Value::Unsigned(_) | Value::Integer(_),
                 Value::Object(_),
             ) => {
-                //TODO: Should this be `Hint::Number`, `Hint::String`, or no-hint?
-                let primitive_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
+                // ECMA-262 converts the object without a hint, so that
+                // objects such as `Date` use their own default.
+                let primitive_other = other.coerce_to_primitive(None, activation)?;
 
                 self.abstract_eq(&primitive_other, activation)
             }
@@ -767,8 +816,7 @@ impl<'gc> Value<'gc> {
                 Value::Object(_),
                 Value::String(_) | Value::Number(_) | Value::Unsigned(_) | Value::Integer(_),
             ) => {
-                //TODO: Should this be `Hint::Number`, `Hint::String`, or no-hint?
-                let primitive_self = self.coerce_to_primitive(Some(Hint::Number), activation)?;
+                let primitive_self = self.coerce_to_primitive(None, activation)?;
 
                 primitive_self.abstract_eq(other, activation)
             }
@@ -791,7 +839,8 @@ impl<'gc> Value<'gc> {
         let prim_other = other.coerce_to_primitive(Some(Hint::Number), activation)?;
 
         if let (Value::String(s), Value::String(o)) = (&prim_self, &prim_other) {
-            return Ok(Some(s.to_string().bytes().lt(o.to_string().bytes())));
+            // Strings compare by UTF-16 code units, not by their UTF-8 bytes.
+            return Ok(Some(s < o));
         }
 
         let num_self = prim_self.coerce_to_number(activation)?;
@@ -804,3 +853,92 @@ impl<'gc> Value<'gc> {
         Ok(Some(num_self < num_other))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::test_utils::*;
+    use crate::avm2::{TObject, Value};
+    use swf::avm2::types::Index;
+
+    #[test]
+    fn comparison_table() {
+        with_avm2(|activation| {
+            let mut abc = AbcBuilder::new();
+            let params = vec![param(Index::new(0), None), param(Index::new(0), None)];
+
+            // function compare(a, b) {
+            //     return [a == b, a === b, a < b, a > b, a <= b, a >= b];
+            // }
+            #[rustfmt::skip]
+            let code = [
+                0xd1, 0xd2, 0xab,               // a == b
+                0xd1, 0xd2, 0xac,               // a === b
+                0xd1, 0xd2, 0xad,               // a < b
+                0xd1, 0xd2, 0xaf,               // a > b
+                0xd1, 0xd2, 0xae,               // a <= b
+                0xd1, 0xd2, 0xb0,               // a >= b
+                0x56, 0x06,                     // newarray 6
+                0x48,                           // returnvalue
+            ];
+            abc.method("compare", params, &code);
+            let compare = abc.functions(activation)?[0];
+
+            let cases: [(Value, Value, &str); 15] = [
+                (
+                    f64::NAN.into(),
+                    f64::NAN.into(),
+                    "false false false false false false",
+                ),
+                (
+                    f64::NAN.into(),
+                    1.into(),
+                    "false false false false false false",
+                ),
+                (0.into(), (-0.0).into(), "true true false false true true"),
+                (
+                    Value::Undefined,
+                    Value::Null,
+                    "true false false false false false",
+                ),
+                (Value::Null, 0.into(), "false false false false true true"),
+                (
+                    Value::Undefined,
+                    0.into(),
+                    "false false false false false false",
+                ),
+                (
+                    Value::Null,
+                    false.into(),
+                    "false false false false true true",
+                ),
+                ("10".into(), 9.into(), "false false false true false true"),
+                ("10".into(), "9".into(), "false false true false true false"),
+                ("1".into(), 1.into(), "true false false false true true"),
+                ("".into(), 0.into(), "true false false false true true"),
+                (
+                    "abc".into(),
+                    0.into(),
+                    "false false false false false false",
+                ),
+                (true.into(), 1.into(), "true false false false true true"),
+                (
+                    f64::INFINITY.into(),
+                    f64::INFINITY.into(),
+                    "true true false false true true",
+                ),
+                (
+                    f64::NEG_INFINITY.into(),
+                    f64::INFINITY.into(),
+                    "false false true false true false",
+                ),
+            ];
+            for (a, b, expected) in cases {
+                let results = compare.call(None, &[a, b], activation)?;
+                let results = traced(activation, results)?;
+                assert_eq!(results.replace(',', " "), expected, "{:?}, {:?}", a, b);
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -94,7 +94,9 @@ impl<'gc> VectorStorage<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<(), Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         self.storage.resize(new_length, self.default(activation));
@@ -153,22 +155,26 @@ impl<'gc> VectorStorage<'gc> {
     /// things). You must use the associated `coerce` fn before storing things
     /// in the vector.
     ///
-    /// This function yields an error if the position is outside the length of
-    /// the vector.
-    pub fn set(
-        &mut self,
-        pos: usize,
-        value: Value<'gc>,
-        activation: &mut Activation<'_, 'gc, '_>,
-    ) -> Result<(), Error> {
-        if !self.is_fixed && pos == self.length() {
-            self.storage.resize(pos + 1, self.default(activation));
+    /// Positions within the vector overwrite the value already there, and the
+    /// position just past the end appends the value to a non-fixed vector.
+    /// All other positions, including appends to fixed vectors, yield an
+    /// error.
+    pub fn set(&mut self, pos: usize, value: Value<'gc>) -> Result<(), Error> {
+        let length = self.length();
+
+        if pos < length {
+            self.storage[pos] = value;
+        } else if pos == length && !self.is_fixed {
+            self.storage.push(value);
+        } else {
+            return Err(format!(
+                "RangeError: Error #1125: The index {} is out of range {}.",
+                pos, length
+            )
+            .into());
         }
 
-        self.storage
-            .get_mut(pos)
-            .map(|v| *v = value)
-            .ok_or_else(|| format!("RangeError: {} is outside the range of the vector", pos).into())
+        Ok(())
     }
 
     /// Push a value to the end of the vector.
@@ -181,7 +187,9 @@ impl<'gc> VectorStorage<'gc> {
     /// in the vector.
     pub fn push(&mut self, value: Value<'gc>) -> Result<(), Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         self.storage.push(value);
@@ -194,7 +202,9 @@ impl<'gc> VectorStorage<'gc> {
     /// This function returns an error if the vector is fixed.
     pub fn pop(&mut self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Value<'gc>, Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         match self.storage.pop() {
@@ -209,7 +219,7 @@ impl<'gc> VectorStorage<'gc> {
         }
     }
 
-    /// Push a value to the end of the vector.
+    /// Insert a value at the start of the vector.
     ///
     /// This function returns an error if the vector is fixed.
     ///
@@ -219,7 +229,9 @@ impl<'gc> VectorStorage<'gc> {
     /// in the vector.
     pub fn unshift(&mut self, value: Value<'gc>) -> Result<(), Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         self.storage.insert(0, value);
@@ -232,7 +244,9 @@ impl<'gc> VectorStorage<'gc> {
     /// This function returns an error if the vector is fixed.
     pub fn shift(&mut self, activation: &mut Activation<'_, 'gc, '_>) -> Result<Value<'gc>, Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         let unshifted = if self.storage.is_empty() {
@@ -266,7 +280,9 @@ impl<'gc> VectorStorage<'gc> {
     /// the array, backwards.
     pub fn insert(&mut self, position: i32, value: Value<'gc>) -> Result<(), Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         let position = self.clamp_parameter_index(position);
@@ -289,7 +305,9 @@ impl<'gc> VectorStorage<'gc> {
     /// check error.
     pub fn remove(&mut self, position: i32) -> Result<Value<'gc>, Error> {
         if self.is_fixed {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         let position = if position < 0 {
@@ -336,7 +354,9 @@ impl<'gc> VectorStorage<'gc> {
         R: Clone + SliceIndex<[Value<'gc>], Output = [Value<'gc>]> + RangeBounds<usize>,
     {
         if self.is_fixed && self.storage.index(range.clone()).len() != replace_with.len() {
-            return Err("RangeError: Vector is fixed".into());
+            return Err(
+                "RangeError: Error #1126: Cannot change the length of a fixed Vector.".into(),
+            );
         }
 
         Ok(self.storage.splice(range, replace_with).collect())
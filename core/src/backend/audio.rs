@@ -102,6 +102,14 @@ pub trait AudioBackend: Downcast {
     /// Set the volume transform for a sound instance.
     fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform);
 
+    /// Get the last 1024 sample frames of mixed output, oldest first.
+    ///
+    /// Each frame holds the left and right channels as normalized floats.
+    /// Backends that can't provide their output return silence.
+    fn get_sample_history(&self) -> [[f32; 2]; 1024] {
+        [[0.0; 2]; 1024]
+    }
+
     // TODO: Eventually remove this/move it to library.
     fn is_loading_complete(&self) -> bool {
         true
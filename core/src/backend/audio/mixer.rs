@@ -27,6 +27,9 @@ pub struct AudioMixer {
 
     /// The sample rate of the output stream in Hz.
     output_sample_rate: u32,
+
+    /// The most recently mixed output, used by `SoundMixer.computeSpectrum`.
+    sample_history: Arc<Mutex<SampleHistory>>,
 }
 
 type Error = Box<dyn std::error::Error>;
@@ -154,6 +157,7 @@ impl AudioMixer {
             sound_instances: Arc::new(Mutex::new(Arena::new())),
             num_output_channels,
             output_sample_rate,
+            sample_history: Arc::new(Mutex::new(SampleHistory::new())),
         }
     }
 
@@ -162,6 +166,7 @@ impl AudioMixer {
         AudioMixerProxy {
             sound_instances: Arc::clone(&self.sound_instances),
             num_output_channels: self.num_output_channels,
+            sample_history: Arc::clone(&self.sample_history),
         }
     }
 
@@ -176,8 +181,10 @@ impl AudioMixer {
         T::Float: dasp::sample::conv::FromSample<f32>,
     {
         let mut sound_instances = self.sound_instances.lock().unwrap();
+        let mut sample_history = self.sample_history.lock().unwrap();
         Self::mix_audio::<T>(
             &mut sound_instances,
+            &mut sample_history,
             self.num_output_channels,
             output_buffer,
         )
@@ -307,6 +314,7 @@ impl AudioMixer {
     /// and mixing in their output.
     fn mix_audio<'a, T>(
         sound_instances: &mut Arena<SoundInstance>,
+        sample_history: &mut SampleHistory,
         num_channels: u8,
         mut output_buffer: &mut [T],
     ) where
@@ -326,11 +334,14 @@ impl AudioMixer {
             .chunks_exact_mut(num_channels.into())
         {
             let mut output_frame = Stereo::<T::Signed>::EQUILIBRIUM;
+            let mut history_frame = [0.0f32; 2];
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.stream.is_exhausted() {
                     let sound_frame = sound.stream.next();
                     let [left_0, left_1] = sound_frame.mul_amp(sound.left_transform);
                     let [right_0, right_1] = sound_frame.mul_amp(sound.right_transform);
+                    history_frame[0] += left_0.to_sample::<f32>() + left_1.to_sample::<f32>();
+                    history_frame[1] += right_0.to_sample::<f32>() + right_1.to_sample::<f32>();
                     let sound_frame: Stereo<T::Signed> = [
                         Sample::add_amp(left_0, left_1).to_sample(),
                         Sample::add_amp(right_0, right_1).to_sample(),
@@ -344,6 +355,11 @@ impl AudioMixer {
             for (buf_sample, output_sample) in buf_frame.iter_mut().zip(output_frame.iter()) {
                 *buf_sample = output_sample.to_sample();
             }
+
+            sample_history.push([
+                history_frame[0].clamp(-1.0, 1.0),
+                history_frame[1].clamp(-1.0, 1.0),
+            ]);
         }
 
         // Remove all dead sounds.
@@ -486,6 +502,11 @@ impl AudioMixer {
     }
 
     /// Sets the sound transform for the given playing sound.
+    /// Returns the most recently mixed sample frames, oldest first.
+    pub fn get_sample_history(&self) -> [[f32; 2]; SAMPLE_HISTORY_LEN] {
+        self.sample_history.lock().unwrap().frames()
+    }
+
     pub fn set_sound_transform(
         &mut self,
         instance: SoundInstanceHandle,
@@ -509,6 +530,9 @@ pub struct AudioMixerProxy {
 
     /// The number of channels in the output stream. Must be 1 or 2.
     num_output_channels: u8,
+
+    /// The most recently mixed output, shared with the main `AudioMixer`.
+    sample_history: Arc<Mutex<SampleHistory>>,
 }
 
 impl AudioMixerProxy {
@@ -523,14 +547,49 @@ impl AudioMixerProxy {
         T::Float: dasp::sample::conv::FromSample<f32>,
     {
         let mut sound_instances = self.sound_instances.lock().unwrap();
+        let mut sample_history = self.sample_history.lock().unwrap();
         AudioMixer::mix_audio::<T>(
             &mut sound_instances,
+            &mut sample_history,
             self.num_output_channels,
             output_buffer,
         )
     }
 }
 
+/// The number of sample frames of mixed output kept by `SampleHistory`.
+pub const SAMPLE_HISTORY_LEN: usize = 1024;
+
+/// A ring buffer of the most recently mixed stereo sample frames.
+struct SampleHistory {
+    /// The stored frames, as normalized floats.
+    data: [[f32; 2]; SAMPLE_HISTORY_LEN],
+
+    /// The index that the next frame will be written to.
+    cursor: usize,
+}
+
+impl SampleHistory {
+    fn new() -> Self {
+        Self {
+            data: [[0.0; 2]; SAMPLE_HISTORY_LEN],
+            cursor: 0,
+        }
+    }
+
+    fn push(&mut self, frame: [f32; 2]) {
+        self.data[self.cursor] = frame;
+        self.cursor = (self.cursor + 1) % SAMPLE_HISTORY_LEN;
+    }
+
+    /// Returns the stored frames, oldest first.
+    fn frames(&self) -> [[f32; 2]; SAMPLE_HISTORY_LEN] {
+        let mut frames = self.data;
+        frames.rotate_left(self.cursor);
+        frames
+    }
+}
+
 /// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>>`.
 /// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
 struct ArcAsRef(Arc<[u8]>);
@@ -902,5 +961,10 @@ macro_rules! impl_audio_mixer_backend {
         ) {
             self.$mixer.set_sound_transform(instance, transform)
         }
+
+        #[inline]
+        fn get_sample_history(&self) -> [[f32; 2]; 1024] {
+            self.$mixer.get_sample_history()
+        }
     };
 }
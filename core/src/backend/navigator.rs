@@ -140,6 +140,35 @@ impl RequestOptions {
     }
 }
 
+/// Identifies a socket opened with `NavigatorBackend::connect_socket`.
+pub type SocketHandle = u32;
+
+/// The outcome of a socket connection attempt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The socket connected successfully and may now send and receive data.
+    Connected,
+
+    /// The connection was refused, or the host could not be reached.
+    Failed,
+
+    /// The connection did not complete before the timeout elapsed.
+    TimedOut,
+}
+
+/// Events reported by the backend about an open socket.
+#[derive(Debug)]
+pub enum SocketAction {
+    /// A connection attempt finished.
+    Connect(SocketHandle, ConnectionState),
+
+    /// Data was received from the remote host.
+    Data(SocketHandle, Vec<u8>),
+
+    /// The remote host closed the connection, or it was lost.
+    Close(SocketHandle),
+}
+
 /// Type alias for pinned, boxed, and owned futures that output a falliable
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
@@ -207,6 +236,26 @@ pub trait NavigatorBackend {
     /// Changing http -> https for example. This function may alter any part of the
     /// URL (generally only if configured to do so by the user).
     fn pre_process_url(&self, url: Url) -> Url;
+
+    /// Open a TCP connection to the given host and port.
+    ///
+    /// The outcome of the connection attempt, any data received, and the
+    /// closing of the connection must be reported through `sender`, tagged
+    /// with the given `handle`. Data to be written to the socket arrives
+    /// through `receiver`; the connection should be closed once `receiver` is
+    /// disconnected.
+    ///
+    /// Backends that cannot open raw TCP connections should report the
+    /// connection as failed.
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    );
 }
 
 /// A null implementation of an event loop that only supports blocking.
@@ -391,4 +440,16 @@ impl NavigatorBackend for NullNavigatorBackend {
     fn pre_process_url(&self, url: Url) -> Url {
         url
     }
+
+    fn connect_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+    }
 }
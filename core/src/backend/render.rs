@@ -1,3 +1,4 @@
+use crate::filters::Filter;
 use crate::matrix::Matrix;
 use crate::shape_utils::DistilledShape;
 pub use crate::{library::MovieLibrary, transform::Transform, Color};
@@ -46,6 +47,13 @@ pub trait RenderBackend: Downcast {
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
 
+    /// Begin drawing through a filter, until the matching `pop_filter`.
+    ///
+    /// This yields `false` if the filter cannot be drawn, in which case
+    /// `pop_filter` must not be called for it.
+    fn push_filter(&mut self, filter: &Filter) -> bool;
+    fn pop_filter(&mut self);
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap>;
     fn register_bitmap_raw(
         &mut self,
@@ -176,6 +184,10 @@ impl RenderBackend for NullRenderer {
     fn activate_mask(&mut self) {}
     fn deactivate_mask(&mut self) {}
     fn pop_mask(&mut self) {}
+    fn push_filter(&mut self, _filter: &Filter) -> bool {
+        true
+    }
+    fn pop_filter(&mut self) {}
 
     fn get_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
         None
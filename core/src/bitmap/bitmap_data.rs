@@ -4,6 +4,7 @@ use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::backend::render::{BitmapHandle, RenderBackend};
 use crate::bitmap::color_transform_params::ColorTransformParams;
 use crate::bitmap::turbulence::Turbulence;
+use crate::string::WStr;
 use bitflags::bitflags;
 use std::ops::Range;
 
@@ -128,6 +129,48 @@ bitflags! {
     }
 }
 
+/// The comparison used by `BitmapData.threshold` to select pixels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ThresholdOperation {
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl ThresholdOperation {
+    pub fn from_wstr(operation: &WStr) -> Option<Self> {
+        if operation == b"<" {
+            Some(Self::LessThan)
+        } else if operation == b"<=" {
+            Some(Self::LessThanOrEqual)
+        } else if operation == b"==" {
+            Some(Self::Equal)
+        } else if operation == b"!=" {
+            Some(Self::NotEqual)
+        } else if operation == b">" {
+            Some(Self::GreaterThan)
+        } else if operation == b">=" {
+            Some(Self::GreaterThanOrEqual)
+        } else {
+            None
+        }
+    }
+
+    pub fn matches(self, value: u32, threshold: u32) -> bool {
+        match self {
+            Self::LessThan => value < threshold,
+            Self::LessThanOrEqual => value <= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+            Self::GreaterThan => value > threshold,
+            Self::GreaterThanOrEqual => value >= threshold,
+        }
+    }
+}
+
 #[derive(Clone, Collect, Default, Debug)]
 #[collect(no_drop)]
 pub struct BitmapData<'gc> {
@@ -240,6 +283,51 @@ impl<'gc> BitmapData<'gc> {
         x >= 0 && x < self.width() as i32 && y >= 0 && y < self.height() as i32
     }
 
+    /// Clip `src_rect` of `source_bitmap` and its copy at `dest_point` in this
+    /// bitmap, so that both lie within their bitmaps.
+    ///
+    /// `margin` extends both bitmaps by that many pixels on each side, for
+    /// operations that sample past the area they write to. Returns the clipped
+    /// `(src_x, src_y, dest_x, dest_y, width, height)`, or `None` if nothing
+    /// is left.
+    fn clip_copy_rect(
+        &self,
+        source_bitmap: &Self,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        margin: (i32, i32),
+    ) -> Option<(i32, i32, i32, i32, i32, i32)> {
+        // Compute in i64, as script-provided rectangles may overflow i32.
+        let clip = |src_min: i32, size: i32, dest_min: i32, margin: i32, source: u32, dest: u32| {
+            let offset = dest_min as i64 - src_min as i64;
+            let margin = margin.max(0) as i64;
+            let min = (src_min as i64).max(-margin).max(-margin - offset);
+            let end = (src_min as i64 + size.max(0) as i64)
+                .min(source as i64 + margin)
+                .min(dest as i64 + margin - offset);
+            (min < end).then(|| (min as i32, (min + offset) as i32, (end - min) as i32))
+        };
+
+        let (src_x, dest_x, width) = clip(
+            src_rect.0,
+            src_rect.2,
+            dest_point.0,
+            margin.0,
+            source_bitmap.width(),
+            self.width(),
+        )?;
+        let (src_y, dest_y, height) = clip(
+            src_rect.1,
+            src_rect.3,
+            dest_point.1,
+            margin.1,
+            source_bitmap.height(),
+            self.height(),
+        )?;
+
+        Some((src_x, src_y, dest_x, dest_y, width, height))
+    }
+
     pub fn get_pixel_raw(&self, x: u32, y: u32) -> Option<Color> {
         if x >= self.width() || y >= self.height() {
             return None;
@@ -598,6 +686,171 @@ impl<'gc> BitmapData<'gc> {
         }
     }
 
+    /// Replace every pixel of `src_rect` whose masked value passes the
+    /// threshold test with `color`, returning how many pixels matched.
+    ///
+    /// Pixels that do not match are copied from the source if `copy_source`
+    /// is set, and otherwise left untouched.
+    #[allow(clippy::too_many_arguments)]
+    pub fn threshold(
+        &mut self,
+        source_bitmap: &Self,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        operation: ThresholdOperation,
+        threshold: u32,
+        color: Color,
+        mask: u32,
+        copy_source: bool,
+    ) -> u32 {
+        let masked_threshold = threshold & mask;
+        let mut matched = 0;
+
+        let (src_min_x, src_min_y, dest_min_x, dest_min_y, width, height) =
+            match self.clip_copy_rect(source_bitmap, src_rect, dest_point, (0, 0)) {
+                Some(clipped) => clipped,
+                None => return 0,
+            };
+
+        for y in 0..height {
+            for x in 0..width {
+                let (dest_x, dest_y) = (dest_min_x + x, dest_min_y + y);
+                let source_color = source_bitmap.get_pixel32(src_min_x + x, src_min_y + y);
+                if operation.matches(u32::from(source_color) & mask, masked_threshold) {
+                    self.set_pixel32(dest_x, dest_y, color);
+                    matched += 1;
+                } else if copy_source {
+                    self.set_pixel32(dest_x, dest_y, source_color);
+                }
+            }
+        }
+
+        matched
+    }
+
+    /// Box blur `src_rect` of the source into this bitmap at `dest_point`.
+    ///
+    /// Each of the `quality` passes blurs horizontally over `blur_x` pixels
+    /// and then vertically over `blur_y` pixels; repeated passes approximate
+    /// a Gaussian blur. Pixels outside of the source are treated as
+    /// transparent.
+    pub fn blur(
+        &mut self,
+        source_bitmap: &Self,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        blur_x: f64,
+        blur_y: f64,
+        quality: u32,
+    ) {
+        // Flash caps blur sizes at 255.
+        let radius_x = (blur_x.clamp(0.0, 255.0) / 2.0).floor() as usize;
+        let radius_y = (blur_y.clamp(0.0, 255.0) / 2.0).floor() as usize;
+
+        // Only pixels within the blur's reach of the destination can affect
+        // it, and everything past the source is transparent anyway.
+        let margin = (
+            (radius_x * quality as usize) as i32,
+            (radius_y * quality as usize) as i32,
+        );
+        let (src_min_x, src_min_y, dest_min_x, dest_min_y, width, height) =
+            match self.clip_copy_rect(source_bitmap, src_rect, dest_point, margin) {
+                Some(clipped) => clipped,
+                None => return,
+            };
+        let width = width as usize;
+        let height = height as usize;
+
+        // Work on premultiplied channels, so that transparent pixels do not
+        // bleed their color into their neighbours.
+        let mut channels: Vec<[f64; 4]> = Vec::with_capacity(width * height);
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                let color = if source_bitmap.is_point_in_bounds(src_min_x + x, src_min_y + y) {
+                    source_bitmap
+                        .get_pixel_raw((src_min_x + x) as u32, (src_min_y + y) as u32)
+                        .unwrap()
+                } else {
+                    0.into()
+                };
+                channels.push([
+                    color.alpha() as f64,
+                    color.red() as f64,
+                    color.green() as f64,
+                    color.blue() as f64,
+                ]);
+            }
+        }
+
+        for _ in 0..quality {
+            if radius_x > 0 {
+                box_blur_pass(&mut channels, width, height, radius_x, 1, width);
+            }
+            if radius_y > 0 {
+                box_blur_pass(&mut channels, height, width, radius_y, width, 1);
+            }
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let dest_x = dest_min_x + x as i32;
+                let dest_y = dest_min_y + y as i32;
+                if !self.is_point_in_bounds(dest_x, dest_y) {
+                    continue;
+                }
+
+                let [a, r, g, b] =
+                    channels[x + y * width].map(|c| c.round().clamp(0.0, 255.0) as u8);
+                let a = if self.transparency { a } else { 255 };
+                self.set_pixel32_raw(dest_x as u32, dest_y as u32, Color::argb(a, r, g, b));
+            }
+        }
+    }
+
+    /// Transform every pixel of `src_rect` of the source by a 4x5 color
+    /// matrix, writing the result into this bitmap at `dest_point`.
+    ///
+    /// The matrix is laid out row by row for red, green, blue and alpha, with
+    /// the last column of each row being an offset.
+    pub fn color_matrix(
+        &mut self,
+        source_bitmap: &Self,
+        src_rect: (i32, i32, i32, i32),
+        dest_point: (i32, i32),
+        matrix: &[f64; 20],
+    ) {
+        let (src_min_x, src_min_y, dest_min_x, dest_min_y, width, height) =
+            match self.clip_copy_rect(source_bitmap, src_rect, dest_point, (0, 0)) {
+                Some(clipped) => clipped,
+                None => return,
+            };
+
+        for y in 0..height {
+            for x in 0..width {
+                let color = source_bitmap.get_pixel32(src_min_x + x, src_min_y + y);
+                let input = [
+                    color.red() as f64,
+                    color.green() as f64,
+                    color.blue() as f64,
+                    color.alpha() as f64,
+                ];
+                let mut output = [0u8; 4];
+                for (channel, row) in output.iter_mut().zip(matrix.chunks_exact(5)) {
+                    let value = row[..4]
+                        .iter()
+                        .zip(input.iter())
+                        .map(|(m, c)| m * c)
+                        .sum::<f64>()
+                        + row[4];
+                    *channel = value.round().clamp(0.0, 255.0) as u8;
+                }
+
+                let [r, g, b, a] = output;
+                self.set_pixel32(dest_min_x + x, dest_min_y + y, Color::argb(a, r, g, b));
+            }
+        }
+    }
+
     pub fn merge(
         &mut self,
         source_bitmap: &Self,
@@ -916,3 +1169,139 @@ impl<'gc> BitmapData<'gc> {
         self.avm2_object = Some(object)
     }
 }
+
+/// Run one pass of a box blur over rows (or columns) of premultiplied
+/// channels.
+///
+/// `lines` rows of `length` samples are blurred, where consecutive samples of
+/// a line are `step` apart and consecutive lines are `line_step` apart.
+/// Samples beyond the ends of a line count as transparent.
+fn box_blur_pass(
+    channels: &mut [[f64; 4]],
+    length: usize,
+    lines: usize,
+    radius: usize,
+    step: usize,
+    line_step: usize,
+) {
+    let size = (radius * 2 + 1) as f64;
+    let mut line = vec![[0.0; 4]; length];
+
+    for line_index in 0..lines {
+        let start = line_index * line_step;
+        for (i, sample) in line.iter_mut().enumerate() {
+            *sample = channels[start + i * step];
+        }
+
+        for i in 0..length {
+            let from = i.saturating_sub(radius);
+            let to = (i + radius).min(length - 1);
+            let mut sum = [0.0; 4];
+            for sample in &line[from..=to] {
+                for (total, value) in sum.iter_mut().zip(sample) {
+                    *total += value;
+                }
+            }
+
+            channels[start + i * step] = sum.map(|total| total / size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(width: u32, height: u32, fill_color: i32) -> BitmapData<'static> {
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(width, height, true, fill_color);
+        bitmap
+    }
+
+    #[test]
+    fn threshold_clips_huge_rect() {
+        let source = bitmap(4, 4, -1);
+        let mut dest = bitmap(2, 2, 0);
+
+        let matched = dest.threshold(
+            &source,
+            (i32::MIN, i32::MIN, i32::MAX, i32::MAX),
+            (0, 0),
+            ThresholdOperation::Equal,
+            0xFFFFFFFF,
+            Color(0x11223344),
+            0xFFFFFFFF,
+            false,
+        );
+        assert_eq!(matched, 0);
+
+        let matched = dest.threshold(
+            &source,
+            (-1, 0, i32::MAX, i32::MAX),
+            (i32::MAX, 0),
+            ThresholdOperation::Equal,
+            0xFFFFFFFF,
+            Color(0x11223344),
+            0xFFFFFFFF,
+            false,
+        );
+        assert_eq!(matched, 0);
+
+        let matched = dest.threshold(
+            &source,
+            (1, 1, i32::MAX, i32::MAX),
+            (0, 0),
+            ThresholdOperation::Equal,
+            0xFFFFFFFF,
+            Color(0x11223344),
+            0xFFFFFFFF,
+            false,
+        );
+        assert_eq!(matched, 4);
+    }
+
+    #[test]
+    fn blur_clips_huge_rect() {
+        let source = bitmap(4, 4, -1);
+        let mut dest = bitmap(4, 4, 0);
+
+        // This would need 10^10 pixels of scratch space if it were not clipped.
+        dest.blur(&source, (0, 0, 100_000, 100_000), (0, 0), 4.0, 4.0, 1);
+        dest.blur(
+            &source,
+            (i32::MIN, 0, i32::MAX, i32::MAX),
+            (0, 0),
+            4.0,
+            4.0,
+            1,
+        );
+
+        // The blur still reads the transparent area around the source.
+        let mut dest = bitmap(4, 4, 0);
+        dest.blur(&source, (0, 0, 4, 4), (0, 0), 4.0, 0.0, 1);
+        assert_eq!(dest.get_pixel32(0, 0).alpha(), 153);
+        assert_eq!(dest.get_pixel32(2, 0).alpha(), 204);
+    }
+
+    #[test]
+    fn color_matrix_clips_huge_rect() {
+        let source = bitmap(4, 4, -1);
+        let mut dest = bitmap(2, 2, 0);
+        let mut identity = [0.0; 20];
+        for i in 0..4 {
+            identity[i * 6] = 1.0;
+        }
+
+        dest.color_matrix(
+            &source,
+            (i32::MIN, i32::MIN, i32::MAX, i32::MAX),
+            (0, 0),
+            &identity,
+        );
+        assert_eq!(dest.get_pixel32(0, 0), Color(0));
+
+        dest.color_matrix(&source, (0, 0, i32::MAX, i32::MAX), (1, 1), &identity);
+        assert_eq!(dest.get_pixel32(0, 0), Color(0));
+        assert_eq!(dest.get_pixel32(1, 1), Color(-1));
+    }
+}
@@ -101,6 +101,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// If the mouse is down, the display object that the mouse is currently pressing.
     pub mouse_down_object: Option<DisplayObject<'gc>>,
 
+    /// The interactive object that last received an AVM2 `mouseOver` event.
+    pub avm2_mouse_over_object: Option<DisplayObject<'gc>>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
@@ -163,6 +166,17 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// The current stage frame rate.
     pub frame_rate: &'a mut f64,
+
+    /// The number of bytes allocated by the garbage collector at the start of
+    /// this update.
+    pub gc_total_allocated: usize,
+
+    /// The number of bytes the garbage collector found to be live at the end
+    /// of its last collection.
+    pub gc_remembered_size: usize,
+
+    /// Whether or not a full garbage collection has been requested.
+    pub needs_gc: &'a mut bool,
 }
 
 /// Convenience methods for controlling audio.
@@ -297,6 +311,7 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
             mouse_down_object: self.mouse_down_object,
+            avm2_mouse_over_object: self.avm2_mouse_over_object,
             mouse_position: self.mouse_position,
             drag_object: self.drag_object,
             player: self.player.clone(),
@@ -316,6 +331,9 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
+            gc_total_allocated: self.gc_total_allocated,
+            gc_remembered_size: self.gc_remembered_size,
+            needs_gc: self.needs_gc,
         }
     }
 
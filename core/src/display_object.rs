@@ -8,6 +8,7 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
+use crate::filters::Filter;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
 use crate::string::{AvmString, WString};
@@ -96,6 +97,10 @@ pub struct DisplayObjectBase<'gc> {
 
     /// Bit flags for various display object properties.
     flags: DisplayObjectFlags,
+
+    /// The filters applied to this display object, in order.
+    #[collect(require_static)]
+    filters: Vec<Filter>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -117,6 +122,7 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             maskee: None,
             sound_transform: Default::default(),
             flags: DisplayObjectFlags::VISIBLE,
+            filters: Vec::new(),
         }
     }
 }
@@ -368,6 +374,14 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.sound_transform = sound_transform;
     }
 
+    fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    fn set_filters(&mut self, filters: Vec<Filter>) {
+        self.filters = filters;
+    }
+
     fn visible(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::VISIBLE)
     }
@@ -449,6 +463,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+    let filters = crate::filters::push_filters(context.renderer, &this.filters());
     context.transform_stack.push(&*this.base().transform());
 
     let mask = this.masker();
@@ -476,6 +491,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     }
 
     context.transform_stack.pop();
+    crate::filters::pop_filters(context.renderer, filters);
 }
 
 #[enum_trait_object(
@@ -550,6 +566,30 @@ pub trait TDisplayObject<'gc>:
         bounds
     }
 
+    /// The untransformed inherent bounding box of this object, without the
+    /// width of any strokes.
+    ///
+    /// Objects without strokes can leave this as their `self_bounds`.
+    fn self_rect_bounds(&self) -> BoundingBox {
+        self.self_bounds()
+    }
+
+    /// Like `bounds_with_transform`, but without the width of any strokes.
+    ///
+    /// This is what `getRect` measures.
+    fn rect_bounds_with_transform(&self, matrix: &Matrix) -> BoundingBox {
+        let mut bounds = self.self_rect_bounds().transform(matrix);
+
+        if let Some(ctr) = self.as_container() {
+            for child in ctr.iter_render_list() {
+                let matrix = *matrix * *child.base().matrix();
+                bounds.union(&child.rect_bounds_with_transform(&matrix));
+            }
+        }
+
+        bounds
+    }
+
     fn place_frame(&self) -> u16 {
         self.base().place_frame()
     }
@@ -836,6 +876,14 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).set_clip_depth(depth);
     }
 
+    /// The filters applied to this display object.
+    fn filters(&self) -> Vec<Filter> {
+        self.base().filters().to_vec()
+    }
+    fn set_filters(&self, gc_context: MutationContext<'gc, '_>, filters: Vec<Filter>) {
+        self.base_mut(gc_context).set_filters(filters);
+    }
+
     /// Retrieve the parent of this display object.
     ///
     /// This version of the function merely exposes the display object parent,
@@ -1201,6 +1249,12 @@ pub trait TDisplayObject<'gc>:
             if let Some(clip_depth) = place_object.clip_depth {
                 self.set_clip_depth(context.gc_context, clip_depth.into());
             }
+            if let Some(filters) = &place_object.filters {
+                self.set_filters(
+                    context.gc_context,
+                    filters.iter().cloned().map(Filter::from).collect(),
+                );
+            }
             if let Some(ratio) = place_object.ratio {
                 if let Some(mut morph_shape) = self.as_morph_shape() {
                     morph_shape.set_ratio(context.gc_context, ratio);
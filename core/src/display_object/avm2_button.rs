@@ -820,6 +820,20 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
 
         handled
     }
+
+    fn mouse_pick_avm2(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Option<InteractiveObject<'gc>> {
+        // `SimpleButton` is not a container, so it is always the target of
+        // hits on its state children or hit area.
+        if self.mouse_enabled() && self.mouse_pick(context, point, false).is_some() {
+            Some(self.into())
+        } else {
+            None
+        }
+    }
 }
 
 impl<'gc> Avm2ButtonData<'gc> {
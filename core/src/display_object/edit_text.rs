@@ -6,7 +6,8 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
+    Activation as Avm2Activation, Avm2, Event as Avm2Event, Object as Avm2Object,
+    StageObject as Avm2StageObject,
 };
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
@@ -90,6 +91,15 @@ pub struct EditTextData<'gc> {
     /// If this is a password input field
     is_password: bool,
 
+    /// The pattern of characters the user is allowed to type, or `None` if
+    /// any character may be entered.
+    #[collect(require_static)]
+    restrict: Option<WString>,
+
+    /// The maximum number of characters the user may enter, or 0 for no
+    /// limit.
+    max_chars: i32,
+
     /// If the text field should have a background. Only applied when has_border.
     has_background: bool,
 
@@ -157,7 +167,7 @@ pub struct EditTextData<'gc> {
 }
 
 // TODO: would be nicer to compute (and return) this during layout, instead of afterwards
-/// Compute line (index, offset, extent) from the layout data.
+/// Compute line (index, offset, extent, start) from the layout data.
 fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
     // if there are no boxes, there are no lines
     if layout.is_empty() {
@@ -169,6 +179,8 @@ fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
     let mut index = 1;
     let mut offset = first_box.bounds().offset_y();
     let mut extent = first_box.bounds().extent_y();
+    // the first line always starts at the beginning of the text
+    let mut start = Some(0);
 
     let mut line_data = Vec::new();
 
@@ -178,27 +190,33 @@ fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
         // if the top of the new box is lower than the bottom of the old box, it's a new line
         if bounds.offset_y() > extent {
             // save old line and reset
+            let prev_start = line_data.last().map_or(0, |l: &LineData| l.start);
             line_data.push(LineData {
                 index,
                 offset,
                 extent,
+                start: start.unwrap_or(prev_start),
             });
 
             index += 1;
             offset = bounds.offset_y();
             extent = bounds.extent_y();
+            start = layout_box.text_start();
         } else {
             // otherwise we continue from the previous box
             offset = offset.min(bounds.offset_y());
             extent = extent.max(bounds.extent_y());
+            start = start.or_else(|| layout_box.text_start());
         }
     }
 
     // save the final line
+    let prev_start = line_data.last().map_or(0, |l: &LineData| l.start);
     line_data.push(LineData {
         index,
         offset,
         extent,
+        start: start.unwrap_or(prev_start),
     });
 
     line_data
@@ -216,6 +234,7 @@ impl<'gc> EditText<'gc> {
         let is_selectable = swf_tag.is_selectable;
         let is_password = swf_tag.is_password;
         let is_editable = !swf_tag.is_read_only;
+        let max_chars = swf_tag.max_length.unwrap_or(0) as i32;
         let is_html = swf_tag.is_html;
         let text = swf_tag.initial_text.unwrap_or_default();
         let default_format = TextFormat::from_swf_tag(swf_tag.clone(), swf_movie.clone(), context);
@@ -305,6 +324,8 @@ impl<'gc> EditText<'gc> {
                 is_editable,
                 is_word_wrap,
                 is_password,
+                restrict: None,
+                max_chars,
                 has_background,
                 background_color,
                 has_border,
@@ -360,7 +381,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -512,6 +533,38 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    pub fn restrict(self) -> Option<WString> {
+        self.0.read().restrict.clone()
+    }
+
+    pub fn set_restrict(self, restrict: Option<&WStr>, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).restrict = restrict.map(WString::from);
+    }
+
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).max_chars = max_chars;
+    }
+
+    /// Whether the user is allowed to type a character into this field.
+    fn is_character_allowed(self, character: char) -> bool {
+        match &self.0.read().restrict {
+            Some(restrict) => is_restricted_char_allowed(restrict, character),
+            None => true,
+        }
+    }
+
+    /// Whether typing over a selection would keep this field within
+    /// `maxChars`.
+    fn has_room_for_input(self, selection: TextSelection) -> bool {
+        let max_chars = self.max_chars();
+        max_chars <= 0
+            || self.text_length() - (selection.end() - selection.start()) < max_chars as usize
+    }
+
     pub fn set_multiline(self, is_multiline: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
         self.0.write(context.gc_context).is_multiline = is_multiline;
         self.relayout(context);
@@ -614,6 +667,25 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    /// Append text to the end of the field, continuing the format of the
+    /// last character.
+    pub fn append_text(self, text: &WStr, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut write = self.0.write(context.gc_context);
+        let length = write.text_spans.text().len();
+        let trailing_format = if length > 0 {
+            Some(write.text_spans.get_text_format(length - 1, length))
+        } else {
+            None
+        };
+
+        write
+            .text_spans
+            .replace_text(length, length, text, trailing_format.as_ref());
+        drop(write);
+
+        self.relayout(context);
+    }
+
     /// Construct a base text transform for a particular `EditText` span.
     ///
     /// This `text_transform` is separate from and relative to the base
@@ -791,14 +863,19 @@ impl<'gc> EditText<'gc> {
         edit_text.hscroll = 0.0;
         edit_text.scroll = 1;
 
+        // The field is sized to fit its text plus the gutter on each side.
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING * 2.0);
+        let new_width = intrinsic_bounds.width() + padding;
+        let new_height = intrinsic_bounds.height() + padding;
+
         match autosize {
             AutoSizeMode::None => {}
             AutoSizeMode::Left => {
                 if !is_word_wrap {
-                    edit_text.bounds.set_width(intrinsic_bounds.width());
+                    edit_text.bounds.set_width(new_width);
                 }
 
-                edit_text.bounds.set_height(intrinsic_bounds.height());
+                edit_text.bounds.set_height(new_height);
                 edit_text.base.base.set_transformed_by_script(true);
                 drop(edit_text);
                 self.redraw_border(context.gc_context);
@@ -806,25 +883,23 @@ impl<'gc> EditText<'gc> {
             AutoSizeMode::Center => {
                 if !is_word_wrap {
                     let center = (edit_text.bounds.x_min + edit_text.bounds.x_max) / 2;
-                    edit_text
-                        .bounds
-                        .set_x(center - intrinsic_bounds.width() / 2);
-                    edit_text.bounds.set_width(intrinsic_bounds.width());
+                    edit_text.bounds.set_x(center - new_width / 2);
+                    edit_text.bounds.set_width(new_width);
                 }
 
-                edit_text.bounds.set_height(intrinsic_bounds.height());
+                edit_text.bounds.set_height(new_height);
                 edit_text.base.base.set_transformed_by_script(true);
                 drop(edit_text);
                 self.redraw_border(context.gc_context);
             }
             AutoSizeMode::Right => {
                 if !is_word_wrap {
-                    let new_x = edit_text.bounds.x_max - intrinsic_bounds.width();
+                    let new_x = edit_text.bounds.x_max - new_width;
                     edit_text.bounds.set_x(new_x);
-                    edit_text.bounds.set_width(intrinsic_bounds.width());
+                    edit_text.bounds.set_width(new_width);
                 }
 
-                edit_text.bounds.set_height(intrinsic_bounds.height());
+                edit_text.bounds.set_height(new_height);
                 edit_text.base.base.set_transformed_by_script(true);
                 drop(edit_text);
                 self.redraw_border(context.gc_context);
@@ -913,6 +988,81 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// How many lines of text the field currently lays out into.
+    pub fn num_lines(self) -> usize {
+        self.0.read().line_data.len().max(1)
+    }
+
+    /// The index of the first character of a line, given its 0-based index.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+
+        match edit_text.line_data.get(line) {
+            Some(line_data) => Some(line_data.start),
+            None if line == 0 => Some(0),
+            None => None,
+        }
+    }
+
+    /// The text of a line, given its 0-based index.
+    ///
+    /// This includes any trailing whitespace or line break that ended the
+    /// line.
+    pub fn line_text(self, line: usize) -> Option<WString> {
+        let start = self.line_offset(line)?;
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+        let end = edit_text
+            .line_data
+            .get(line + 1)
+            .map_or(text.len(), |l| l.start);
+
+        Some(text.slice(start..end).unwrap_or_default().into())
+    }
+
+    /// The bounding box of a single character, relative to the field's own
+    /// coordinate space.
+    pub fn char_boundaries(self, index: usize) -> Option<BoxBounds<Twips>> {
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.displayed_text();
+
+        for layout_box in edit_text.layout.iter() {
+            let (box_start, box_end) = match layout_box.content() {
+                LayoutContent::Text { start, end, .. } => (*start, *end),
+                _ => continue,
+            };
+            if index < box_start || index >= box_end {
+                continue;
+            }
+
+            let (box_text, _tf, font, params, color) = layout_box.as_renderable_text(text)?;
+            let baseline_adjustment =
+                font.get_baseline_for_height(params.height()) - params.height();
+            let mut result = None;
+            font.evaluate(
+                box_text,
+                self.text_transform(color, baseline_adjustment),
+                params,
+                |pos, _transform, _glyph: &Glyph, advance, x| {
+                    if pos == index - box_start {
+                        result = Some((x, advance));
+                    }
+                },
+            );
+
+            let (x, advance) = result?;
+            let bounds = layout_box.bounds();
+            let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+
+            return Some(BoxBounds::from_position_and_size(
+                (bounds.offset_x() + x + padding, bounds.offset_y() + padding).into(),
+                (advance, bounds.height()).into(),
+            ));
+        }
+
+        None
+    }
+
     /// Render a layout box, plus its children.
     fn render_layout_box(self, context: &mut RenderContext<'_, 'gc>, lbox: &LayoutBox<'gc>) {
         let box_transform: Transform = lbox.bounds().origin().into();
@@ -1185,7 +1335,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc, '_>) {
-        self.0.write(context.gc_context).hscroll = hscroll;
+        let old_hscroll = std::mem::replace(&mut self.0.write(context.gc_context).hscroll, hscroll);
+        if old_hscroll != hscroll {
+            self.dispatch_scroll_event(context);
+        }
     }
 
     pub fn scroll(self) -> usize {
@@ -1202,7 +1355,92 @@ impl<'gc> EditText<'gc> {
             scroll as usize
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
-        self.0.write(context.gc_context).scroll = clamped;
+        let old_scroll = std::mem::replace(&mut self.0.write(context.gc_context).scroll, clamped);
+        if old_scroll != clamped {
+            self.dispatch_scroll_event(context);
+        }
+    }
+
+    /// Fires `Event.SCROLL` on this text field's AVM2 object, if it has one.
+    fn dispatch_scroll_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if let Avm2Value::Object(object) = self.object2() {
+            let mut scroll_evt = Avm2Event::new("scroll");
+            scroll_evt.set_bubbles(false);
+            scroll_evt.set_cancelable(false);
+
+            if let Err(e) = Avm2::dispatch_event(context, scroll_evt, object) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
+    /// Returns the URL and target window of the hyperlink under a screen
+    /// position, if any.
+    pub fn link_at_position(self, position: (Twips, Twips)) -> Option<(WString, WString)> {
+        let text = self.0.read();
+        let position = self.global_to_local(position);
+        let position = (
+            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
+            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
+        );
+
+        text.layout.iter().find_map(|layout_box| {
+            let text_format = match layout_box.content() {
+                LayoutContent::Text { text_format, .. } => text_format,
+                _ => return None,
+            };
+            let url = text_format.url.as_ref().filter(|url| !url.is_empty())?;
+            let bounds = layout_box.bounds();
+
+            if position.0 >= bounds.offset_x()
+                && position.0 <= bounds.extent_x()
+                && position.1 >= bounds.offset_y()
+                && position.1 <= bounds.extent_y()
+            {
+                let target = text_format.target.clone().unwrap_or_default();
+                Some((url.clone(), target))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Follow a hyperlink that was clicked in this text field.
+    ///
+    /// Links using the `event:` scheme fire `TextEvent.LINK` in AVM2, with
+    /// the remainder of the URL as the event's `text`. Other links are
+    /// opened by the navigator.
+    fn link_clicked(self, context: &mut UpdateContext<'_, 'gc, '_>, url: WString, target: WString) {
+        let event_prefix = WStr::from_units(b"event:");
+        if url.starts_with(event_prefix) {
+            if let Avm2Value::Object(object) = self.object2() {
+                let text = AvmString::new(context.gc_context, &url[event_prefix.len()..]);
+                let mut link_evt = Avm2Event::new("link");
+                link_evt.set_bubbles(true);
+                link_evt.set_cancelable(false);
+
+                let event_class = context.avm2.classes().textevent;
+                if let Err(e) = Avm2::dispatch_event_with_properties(
+                    context,
+                    link_evt,
+                    event_class,
+                    &[("text", text.into())],
+                    object,
+                ) {
+                    log::error!("Encountered AVM2 error when dispatching event: {}", e);
+                }
+            }
+        } else if !url.starts_with(WStr::from_units(b"asfunction:")) {
+            // TODO: AVM1 `asfunction:` links should call the named function.
+            let target = if target.is_empty() {
+                None
+            } else {
+                Some(target.to_string())
+            };
+            context
+                .navigator
+                .navigate_to_url(url.to_string(), target, None);
+        }
     }
 
     pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
@@ -1294,7 +1532,10 @@ impl<'gc> EditText<'gc> {
                         changed = true;
                     }
                 }
-                code if !(code as char).is_control() => {
+                code if !(code as char).is_control()
+                    && self.is_character_allowed(character)
+                    && self.has_room_for_input(selection) =>
+                {
                     self.replace_text(
                         selection.start(),
                         selection.end(),
@@ -1312,17 +1553,27 @@ impl<'gc> EditText<'gc> {
             }
 
             if changed {
-                let globals = context.avm1.global_object_cell();
-                let swf_version = context.swf.version();
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    swf_version,
-                    globals,
-                    self.into(),
-                );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
+                if let Avm2Value::Object(object) = self.object2() {
+                    let mut change_evt = Avm2Event::new("change");
+                    change_evt.set_bubbles(true);
+                    change_evt.set_cancelable(false);
+
+                    if let Err(e) = Avm2::dispatch_event(context, change_evt, object) {
+                        log::error!("Encountered AVM2 error when dispatching event: {}", e);
+                    }
+                } else {
+                    let globals = context.avm1.global_object_cell();
+                    let swf_version = context.swf.version();
+                    let mut activation = Avm1Activation::from_nothing(
+                        context.reborrow(),
+                        ActivationIdentifier::root("[Propagate Text Binding]"),
+                        swf_version,
+                        globals,
+                        self.into(),
+                    );
+                    self.propagate_text_binding(&mut activation);
+                    self.on_changed(&mut activation);
+                }
             }
         }
     }
@@ -1832,7 +2083,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 
     fn filter_clip_event(self, event: ClipEvent) -> ClipEventResult {
-        if event != ClipEvent::Press {
+        if event != ClipEvent::Press && event != ClipEvent::Release {
             return ClipEventResult::NotHandled;
         }
 
@@ -1842,8 +2093,16 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        _event: ClipEvent,
+        event: ClipEvent,
     ) -> ClipEventResult {
+        if event == ClipEvent::Release {
+            if let Some((url, target)) = self.link_at_position(*context.mouse_position) {
+                self.link_clicked(context, url, target);
+            }
+
+            return ClipEventResult::Handled;
+        }
+
         let tracker = context.focus_tracker;
         tracker.set(Some(self.into()), context);
         if let Some(position) = self
@@ -1860,6 +2119,58 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     }
 }
 
+/// Determine if a `restrict` pattern allows a character to be typed.
+///
+/// A pattern lists characters and `a-z` ranges. A `^` switches between
+/// listing allowed and disallowed characters, and `\` escapes the next
+/// character. Later entries take precedence over earlier ones, and a
+/// pattern starting with `^` allows anything it does not exclude.
+fn is_restricted_char_allowed(restrict: &WStr, character: char) -> bool {
+    let mut chars = restrict
+        .chars()
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .peekable();
+    let mut allowed = chars.peek() == Some(&'^');
+    let mut including = true;
+
+    while let Some(c) = chars.next() {
+        let start = match c {
+            '^' => {
+                including = !including;
+                continue;
+            }
+            '\\' => match chars.next() {
+                Some(c) => c,
+                None => break,
+            },
+            c => c,
+        };
+
+        let end = if chars.peek() == Some(&'-') {
+            chars.next();
+            match chars.next() {
+                Some('\\') => chars.next().unwrap_or('\\'),
+                Some(c) => c,
+                None => {
+                    // A trailing `-` is taken literally.
+                    if character == start || character == '-' {
+                        allowed = including;
+                    }
+                    break;
+                }
+            }
+        } else {
+            start
+        };
+
+        if (start..=end).contains(&character) {
+            allowed = including;
+        }
+    }
+
+    allowed
+}
+
 /// Static data shared between all instances of a text object.
 #[derive(Debug, Clone, Collect)]
 #[collect(no_drop)]
@@ -1910,6 +2221,8 @@ pub struct LineData {
     offset: Twips,
     /// How many twips down the lowest point of the line is
     extent: Twips,
+    /// The index of the first character on the line
+    start: usize,
 }
 
 impl TextSelection {
@@ -125,6 +125,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         }
     }
 
+    fn self_rect_bounds(&self) -> BoundingBox {
+        if let Some(drawing) = &self.0.read().drawing {
+            drawing.self_edge_bounds()
+        } else {
+            self.0.read().static_data.shape.edge_bounds.clone().into()
+        }
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         if context.avm_type() == AvmType::Avm2 && matches!(self.object2(), Avm2Value::Undefined) {
             let shape_constr = context.avm2.classes().shape;
@@ -7,7 +7,7 @@ use crate::display_object::edit_text::EditText;
 use crate::display_object::movie_clip::MovieClip;
 use crate::display_object::stage::Stage;
 use crate::display_object::{
-    DisplayObject, DisplayObjectBase, TDisplayObject, TDisplayObjectContainer,
+    DisplayObject, DisplayObjectBase, HitTestOptions, TDisplayObject, TDisplayObjectContainer,
 };
 use crate::events::{ClipEvent, ClipEventResult};
 use bitflags::bitflags;
@@ -15,6 +15,7 @@ use gc_arena::{Collect, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
+use swf::Twips;
 
 bitflags! {
     /// Boolean state flags used by `InteractiveObject`.
@@ -27,6 +28,10 @@ bitflags! {
 
         /// Whether this `InteractiveObject` accepts double-clicks.
         const DOUBLE_CLICK_ENABLED = 1 << 1;
+
+        /// Whether the children of this `InteractiveObject` accept mouse
+        /// events. Only containers have children to block.
+        const MOUSE_CHILDREN = 1 << 2;
     }
 }
 
@@ -41,7 +46,7 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
     fn default() -> Self {
         Self {
             base: Default::default(),
-            flags: InteractiveObjectFlags::MOUSE_ENABLED,
+            flags: InteractiveObjectFlags::MOUSE_ENABLED | InteractiveObjectFlags::MOUSE_CHILDREN,
         }
     }
 }
@@ -94,6 +99,71 @@ pub trait TInteractiveObject<'gc>:
             .set(InteractiveObjectFlags::DOUBLE_CLICK_ENABLED, value)
     }
 
+    /// Check if the children of this interactive object accept mouse events.
+    fn mouse_children(self) -> bool {
+        self.ibase()
+            .flags
+            .contains(InteractiveObjectFlags::MOUSE_CHILDREN)
+    }
+
+    /// Set if the children of this interactive object accept mouse events.
+    fn set_mouse_children(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.ibase_mut(mc)
+            .flags
+            .set(InteractiveObjectFlags::MOUSE_CHILDREN, value)
+    }
+
+    /// Find the topmost interactive object under a point that should be the
+    /// target of AVM2 mouse events.
+    ///
+    /// Objects with `mouseEnabled` unset never become the target, but their
+    /// children are still searched. A container with `mouseChildren` unset
+    /// becomes the target in place of any of its children. Hits on
+    /// non-interactive children, such as shapes, target their container.
+    fn mouse_pick_avm2(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Option<InteractiveObject<'gc>> {
+        let this = self.as_displayobject();
+        if !this.visible() {
+            return None;
+        }
+
+        if let Some(masker) = this.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                return None;
+            }
+        }
+
+        if self.mouse_children() {
+            if let Some(container) = this.as_container() {
+                for child in container.iter_render_list().rev() {
+                    // Masks are never mouse targets.
+                    if child.clip_depth() > 0 {
+                        continue;
+                    }
+
+                    if let Some(interactive) = child.as_interactive() {
+                        if let Some(target) = interactive.mouse_pick_avm2(context, point) {
+                            return Some(target);
+                        }
+                    } else if self.mouse_enabled()
+                        && child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+                    {
+                        return Some(self.into());
+                    }
+                }
+            }
+        }
+
+        if self.mouse_enabled() && this.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
+            Some(self.into())
+        } else {
+            None
+        }
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
@@ -690,7 +690,7 @@ impl<'gc> MovieClip<'gc> {
         }
 
         for FrameLabelData { frame_num, label } in sfl_data.frame_labels {
-            static_data.frame_labels.insert(
+            static_data.insert_frame_label(
                 WString::from_utf8(&label.to_string_lossy(reader.encoding())),
                 frame_num as u16 + 1,
             );
@@ -909,13 +909,15 @@ impl<'gc> MovieClip<'gc> {
         let current_frame = read.current_frame();
         let mut best: Option<(&WString, FrameNumber)> = None;
 
-        for (label, frame) in read.static_data.frame_labels.iter() {
-            if *frame > current_frame {
-                continue;
-            }
+        for (label, frames) in read.static_data.frame_labels.iter() {
+            for frame in frames {
+                if *frame > current_frame {
+                    continue;
+                }
 
-            if best.map(|v| *frame >= v.1).unwrap_or(true) {
-                best = Some((label, *frame));
+                if best.map(|v| *frame >= v.1).unwrap_or(true) {
+                    best = Some((label, *frame));
+                }
             }
         }
 
@@ -936,8 +938,9 @@ impl<'gc> MovieClip<'gc> {
             .static_data
             .frame_labels
             .iter()
-            .filter(|(_label, frame)| **frame >= from && **frame < to)
-            .map(|(label, frame)| (label.clone(), *frame))
+            .flat_map(|(label, frames)| frames.iter().map(move |frame| (label, *frame)))
+            .filter(|(_label, frame)| *frame >= from && *frame < to)
+            .map(|(label, frame)| (label.clone(), frame))
             .collect();
 
         values.sort_unstable_by(|(_, framea), (_, frameb)| framea.cmp(frameb));
@@ -967,7 +970,41 @@ impl<'gc> MovieClip<'gc> {
         // Frame labels are case insensitive (ASCII).
         // TODO: Should be case sensitive in AVM2.
         let label = frame_label.to_ascii_lowercase();
-        self.0.read().static_data.frame_labels.get(&label).copied()
+        self.0
+            .read()
+            .static_data
+            .frame_labels
+            .get(&label)
+            .and_then(|frames| frames.first())
+            .copied()
+    }
+
+    /// Resolve a frame label within the named scene.
+    ///
+    /// The same label may be used once in each scene, so this picks the
+    /// occurrence that lies inside the scene rather than the first one on the
+    /// timeline.
+    pub fn frame_label_to_number_in_scene(
+        self,
+        frame_label: &WStr,
+        scene_label: &WStr,
+    ) -> Option<FrameNumber> {
+        let read = self.0.read();
+        let Scene { start, length, .. } = read
+            .static_data
+            .scene_labels
+            .get(&WString::from(scene_label))?;
+        let end = *start as u32 + *length as u32;
+
+        // Frame labels are case insensitive (ASCII).
+        // TODO: Should be case sensitive in AVM2.
+        let label = frame_label.to_ascii_lowercase();
+        read.static_data
+            .frame_labels
+            .get(&label)?
+            .iter()
+            .copied()
+            .find(|frame| *frame >= *start && (*frame as u32) < end)
     }
 
     pub fn scene_label_to_number(self, scene_label: &WStr) -> Option<FrameNumber> {
@@ -981,38 +1018,6 @@ impl<'gc> MovieClip<'gc> {
             .copied()
     }
 
-    pub fn frame_exists_within_scene(self, frame_label: &WStr, scene_label: &WStr) -> bool {
-        let scene = self.scene_label_to_number(scene_label);
-        let frame = self.frame_label_to_number(frame_label);
-
-        if scene.is_none() || frame.is_none() {
-            return false;
-        }
-
-        let scene = scene.unwrap();
-        let frame = frame.unwrap();
-
-        if scene <= frame {
-            let mut end = self.total_frames();
-            for (
-                _label,
-                Scene {
-                    start: new_scene_start,
-                    ..
-                },
-            ) in self.0.read().static_data.scene_labels.iter()
-            {
-                if *new_scene_start < end && *new_scene_start > scene {
-                    end = *new_scene_start;
-                }
-            }
-
-            frame < end
-        } else {
-            false
-        }
-    }
-
     /// Gets the clip events for this MovieClip.
     pub fn clip_actions(&self) -> Ref<[ClipEventHandler]> {
         Ref::map(self.0.read(), |mc| mc.clip_event_handlers())
@@ -1600,17 +1605,24 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Register (or, with `None`, clear) the AVM2 frame script for a frame.
+    ///
+    /// Each frame has at most one frame script; registering another replaces
+    /// it.
     pub fn register_frame_script(
         self,
         frame_id: FrameNumber,
-        callable: Avm2Object<'gc>,
+        callable: Option<Avm2Object<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
         let mut write = self.0.write(context.gc_context);
 
-        write
-            .frame_scripts
-            .push(Avm2FrameScript { frame_id, callable });
+        write.frame_scripts.retain(|fs| fs.frame_id != frame_id);
+        if let Some(callable) = callable {
+            write
+                .frame_scripts
+                .push(Avm2FrameScript { frame_id, callable });
+        }
     }
 
     pub fn set_focusable(self, focusable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -1829,14 +1841,15 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                                 log::error!("Error occured when running AVM2 frame script: {}", e);
                             }
                             write = self.0.write(context.gc_context);
-                            write
-                                .flags
-                                .remove(MovieClipFlags::EXECUTING_AVM2_FRAME_SCRIPT);
                         }
 
                         index += 1;
                     }
                 }
+
+                write
+                    .flags
+                    .remove(MovieClipFlags::EXECUTING_AVM2_FRAME_SCRIPT);
             }
         }
 
@@ -1862,6 +1875,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.0.read().drawing.self_bounds()
     }
 
+    fn self_rect_bounds(&self) -> BoundingBox {
+        self.0.read().drawing.self_edge_bounds()
+    }
+
     fn hit_test_shape(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -3078,10 +3095,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         // Frame labels are case insensitive (ASCII).
         label.make_ascii_lowercase();
         let label = WString::from_utf8_owned(label);
-        if let std::collections::hash_map::Entry::Vacant(v) = static_data.frame_labels.entry(label)
-        {
-            v.insert(cur_frame);
-        } else {
+        if !static_data.insert_frame_label(label, cur_frame) {
             log::warn!("Movie clip {}: Duplicated frame label", self.id());
         }
         Ok(())
@@ -3356,7 +3370,9 @@ impl Default for Scene {
 struct MovieClipStatic<'gc> {
     id: CharacterId,
     swf: SwfSlice,
-    frame_labels: HashMap<WString, FrameNumber>,
+    /// Every frame carrying each label, in timeline order. A label name can
+    /// be reused once per scene.
+    frame_labels: HashMap<WString, Vec<FrameNumber>>,
     #[collect(require_static)]
     scene_labels: HashMap<WString, Scene>,
     #[collect(require_static)]
@@ -3391,6 +3407,18 @@ impl<'gc> MovieClipStatic<'gc> {
             exported_name: GcCell::allocate(gc_context, None),
         }
     }
+
+    /// Record `label` on `frame`, returning `false` if the frame already has it.
+    fn insert_frame_label(&mut self, label: WString, frame: FrameNumber) -> bool {
+        let frames = self.frame_labels.entry(label).or_default();
+        match frames.binary_search(&frame) {
+            Ok(_) => false,
+            Err(index) => {
+                frames.insert(index, frame);
+                true
+            }
+        }
+    }
 }
 
 /// Stores the placement settings for display objects during a
@@ -3561,3 +3589,81 @@ pub struct Avm2FrameScript<'gc> {
     /// The AVM2 callable object to invoke when the frame script runs.
     pub callable: Avm2Object<'gc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::rootless_arena;
+
+    /// Build a clip with two five-frame scenes that both use the label "intro".
+    fn two_scene_clip<'gc>(gc_context: MutationContext<'gc, '_>) -> MovieClip<'gc> {
+        let movie = Arc::new(SwfMovie::empty(10));
+        let clip = MovieClip::new(movie.clone(), gc_context);
+        let mut static_data = MovieClipStatic::empty(movie, gc_context);
+        static_data.total_frames = 10;
+        for (name, start) in [("Scene 1", 1), ("Scene 2", 6)] {
+            let name = WString::from_utf8(name);
+            static_data.scene_labels.insert(
+                name.clone(),
+                Scene {
+                    name,
+                    start,
+                    length: 5,
+                },
+            );
+        }
+        static_data.insert_frame_label(WString::from_utf8("intro"), 2);
+        static_data.insert_frame_label(WString::from_utf8("intro"), 7);
+        static_data.insert_frame_label(WString::from_utf8("outro"), 9);
+        clip.0.write(gc_context).static_data = Gc::allocate(gc_context, static_data);
+        clip
+    }
+
+    #[test]
+    fn frame_label_resolves_within_scene() {
+        rootless_arena(|gc_context| {
+            let clip = two_scene_clip(gc_context);
+            let intro = WStr::from_units(b"intro");
+            let outro = WStr::from_units(b"outro");
+
+            assert_eq!(clip.frame_label_to_number(intro), Some(2));
+            assert_eq!(
+                clip.frame_label_to_number_in_scene(intro, WStr::from_units(b"Scene 1")),
+                Some(2)
+            );
+            assert_eq!(
+                clip.frame_label_to_number_in_scene(intro, WStr::from_units(b"Scene 2")),
+                Some(7)
+            );
+            assert_eq!(
+                clip.frame_label_to_number_in_scene(outro, WStr::from_units(b"Scene 1")),
+                None
+            );
+            assert_eq!(
+                clip.frame_label_to_number_in_scene(intro, WStr::from_units(b"Scene 3")),
+                None
+            );
+        });
+    }
+
+    #[test]
+    fn labels_are_kept_per_scene() {
+        rootless_arena(|gc_context| {
+            let clip = two_scene_clip(gc_context);
+            let intro = WString::from_utf8("intro");
+
+            assert_eq!(clip.labels_in_range(1, 6), vec![(intro.clone(), 2)]);
+            assert_eq!(
+                clip.labels_in_range(6, 11),
+                vec![(intro.clone(), 7), (WString::from_utf8("outro"), 9)]
+            );
+
+            clip.0.write(gc_context).current_frame = 8;
+            assert_eq!(clip.current_label(), Some((intro, 7)));
+            assert_eq!(
+                clip.current_scene().map(|scene| scene.name),
+                Some(WString::from_utf8("Scene 2"))
+            );
+        });
+    }
+}
@@ -97,6 +97,9 @@ pub struct StageData<'gc> {
     /// Whether to show default context menu items
     show_menu: bool,
 
+    /// Whether `stage.invalidate()` was called since the last `render` event.
+    invalidated: bool,
+
     /// The AVM2 view of this stage object.
     avm2_object: Avm2Object<'gc>,
 }
@@ -121,6 +124,7 @@ impl<'gc> Stage<'gc> {
                 viewport_scale_factor: 1.0,
                 view_bounds: Default::default(),
                 show_menu: true,
+                invalidated: false,
                 avm2_object: Avm2ScriptObject::bare_object(gc_context),
             },
         ));
@@ -331,6 +335,14 @@ impl<'gc> Stage<'gc> {
         write.show_menu = show_menu;
     }
 
+    pub fn invalidated(self) -> bool {
+        self.0.read().invalidated
+    }
+
+    pub fn set_invalidated(self, gc_context: MutationContext<'gc, '_>, invalidated: bool) {
+        self.0.write(gc_context).invalidated = invalidated;
+    }
+
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self) -> bool {
         // Only enable letterbox is the default `ShowAll` scale mode.
@@ -568,6 +580,31 @@ impl<'gc> Stage<'gc> {
         }
     }
 
+    /// Broadcasts `Event.RENDER` in AVM2 to display objects on the stage, if
+    /// the stage has been invalidated since it was last fired.
+    pub fn fire_render_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.invalidated() {
+            return;
+        }
+
+        // Invalidating the stage from a `render` handler waits for the next
+        // frame.
+        self.set_invalidated(context.gc_context, false);
+
+        let mut render_evt = Avm2Event::new("render");
+        render_evt.set_bubbles(false);
+        render_evt.set_cancelable(false);
+
+        let dobject_constr = context.avm2.classes().display_object;
+
+        if let Err(e) = crate::avm2::Avm2::broadcast_event(context, render_evt, dobject_constr) {
+            log::error!(
+                "Encountered AVM2 error when broadcasting render event: {}",
+                e
+            );
+        }
+    }
+
     /// Fires `Stage.onFullScreen` in AVM1 or `Event.FULLSCREEN` in AVM2.
     pub fn fire_fullscreen_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let library = context.library.library_for_movie_mut(context.swf.clone());
@@ -54,6 +54,16 @@ pub struct VideoData<'gc> {
     /// the prior keyframe. The first frame in the stream will always be
     /// treated as a keyframe regardless of it being flagged as one.
     keyframes: BTreeSet<u32>,
+
+    /// Whether or not the decoded frame is smoothed when scaled.
+    smoothing: bool,
+
+    /// The deblocking filter requested by ActionScript.
+    deblocking: i32,
+
+    /// The `NetStream` attached with `attachNetStream`, whose frames are
+    /// shown by this player.
+    net_stream: Option<Avm2Object<'gc>>,
 }
 
 /// An optionally-instantiated video stream.
@@ -87,6 +97,18 @@ pub enum VideoSource {
         /// to reconstruct a reference to the embedded bitstream.
         frames: BTreeMap<u32, (usize, usize)>,
     },
+
+    /// A video player constructed by ActionScript.
+    ///
+    /// Frames are supplied by whatever `NetStream` or `Camera` is attached to
+    /// the player, rather than by an embedded bitstream.
+    Attached {
+        /// The width of the player, as passed to the constructor.
+        width: i32,
+
+        /// The height of the player, as passed to the constructor.
+        height: i32,
+    },
 }
 
 impl<'gc> Video<'gc> {
@@ -114,10 +136,103 @@ impl<'gc> Video<'gc> {
                 decoded_frame: None,
                 object: None,
                 keyframes: BTreeSet::new(),
+                smoothing: false,
+                deblocking: 0,
+                net_stream: None,
+            },
+        ))
+    }
+
+    /// Construct an empty Video player for an AVM2 `Video` object.
+    pub fn new_with_avm2(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+        width: i32,
+        height: i32,
+    ) -> Self {
+        let source = GcCell::allocate(context.gc_context, VideoSource::Attached { width, height });
+
+        Video(GcCell::allocate(
+            context.gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object: Some(avm2_object.into()),
+                keyframes: BTreeSet::new(),
+                smoothing: false,
+                deblocking: 0,
+                net_stream: None,
             },
         ))
     }
 
+    /// Replace the currently displayed frame with one supplied by an attached
+    /// frame source.
+    pub fn set_frame(self, mc: MutationContext<'gc, '_>, frame_id: u32, bitmap: BitmapInfo) {
+        self.0.write(mc).decoded_frame = Some((frame_id, bitmap));
+    }
+
+    /// Discard the last displayed frame, leaving the player blank.
+    pub fn clear(self, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).decoded_frame = None;
+    }
+
+    /// Show the frames of a `NetStream`, or of nothing if `None`.
+    ///
+    /// The last frame of the previous source is discarded.
+    pub fn attach_net_stream(
+        self,
+        mc: MutationContext<'gc, '_>,
+        net_stream: Option<Avm2Object<'gc>>,
+    ) {
+        let mut write = self.0.write(mc);
+        write.net_stream = net_stream;
+        write.decoded_frame = None;
+    }
+
+    /// Show the current frame of the attached `NetStream`, as reported by
+    /// `source`.
+    ///
+    /// The player keeps showing its last frame while the stream has none.
+    fn pull_frame(
+        self,
+        mc: MutationContext<'gc, '_>,
+        source: impl FnOnce(Avm2Object<'gc>) -> Option<(u32, BitmapInfo)>,
+    ) {
+        let net_stream = self.0.read().net_stream;
+        if let Some((frame_id, bitmap)) = net_stream.and_then(source) {
+            self.set_frame(mc, frame_id, bitmap);
+        }
+    }
+
+    /// The size of the last displayed frame, or zero if there is none.
+    pub fn video_size(self) -> (u32, u32) {
+        self.0
+            .read()
+            .decoded_frame
+            .as_ref()
+            .map(|(_, bitmap)| (bitmap.width.into(), bitmap.height.into()))
+            .unwrap_or((0, 0))
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, mc: MutationContext<'gc, '_>, smoothing: bool) {
+        self.0.write(mc).smoothing = smoothing;
+    }
+
+    pub fn deblocking(self) -> i32 {
+        self.0.read().deblocking
+    }
+
+    pub fn set_deblocking(self, mc: MutationContext<'gc, '_>, deblocking: i32) {
+        self.0.write(mc).deblocking = deblocking;
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -147,6 +262,9 @@ impl<'gc> Video<'gc> {
                     log::warn!("Invalid bitstream subslice on frame {}", tag.frame_num);
                 }
             }
+            VideoSource::Attached { .. } => {
+                log::warn!("Attempted to preload SWF frame into a video player without one")
+            }
         }
     }
 
@@ -170,6 +288,7 @@ impl<'gc> Video<'gc> {
 
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames),
+            VideoSource::Attached { .. } => None,
         };
 
         if let Some(num_frames) = num_frames {
@@ -262,6 +381,9 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
+            VideoSource::Attached { .. } => Err(Box::from(
+                "Attempted to seek a video player without a bitstream",
+            )),
         };
 
         drop(read);
@@ -358,6 +480,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
+            // Attached sources push their own frames; there is nothing to
+            // register with the video backend.
+            VideoSource::Attached { .. } => return,
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -394,6 +519,13 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         }
     }
 
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let avm2 = &*context.avm2;
+        self.pull_frame(context.gc_context, |net_stream| {
+            avm2.net_stream_frame(net_stream)
+        });
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let vm_type = context.avm_type();
         if vm_type == AvmType::Avm2 && matches!(self.object2(), Avm2Value::Undefined) {
@@ -416,6 +548,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
+            VideoSource::Attached { .. } => 0,
         }
     }
 
@@ -427,6 +560,10 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 bounding_box.set_width(Twips::from_pixels(streamdef.width as f64));
                 bounding_box.set_height(Twips::from_pixels(streamdef.height as f64));
             }
+            VideoSource::Attached { width, height } => {
+                bounding_box.set_width(Twips::from_pixels(*width as f64));
+                bounding_box.set_height(Twips::from_pixels(*height as f64));
+            }
         }
 
         bounding_box
@@ -438,9 +575,11 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
             return;
         }
 
+        let filters = crate::filters::push_filters(context.renderer, &self.filters());
         context.transform_stack.push(&*self.base().transform());
 
-        if let Some((_frame_id, ref bitmap)) = self.0.read().decoded_frame {
+        let read = self.0.read();
+        if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
             let mut transform = context.transform_stack.transform().clone();
             let bounds = self.self_bounds();
 
@@ -453,15 +592,103 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
             context
                 .renderer
-                .render_bitmap(bitmap.handle, &transform, false);
-        } else {
+                .render_bitmap(bitmap.handle, &transform, read.smoothing);
+        } else if matches!(&*read.source.read(), VideoSource::Swf { .. }) {
+            // Players fed by ActionScript are legitimately blank until their
+            // source produces a frame.
             log::warn!("Video has no decoded frame to render.");
         }
 
+        drop(read);
+
         context.transform_stack.pop();
+        crate::filters::pop_filters(context.renderer, filters);
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
     }
 
     fn set_object2(&mut self, mc: MutationContext<'gc, '_>, to: Avm2Object<'gc>) {
         self.0.write(mc).object = Some(to.into());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::ScriptObject as Avm2ScriptObject;
+    use crate::backend::render::BitmapHandle;
+    use gc_arena::rootless_arena;
+
+    fn attached_video<'gc>(gc_context: MutationContext<'gc, '_>) -> Video<'gc> {
+        let source = GcCell::allocate(
+            gc_context,
+            VideoSource::Attached {
+                width: 320,
+                height: 240,
+            },
+        );
+
+        Video(GcCell::allocate(
+            gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object: None,
+                keyframes: BTreeSet::new(),
+                smoothing: false,
+                deblocking: 0,
+                net_stream: None,
+            },
+        ))
+    }
+
+    fn frame(frame_id: u32, width: u16, height: u16) -> Option<(u32, BitmapInfo)> {
+        let handle = BitmapHandle(frame_id as usize);
+        Some((
+            frame_id,
+            BitmapInfo {
+                handle,
+                width,
+                height,
+            },
+        ))
+    }
+
+    #[test]
+    fn shows_frames_of_the_attached_stream() {
+        rootless_arena(|gc_context| {
+            let video = attached_video(gc_context);
+            let net_stream = Avm2ScriptObject::bare_object(gc_context);
+
+            // Nothing is pulled until a stream is attached.
+            video.pull_frame(gc_context, |_| frame(0, 320, 240));
+            assert_eq!(video.video_size(), (0, 0));
+
+            video.attach_net_stream(gc_context, Some(net_stream));
+            video.pull_frame(gc_context, |stream| {
+                assert!(Avm2Object::ptr_eq(stream, net_stream));
+                frame(0, 320, 240)
+            });
+            assert_eq!(video.video_size(), (320, 240));
+
+            video.pull_frame(gc_context, |_| frame(1, 160, 120));
+            assert_eq!(video.video_size(), (160, 120));
+
+            // A stream with no new frame keeps the last one on screen.
+            video.pull_frame(gc_context, |_| None);
+            assert_eq!(video.video_size(), (160, 120));
+
+            video.attach_net_stream(gc_context, None);
+            assert_eq!(video.video_size(), (0, 0));
+        });
+    }
+}
@@ -1,7 +1,7 @@
 use crate::backend::render::{BitmapInfo, BitmapSource, ShapeHandle};
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
 use gc_arena::Collect;
 use std::cell::Cell;
 use swf::{FillStyle, LineStyle, Twips};
@@ -76,8 +76,13 @@ impl Drawing {
 
                     this.set_line_style(None);
                 }
-                DrawPath::Fill { style, commands } => {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
                     this.set_fill_style(Some(style.clone()));
+                    this.set_winding_rule(winding_rule);
 
                     for command in commands {
                         this.draw_command(command);
@@ -103,12 +108,26 @@ impl Drawing {
                     x: self.cursor.0,
                     y: self.cursor.1,
                 }],
+                winding_rule: FillRule::EvenOdd,
             });
         }
         self.fill_start = self.cursor;
         self.dirty.set(true);
     }
 
+    /// The style of the fill being drawn, if any.
+    pub fn fill_style(&self) -> Option<&FillStyle> {
+        self.current_fill.as_ref().map(|fill| &fill.style)
+    }
+
+    /// Set the rule deciding which areas the current fill covers.
+    pub fn set_winding_rule(&mut self, winding_rule: FillRule) {
+        if let Some(fill) = &mut self.current_fill {
+            fill.winding_rule = winding_rule;
+            self.dirty.set(true);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.current_fill = None;
         self.current_line = None;
@@ -182,6 +201,11 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// The point that the next drawing command will start from.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
+
     pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
         let id = self.bitmaps.len() as u16;
         self.bitmaps.push(bitmap);
@@ -197,6 +221,7 @@ impl Drawing {
                 paths.push(DrawPath::Fill {
                     style: &fill.style,
                     commands: fill.commands.to_owned(),
+                    winding_rule: fill.winding_rule,
                 })
             }
 
@@ -204,6 +229,7 @@ impl Drawing {
                 paths.push(DrawPath::Fill {
                     style: &fill.style,
                     commands: fill.commands.to_owned(),
+                    winding_rule: fill.winding_rule,
                 })
             }
 
@@ -258,10 +284,15 @@ impl Drawing {
         self.shape_bounds.clone()
     }
 
+    /// The bounds of the drawn geometry, without the width of any strokes.
+    pub fn self_edge_bounds(&self) -> BoundingBox {
+        self.edge_bounds.clone()
+    }
+
     pub fn hit_test(&self, point: (Twips, Twips), local_matrix: &crate::matrix::Matrix) -> bool {
         use crate::shape_utils;
         for fill in &self.fills {
-            if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.winding_rule, point) {
                 return true;
             }
         }
@@ -279,7 +310,7 @@ impl Drawing {
 
         // The pending fill will auto-close.
         if let Some(fill) = &self.current_fill {
-            if shape_utils::draw_command_fill_hit_test(&fill.commands, point) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.winding_rule, point) {
                 return true;
             }
         }
@@ -351,6 +382,7 @@ impl BitmapSource for Drawing {
 struct DrawingFill {
     style: FillStyle,
     commands: Vec<DrawCommand>,
+    winding_rule: FillRule,
 }
 
 #[derive(Debug, Clone)]
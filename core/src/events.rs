@@ -414,3 +414,15 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
     };
     Some(out)
 }
+
+/// The `KeyboardEvent.keyLocation` of a key.
+///
+/// Number pad keys are `KeyLocation.NUM_PAD`, and every other key is
+/// `KeyLocation.STANDARD`, since key codes don't tell the left and right
+/// modifier keys apart.
+pub fn key_code_to_key_location(key_code: KeyCode) -> u32 {
+    match key_code as u32 {
+        96..=111 => 3,
+        _ => 0,
+    }
+}
@@ -7,6 +7,10 @@ use crate::avm1::{
     ArrayObject as Avm1ArrayObject, Error as Avm1Error, Object as Avm1Object,
     ScriptObject as Avm1ScriptObject,
 };
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    Error as Avm2Error, Object as Avm2Object, QName as Avm2QName, TObject as _, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
 use gc_arena::Collect;
@@ -177,6 +181,82 @@ impl Value {
             .into(),
         }
     }
+
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, Avm2Error> {
+        Ok(match value {
+            Avm2Value::Undefined | Avm2Value::Null => Value::Null,
+            Avm2Value::Bool(value) => value.into(),
+            Avm2Value::Number(value) => value.into(),
+            Avm2Value::Unsigned(value) => value.into(),
+            Avm2Value::Integer(value) => value.into(),
+            Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(object) => {
+                let elements: Option<Vec<_>> = object
+                    .as_array_storage()
+                    .map(|array| array.iter().collect());
+                if let Some(elements) = elements {
+                    let mut values = Vec::with_capacity(elements.len());
+                    for element in elements {
+                        let element = element.unwrap_or(Avm2Value::Undefined);
+                        values.push(Value::from_avm2(activation, element)?);
+                    }
+                    Value::List(values)
+                } else {
+                    let mut values = BTreeMap::new();
+                    let mut index = 0;
+                    while let Some(next_index) = object.get_next_enumerant(index, activation)? {
+                        let key = object
+                            .get_enumerant_name(next_index, activation)?
+                            .coerce_to_string(activation)?;
+                        let value = object.get_enumerant_value(next_index, activation)?;
+                        values.insert(key.to_string(), Value::from_avm2(activation, value)?);
+                        index = next_index;
+                    }
+                    Value::Object(values)
+                }
+            }
+        })
+    }
+
+    pub fn into_avm2<'gc>(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        Ok(match self {
+            Value::Null => Avm2Value::Null,
+            Value::Bool(value) => Avm2Value::Bool(value),
+            Value::Number(value) => Avm2Value::Number(value),
+            Value::String(value) => {
+                Avm2Value::String(AvmString::new_utf8(activation.context.gc_context, value))
+            }
+            Value::Object(values) => {
+                let object_class = activation.avm2().classes().object;
+                let mut object = object_class.construct(activation, &[])?;
+                for (key, value) in values {
+                    let key = AvmString::new_utf8(activation.context.gc_context, key);
+                    let value = value.into_avm2(activation)?;
+                    object.set_property(
+                        object,
+                        &Avm2QName::dynamic_name(key).into(),
+                        value,
+                        activation,
+                    )?;
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let mut storage = Vec::with_capacity(values.len());
+                for value in values {
+                    storage.push(Some(value.into_avm2(activation)?));
+                }
+                Avm2ArrayObject::from_storage(activation, Avm2ArrayStorage::from_storage(storage))?
+                    .into()
+            }
+        })
+    }
 }
 
 #[derive(Collect, Clone)]
@@ -186,6 +266,9 @@ pub enum Callback<'gc> {
         this: Avm1Value<'gc>,
         method: Avm1Object<'gc>,
     },
+    Avm2 {
+        method: Avm2Object<'gc>,
+    },
 }
 
 impl<'gc> Callback<'gc> {
@@ -222,6 +305,26 @@ impl<'gc> Callback<'gc> {
                     Value::Null
                 }
             }
+            Callback::Avm2 { method } => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let result = args
+                    .into_iter()
+                    .map(|v| v.into_avm2(&mut activation))
+                    .collect::<Result<Vec<_>, _>>()
+                    .and_then(|args| method.call(None, &args, &mut activation))
+                    .and_then(|value| Value::from_avm2(&mut activation, value));
+                match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        log::error!(
+                            "Unhandled error in External Interface callback {}: {}",
+                            name,
+                            e
+                        );
+                        Value::Null
+                    }
+                }
+            }
         }
     }
 }
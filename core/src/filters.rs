@@ -0,0 +1,319 @@
+//! Bitmap filters applied to display objects
+
+use crate::backend::render::RenderBackend;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A bitmap filter applied to a display object.
+///
+/// Filters placed by the timeline and filters assigned by ActionScript share
+/// this representation, so that either can be read back from the other.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    Blur(BlurFilter),
+    ColorMatrix(ColorMatrixFilter),
+    DropShadow(DropShadowFilter),
+    Glow(GlowFilter),
+
+    /// A filter placed by the timeline that ActionScript has no class for.
+    Unsupported(swf::Filter),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlurFilter {
+    pub blur_x: f64,
+    pub blur_y: f64,
+    pub quality: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorMatrixFilter {
+    pub matrix: [f64; 20],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropShadowFilter {
+    pub distance: f64,
+
+    /// The angle of the shadow, in degrees.
+    pub angle: f64,
+
+    /// The color of the shadow, as 0xRRGGBB.
+    pub color: u32,
+    pub alpha: f64,
+    pub blur_x: f64,
+    pub blur_y: f64,
+    pub strength: f64,
+    pub quality: i32,
+    pub inner: bool,
+    pub knockout: bool,
+    pub hide_object: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlowFilter {
+    /// The color of the glow, as 0xRRGGBB.
+    pub color: u32,
+    pub alpha: f64,
+    pub blur_x: f64,
+    pub blur_y: f64,
+    pub strength: f64,
+    pub quality: i32,
+    pub inner: bool,
+    pub knockout: bool,
+}
+
+/// Split a SWF color into its 0xRRGGBB value and its alpha.
+fn color_and_alpha(color: &swf::Color) -> (u32, f64) {
+    let rgb = (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b);
+    (rgb, f64::from(color.a) / 255.0)
+}
+
+impl From<swf::Filter> for Filter {
+    fn from(filter: swf::Filter) -> Self {
+        match filter {
+            swf::Filter::BlurFilter(blur) => Filter::Blur(BlurFilter {
+                blur_x: blur.blur_x.to_f64(),
+                blur_y: blur.blur_y.to_f64(),
+                quality: blur.num_passes.into(),
+            }),
+            swf::Filter::ColorMatrixFilter(color_matrix) => {
+                let mut matrix = [0.0; 20];
+                for (value, fixed) in matrix.iter_mut().zip(color_matrix.matrix.iter()) {
+                    *value = fixed.to_f64();
+                }
+
+                Filter::ColorMatrix(ColorMatrixFilter { matrix })
+            }
+            swf::Filter::DropShadowFilter(shadow) => {
+                let (color, alpha) = color_and_alpha(&shadow.color);
+                Filter::DropShadow(DropShadowFilter {
+                    distance: shadow.distance.to_f64(),
+                    angle: shadow.angle.to_f64().to_degrees(),
+                    color,
+                    alpha,
+                    blur_x: shadow.blur_x.to_f64(),
+                    blur_y: shadow.blur_y.to_f64(),
+                    strength: shadow.strength.to_f64(),
+                    quality: shadow.num_passes.into(),
+                    inner: shadow.is_inner,
+                    knockout: shadow.is_knockout,
+                    hide_object: false,
+                })
+            }
+            swf::Filter::GlowFilter(glow) => {
+                let (color, alpha) = color_and_alpha(&glow.color);
+                Filter::Glow(GlowFilter {
+                    color,
+                    alpha,
+                    blur_x: glow.blur_x.to_f64(),
+                    blur_y: glow.blur_y.to_f64(),
+                    strength: glow.strength.to_f64(),
+                    quality: glow.num_passes.into(),
+                    inner: glow.is_inner,
+                    knockout: glow.is_knockout,
+                })
+            }
+            filter => Filter::Unsupported(filter),
+        }
+    }
+}
+
+/// The ActionScript class names of every kind of filter.
+const CLASS_NAMES: [&str; 8] = [
+    "BevelFilter",
+    "BlurFilter",
+    "ColorMatrixFilter",
+    "ConvolutionFilter",
+    "DropShadowFilter",
+    "GlowFilter",
+    "GradientBevelFilter",
+    "GradientGlowFilter",
+];
+
+impl Filter {
+    /// The position of this filter's class in `CLASS_NAMES`.
+    fn class_index(&self) -> usize {
+        match self {
+            Filter::Blur(_) | Filter::Unsupported(swf::Filter::BlurFilter(_)) => 1,
+            Filter::ColorMatrix(_) | Filter::Unsupported(swf::Filter::ColorMatrixFilter(_)) => 2,
+            Filter::DropShadow(_) | Filter::Unsupported(swf::Filter::DropShadowFilter(_)) => 4,
+            Filter::Glow(_) | Filter::Unsupported(swf::Filter::GlowFilter(_)) => 5,
+            Filter::Unsupported(swf::Filter::BevelFilter(_)) => 0,
+            Filter::Unsupported(swf::Filter::ConvolutionFilter(_)) => 3,
+            Filter::Unsupported(swf::Filter::GradientBevelFilter(_)) => 6,
+            Filter::Unsupported(swf::Filter::GradientGlowFilter(_)) => 7,
+        }
+    }
+
+    /// The name of this filter's ActionScript class.
+    pub fn class_name(&self) -> &'static str {
+        CLASS_NAMES[self.class_index()]
+    }
+}
+
+/// Warn that the render backend cannot draw a filter.
+///
+/// Filtered objects are still rendered, just without the filter. This is only
+/// logged the first time it happens for each filter class.
+fn warn_unsupported(filter: &Filter) {
+    static WARNED: AtomicU8 = AtomicU8::new(0);
+
+    let class = 1 << filter.class_index();
+    if WARNED.fetch_or(class, Ordering::Relaxed) & class == 0 {
+        log::warn!("{} is not rendered yet", filter.class_name());
+    }
+}
+
+/// Begin drawing through a list of filters.
+///
+/// The filters are pushed so that the first one applies first. This yields
+/// how many of them the renderer draws, which must be passed on to
+/// `pop_filters` once the filtered object is drawn.
+pub fn push_filters(renderer: &mut dyn RenderBackend, filters: &[Filter]) -> usize {
+    let mut pushed = 0;
+    for filter in filters.iter().rev() {
+        if renderer.push_filter(filter) {
+            pushed += 1;
+        } else {
+            warn_unsupported(filter);
+        }
+    }
+
+    pushed
+}
+
+/// Finish drawing through the filters started by `push_filters`.
+pub fn pop_filters(renderer: &mut dyn RenderBackend, pushed: usize) {
+    for _ in 0..pushed {
+        renderer.pop_filter();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::render::NullRenderer;
+    use swf::{Color, Fixed16, Fixed8};
+
+    #[test]
+    fn converts_placed_glow_filter() {
+        let filter = swf::Filter::GlowFilter(Box::new(swf::GlowFilter {
+            color: Color {
+                r: 0x00,
+                g: 0xFF,
+                b: 0x00,
+                a: 0xFF,
+            },
+            blur_x: Fixed16::from_f64(8.0),
+            blur_y: Fixed16::from_f64(10.0),
+            strength: Fixed8::from_f64(3.0),
+            is_inner: true,
+            is_knockout: false,
+            num_passes: 2,
+        }));
+
+        assert_eq!(
+            Filter::from(filter),
+            Filter::Glow(GlowFilter {
+                color: 0x00FF00,
+                alpha: 1.0,
+                blur_x: 8.0,
+                blur_y: 10.0,
+                strength: 3.0,
+                quality: 2,
+                inner: true,
+                knockout: false,
+            })
+        );
+    }
+
+    #[test]
+    fn converts_placed_drop_shadow_angle_to_degrees() {
+        let filter = swf::Filter::DropShadowFilter(Box::new(swf::DropShadowFilter {
+            color: Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+            blur_x: Fixed16::from_f64(4.0),
+            blur_y: Fixed16::from_f64(4.0),
+            angle: Fixed16::from_f64(std::f64::consts::FRAC_PI_2),
+            distance: Fixed16::from_f64(6.0),
+            strength: Fixed8::from_f64(1.0),
+            is_inner: false,
+            is_knockout: false,
+            num_passes: 1,
+        }));
+
+        match Filter::from(filter) {
+            Filter::DropShadow(shadow) => {
+                assert!((shadow.angle - 90.0).abs() < 0.01);
+                assert!((shadow.distance - 6.0).abs() < f64::EPSILON);
+                assert!(shadow.alpha.abs() < f64::EPSILON);
+            }
+            filter => panic!("Expected a drop shadow, got {:?}", filter),
+        }
+    }
+
+    #[test]
+    fn keeps_filters_without_a_class() {
+        let bevel = swf::Filter::BevelFilter(Box::new(swf::BevelFilter {
+            shadow_color: Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+            highlight_color: Color {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: 255,
+            },
+            blur_x: Fixed16::from_f64(4.0),
+            blur_y: Fixed16::from_f64(4.0),
+            angle: Fixed16::from_f64(0.0),
+            distance: Fixed16::from_f64(4.0),
+            strength: Fixed8::from_f64(1.0),
+            is_inner: true,
+            is_knockout: false,
+            is_on_top: false,
+            num_passes: 1,
+        }));
+
+        assert_eq!(
+            Filter::from(bevel.clone()),
+            Filter::Unsupported(bevel.clone())
+        );
+        assert_eq!(Filter::from(bevel).class_name(), "BevelFilter");
+    }
+
+    #[test]
+    fn names_filter_classes() {
+        let blur = Filter::Blur(BlurFilter {
+            blur_x: 4.0,
+            blur_y: 4.0,
+            quality: 1,
+        });
+        assert_eq!(blur.class_name(), "BlurFilter");
+
+        let mut matrix = [0.0; 20];
+        matrix[0] = 1.0;
+        let color_matrix = Filter::ColorMatrix(ColorMatrixFilter { matrix });
+        assert_eq!(color_matrix.class_name(), "ColorMatrixFilter");
+    }
+
+    #[test]
+    fn pushes_every_drawn_filter() {
+        let blur = Filter::Blur(BlurFilter {
+            blur_x: 4.0,
+            blur_y: 4.0,
+            quality: 1,
+        });
+        let mut renderer = NullRenderer::new();
+
+        assert_eq!(push_filters(&mut renderer, &[]), 0);
+        assert_eq!(push_filters(&mut renderer, &[blur.clone(), blur]), 2);
+    }
+}
@@ -35,6 +35,7 @@ pub mod context_menu;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
+pub mod filters;
 pub mod focus_tracker;
 mod font;
 mod html;
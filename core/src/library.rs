@@ -399,6 +399,9 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// Fonts made available to every movie with `Font.registerFont`.
+    registered_fonts: HashMap<FontDescriptor, Font<'gc>>,
+
     constructor_registry_case_insensitive: Gc<'gc, Avm1ConstructorRegistry<'gc>>,
     constructor_registry_case_sensitive: Gc<'gc, Avm1ConstructorRegistry<'gc>>,
 
@@ -414,6 +417,7 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        self.registered_fonts.trace(cc);
         self.constructor_registry_case_insensitive.trace(cc);
         self.constructor_registry_case_sensitive.trace(cc);
         self.avm2_class_registry.trace(cc);
@@ -425,6 +429,7 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            registered_fonts: HashMap::new(),
             constructor_registry_case_insensitive: Gc::allocate(
                 gc_context,
                 Avm1ConstructorRegistry::new(false, gc_context),
@@ -466,6 +471,24 @@ impl<'gc> Library<'gc> {
         self.device_font = font;
     }
 
+    /// Make a font available to text in every movie.
+    pub fn register_font(&mut self, font: Font<'gc>) {
+        self.registered_fonts
+            .insert(font.descriptor().clone(), font);
+    }
+
+    /// Find a globally registered font by its name and parameters.
+    pub fn get_registered_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+
+        self.registered_fonts.get(&descriptor).copied()
+    }
+
     /// Gets the constructor registry to use for the given SWF version.
     /// Because SWFs v6 and v7+ use different case-sensitivity rules, Flash
     /// keeps two separate registries, one case-sensitive, the other not.
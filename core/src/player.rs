@@ -4,7 +4,10 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, ScriptObject, TObject, Timers, Value};
-use crate::avm2::{Activation as Avm2Activation, Avm2, Domain as Avm2Domain};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Event as Avm2Event,
+    Object as Avm2Object,
+};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
     locale::LocaleBackend,
@@ -70,6 +73,9 @@ struct GcRootData<'gc> {
     /// If the mouse is down, the display object that the mouse is currently pressing.
     mouse_pressed_object: Option<DisplayObject<'gc>>,
 
+    /// The interactive object that last received an AVM2 `mouseOver` event.
+    avm2_mouse_hovered_object: Option<DisplayObject<'gc>>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -191,6 +197,10 @@ pub struct Player {
 
     gc_arena: GcArena,
 
+    /// Whether or not a full garbage collection has been requested by the
+    /// movie, to be performed at the end of the current update.
+    needs_gc: bool,
+
     frame_rate: f64,
 
     /// A time budget for executing frames.
@@ -275,6 +285,7 @@ impl Player {
                         stage: Stage::empty(gc_context, movie_width, movie_height),
                         mouse_hovered_object: None,
                         mouse_pressed_object: None,
+                        avm2_mouse_hovered_object: None,
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -290,6 +301,7 @@ impl Player {
                     },
                 ))
             }),
+            needs_gc: false,
 
             frame_rate,
             frame_accumulator: 0.0,
@@ -958,6 +970,7 @@ impl Player {
         }
 
         // Propagate clip events.
+        let is_mouse_down = self.is_mouse_down;
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
                 PlayerEvent::KeyDown { .. } => {
@@ -1008,6 +1021,32 @@ impl Player {
                 );
             }
 
+            // Dispatch keyboard and mouse wheel events to AVM2 content.
+            if context.swf.avm_type() == AvmType::Avm2 {
+                match event {
+                    PlayerEvent::KeyDown { key_code } => {
+                        let key_char = context.ui.last_key_char();
+                        Self::dispatch_avm2_keyboard_event(context, "keyDown", key_code, key_char)
+                    }
+                    PlayerEvent::KeyUp { key_code } => {
+                        let key_char = context.ui.last_key_char();
+                        Self::dispatch_avm2_keyboard_event(context, "keyUp", key_code, key_char)
+                    }
+                    PlayerEvent::MouseWheel { delta } => {
+                        let target = Self::avm2_mouse_target(context);
+                        Self::dispatch_avm2_mouse_event(
+                            context,
+                            "mouseWheel",
+                            target,
+                            None,
+                            delta.lines().round() as i32,
+                            is_mouse_down,
+                        )
+                    }
+                    _ => {}
+                }
+            }
+
             Self::run_actions(context);
         });
 
@@ -1108,6 +1147,11 @@ impl Player {
 
             let mut events: smallvec::SmallVec<[(DisplayObject<'_>, ClipEvent); 2]> =
                 Default::default();
+            let mut avm2_events: smallvec::SmallVec<[&'static str; 2]> = Default::default();
+
+            if let Some(PlayerEvent::MouseMove { .. }) = event {
+                avm2_events.push("mouseMove");
+            }
 
             // Cancel hover if an object is removed from the stage.
             if let Some(hovered) = context.mouse_over_object {
@@ -1161,6 +1205,8 @@ impl Player {
             // Handle presses and releases.
             if is_mouse_button_changed {
                 if is_mouse_down {
+                    avm2_events.push("mouseDown");
+
                     // Pressed on a hovered object.
                     if let Some(over_object) = context.mouse_over_object {
                         events.push((over_object, ClipEvent::Press));
@@ -1171,6 +1217,11 @@ impl Player {
                         context.mouse_down_object,
                         context.mouse_over_object,
                     );
+                    avm2_events.push("mouseUp");
+                    if released_inside {
+                        avm2_events.push("click");
+                    }
+
                     if released_inside {
                         // Released inside the clicked object.
                         if let Some(down_object) = context.mouse_down_object {
@@ -1206,6 +1257,24 @@ impl Player {
                 }
                 true
             };
+
+            // AVM2 mouse events go to the interactive object under the mouse,
+            // or to the stage if there is none.
+            if context.swf.avm_type() == AvmType::Avm2 {
+                let target = Self::avm2_mouse_target(context);
+                Self::update_avm2_mouse_over(context, target, is_mouse_down);
+                for event_type in avm2_events {
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        event_type,
+                        target,
+                        None,
+                        0,
+                        is_mouse_down,
+                    );
+                }
+            }
+
             Self::run_actions(context);
             needs_render
         });
@@ -1219,6 +1288,210 @@ impl Player {
         needs_render
     }
 
+    /// Find the topmost interactive object under the mouse that AVM2 mouse
+    /// events should target, honoring `mouseEnabled` and `mouseChildren`.
+    fn avm2_mouse_target<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Option<DisplayObject<'gc>> {
+        context
+            .stage
+            .iter_depth_list()
+            .rev()
+            .filter_map(|(_depth, level)| {
+                level
+                    .as_interactive()?
+                    .mouse_pick_avm2(context, *context.mouse_position)
+            })
+            .next()
+            .map(|target| target.as_displayobject())
+    }
+
+    /// Dispatch `mouseOut`, `rollOut`, `mouseOver` and `rollOver` when the
+    /// interactive object under the mouse changes.
+    ///
+    /// The roll events do not bubble. Instead, they go to every ancestor that
+    /// the mouse left or entered, while ancestors shared by the old and new
+    /// objects receive neither.
+    fn update_avm2_mouse_over<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Option<DisplayObject<'gc>>,
+        button_down: bool,
+    ) {
+        let previous = context
+            .avm2_mouse_over_object
+            .filter(|object| object.is_on_stage(context));
+        context.avm2_mouse_over_object = target;
+        if DisplayObject::option_ptr_eq(previous, target) {
+            return;
+        }
+
+        let left = Self::avm2_mouse_ancestors(previous);
+        let entered = Self::avm2_mouse_ancestors(target);
+        let contains = |list: &[DisplayObject<'gc>], object: DisplayObject<'gc>| {
+            list.iter()
+                .any(|other| DisplayObject::ptr_eq(*other, object))
+        };
+
+        if previous.is_some() {
+            Self::dispatch_avm2_mouse_event(context, "mouseOut", previous, target, 0, button_down);
+            for object in left.iter().filter(|object| !contains(&entered, **object)) {
+                Self::dispatch_avm2_mouse_event(
+                    context,
+                    "rollOut",
+                    Some(*object),
+                    target,
+                    0,
+                    button_down,
+                );
+            }
+        }
+
+        if target.is_some() {
+            Self::dispatch_avm2_mouse_event(context, "mouseOver", target, previous, 0, button_down);
+            for object in entered
+                .iter()
+                .rev()
+                .filter(|object| !contains(&left, **object))
+            {
+                Self::dispatch_avm2_mouse_event(
+                    context,
+                    "rollOver",
+                    Some(*object),
+                    previous,
+                    0,
+                    button_down,
+                );
+            }
+        }
+    }
+
+    /// List a display object and its ancestors below the stage, starting with
+    /// the object itself.
+    fn avm2_mouse_ancestors<'gc>(object: Option<DisplayObject<'gc>>) -> Vec<DisplayObject<'gc>> {
+        let mut ancestors = Vec::new();
+        let mut current = object;
+        while let Some(object) = current {
+            if object.as_stage().is_some() {
+                break;
+            }
+
+            ancestors.push(object);
+            current = object.parent();
+        }
+
+        ancestors
+    }
+
+    /// Find the AVM2 object that should receive an input event aimed at the
+    /// given display object, falling back to the stage.
+    fn avm2_event_target<'gc>(
+        context: &UpdateContext<'_, 'gc, '_>,
+        object: Option<DisplayObject<'gc>>,
+    ) -> Option<(DisplayObject<'gc>, Avm2Object<'gc>)> {
+        object
+            .into_iter()
+            .chain(std::iter::once(context.stage.into()))
+            .find_map(|object| match object.object2() {
+                Avm2Value::Object(target) => Some((object, target)),
+                _ => None,
+            })
+    }
+
+    /// Dispatch a `KeyboardEvent` to the focused object, or the stage if
+    /// nothing has focus.
+    ///
+    /// `key_char` is the character the backend reported for the key, which
+    /// becomes the event's `charCode`.
+    fn dispatch_avm2_keyboard_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        key_code: KeyCode,
+        key_char: Option<char>,
+    ) {
+        let focus = context.focus_tracker.get();
+        if let Some((_, target)) = Self::avm2_event_target(context, focus) {
+            let ctrl_key = context.ui.is_key_down(KeyCode::Control);
+            let alt_key = context.ui.is_key_down(KeyCode::Alt);
+            let shift_key = context.ui.is_key_down(KeyCode::Shift);
+
+            // Keys that don't produce a character have a char code of 0.
+            let char_code = key_char.map(|c| c as u32).unwrap_or(0);
+            let key_location = crate::events::key_code_to_key_location(key_code);
+
+            let mut event = Avm2Event::new(event_type);
+            event.set_bubbles(true);
+            event.set_cancelable(false);
+
+            let properties: [(&'static str, Avm2Value<'gc>); 6] = [
+                ("charCode", char_code.into()),
+                ("keyCode", (key_code as u32).into()),
+                ("keyLocation", key_location.into()),
+                ("ctrlKey", ctrl_key.into()),
+                ("altKey", alt_key.into()),
+                ("shiftKey", shift_key.into()),
+            ];
+            let event_class = context.avm2.classes().keyboardevent;
+
+            if let Err(e) = Avm2::dispatch_event_with_properties(
+                context,
+                event,
+                event_class,
+                &properties,
+                target,
+            ) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
+    /// Dispatch a `MouseEvent` at the current mouse position to the given
+    /// object, or the stage if there is none.
+    ///
+    /// `related_object` is the object the mouse moved from or to, for the
+    /// over, out and roll events.
+    fn dispatch_avm2_mouse_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event_type: &'static str,
+        object: Option<DisplayObject<'gc>>,
+        related_object: Option<DisplayObject<'gc>>,
+        delta: i32,
+        button_down: bool,
+    ) {
+        if let Some((object, target)) = Self::avm2_event_target(context, object) {
+            let (local_x, local_y) = object.global_to_local(*context.mouse_position);
+            let related_object = match related_object.map(|object| object.object2()) {
+                Some(Avm2Value::Object(related_object)) => related_object.into(),
+                _ => Avm2Value::Null,
+            };
+
+            let mut event = Avm2Event::new(event_type);
+            event.set_bubbles(!matches!(event_type, "rollOver" | "rollOut"));
+            event.set_cancelable(false);
+
+            let properties: [(&'static str, Avm2Value<'gc>); 8] = [
+                ("localX", local_x.to_pixels().into()),
+                ("localY", local_y.to_pixels().into()),
+                ("ctrlKey", context.ui.is_key_down(KeyCode::Control).into()),
+                ("altKey", context.ui.is_key_down(KeyCode::Alt).into()),
+                ("shiftKey", context.ui.is_key_down(KeyCode::Shift).into()),
+                ("buttonDown", button_down.into()),
+                ("delta", delta.into()),
+                ("relatedObject", related_object),
+            ];
+            let event_class = context.avm2.classes().mouseevent;
+
+            if let Err(e) = Avm2::dispatch_event_with_properties(
+                context,
+                event,
+                event_class,
+                &properties,
+                target,
+            ) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
     /// Preload the first movie in the player.
     ///
     /// This should only be called once. Further movie loads should preload the
@@ -1264,12 +1537,19 @@ impl Player {
                     }
                 }
                 AvmType::Avm2 => {
+                    Avm2::deliver_local_connection_messages(context);
+                    Avm2::update_sockets(context);
+                    Avm2::update_net_streams(context);
+                    // The previous frame ends just before this one starts.
                     stage.exit_frame(context);
                     stage.enter_frame(context);
                     stage.construct_frame(context);
                     stage.frame_constructed(context);
                     stage.run_frame_avm2(context);
                     stage.run_frame_scripts(context);
+                    // Render handlers run once the frame's scripts are done,
+                    // just before it is drawn.
+                    stage.fire_render_event(context);
                 }
             }
             context.update_sounds();
@@ -1477,10 +1757,14 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        let gc_total_allocated = self.gc_arena.total_allocated();
+        let gc_remembered_size = self.gc_arena.remembered_size();
+
         self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let mouse_pressed_object = root_data.mouse_pressed_object;
+            let avm2_mouse_hovered_object = root_data.avm2_mouse_hovered_object;
             let focus_tracker = root_data.focus_tracker;
             let (
                 stage,
@@ -1512,6 +1796,7 @@ impl Player {
                 stage,
                 mouse_over_object: mouse_hovered_object,
                 mouse_down_object: mouse_pressed_object,
+                avm2_mouse_over_object: avm2_mouse_hovered_object,
                 mouse_position: &self.mouse_pos,
                 drag_object,
                 player: self.self_reference.clone(),
@@ -1537,6 +1822,9 @@ impl Player {
                 time_offset: &mut self.time_offset,
                 audio_manager,
                 frame_rate: &mut self.frame_rate,
+                gc_total_allocated,
+                gc_remembered_size,
+                needs_gc: &mut self.needs_gc,
             };
 
             let old_frame_rate = *update_context.frame_rate;
@@ -1560,8 +1848,10 @@ impl Player {
             // Hovered object may have been updated; copy it back to the GC root.
             let mouse_hovered_object = update_context.mouse_over_object;
             let mouse_pressed_object = update_context.mouse_down_object;
+            let avm2_mouse_hovered_object = update_context.avm2_mouse_over_object;
             root_data.mouse_hovered_object = mouse_hovered_object;
             root_data.mouse_pressed_object = mouse_pressed_object;
+            root_data.avm2_mouse_hovered_object = avm2_mouse_hovered_object;
 
             ret
         })
@@ -1611,7 +1901,12 @@ impl Player {
         self.update_mouse_state(None);
 
         // GC
-        self.gc_arena.collect_debt();
+        if self.needs_gc {
+            self.needs_gc = false;
+            self.gc_arena.collect_all();
+        } else {
+            self.gc_arena.collect_debt();
+        }
 
         rval
     }
@@ -1687,3 +1982,140 @@ pub struct DragObject<'gc> {
     #[collect(require_static)]
     pub constraint: BoundingBox,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::test_utils::*;
+    use crate::avm2::{Activation, Error, Object, TObject, Value};
+
+    fn on_key<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let mut fields = Vec::new();
+        for name in ["type", "charCode", "keyCode", "keyLocation", "shiftKey"] {
+            let value = get(activation, event, name)?;
+            fields.push(traced(activation, value)?);
+        }
+        log(fields.join(" "));
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_mouse<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let event_type = get(activation, event, "type")?;
+        let event_type = traced(activation, event_type)?;
+        let target = get(activation, event, "target")?.coerce_to_object(activation)?;
+        let name = get(activation, target, "name")?;
+        let name = traced(activation, name)?;
+        let related_object = get(activation, event, "relatedObject")?;
+        let related_object = traced(activation, related_object)?;
+        log(format!("{} {} {}", event_type, name, related_object));
+
+        Ok(Value::Undefined)
+    }
+
+    fn on_roll<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let event = args[0].coerce_to_object(activation)?;
+        let event_type = get(activation, event, "type")?;
+        let event_type = traced(activation, event_type)?;
+        let target = get(activation, event, "currentTarget")?.coerce_to_object(activation)?;
+        let name = get(activation, target, "name")?;
+        let name = traced(activation, name)?;
+        log(format!("{} {}", event_type, name));
+
+        Ok(Value::Undefined)
+    }
+
+    fn listen<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+        event_types: &[&'static str],
+        listener: Object<'gc>,
+    ) -> Result<(), Error> {
+        for event_type in event_types {
+            let args = [(*event_type).into(), listener.into()];
+            call(activation, object, "addEventListener", &args)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn keyboard_events_report_the_backend_character() {
+        with_avm2(|activation| {
+            let stage = activation.context.stage.object2();
+            let stage = stage.coerce_to_object(activation)?;
+            let listener = function(activation, on_key)?;
+            listen(activation, stage, &["keyDown", "keyUp"], listener)?;
+
+            let context = &mut activation.context;
+            Player::dispatch_avm2_keyboard_event(context, "keyDown", KeyCode::A, Some('A'));
+            Player::dispatch_avm2_keyboard_event(context, "keyUp", KeyCode::A, Some('a'));
+            Player::dispatch_avm2_keyboard_event(context, "keyDown", KeyCode::Numpad1, Some('1'));
+            Player::dispatch_avm2_keyboard_event(context, "keyDown", KeyCode::Left, None);
+            assert_eq!(
+                take_log(),
+                [
+                    "keyDown 65 65 0 false",
+                    "keyUp 97 65 0 false",
+                    "keyDown 49 97 3 false",
+                    "keyDown 0 37 0 false",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn mouse_over_dispatches_roll_events_to_ancestors() {
+        with_avm2(|activation| {
+            let root = timeline(activation, 1)?;
+            let outer = construct(activation, "flash.display", "Sprite", &[])?;
+            set(activation, outer, "name", "outer")?;
+            let inner = construct(activation, "flash.display", "Sprite", &[])?;
+            set(activation, inner, "name", "inner")?;
+            call(activation, outer, "addChild", &[inner.into()])?;
+            call(activation, root, "addChild", &[outer.into()])?;
+
+            let listener = function(activation, on_mouse)?;
+            listen(activation, root, &["mouseOver", "mouseOut"], listener)?;
+            let listener = function(activation, on_roll)?;
+            for sprite in [outer, inner] {
+                listen(activation, sprite, &["rollOver", "rollOut"], listener)?;
+            }
+
+            // The mouse moves onto `inner`, then off it onto the empty stage.
+            let context = &mut activation.context;
+            let inner = inner.as_display_object();
+            Player::update_avm2_mouse_over(context, inner, false);
+            Player::update_avm2_mouse_over(context, None, false);
+            assert_eq!(
+                take_log(),
+                [
+                    "mouseOver inner null",
+                    "rollOver outer",
+                    "rollOver inner",
+                    "mouseOut inner null",
+                    "rollOut inner",
+                    "rollOut outer",
+                ]
+            );
+
+            Ok(())
+        });
+    }
+}
@@ -60,6 +60,16 @@ pub fn calculate_shape_bounds(shape_records: &[swf::ShapeRecord]) -> swf::Rectan
     bounds
 }
 
+/// The rule deciding which areas enclosed by a fill's outline are filled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FillRule {
+    /// Areas enclosed by an odd number of edges are filled.
+    EvenOdd,
+
+    /// Areas the outline winds around a non-zero number of times are filled.
+    NonZero,
+}
+
 /// `DrawPath` represents a solid fill or a stroke.
 /// Fills are always closed paths, while strokes may be open or closed.
 /// Closed paths will have the first point equal to the last point.
@@ -73,6 +83,7 @@ pub enum DrawPath<'a> {
     Fill {
         style: &'a FillStyle,
         commands: Vec<DrawCommand>,
+        winding_rule: FillRule,
     },
 }
 
@@ -541,6 +552,7 @@ impl<'a> ShapeConverter<'a> {
             self.commands.push(DrawPath::Fill {
                 style,
                 commands: path.into_draw_commands().collect(),
+                winding_rule: FillRule::EvenOdd,
             });
         }
 
@@ -625,6 +637,7 @@ mod tests {
         let commands = ShapeConverter::from_shape(&shape).into_commands();
         let expected = vec![DrawPath::Fill {
             style: &FILL_STYLES[0],
+            winding_rule: FillRule::EvenOdd,
             commands: vec![
                 DrawCommand::MoveTo {
                     x: Twips::from_pixels(100.0),
@@ -689,6 +702,7 @@ mod tests {
         let commands = ShapeConverter::from_shape(&shape).into_commands();
         let expected = vec![DrawPath::Fill {
             style: &FILL_STYLES[0],
+            winding_rule: FillRule::EvenOdd,
             commands: vec![
                 DrawCommand::MoveTo {
                     x: Twips::from_pixels(100.0),
@@ -855,7 +869,11 @@ pub fn shape_hit_test(
 }
 
 /// Test whether the given point is contained within the paths specified by the draw commands.
-pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips, Twips)) -> bool {
+pub fn draw_command_fill_hit_test(
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    test_point: (Twips, Twips),
+) -> bool {
     let mut cursor = (Twips::ZERO, Twips::ZERO);
     let mut fill_start = (Twips::ZERO, Twips::ZERO);
     let mut winding = 0;
@@ -882,7 +900,10 @@ pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips,
         winding += winding_number_line(test_point, cursor, fill_start);
     }
 
-    winding & 0b1 != 0
+    match winding_rule {
+        FillRule::EvenOdd => winding & 0b1 != 0,
+        FillRule::NonZero => winding != 0,
+    }
 }
 
 /// Test whether the given point is contained within the strokes specified by the draw commands.
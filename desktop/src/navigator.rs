@@ -3,14 +3,18 @@
 use crate::custom_event::RuffleEvent;
 use isahc::{config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request};
 use ruffle_core::backend::navigator::{
-    NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
+    ConnectionState, NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions, SocketAction,
+    SocketHandle,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
 use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
 use std::rc::Rc;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -194,4 +198,84 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
         url
     }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        // Sockets use blocking I/O on their own threads, so that slow hosts
+        // never stall the player.
+        thread::spawn(move || {
+            let stream = match connect_tcp(&host, port, timeout) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Failed to connect to {}:{}: {}", host, port, e);
+
+                    let state = if e.kind() == io::ErrorKind::TimedOut {
+                        ConnectionState::TimedOut
+                    } else {
+                        ConnectionState::Failed
+                    };
+                    let _ = sender.send(SocketAction::Connect(handle, state));
+                    return;
+                }
+            };
+
+            let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Connected));
+
+            match stream.try_clone() {
+                Ok(mut reader) => {
+                    thread::spawn(move || {
+                        let mut buffer = [0; 4096];
+                        loop {
+                            match reader.read(&mut buffer) {
+                                Ok(0) | Err(_) => break,
+                                Ok(read) => {
+                                    let data = buffer[..read].to_vec();
+                                    if sender.send(SocketAction::Data(handle, data)).is_err() {
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+
+                        let _ = sender.send(SocketAction::Close(handle));
+                    });
+                }
+                Err(e) => {
+                    log::error!("Failed to read from {}:{}: {}", host, port, e);
+                    let _ = sender.send(SocketAction::Close(handle));
+                }
+            }
+
+            let mut writer = stream;
+            for data in receiver {
+                if writer.write_all(&data).is_err() {
+                    break;
+                }
+            }
+
+            let _ = writer.shutdown(Shutdown::Both);
+        });
+    }
+}
+
+/// Connect to the first address of `host` that accepts a connection within
+/// `timeout`.
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "host has no addresses");
+
+    for address in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+
+    Err(last_error)
 }
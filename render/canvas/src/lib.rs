@@ -4,8 +4,9 @@ use ruffle_core::backend::render::{
     NullBitmapSource, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::filters::Filter;
 use ruffle_core::matrix::Matrix;
-use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, FillRule};
 use ruffle_web_common::JsResult;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -29,6 +30,9 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+
+    /// The CSS filters of the render targets pushed by `push_filter`.
+    filters: Vec<String>,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -76,6 +80,7 @@ enum CanvasDrawCommand {
     Fill {
         path: Path2d,
         fill_style: CanvasFillStyle,
+        winding_rule: CanvasWindingRule,
     },
 
     /// A command to draw a particular image (such as an SVG)
@@ -211,6 +216,7 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            filters: Vec::new(),
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -568,7 +574,11 @@ impl RenderBackend for WebCanvasRenderBackend {
         if let Some(shape) = self.shapes.get(shape.0) {
             for command in shape.0.iter() {
                 match command {
-                    CanvasDrawCommand::Fill { path, fill_style } => {
+                    CanvasDrawCommand::Fill {
+                        path,
+                        fill_style,
+                        winding_rule,
+                    } => {
                         let xformed_fill_style =
                             fill_style.color_transform(&transform.color_transform);
                         if xformed_fill_style.is_none() {
@@ -584,7 +594,7 @@ impl RenderBackend for WebCanvasRenderBackend {
                         };
 
                         self.context
-                            .fill_with_path_2d_and_winding(path, CanvasWindingRule::Evenodd);
+                            .fill_with_path_2d_and_winding(path, *winding_rule);
 
                         if xformed_fill_style.is_none() {
                             self.clear_color_filter();
@@ -702,6 +712,31 @@ impl RenderBackend for WebCanvasRenderBackend {
             .unwrap();
     }
 
+    fn push_filter(&mut self, filter: &Filter) -> bool {
+        // Filtered objects are drawn to their own render target, which is then
+        // drawn through the filter.
+        match css_filter(filter) {
+            Some(css_filter) => {
+                self.filters.push(css_filter);
+                self.push_render_target();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn pop_filter(&mut self) {
+        let (filtered_canvas, _filtered_context) = self.pop_render_target();
+        let css_filter = self.filters.pop().unwrap_or_else(|| "none".to_string());
+
+        self.context.reset_transform().warn_on_error();
+        self.context.set_filter(&css_filter);
+        self.context
+            .draw_image_with_html_canvas_element(&filtered_canvas, 0.0, 0.0)
+            .unwrap();
+        self.context.set_filter("none");
+    }
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         let window = web_sys::window().unwrap();
         let document = window.document().unwrap();
@@ -783,6 +818,62 @@ impl RenderBackend for WebCanvasRenderBackend {
     }
 }
 
+/// The standard deviation of the gaussian blur closest to a Flash blur.
+///
+/// Flash blurs with `quality` passes of a box blur. The canvas only blurs with
+/// a single radius, so the horizontal and vertical sizes are averaged.
+fn blur_deviation(blur_x: f64, blur_y: f64, quality: i32) -> f64 {
+    let size = (blur_x + blur_y) / 2.0;
+    let passes = f64::from(quality.clamp(0, 15));
+
+    (passes * (size * size - 1.0).max(0.0) / 12.0).sqrt()
+}
+
+/// A CSS `drop-shadow` of the given color, which both drop shadows and glows
+/// are drawn with.
+fn css_drop_shadow(offset: (f64, f64), deviation: f64, color: u32, alpha: f64) -> String {
+    // The blur radius of a drop shadow is twice its standard deviation.
+    format!(
+        "drop-shadow({}px {}px {}px rgba({},{},{},{}))",
+        offset.0,
+        offset.1,
+        deviation * 2.0,
+        (color >> 16) & 0xFF,
+        (color >> 8) & 0xFF,
+        color & 0xFF,
+        alpha.clamp(0.0, 1.0)
+    )
+}
+
+/// The CSS filter that draws a filter, if there is one.
+///
+/// Inner and knockout shadows and glows have no CSS equivalent. The strength
+/// of a shadow or glow is approximated by scaling its alpha.
+fn css_filter(filter: &Filter) -> Option<String> {
+    match filter {
+        Filter::Blur(blur) => Some(format!(
+            "blur({}px)",
+            blur_deviation(blur.blur_x, blur.blur_y, blur.quality)
+        )),
+        Filter::DropShadow(shadow) if !shadow.inner && !shadow.knockout && !shadow.hide_object => {
+            let angle = shadow.angle.to_radians();
+            Some(css_drop_shadow(
+                (shadow.distance * angle.cos(), shadow.distance * angle.sin()),
+                blur_deviation(shadow.blur_x, shadow.blur_y, shadow.quality),
+                shadow.color,
+                shadow.alpha * shadow.strength,
+            ))
+        }
+        Filter::Glow(glow) if !glow.inner && !glow.knockout => Some(css_drop_shadow(
+            (0.0, 0.0),
+            blur_deviation(glow.blur_x, glow.blur_y, glow.quality),
+            glow.color,
+            glow.alpha * glow.strength,
+        )),
+        _ => None,
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 fn swf_shape_to_svg(
     shape: DistilledShape,
@@ -840,7 +931,11 @@ fn swf_shape_to_svg(
     let mut svg_paths = Vec::with_capacity(shape.paths.len());
     for path in shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
                 let mut svg_path = SvgPath::new();
 
                 let fill = match style {
@@ -1120,7 +1215,13 @@ fn swf_shape_to_svg(
 
                 svg_path = svg_path
                     .set("fill", fill)
-                    .set("fill-rule", "evenodd")
+                    .set(
+                        "fill-rule",
+                        match winding_rule {
+                            FillRule::EvenOdd => "evenodd",
+                            FillRule::NonZero => "nonzero",
+                        },
+                    )
                     .set("d", data);
                 svg_paths.push(svg_path);
             }
@@ -1307,7 +1408,11 @@ fn swf_shape_to_canvas_commands(
 
     for path in &shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
                 let fill_style = match style {
                     FillStyle::Color(Color { r, g, b, a }) => CanvasFillStyle::Color(CanvasColor(
                         format!("rgba({},{},{},{})", r, g, b, f32::from(*a) / 255.0),
@@ -1386,9 +1491,16 @@ fn swf_shape_to_canvas_commands(
                     &bounds_viewbox_matrix,
                 );
 
-                canvas_data
-                    .0
-                    .push(CanvasDrawCommand::Fill { path, fill_style });
+                let winding_rule = match winding_rule {
+                    FillRule::EvenOdd => CanvasWindingRule::Evenodd,
+                    FillRule::NonZero => CanvasWindingRule::Nonzero,
+                };
+
+                canvas_data.0.push(CanvasDrawCommand::Fill {
+                    path,
+                    fill_style,
+                    winding_rule,
+                });
             }
             DrawPath::Stroke {
                 style,
@@ -6,7 +6,7 @@ use lyon::tessellation::{
 };
 use lyon::tessellation::{FillOptions, StrokeOptions};
 use ruffle_core::backend::render::{srgb_to_linear, swf, BitmapHandle, BitmapSource};
-use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
 
 pub struct ShapeTessellator {
     fill_tess: FillTessellator,
@@ -45,7 +45,11 @@ impl ShapeTessellator {
 
         for path in shape.paths {
             match path {
-                DrawPath::Fill { style, commands } => match style {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => match style {
                     swf::FillStyle::Color(color) => {
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -56,7 +60,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -76,7 +80,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -106,7 +110,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -139,7 +143,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -174,7 +178,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -374,6 +378,14 @@ fn swf_bitmap_to_gl_matrix(
     [[a, d, 0.0], [b, e, 0.0], [c, f, 1.0]]
 }
 
+/// The tessellator options for filling a path with the given winding rule.
+fn fill_options(winding_rule: FillRule) -> FillOptions {
+    match winding_rule {
+        FillRule::EvenOdd => FillOptions::even_odd(),
+        FillRule::NonZero => FillOptions::non_zero(),
+    }
+}
+
 fn ruffle_path_to_lyon_path(commands: Vec<DrawCommand>, is_closed: bool) -> Path {
     fn point(x: swf::Twips, y: swf::Twips) -> lyon::math::Point {
         lyon::math::Point::new(x.to_pixels() as f32, y.to_pixels() as f32)
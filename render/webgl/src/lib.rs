@@ -3,6 +3,7 @@ use ruffle_core::backend::render::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, NullBitmapSource,
     RenderBackend, ShapeHandle, Transform,
 };
+use ruffle_core::filters::Filter;
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf;
 use ruffle_render_common_tess::{
@@ -1263,6 +1264,13 @@ impl RenderBackend for WebGlRenderBackend {
         self.mask_state_dirty = true;
     }
 
+    fn push_filter(&mut self, _filter: &Filter) -> bool {
+        // Filters need an offscreen render target, which this backend lacks.
+        false
+    }
+
+    fn pop_filter(&mut self) {}
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         self.bitmap_registry.get(&bitmap).cloned()
     }
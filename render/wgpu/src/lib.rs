@@ -2,6 +2,7 @@ use ruffle_core::backend::render::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, RenderBackend,
     ShapeHandle, Transform,
 };
+use ruffle_core::filters::Filter;
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf;
 use std::{borrow::Cow, num::NonZeroU32};
@@ -1263,6 +1264,13 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
     }
 
+    fn push_filter(&mut self, _filter: &Filter) -> bool {
+        // Filters need an offscreen render target, which this backend lacks.
+        false
+    }
+
+    fn pop_filter(&mut self) {}
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         self.bitmap_registry.get(&bitmap).cloned()
     }
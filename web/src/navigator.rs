@@ -1,11 +1,13 @@
 //! Navigator backend for web
 use js_sys::{Array, ArrayBuffer, Uint8Array};
 use ruffle_core::backend::navigator::{
-    url_from_relative_url, NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
+    url_from_relative_url, ConnectionState, NavigationMethod, NavigatorBackend, OwnedFuture,
+    RequestOptions, SocketAction, SocketHandle,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
+use std::sync::mpsc::{Receiver, Sender};
 use std::time::Duration;
 use url::Url;
 use wasm_bindgen::JsCast;
@@ -272,4 +274,22 @@ impl NavigatorBackend for WebNavigatorBackend {
         }
         url
     }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        // Browsers don't allow raw TCP connections.
+        log::warn!(
+            "Socket connections are not supported on web ({}:{})",
+            host,
+            port
+        );
+        let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+    }
 }